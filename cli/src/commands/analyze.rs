@@ -1,54 +1,511 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use clap::Args;
 use colored::*;
 use indicatif::{ProgressBar, ProgressStyle};
-use log::{debug, info, warn};
+use log::{debug, warn};
+use serde::Serialize;
 use rust_solana_analyzer::{analyzer, ast};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::io::{IsTerminal, Read};
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
-pub fn run(
-    path: PathBuf,
-    templates: Option<PathBuf>,
-    output: Option<PathBuf>,
-    generate_ast: bool,
-    ignore: Option<String>,
-    ignore_rules: Option<String>,
-    verbose: bool,
-    quiet: bool,
-) -> Result<()> {
+use crate::theme::{severity_icon, severity_style};
+
+/// Arguments of the `analyze` command
+#[derive(Args, Debug, Clone, Default)]
+pub struct AnalyzeArgs {
+    /// Path to Solana project directory or .tar.gz/.zip archive of it; repeat to analyze several programs into one report.
+    /// '-' analyzes Rust source read from stdin
+    #[arg(short, long, value_name = "PATH", required = true)]
+    pub path: Vec<PathBuf>,
+
+    /// File name reported in the finding locations of source read from stdin (--path -)
+    #[arg(long, value_name = "FILE", default_value = "<stdin>")]
+    pub stdin_filename: String,
+
+    /// Directory of YAML/TOML custom rule templates, loaded on top of the built-in rules, or an
+    /// https:// URL of a template or a .tar.gz/.zip archive of templates, downloaded and cached
+    #[arg(short, long, value_name = "DIR|URL", visible_alias = "rules-dir")]
+    pub templates: Option<PathBuf>,
+
+    /// Expected SHA-256 of the --templates URL download; a different download fails the run
+    #[arg(long, value_name = "HEX")]
+    pub templates_sha256: Option<String>,
+
+    /// Only use the cached copy of a --templates URL, never download it
+    #[arg(long)]
+    pub offline: bool,
+
+    /// What to do when a custom rule reuses the id of a built-in rule (warn or error)
+    #[arg(long, value_name = "POLICY", default_value = "warn")]
+    pub rule_conflicts: analyzer::RuleConflictPolicy,
+
+    /// Let custom rules replace built-in rules with the same id
+    #[arg(long)]
+    pub allow_override: bool,
+
+    /// Output report file path (supports .md, .json, .sarif, .txt, .csv, .html, .xml and .ndjson formats)
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Report format (md, json, sarif, text, github, csv, html, junit, count, ndjson); printed to stdout when no output file is given
+    #[arg(short, long, value_name = "FORMAT")]
+    pub format: Option<analyzer::reporting::ReportFormat>,
+
+    /// Generate AST JSON files
+    #[arg(long)]
+    pub ast: bool,
+
+    /// Severities to ignore (comma-separated: low,medium,high,informational)
+    #[arg(short, long, value_name = "SEVERITIES")]
+    pub ignore: Option<String>,
+
+    /// Only report findings at or above this severity (high, medium, low, informational)
+    #[arg(long, value_name = "SEVERITY")]
+    pub min_severity: Option<analyzer::Severity>,
+
+    /// Specific rule IDs to ignore (comma-separated)
+    #[arg(long, value_name = "RULE_IDS")]
+    pub ignore_rules: Option<String>,
+
+    /// Only run these rule IDs (comma-separated), whatever --rule-types and --category select
+    #[arg(long, value_name = "RULE_IDS")]
+    pub only_rules: Option<String>,
+
+    /// Rule types to run (comma-separated: solana, anchor, general) [default: all]
+    #[arg(long, value_name = "TYPES")]
+    pub rule_types: Option<String>,
+
+    /// Only run rules in these categories (comma-separated, e.g. access-control,cpi)
+    #[arg(long, value_name = "CATEGORIES")]
+    pub category: Option<String>,
+
+    /// Only analyze files matching this glob, relative to the analysis root (repeatable)
+    #[arg(long, value_name = "GLOB")]
+    pub include: Vec<String>,
+
+    /// Skip files or directories matching this glob, relative to the analysis root (repeatable)
+    #[arg(long, value_name = "GLOB")]
+    pub exclude: Vec<String>,
+
+    /// Analyze files ignored by .gitignore
+    #[arg(long)]
+    pub no_gitignore: bool,
+
+    /// Analyze files under tests/, benches/ and examples/ directories (skipped by default)
+    #[arg(long)]
+    pub include_tests: bool,
+
+    /// Only analyze files changed since this git ref (e.g. origin/main)
+    #[arg(long, value_name = "GIT_REF")]
+    pub since: Option<String>,
+
+    /// Only report findings starting inside this line range, as FILE:START-END (repeatable)
+    #[arg(long, value_name = "FILE:START-END")]
+    pub lines: Vec<analyzer::line_filter::LineRange>,
+
+    /// Only report findings starting inside the hunks of this unified diff ('-' reads stdin)
+    #[arg(long, value_name = "FILE")]
+    pub diff: Option<PathBuf>,
+
+    /// Maximum number of files analyzed in parallel (defaults to the number of CPUs)
+    #[arg(short, long, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    pub jobs: Option<u16>,
+
+    /// Skip files whose parsing and analysis take longer than N seconds
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    pub timeout_secs: Option<u64>,
+
+    /// Re-analyze every file instead of reusing cached findings of unchanged files
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// Directory of the incremental analysis cache
+    #[arg(long, value_name = "DIR", default_value = analyzer::cache::DEFAULT_CACHE_DIR)]
+    pub cache_dir: PathBuf,
+
+    /// Baseline file of known findings to leave out of the report
+    #[arg(long, value_name = "FILE")]
+    pub baseline: Option<PathBuf>,
+
+    /// Exit with code 2 if any finding at or above this severity is reported
+    #[arg(long, value_name = "SEVERITY")]
+    pub fail_on: Option<analyzer::Severity>,
+
+    /// Collapse findings in the terminal, text, Markdown and HTML output (rule)
+    #[arg(long, value_name = "GROUP")]
+    pub group_by: Option<analyzer::reporting::GroupBy>,
+
+    /// Write analysis statistics (timing, file and line totals, per-rule counts) as JSON to this file
+    #[arg(long, value_name = "FILE")]
+    pub stats: Option<PathBuf>,
+
+    /// Write finding counts per severity, files analyzed and duration as JSON to this file
+    #[arg(long, value_name = "FILE")]
+    pub summary: Option<PathBuf>,
+
+    /// Maximum locations listed per group before the rest are summarized
+    #[arg(long, value_name = "N", default_value_t = analyzer::reporting::DEFAULT_GROUP_LIMIT)]
+    pub group_limit: usize,
+
+    /// Order of findings in the terminal output and reports (severity, file or rule)
+    #[arg(long, value_name = "ORDER", default_value = "severity")]
+    pub sort: analyzer::reporting::SortOrder,
+
+    /// Show at most N findings per severity (the first in the sort order); totals and --fail-on still count every finding
+    #[arg(long, value_name = "N")]
+    pub top: Option<usize>,
+
+    /// Don't print the ASCII banner (the rest of the output is unchanged, unlike --quiet)
+    #[arg(long)]
+    pub no_banner: bool,
+
+    /// Report file paths relative to the analysis root instead of as found on disk
+    #[arg(long)]
+    pub relative_paths: bool,
+
+    /// Browse the findings in a terminal UI and mark them reviewed or ignored; ignored
+    /// findings are added to the --baseline file
+    #[arg(long, conflicts_with = "format", requires = "baseline")]
+    pub interactive: bool,
+}
+
+/// How findings are laid out in the terminal output and reports
+#[derive(Clone, Copy)]
+struct Layout {
+    group_by: Option<analyzer::reporting::GroupBy>,
+    group_limit: usize,
+    sort: analyzer::reporting::SortOrder,
+    top: Option<usize>,
+}
+
+/// Outcome of an analysis that ran to completion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Passed,
+    /// Findings reached the --fail-on threshold; the process exits with code 2
+    Failed,
+}
+
+pub fn run(args: AnalyzeArgs, verbose: bool, quiet: bool) -> Result<Outcome> {
+    let AnalyzeArgs {
+        path: paths,
+        stdin_filename,
+        templates,
+        templates_sha256,
+        offline,
+        rule_conflicts,
+        allow_override,
+        output,
+        format,
+        ast: generate_ast,
+        ignore,
+        min_severity,
+        ignore_rules,
+        only_rules,
+        rule_types,
+        category,
+        include,
+        exclude,
+        no_gitignore,
+        include_tests,
+        since,
+        lines,
+        diff,
+        jobs,
+        timeout_secs,
+        no_cache,
+        cache_dir,
+        baseline,
+        fail_on,
+        group_by,
+        group_limit,
+        stats,
+        summary,
+        sort,
+        top,
+        no_banner,
+        relative_paths,
+        interactive,
+    } = args;
+
+    let layout = Layout {
+        group_by,
+        group_limit,
+        sort,
+        top,
+    };
+
+    // A report written to stdout must not be mixed with progress output
+    let quiet = quiet || (output.is_none() && format.is_some());
+
+    // `--path -` analyzes a single source file read from stdin
+    let stdin = paths.iter().any(|path| path == Path::new("-"));
+    if stdin && (paths.len() > 1 || diff.as_deref() == Some(Path::new("-"))) {
+        let message = "--path - reads the source from stdin and cannot be combined with other paths or --diff -";
+        eprintln!("{} {}", "✗".red().bold(), message.red());
+        anyhow::bail!(message);
+    }
+    if stdin {
+        let unsupported = [
+            (stats.is_some(), "--stats"),
+            (summary.is_some(), "--summary"),
+            (interactive, "--interactive"),
+            (relative_paths, "--relative-paths"),
+        ];
+        if let Some((_, flag)) = unsupported.iter().find(|(set, _)| *set) {
+            let message = format!("{flag} is not supported with --path -");
+            eprintln!("{} {}", "✗".red().bold(), message.red());
+            anyhow::bail!(message);
+        }
+    }
+
     // Print banner
-    if !quiet {
+    if !quiet && !no_banner {
         print_banner();
     }
 
-    // Verify path exists
-    if !path.exists() {
-        eprintln!(
-            "{} Path does not exist: {}",
-            "✗".red().bold(),
-            path.display().to_string().yellow()
-        );
-        anyhow::bail!("Path {} does not exist", path.display());
+    let remote = analyzer::remote_templates::RemoteOptions {
+        cache_dir: cache_dir.clone(),
+        sha256: templates_sha256,
+        offline,
+    };
+    let templates = match resolve_templates(templates, &remote, quiet) {
+        Ok(templates) => templates,
+        Err(e) => {
+            eprintln!("{} {}", "✗".red().bold(), format!("{e:#}").red());
+            return Err(e);
+        }
+    };
+
+    // Archives are analyzed from a temporary copy, removed when the run ends
+    let mut archives = Vec::new();
+    let mut paths = paths;
+    for path in &mut paths {
+        if !(path.is_file() && ast::archive::is_archive(path)) {
+            continue;
+        }
+        if !quiet {
+            println!(
+                "{} Extracting archive: {}",
+                "→".cyan().bold(),
+                path.display().to_string().bright_blue()
+            );
+        }
+        let extracted = match ast::archive::extract(path) {
+            Ok(extracted) => extracted,
+            Err(e) => {
+                eprintln!("{} {}", "✗".red().bold(), format!("{e:#}").red());
+                return Err(e);
+            }
+        };
+        archives.push((std::mem::replace(path, extracted.path().to_path_buf()), extracted));
     }
 
-    // Verify path is a directory
-    if !path.is_dir() {
-        eprintln!(
-            "{} Path is not a directory: {}",
-            "✗".red().bold(),
-            path.display().to_string().yellow()
-        );
-        anyhow::bail!("Path {} is not a directory", path.display());
+    for path in paths.iter().filter(|_| !stdin) {
+        // Verify path exists
+        if !path.exists() {
+            eprintln!(
+                "{} Path does not exist: {}",
+                "✗".red().bold(),
+                path.display().to_string().yellow()
+            );
+            anyhow::bail!("Path {} does not exist", path.display());
+        }
+
+        // Verify path is a directory
+        if !path.is_dir() {
+            eprintln!(
+                "{} Path is not a directory: {}",
+                "✗".red().bold(),
+                path.display().to_string().yellow()
+            );
+            anyhow::bail!("Path {} is not a directory", path.display());
+        }
+    }
+
+    // Report paths and baseline fingerprints are relative to the project root,
+    // which does not depend on the paths passed to this run
+    let root = project_root(if stdin { &[] } else { &paths });
+
+    // Read the line ranges before spending time on the analysis
+    let line_filter = match load_line_filter(lines, diff.as_deref()) {
+        Ok(line_filter) => line_filter,
+        Err(e) => {
+            eprintln!("{} {}", "✗".red().bold(), format!("{e:#}").red());
+            return Err(e);
+        }
+    };
+
+    // Findings ignored in the interactive mode are added to the baseline
+    let baseline_file = baseline.clone();
+
+    // Load the baseline before spending time on the analysis. The interactive
+    // mode creates it when it does not exist yet
+    let baseline = match baseline {
+        Some(baseline_path) if interactive && !baseline_path.exists() => None,
+        Some(baseline_path) => match analyzer::baseline::Baseline::load(&baseline_path) {
+            Ok(baseline) => Some(baseline),
+            Err(e) => {
+                eprintln!("{} {}", "✗".red().bold(), format!("{e:#}").red());
+                return Err(e);
+            }
+        },
+        None => None,
+    };
+
+    // Archives are shown under their own name rather than the temporary directory
+    let display_paths: Vec<PathBuf> = paths
+        .iter()
+        .map(|path| {
+            archives
+                .iter()
+                .find(|(_, extracted)| extracted.path() == path)
+                .map_or_else(|| path.clone(), |(archive, _)| archive.clone())
+        })
+        .collect();
+
+    // Finding files under these roots are reported relative to them
+    let mut relative_roots: Vec<PathBuf> = archives
+        .iter()
+        .map(|(_, extracted)| extracted.path().to_path_buf())
+        .collect();
+    if relative_paths {
+        relative_roots.push(common_root(&paths));
     }
 
     if !quiet {
-        println!(
-            "\n{} Analyzing directory: {}\n",
-            "→".cyan().bold(),
-            path.display().to_string().bright_blue()
-        );
+        println!();
+        if stdin {
+            println!(
+                "{} Analyzing stdin as: {}",
+                "→".cyan().bold(),
+                stdin_filename.bright_blue()
+            );
+        }
+        for path in display_paths.iter().filter(|_| !stdin) {
+            println!(
+                "{} Analyzing directory: {}",
+                "→".cyan().bold(),
+                path.display().to_string().bright_blue()
+            );
+        }
+        println!();
+    }
+
+    // Create analysis options
+    let mut options = analyzer::AnalysisOptions::default();
+    options.generate_ast = generate_ast;
+    options.custom_templates_path = templates.map(|p| p.to_string_lossy().to_string());
+    options.include_globs = include;
+    options.exclude_globs = exclude;
+    options.respect_gitignore = !no_gitignore;
+    options.include_tests = include_tests;
+    options.since = since;
+    options.max_threads = jobs.map(usize::from);
+    options.per_file_timeout = timeout_secs.map(std::time::Duration::from_secs);
+    options.project_root = Some(root.to_string_lossy().to_string());
+    options.min_severity = min_severity;
+    // Extracted archives live in a new directory every run, so caching them would only grow the cache
+    options.cache_dir = (!no_cache && archives.is_empty()).then(|| cache_dir.to_string_lossy().to_string());
+    options.line_filter = line_filter;
+    options.rule_conflicts = if allow_override {
+        analyzer::RuleConflictPolicy::Override
+    } else {
+        rule_conflicts
+    };
+    options.include_rule_types = match rule_types {
+        Some(rule_types) => rule_types
+            .split(',')
+            .map(|rule_type| {
+                rule_type
+                    .parse::<analyzer::RuleType>()
+                    .map_err(|e| anyhow::anyhow!("{e} (expected solana, anchor or general)"))
+            })
+            .collect::<Result<_>>()?,
+        None => vec![
+            analyzer::RuleType::Solana,
+            analyzer::RuleType::Anchor,
+            analyzer::RuleType::General,
+        ],
+    };
+
+    // Parse severities to ignore
+    if let Some(ignore_str) = ignore {
+        for sev in ignore_str.split(',') {
+            match sev.trim().to_lowercase().as_str() {
+                "high" => options.ignore_severities.push(analyzer::Severity::High),
+                "medium" => options.ignore_severities.push(analyzer::Severity::Medium),
+                "low" => options.ignore_severities.push(analyzer::Severity::Low),
+                "informational" => options
+                    .ignore_severities
+                    .push(analyzer::Severity::Informational),
+                _ => warn!("Unknown severity level: {}", sev),
+            }
+        }
+    }
+
+    // Parse rule IDs to ignore
+    if let Some(ignore_rules_str) = ignore_rules {
+        for rule_id in ignore_rules_str.split(',') {
+            options.ignore_rules.push(rule_id.trim().to_string());
+        }
+    }
+
+    if let Some(only_rules) = only_rules {
+        options.only_rules = only_rules
+            .split(',')
+            .map(str::trim)
+            .filter(|rule_id| !rule_id.is_empty())
+            .map(str::to_string)
+            .collect();
+    }
+
+    if let Some(categories) = category {
+        options.include_categories = analyzer::engine::parse_categories(&categories).map_err(anyhow::Error::msg)?;
+    }
+
+    // Malformed rule templates and conflicts with built-in rules fail the run
+    // rather than being skipped
+    let walk_options = options.walk_options();
+    let analyzer_instance = match analyzer::Analyzer::try_with_options(options) {
+        Ok(analyzer_instance) => analyzer_instance,
+        Err(e) => {
+            eprintln!("{} {}", "✗".red().bold(), format!("{e:#}").red());
+            return Err(e);
+        }
+    };
+
+    if stdin {
+        let mut source = String::new();
+        std::io::stdin()
+            .read_to_string(&mut source)
+            .context("Failed to read the source from stdin")?;
+        let mut analysis_result = match analyzer_instance.analyze_source(&source, &stdin_filename) {
+            Ok(analysis_result) => analysis_result,
+            Err(e) => {
+                eprintln!("{} {}", "✗".red().bold(), format!("{e:#}").red());
+                anyhow::bail!("Analysis failed: {e:#}");
+            }
+        };
+        if let Some(baseline) = &baseline {
+            baseline.apply(&mut analysis_result, &root.to_string_lossy());
+        }
+
+        if let Some(output_path) = &output {
+            save_report(&analysis_result, output_path, format, &root, layout, quiet)?;
+        } else if let Some(format) = format {
+            print_report(&analysis_result, format, &root, layout)?;
+        } else if !quiet {
+            print_summary(&analysis_result);
+            match group_by {
+                Some(analyzer::reporting::GroupBy::Rule) => print_grouped_findings(&analysis_result, layout, verbose),
+                None => print_findings(&analysis_result, layout, verbose),
+            }
+        }
+        return Ok(outcome(&analysis_result, fail_on.as_ref()));
     }
 
     let start_time = Instant::now();
@@ -68,20 +525,52 @@ pub fn run(
         None
     };
 
-    // Process directory
-    let results = ast::parser::process_directory(&path);
+    // Collect the files of each path; they are parsed by the analysis, and not at
+    // all when served from the cache
+    let mut results: Vec<PathBuf> = Vec::new();
+    // Files of each path, to break the summary down per path
+    let mut path_files: Vec<HashSet<String>> = Vec::new();
+    for path in &paths {
+        match ast::parser::collect_rust_files(path, &walk_options) {
+            Ok(path_results) => {
+                let files: HashSet<String> = path_results
+                    .iter()
+                    .map(|file| file.to_string_lossy().to_string())
+                    .collect();
+                // Overlapping paths must not analyze a file twice
+                results.extend(path_results.into_iter().filter(|file| {
+                    !path_files
+                        .iter()
+                        .any(|seen| seen.contains(file.to_string_lossy().as_ref()))
+                }));
+                path_files.push(files);
+            }
+            Err(e) => {
+                if let Some(pb) = &spinner {
+                    pb.finish_and_clear();
+                }
+                eprintln!("{} {}", "✗".red().bold(), e.to_string().red());
+                return Err(e);
+            }
+        }
+    }
 
     if let Some(pb) = &spinner {
         pb.finish_and_clear();
     }
 
     if results.is_empty() {
+        let searched: Vec<String> = display_paths.iter().map(|path| path.display().to_string()).collect();
         eprintln!(
             "\n{} No Rust files found in {}",
             "⚠".yellow().bold(),
-            path.display().to_string().yellow()
+            searched.join(", ").yellow()
         );
-        return Ok(());
+        // Scripts reading the report from stdout still get an (empty) report
+        if let (None, Some(format)) = (&output, format) {
+            print_report(&analyzer::AnalysisResult::default(), format, &root, layout)?;
+        }
+        return Ok(Outcome::Passed);
     }
 
     if !quiet {
@@ -98,8 +587,15 @@ pub fn run(
             println!("{} Generating AST JSON files...\n", "→".cyan().bold());
         }
 
-        for (file_path, ast_data) in &results {
-            let json = ast::json::ast_to_json(ast_data);
+        for file_path in &results {
+            let ast_data = match ast::parser::parse_rust_file(file_path) {
+                Ok(ast_data) => ast_data,
+                Err(e) => {
+                    warn!("{e:#}");
+                    continue;
+                }
+            };
+            let json = ast::json::ast_to_json(&ast_data);
             let mut json_path = file_path.clone();
             json_path.set_extension("json");
             fs::write(&json_path, json)?;
@@ -126,41 +622,6 @@ pub fn run(
         );
     }
 
-    // Create analysis options
-    let mut options = analyzer::AnalysisOptions::default();
-    options.generate_ast = generate_ast;
-    options.custom_templates_path = templates.map(|p| p.to_string_lossy().to_string());
-    options.include_rule_types = vec![
-        analyzer::RuleType::Solana,
-        analyzer::RuleType::Anchor,
-        analyzer::RuleType::General,
-    ];
-
-    // Parse severities to ignore
-    if let Some(ignore_str) = ignore {
-        for sev in ignore_str.split(',') {
-            match sev.trim().to_lowercase().as_str() {
-                "high" => options.ignore_severities.push(analyzer::Severity::High),
-                "medium" => options.ignore_severities.push(analyzer::Severity::Medium),
-                "low" => options.ignore_severities.push(analyzer::Severity::Low),
-                "informational" => options
-                    .ignore_severities
-                    .push(analyzer::Severity::Informational),
-                _ => warn!("Unknown severity level: {}", sev),
-            }
-        }
-    }
-
-    // Parse rule IDs to ignore
-    if let Some(ignore_rules_str) = ignore_rules {
-        for rule_id in ignore_rules_str.split(',') {
-            options.ignore_rules.push(rule_id.trim().to_string());
-        }
-    }
-
-    // Create analyzer
-    let analyzer_instance = analyzer::create_analyzer_with_options(options);
-
     let analysis_spinner = if !quiet {
         let pb = ProgressBar::new_spinner();
         pb.set_style(
@@ -175,13 +636,58 @@ pub fn run(
         None
     };
 
-    // Run analysis
-    match analyzer_instance.analyze_files(&results) {
-        Ok(analysis_result) => {
+    // Run analysis. NDJSON written to stdout is streamed as files finish
+    // unless --top needs every finding first.
+    let stream = output.is_none() && format == Some(analyzer::reporting::ReportFormat::Ndjson) && top.is_none();
+    let analysis = if stream {
+        let project_root = root.to_string_lossy().to_string();
+        let mut findings = Vec::new();
+        let mut closed = false;
+        analyzer_instance
+            .analyze_paths_with(&results, |finding| {
+                // Baseline findings are returned with the others so the baseline applies as usual
+                findings.push(finding.clone());
+                let known = baseline.as_ref().is_some_and(|b| b.contains(finding, &project_root));
+                if closed || known {
+                    return;
+                }
+                let written = if !relative_roots.is_empty() {
+                    let mut finding = finding.clone();
+                    relativize_paths(std::slice::from_mut(&mut finding), &relative_roots);
+                    write_ndjson_line(&finding, &project_root)
+                } else {
+                    write_ndjson_line(finding, &project_root)
+                };
+                if let Err(e) = written {
+                    // The reader went away (e.g. piped into `head`); finish the analysis quietly
+                    if e.kind() != std::io::ErrorKind::BrokenPipe {
+                        warn!("Failed to write finding: {e}");
+                    }
+                    closed = true;
+                }
+            })
+            .map(|stats| analyzer::AnalysisResult { findings, stats })
+    } else {
+        analyzer_instance.analyze_paths(&results)
+    };
+    match analysis {
+        Ok(mut analysis_result) => {
             if let Some(pb) = &analysis_spinner {
                 pb.finish_and_clear();
             }
 
+            // Leave out findings already recorded in the baseline
+            if let Some(baseline) = &baseline {
+                let known = baseline.apply(&mut analysis_result, &root.to_string_lossy());
+                if !quiet && known > 0 {
+                    println!(
+                        "{} {} known finding(s) hidden by the baseline\n",
+                        "ℹ".cyan().bold(),
+                        known.to_string().bold()
+                    );
+                }
+            }
+
             let elapsed = start_time.elapsed();
 
             if !quiet {
@@ -195,13 +701,53 @@ pub fn run(
             // Show summary
             if !quiet {
                 print_summary(&analysis_result);
+                if paths.len() > 1 {
+                    print_path_breakdown(&analysis_result, &display_paths, &path_files);
+                }
             }
 
+            relativize_paths(&mut analysis_result.findings, &relative_roots);
+
+            if let Some(stats_path) = &stats {
+                save_stats(&analysis_result, elapsed, stats_path, quiet)?;
+            }
+
+            if let Some(summary_path) = &summary {
+                save_summary(&analysis_result, summary_path, quiet)?;
+            }
+
+            let interactive = interactive && {
+                let terminal = std::io::stdin().is_terminal() && std::io::stdout().is_terminal();
+                if !terminal {
+                    warn!("--interactive needs a terminal, printing the findings instead");
+                }
+                terminal
+            };
+
             // Save or display results
-            if let Some(output_path) = output {
-                save_report(&analysis_result, &output_path, &path, quiet)?;
+            if let Some(output_path) = &output {
+                save_report(&analysis_result, output_path, format, &root, layout, quiet)?;
+            }
+            if interactive {
+                let marks = crate::tui::run(&analysis_result.findings)?;
+                if let Some(baseline_file) = &baseline_file {
+                    save_ignored(&analysis_result.findings, &marks, baseline_file, baseline, &root, quiet)?;
+                }
+            } else if output.is_some() {
+                // Reported to the output file
+            } else if let (Some(format), false) = (format, stream) {
+                print_report(&analysis_result, format, &root, layout)?;
             } else if !quiet {
-                print_findings(&analysis_result, verbose);
+                match group_by {
+                    Some(analyzer::reporting::GroupBy::Rule) => {
+                        print_grouped_findings(&analysis_result, layout, verbose)
+                    }
+                    None => print_findings(&analysis_result, layout, verbose),
+                }
+            }
+
+            if outcome(&analysis_result, fail_on.as_ref()) == Outcome::Failed {
+                return Ok(Outcome::Failed);
             }
         }
         Err(e) => {
@@ -224,6 +770,72 @@ pub fn run(
         );
     }
 
+    Ok(Outcome::Passed)
+}
+
+/// Fail the run if findings reach the requested threshold. The exit happens in
+/// `main`, once temporary directories of extracted archives are removed
+fn outcome(analysis_result: &analyzer::AnalysisResult, fail_on: Option<&analyzer::Severity>) -> Outcome {
+    let Some(threshold) = fail_on else {
+        return Outcome::Passed;
+    };
+    let failing = analysis_result
+        .findings
+        .iter()
+        .filter(|f| f.severity.is_at_least(threshold))
+        .count();
+
+    if failing > 0 {
+        eprintln!(
+            "\n{} {} finding(s) at or above {:?} severity\n",
+            "✗".red().bold(),
+            failing.to_string().red().bold(),
+            threshold
+        );
+        return Outcome::Failed;
+    }
+    Outcome::Passed
+}
+
+/// Add the findings ignored in the interactive mode to the baseline file
+fn save_ignored(
+    findings: &[analyzer::Finding],
+    marks: &[crate::tui::Mark],
+    baseline_file: &Path,
+    baseline: Option<analyzer::baseline::Baseline>,
+    project_root: &Path,
+    quiet: bool,
+) -> Result<()> {
+    let ignored: Vec<&analyzer::Finding> = findings
+        .iter()
+        .zip(marks)
+        .filter(|(_, mark)| **mark == crate::tui::Mark::Ignored)
+        .map(|(finding, _)| finding)
+        .collect();
+    if ignored.is_empty() {
+        return Ok(());
+    }
+
+    let project_root = project_root.to_string_lossy();
+    let mut baseline = match baseline {
+        Some(baseline) => baseline,
+        None if baseline_file.exists() => analyzer::baseline::Baseline::load(baseline_file)?,
+        None => analyzer::baseline::Baseline::from_findings(&[], &project_root),
+    };
+    let added = ignored
+        .into_iter()
+        .filter(|finding| baseline.insert(finding, &project_root))
+        .count();
+    baseline.save(baseline_file)?;
+
+    if !quiet {
+        println!(
+            "{} {} ignored finding(s) added to the baseline: {}",
+            "✓".green().bold(),
+            added.to_string().bold(),
+            baseline_file.display().to_string().bright_green()
+        );
+    }
     Ok(())
 }
 
@@ -244,6 +856,15 @@ fn print_summary(analysis_result: &analyzer::AnalysisResult) {
 
     let total = analysis_result.findings.len();
 
+    let skipped = analysis_result.stats.files_skipped_timeout;
+    if skipped > 0 {
+        println!(
+            "  {} {} file(s) skipped (timeout)\n",
+            "⚠".yellow().bold(),
+            skipped.to_string().yellow().bold()
+        );
+    }
+
     if total == 0 {
         println!("  {} No vulnerabilities found!\n", "✓".green().bold());
         return;
@@ -265,18 +886,11 @@ fn print_summary(analysis_result: &analyzer::AnalysisResult) {
         analyzer::Severity::Informational,
     ] {
         if let Some(count) = severity_counts.get(severity) {
-            let (icon, color_fn): (&str, fn(&str) -> ColoredString) = match severity {
-                analyzer::Severity::High => ("🔴", |s: &str| s.red().bold()),
-                analyzer::Severity::Medium => ("🟡", |s: &str| s.yellow().bold()),
-                analyzer::Severity::Low => ("🟢", |s: &str| s.blue().bold()),
-                analyzer::Severity::Informational => ("ℹ️", |s: &str| s.cyan()),
-            };
-
             println!(
                 "  {} {:<15} {}",
-                icon,
+                severity_icon(severity),
                 format!("{:?}:", severity),
-                color_fn(&count.to_string())
+                severity_style(severity).emphasize(&count.to_string())
             );
         }
     }
@@ -284,7 +898,133 @@ fn print_summary(analysis_result: &analyzer::AnalysisResult) {
     println!();
 }
 
-fn print_findings(analysis_result: &analyzer::AnalysisResult, verbose: bool) {
+/// Local directory of the templates, downloading them first when given a URL
+pub(crate) fn resolve_templates(
+    templates: Option<PathBuf>,
+    remote: &analyzer::remote_templates::RemoteOptions,
+    quiet: bool,
+) -> Result<Option<PathBuf>> {
+    let Some(location) = templates else {
+        return Ok(None);
+    };
+    let location = location.to_string_lossy().to_string();
+    if !analyzer::remote_templates::is_remote(&location) {
+        return Ok(Some(PathBuf::from(location)));
+    }
+    if !quiet {
+        println!(
+            "{} {} templates: {}",
+            "→".cyan().bold(),
+            if remote.offline { "Using cached" } else { "Fetching" },
+            location.bright_blue()
+        );
+    }
+    analyzer::remote_templates::fetch(&location, remote).map(Some)
+}
+
+/// Root that finding paths are made relative to in reports and baseline
+/// fingerprints: the git top-level of the analyzed paths, or the working
+/// directory outside of a repository. Unlike their common root, it does not
+/// change with the paths passed on a run.
+pub(crate) fn project_root(paths: &[PathBuf]) -> PathBuf {
+    let dir = if paths.is_empty() { PathBuf::from(".") } else { common_root(paths) };
+    ast::git::toplevel(&dir)
+        .or_else(|_| std::env::current_dir().map_err(anyhow::Error::from))
+        .unwrap_or(dir)
+}
+
+/// Deepest directory containing every path, so a single path is its own root
+fn common_root(paths: &[PathBuf]) -> PathBuf {
+    let mut root = paths.first().cloned().unwrap_or_default();
+    for path in &paths[1..] {
+        root = root
+            .components()
+            .zip(path.components())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a)
+            .collect();
+    }
+    if root.as_os_str().is_empty() {
+        PathBuf::from(".")
+    } else {
+        root
+    }
+}
+
+/// Rewrite finding files relative to the first root containing them; files
+/// outside of every root are kept as is
+fn relativize_paths(findings: &mut [analyzer::Finding], roots: &[PathBuf]) {
+    for finding in findings {
+        let file = Path::new(&finding.location.file);
+        if let Some(relative) = roots.iter().find_map(|root| file.strip_prefix(root).ok()) {
+            finding.location.file = relative.to_string_lossy().to_string();
+        }
+    }
+}
+
+/// Print the files and findings of each analyzed path
+fn print_path_breakdown(
+    analysis_result: &analyzer::AnalysisResult,
+    paths: &[PathBuf],
+    path_files: &[HashSet<String>],
+) {
+    println!("  {}\n", "By path:".bold());
+
+    // A finding under overlapping paths (e.g. `programs/` and `programs/vault/`)
+    // is counted once, under the longest path that contains its file
+    let owner = |file: &String| {
+        paths
+            .iter()
+            .zip(path_files)
+            .enumerate()
+            .filter(|(_, (_, files))| files.contains(file))
+            .max_by_key(|(_, (path, _))| path.components().count())
+            .map(|(index, _)| index)
+    };
+
+    for (index, (path, files)) in paths.iter().zip(path_files).enumerate() {
+        let mut counts: HashMap<&analyzer::Severity, usize> = HashMap::new();
+        for finding in &analysis_result.findings {
+            if owner(&finding.location.file) == Some(index) {
+                *counts.entry(&finding.severity).or_default() += 1;
+            }
+        }
+        let total: usize = counts.values().sum();
+
+        let breakdown: Vec<String> = [
+            analyzer::Severity::High,
+            analyzer::Severity::Medium,
+            analyzer::Severity::Low,
+            analyzer::Severity::Informational,
+        ]
+        .iter()
+        .filter_map(|severity| {
+            counts
+                .get(severity)
+                .map(|count| format!("{count} {}", format!("{severity:?}").to_lowercase()))
+        })
+        .collect();
+
+        println!(
+            "  {} {} ({} file(s)): {} finding(s){}",
+            "•".dimmed(),
+            path.display().to_string().bright_blue(),
+            files.len(),
+            total.to_string().bold(),
+            if breakdown.is_empty() {
+                String::new()
+            } else {
+                format!(" - {}", breakdown.join(", "))
+            }
+        );
+    }
+
+    println!();
+}
+
+fn print_findings(analysis_result: &analyzer::AnalysisResult, layout: Layout, verbose: bool) {
+    use analyzer::reporting::SortOrder;
+
     if analysis_result.findings.is_empty() {
         return;
     }
@@ -292,111 +1032,287 @@ fn print_findings(analysis_result: &analyzer::AnalysisResult, verbose: bool) {
     println!("{}", "═".repeat(70).dimmed());
     println!("\n{}\n", "🔍 DETAILED FINDINGS".bright_white().bold());
 
-    // Group by severity
-    let mut findings_by_severity = HashMap::new();
-    for finding in &analysis_result.findings {
-        findings_by_severity
-            .entry(&finding.severity)
-            .or_insert_with(Vec::new)
-            .push(finding);
+    let sort = layout.sort;
+    let (findings, hidden) = displayed_findings(analysis_result, layout);
+
+    // A section header is printed whenever the sort key changes
+    let mut section: Option<&str> = None;
+    for (index, finding) in findings.iter().enumerate() {
+        let severity = &finding.severity;
+        let key = match sort {
+            SortOrder::Severity => severity_label(severity),
+            SortOrder::File => finding.location.file.as_str(),
+            SortOrder::Rule => finding.rule_id.as_str(),
+        };
+        if section != Some(key) {
+            match sort {
+                SortOrder::Severity => println!(
+                    "{} {}\n",
+                    severity_icon(severity),
+                    severity_style(severity).paint(&format!("{:?} Severity", severity)).bold()
+                ),
+                SortOrder::File => println!("📄 {}\n", key.bright_blue().bold()),
+                SortOrder::Rule => println!("📋 {}\n", key.bright_white().bold()),
+            }
+            section = Some(key);
+        }
+
+        let style = severity_style(severity);
+        let description_colored = style.emphasize(&finding.description);
+        let location = format!("{}:{}", finding.location.file, finding.location.position());
+        let location_colored = style.paint(&location);
+
+        println!(
+            "  {}. {}",
+            (index + 1).to_string().bold(),
+            description_colored
+        );
+        println!("     📍 {location_colored}");
+
+        if verbose {
+            if let Some(context) = &finding.context {
+                print_source_context(context, finding);
+            } else if let Some(snippet) = &finding.code_snippet {
+                println!("     {} {}", "Code:".dimmed(), style.paint(snippet));
+            }
+            if !finding.recommendations.is_empty() {
+                println!("     💡 {}", finding.recommendations.join(", ").green());
+            }
+        }
+
+        println!();
     }
 
-    let mut index = 1;
-    for severity in &[
-        analyzer::Severity::High,
-        analyzer::Severity::Medium,
-        analyzer::Severity::Low,
-        analyzer::Severity::Informational,
-    ] {
-        if let Some(findings) = findings_by_severity.get(severity) {
-            let (icon, header_color): (&str, fn(&str) -> ColoredString) = match severity {
-                analyzer::Severity::High => ("🔴", |s: &str| s.red().bold()),
-                analyzer::Severity::Medium => ("🟡", |s: &str| s.yellow().bold()),
-                analyzer::Severity::Low => ("🟢", |s: &str| s.blue().bold()),
-                analyzer::Severity::Informational => ("ℹ️", |s: &str| s.cyan().bold()),
-            };
+    print_hidden_note(hidden, layout);
+}
 
-            println!(
-                "{} {}\n",
-                icon,
-                header_color(&format!("{:?} Severity", severity))
-            );
+/// Findings to display: sorted, and limited per severity with `--top`.
+/// Also returns how many findings were left out.
+fn displayed_findings(analysis_result: &analyzer::AnalysisResult, layout: Layout) -> (Vec<analyzer::Finding>, usize) {
+    let mut findings = analysis_result.findings.clone();
+    analyzer::reporting::sort_findings(&mut findings, layout.sort);
+    let hidden = match layout.top {
+        Some(top) => analyzer::reporting::limit_per_severity(&mut findings, top).values().sum(),
+        None => 0,
+    };
+    (findings, hidden)
+}
 
-            for finding in findings {
-                // Aplicar color según severidad
-                let description_colored = match severity {
-                    analyzer::Severity::High => finding.description.red().bold().to_string(),
-                    analyzer::Severity::Medium => finding.description.yellow().bold().to_string(),
-                    analyzer::Severity::Low => finding.description.blue().bold().to_string(),
-                    analyzer::Severity::Informational => finding.description.cyan().to_string(),
-                };
-                
-                let location_colored = match severity {
-                    analyzer::Severity::High => format!("{}:{}", finding.location.file, finding.location.line).red().to_string(),
-                    analyzer::Severity::Medium => format!("{}:{}", finding.location.file, finding.location.line).yellow().to_string(),
-                    analyzer::Severity::Low => format!("{}:{}", finding.location.file, finding.location.line).blue().to_string(),
-                    analyzer::Severity::Informational => format!("{}:{}", finding.location.file, finding.location.line).cyan().to_string(),
-                };
+/// Tell how many findings `--top` left out of the terminal output
+fn print_hidden_note(hidden: usize, layout: Layout) {
+    if let (Some(top), true) = (layout.top, hidden > 0) {
+        println!(
+            "  {}\n",
+            format!("... {hidden} more finding(s) not shown (--top {top} per severity)").dimmed()
+        );
+    }
+}
 
-                println!(
-                    "  {}. {}",
-                    index.to_string().bold(),
-                    description_colored
-                );
-                println!(
-                    "     {} {}",
-                    "📍",
-                    location_colored
-                );
+/// Print the lines around a finding in a rustc-like gutter, marking the
+/// offending lines with `>` and highlighting them in the severity color
+fn print_source_context(context: &analyzer::SourceContext, finding: &analyzer::Finding) {
+    let marked = analyzer::SourceContext::marked_lines(&finding.location);
+    let width = context.gutter_width();
 
-                if verbose {
-                    if let Some(snippet) = &finding.code_snippet {
-                        let snippet_colored = match severity {
-                            analyzer::Severity::High => snippet.red().to_string(),
-                            analyzer::Severity::Medium => snippet.yellow().to_string(),
-                            analyzer::Severity::Low => snippet.blue().to_string(),
-                            analyzer::Severity::Informational => snippet.cyan().to_string(),
-                        };
-                        println!("     {} {}", "Code:".dimmed(), snippet_colored);
-                    }
-                    if !finding.recommendations.is_empty() {
-                        println!(
-                            "     {} {}",
-                            "💡",
-                            finding.recommendations.join(", ").green()
-                        );
-                    }
-                }
+    for (number, line) in context.numbered_lines() {
+        let gutter = format!("{:>width$} |", number, width = width);
+        if marked.contains(&number) {
+            let highlighted = severity_style(&finding.severity).paint(line).bold();
+            println!("     {} {} {}", ">".bold(), gutter.bright_blue().bold(), highlighted);
+        } else {
+            println!("       {} {}", gutter.bright_blue(), line.dimmed());
+        }
+    }
+}
 
-                println!();
-                index += 1;
+/// Stable name of a severity, used as a section key
+fn severity_label(severity: &analyzer::Severity) -> &'static str {
+    match severity {
+        analyzer::Severity::High => "high",
+        analyzer::Severity::Medium => "medium",
+        analyzer::Severity::Low => "low",
+        analyzer::Severity::Informational => "informational",
+    }
+}
+
+fn print_grouped_findings(analysis_result: &analyzer::AnalysisResult, layout: Layout, verbose: bool) {
+    if analysis_result.findings.is_empty() {
+        return;
+    }
+
+    println!("{}", "═".repeat(70).dimmed());
+    println!("\n{}\n", "🔍 FINDINGS BY RULE".bright_white().bold());
+
+    let limit = layout.group_limit;
+    let (findings, hidden) = displayed_findings(analysis_result, layout);
+    let groups = analyzer::reporting::group_findings_by_rule(&findings);
+    for (index, group) in groups.iter().enumerate() {
+        let style = severity_style(&group.severity);
+
+        println!(
+            "  {}. {} {} {}",
+            (index + 1).to_string().bold(),
+            style.paint(&format!("[{:?}] {}", group.severity, group.title)).bold(),
+            format!("({})", group.rule_id).dimmed(),
+            format!("× {}", group.findings.len()).bold()
+        );
+        println!("     {}", group.description.dimmed());
+
+        for finding in group.findings.iter().take(limit) {
+            println!(
+                "     📍 {}",
+                style.paint(&format!("{}:{}", finding.location.file, finding.location.position()))
+            );
+        }
+        if group.findings.len() > limit {
+            println!(
+                "     {}",
+                format!("... and {} more", group.findings.len() - limit).dimmed()
+            );
+        }
+
+        if verbose {
+            if let Some(first) = group.findings.first() {
+                if !first.recommendations.is_empty() {
+                    println!("     💡 {}", first.recommendations.join(", ").green());
+                }
             }
         }
+
+        println!();
     }
+
+    print_hidden_note(hidden, layout);
 }
 
-fn save_report(
+/// Contents of the `--stats` file
+#[derive(Serialize)]
+struct StatsFile<'a> {
+    #[serde(flatten)]
+    stats: &'a analyzer::AnalysisStats,
+    total_findings: usize,
+    /// Duration of the whole run, including file discovery and parsing
+    wall_clock_ms: u64,
+}
+
+fn save_stats(
+    analysis_result: &analyzer::AnalysisResult,
+    elapsed: std::time::Duration,
+    stats_path: &Path,
+    quiet: bool,
+) -> Result<()> {
+    let stats_file = StatsFile {
+        stats: &analysis_result.stats,
+        total_findings: analysis_result.findings.len(),
+        wall_clock_ms: u64::try_from(elapsed.as_millis())?,
+    };
+    let json = serde_json::to_string_pretty(&stats_file)?;
+    fs::write(stats_path, json)
+        .with_context(|| format!("Failed to write stats to {}", stats_path.display()))?;
+
+    if !quiet {
+        println!(
+            "{} Stats saved to: {}\n",
+            "📊".bold(),
+            stats_path.display().to_string().bright_green()
+        );
+    }
+    Ok(())
+}
+
+fn save_summary(
     analysis_result: &analyzer::AnalysisResult,
-    output_path: &PathBuf,
-    project_path: &PathBuf,
+    summary_path: &Path,
     quiet: bool,
 ) -> Result<()> {
-    let report_generator = analyzer::reporting::ReportGenerator::new(
+    analyzer::reporting::ReportGenerator::new(analysis_result.findings.clone(), String::new())
+        .with_stats(analysis_result.stats.clone())
+        .save_summary(&summary_path.to_string_lossy())
+        .with_context(|| format!("Failed to write summary to {}", summary_path.display()))?;
+
+    if !quiet {
+        println!(
+            "{} Summary saved to: {}\n",
+            "📊".bold(),
+            summary_path.display().to_string().bright_green()
+        );
+    }
+    Ok(())
+}
+
+fn report_generator(
+    analysis_result: &analyzer::AnalysisResult,
+    project_path: &Path,
+    layout: Layout,
+) -> analyzer::reporting::ReportGenerator {
+    analyzer::reporting::ReportGenerator::new(
         analysis_result.findings.clone(),
         project_path.to_string_lossy().to_string(),
-    );
+    )
+    .with_stats(analysis_result.stats.clone())
+    .with_group_by(layout.group_by)
+    .with_group_limit(layout.group_limit)
+    .with_sort(layout.sort)
+    .with_top(layout.top)
+}
 
-    let output_str = output_path.to_string_lossy();
-    let final_path = if output_str.ends_with(".md") || output_str.ends_with(".markdown") {
-        output_path.clone()
+fn print_report(
+    analysis_result: &analyzer::AnalysisResult,
+    format: analyzer::reporting::ReportFormat,
+    project_path: &Path,
+    layout: Layout,
+) -> Result<()> {
+    use std::io::Write;
+
+    let report = report_generator(analysis_result, project_path, layout).generate_report(format)?;
+    // Line-oriented reports (NDJSON) already end each line
+    let written = if report.is_empty() || report.ends_with('\n') {
+        write!(std::io::stdout().lock(), "{report}")
     } else {
-        let mut md_path = output_path.clone();
-        md_path.set_extension("md");
-        md_path
+        writeln!(std::io::stdout().lock(), "{report}")
+    };
+    match written {
+        // The reader went away (e.g. piped into `head`), nothing left to do
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
+
+/// Print a finding as one NDJSON line, flushed so consumers see it right away
+fn write_ndjson_line(finding: &analyzer::Finding, project_root: &str) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let line = analyzer::reporting::ndjson_line(finding, project_root)?;
+    let mut stdout = std::io::stdout().lock();
+    writeln!(stdout, "{line}")?;
+    stdout.flush()
+}
+
+fn save_report(
+    analysis_result: &analyzer::AnalysisResult,
+    output_path: &Path,
+    format: Option<analyzer::reporting::ReportFormat>,
+    project_path: &Path,
+    layout: Layout,
+    quiet: bool,
+) -> Result<()> {
+    use analyzer::reporting::ReportFormat;
+
+    let report_generator = report_generator(analysis_result, project_path, layout);
+
+    // An explicit --format wins over the extension; unknown extensions fall back to Markdown
+    let output_str = output_path.to_string_lossy();
+    let (format, final_path) = match format.or_else(|| ReportFormat::from_extension(&output_str)) {
+        Some(format) => (format, output_path.to_path_buf()),
+        None => (ReportFormat::Markdown, output_path.with_extension("md")),
     };
 
-    match report_generator.save_markdown_report(&final_path.to_string_lossy()) {
-        Ok(()) => {
+    let saved = report_generator
+        .save_report(format, &final_path.to_string_lossy())
+        .map(|()| final_path);
+
+    match saved {
+        Ok(final_path) => {
             if !quiet {
                 println!(
                     "\n{} Report saved to: {}\n",
@@ -416,3 +1332,32 @@ fn save_report(
         }
     }
 }
+
+/// Line filter built from `--lines` ranges and the hunks of a `--diff` file,
+/// or `None` when neither is given
+fn load_line_filter(
+    lines: Vec<analyzer::line_filter::LineRange>,
+    diff: Option<&Path>,
+) -> Result<Option<analyzer::line_filter::LineFilter>> {
+    if lines.is_empty() && diff.is_none() {
+        return Ok(None);
+    }
+
+    let mut filter = analyzer::line_filter::LineFilter::from_ranges(lines);
+    if let Some(diff) = diff {
+        let content = if diff == Path::new("-") {
+            let mut content = String::new();
+            std::io::stdin()
+                .read_to_string(&mut content)
+                .context("Failed to read the diff from stdin")?;
+            content
+        } else {
+            fs::read_to_string(diff)
+                .with_context(|| format!("Failed to read diff {}", diff.display()))?
+        };
+        filter.extend(analyzer::line_filter::LineFilter::from_unified_diff(&content));
+    }
+
+    debug!("Reporting findings inside line ranges of {} file(s)", filter.file_count());
+    Ok(Some(filter))
+}
@@ -0,0 +1,118 @@
+use anyhow::Result;
+use clap::Args;
+use colored::*;
+use rust_solana_analyzer::{analyzer, ast};
+use std::path::PathBuf;
+
+/// Arguments of the `baseline` command
+#[derive(Args, Debug)]
+pub struct BaselineArgs {
+    /// Path to Solana project directory
+    #[arg(short, long, value_name = "PATH")]
+    pub path: PathBuf,
+
+    /// Baseline file to write
+    #[arg(short, long, value_name = "FILE", default_value = analyzer::baseline::DEFAULT_BASELINE_FILE)]
+    pub output: PathBuf,
+
+    /// Directory of YAML/TOML custom rule templates, or an https:// URL of them
+    #[arg(short, long, value_name = "DIR|URL")]
+    pub templates: Option<PathBuf>,
+
+    /// Expected SHA-256 of the --templates URL download
+    #[arg(long, value_name = "HEX")]
+    pub templates_sha256: Option<String>,
+
+    /// Only use the cached copy of a --templates URL, never download it
+    #[arg(long)]
+    pub offline: bool,
+
+    /// Directory the --templates URL download is cached in
+    #[arg(long, value_name = "DIR", default_value = analyzer::cache::DEFAULT_CACHE_DIR)]
+    pub cache_dir: PathBuf,
+
+    /// Let custom rules replace built-in rules with the same id
+    #[arg(long)]
+    pub allow_override: bool,
+
+    /// Only analyze files matching this glob, relative to the analysis root (repeatable)
+    #[arg(long, value_name = "GLOB")]
+    pub include: Vec<String>,
+
+    /// Skip files or directories matching this glob, relative to the analysis root (repeatable)
+    #[arg(long, value_name = "GLOB")]
+    pub exclude: Vec<String>,
+
+    /// Analyze files ignored by .gitignore
+    #[arg(long)]
+    pub no_gitignore: bool,
+
+    /// Analyze files under tests/, benches/ and examples/ directories (skipped by default)
+    #[arg(long)]
+    pub include_tests: bool,
+}
+
+pub fn run(args: BaselineArgs, quiet: bool) -> Result<()> {
+    if !args.path.is_dir() {
+        eprintln!(
+            "{} Path is not a directory: {}",
+            "✗".red().bold(),
+            args.path.display().to_string().yellow()
+        );
+        anyhow::bail!("Path {} is not a directory", args.path.display());
+    }
+
+    // Fingerprints are relative to the same root as in `analyze`, so the baseline
+    // matches whichever paths are analyzed later
+    let root = super::analyze::project_root(std::slice::from_ref(&args.path));
+
+    let remote = analyzer::remote_templates::RemoteOptions {
+        cache_dir: args.cache_dir,
+        sha256: args.templates_sha256,
+        offline: args.offline,
+    };
+    let templates = super::analyze::resolve_templates(args.templates, &remote, quiet)?;
+
+    let options = analyzer::AnalysisOptions {
+        custom_templates_path: templates.map(|p| p.to_string_lossy().to_string()),
+        include_globs: args.include,
+        exclude_globs: args.exclude,
+        respect_gitignore: !args.no_gitignore,
+        include_tests: args.include_tests,
+        project_root: Some(root.to_string_lossy().to_string()),
+        rule_conflicts: if args.allow_override {
+            analyzer::RuleConflictPolicy::Override
+        } else {
+            analyzer::RuleConflictPolicy::Warn
+        },
+        include_rule_types: vec![
+            analyzer::RuleType::Solana,
+            analyzer::RuleType::Anchor,
+            analyzer::RuleType::General,
+        ],
+        ..Default::default()
+    };
+
+    let files = ast::parser::collect_rust_files(&args.path, &options.walk_options())?;
+    let analyzer_instance = analyzer::create_analyzer_with_options(options);
+    let analysis_result = analyzer_instance
+        .analyze_paths(&files)
+        .map_err(|e| anyhow::anyhow!("Analysis failed: {e}"))?;
+
+    let baseline = analyzer::baseline::Baseline::from_findings(
+        &analysis_result.findings,
+        &root.to_string_lossy(),
+    );
+    baseline.save(&args.output)?;
+
+    if !quiet {
+        println!(
+            "\n{} Baseline with {} finding(s) saved to: {}\n",
+            "✓".green().bold(),
+            baseline.findings.len().to_string().bold(),
+            args.output.display().to_string().bright_green()
+        );
+    }
+
+    Ok(())
+}
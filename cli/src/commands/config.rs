@@ -1,8 +1,12 @@
 use anyhow::Result;
+use clap::Args;
 use colored::*;
+use rust_solana_analyzer::analyzer;
 use serde::Deserialize;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::theme::{self, Palette, Theme};
 
 #[derive(Debug, Deserialize)]
 struct Config {
@@ -18,11 +22,35 @@ struct AnalysisConfig {
     path: String,
     #[serde(default)]
     generate_ast: bool,
+    /// Custom templates directory, absolute or relative to the config file, or an https:// URL
+    #[serde(default)]
+    templates: Option<PathBuf>,
+    /// Expected SHA-256 of the templates URL download
+    #[serde(default)]
+    templates_sha256: Option<String>,
+    /// Only use the cached copy of the templates URL, never download it
+    #[serde(default)]
+    offline: bool,
+    /// Directory of the analysis cache and of the templates URL download
+    #[serde(default)]
+    cache_dir: Option<PathBuf>,
+    /// What to do when a custom rule reuses the id of a built-in rule (warn or error)
+    #[serde(default)]
+    rule_conflicts: Option<String>,
+    /// Let custom rules replace built-in rules with the same id
+    #[serde(default)]
+    allow_override: bool,
+    /// Analyze files under tests/, benches/ and examples/ directories
+    #[serde(default)]
+    include_tests: bool,
 }
 
 #[derive(Debug, Deserialize)]
 struct OutputConfig {
     report_file: String,
+    /// Exit with code 2 if any finding at or above this severity is reported
+    #[serde(default)]
+    fail_on: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -33,6 +61,8 @@ struct RulesConfig {
     ignore_rules: Vec<String>,
     #[serde(default)]
     include_rule_types: Vec<String>,
+    #[serde(default)]
+    include_categories: Vec<String>,
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -43,9 +73,219 @@ struct DisplayConfig {
     quiet: bool,
     #[serde(default)]
     no_color: bool,
+    /// Don't print the ASCII banner
+    #[serde(default)]
+    no_banner: bool,
+    /// Report file paths relative to the analysis root
+    #[serde(default)]
+    relative_paths: bool,
+    /// Severity color theme (default, high-contrast or none)
+    #[serde(default)]
+    theme: Option<Theme>,
+    /// Colors overriding the theme for single severities
+    #[serde(default)]
+    colors: ColorsConfig,
+}
+
+/// The `[display.colors]` table: an ANSI color name per severity
+#[derive(Debug, Deserialize, Default)]
+struct ColorsConfig {
+    high: Option<String>,
+    medium: Option<String>,
+    low: Option<String>,
+    informational: Option<String>,
+}
+
+impl ColorsConfig {
+    fn entries(&self) -> [(analyzer::Severity, &Option<String>); 4] {
+        [
+            (analyzer::Severity::High, &self.high),
+            (analyzer::Severity::Medium, &self.medium),
+            (analyzer::Severity::Low, &self.low),
+            (analyzer::Severity::Informational, &self.informational),
+        ]
+    }
+}
+
+/// Analysis flags of `eloizer config` merged over the configuration file:
+/// list flags extend the lists of the file, the others replace its values
+#[derive(Args, Debug, Default)]
+pub struct ConfigOverrides {
+    /// Severities to ignore on top of rules.ignore_severities (comma-separated)
+    #[arg(short, long, value_name = "SEVERITIES")]
+    pub ignore: Option<String>,
+
+    /// Rule IDs to ignore on top of rules.ignore_rules (comma-separated)
+    #[arg(long, value_name = "RULE_IDS")]
+    pub ignore_rules: Option<String>,
+
+    /// Report file path replacing output.report_file
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Templates directory (relative to the current directory) or URL replacing analysis.templates
+    #[arg(short, long, value_name = "DIR|URL")]
+    pub templates: Option<PathBuf>,
+
+    /// Only use the cached copy of a templates URL, never download it
+    #[arg(long)]
+    pub offline: bool,
+
+    /// Cache directory replacing analysis.cache_dir
+    #[arg(long, value_name = "DIR")]
+    pub cache_dir: Option<PathBuf>,
+}
+
+impl Config {
+    /// Apply the command line on top of the file, so the precedence is
+    /// CLI > config > defaults
+    fn merge(&mut self, overrides: &ConfigOverrides, cli_verbose: bool, cli_quiet: bool) -> Result<()> {
+        if let Some(ignore) = &overrides.ignore {
+            extend_list(&mut self.rules.ignore_severities, ignore);
+        }
+        if let Some(ignore_rules) = &overrides.ignore_rules {
+            extend_list(&mut self.rules.ignore_rules, ignore_rules);
+        }
+        if let Some(output) = &overrides.output {
+            self.output.report_file = output.display().to_string();
+        }
+        if let Some(templates) = &overrides.templates {
+            // Absolute, so it is not resolved against the config file
+            self.analysis.templates = Some(if is_url(templates) {
+                templates.clone()
+            } else {
+                std::path::absolute(templates)?
+            });
+        }
+        self.analysis.offline |= overrides.offline;
+        if let Some(cache_dir) = &overrides.cache_dir {
+            self.analysis.cache_dir = Some(cache_dir.clone());
+        }
+        self.display.verbose |= cli_verbose;
+        self.display.quiet |= cli_quiet;
+        Ok(())
+    }
+}
+
+/// Append the entries of a comma-separated list that are not in `list` yet
+fn extend_list(list: &mut Vec<String>, values: &str) {
+    for value in values.split(',').map(str::trim).filter(|value| !value.is_empty()) {
+        if !list.iter().any(|existing| existing.eq_ignore_ascii_case(value)) {
+            list.push(value.to_string());
+        }
+    }
+}
+
+/// Palette of the theme with the `[display.colors]` overrides applied
+fn palette(display: &DisplayConfig) -> Result<Palette> {
+    let mut palette = display.theme.unwrap_or_default().palette();
+    for (severity, color) in display.colors.entries() {
+        if let Some(color) = color {
+            *palette.color_mut(&severity) = theme::parse_color(color)
+                .map_err(|e| anyhow::anyhow!("display.colors.{}: {e}", format!("{severity:?}").to_lowercase()))?;
+        }
+    }
+    Ok(palette)
+}
+
+pub fn run(
+    config_path: PathBuf,
+    overrides: ConfigOverrides,
+    cli_verbose: bool,
+    cli_quiet: bool,
+) -> Result<super::analyze::Outcome> {
+    let mut config = load(&config_path)?;
+    config.merge(&overrides, cli_verbose, cli_quiet)?;
+
+    // A --theme flag has already set the palette and wins over the file
+    match palette(&config.display) {
+        Ok(palette) => theme::set_palette(palette),
+        Err(e) => {
+            eprintln!("{} {}", "✗".red().bold(), e.to_string().red());
+            return Err(e);
+        }
+    }
+
+    println!(
+        "\n{} Using configuration: {}\n",
+        "⚙".cyan().bold(),
+        config_path.display().to_string().bright_blue()
+    );
+
+    // Prepare parameters for analyze command
+    let path = PathBuf::from(&config.analysis.path);
+    let templates = templates_path(&config_path, &config);
+    let output = Some(PathBuf::from(&config.output.report_file));
+    let ast = config.analysis.generate_ast;
+
+    let ignore = if config.rules.ignore_severities.is_empty() {
+        None
+    } else {
+        Some(config.rules.ignore_severities.join(","))
+    };
+
+    let ignore_rules = if config.rules.ignore_rules.is_empty() {
+        None
+    } else {
+        Some(config.rules.ignore_rules.join(","))
+    };
+
+    let rule_types = if config.rules.include_rule_types.is_empty() {
+        None
+    } else {
+        Some(config.rules.include_rule_types.join(","))
+    };
+
+    let fail_on = config
+        .output
+        .fail_on
+        .as_deref()
+        .map(str::parse::<analyzer::Severity>)
+        .transpose()
+        .map_err(anyhow::Error::msg)?;
+
+    let rule_conflicts = config
+        .analysis
+        .rule_conflicts
+        .as_deref()
+        .map(str::parse::<analyzer::RuleConflictPolicy>)
+        .transpose()
+        .map_err(anyhow::Error::msg)?
+        .unwrap_or_default();
+
+    let category = if config.rules.include_categories.is_empty() {
+        None
+    } else {
+        Some(config.rules.include_categories.join(","))
+    };
+
+    // Run analysis
+    let args = super::analyze::AnalyzeArgs {
+        path: vec![path],
+        templates,
+        templates_sha256: config.analysis.templates_sha256.clone(),
+        offline: config.analysis.offline,
+        rule_conflicts,
+        allow_override: config.analysis.allow_override,
+        include_tests: config.analysis.include_tests,
+        output,
+        ast,
+        ignore,
+        ignore_rules,
+        rule_types,
+        category,
+        fail_on,
+        cache_dir: cache_dir(&config),
+        group_limit: analyzer::reporting::DEFAULT_GROUP_LIMIT,
+        no_banner: config.display.no_banner,
+        relative_paths: config.display.relative_paths,
+        ..Default::default()
+    };
+    super::analyze::run(args, config.display.verbose, config.display.quiet)
 }
 
-pub fn run(config_path: PathBuf, cli_verbose: bool, cli_quiet: bool) -> Result<()> {
+/// Read and parse a configuration file, reporting problems on stderr
+fn load(config_path: &Path) -> Result<Config> {
     if !config_path.exists() {
         eprintln!(
             "{} Configuration file not found: {}",
@@ -60,7 +300,7 @@ pub fn run(config_path: PathBuf, cli_verbose: bool, cli_quiet: bool) -> Result<(
     }
 
     // Read and parse config
-    let config_content = fs::read_to_string(&config_path)?;
+    let config_content = fs::read_to_string(config_path)?;
     let config: Config = toml::from_str(&config_content).map_err(|e| {
         eprintln!(
             "{} Failed to parse configuration file: {}",
@@ -70,34 +310,196 @@ pub fn run(config_path: PathBuf, cli_verbose: bool, cli_quiet: bool) -> Result<(
         e
     })?;
 
+    Ok(config)
+}
+
+/// Resolve the templates directory relative to the config file; URLs are kept as is
+fn templates_path(config_path: &Path, config: &Config) -> Option<PathBuf> {
+    config.analysis.templates.as_ref().map(|templates| {
+        if templates.is_absolute() || is_url(templates) {
+            templates.clone()
+        } else {
+            config_path
+                .parent()
+                .unwrap_or_else(|| Path::new(""))
+                .join(templates)
+        }
+    })
+}
+
+fn is_url(templates: &Path) -> bool {
+    analyzer::remote_templates::is_remote(&templates.to_string_lossy())
+}
+
+/// Cache directory of the configuration, the same default as `analyze --cache-dir`
+fn cache_dir(config: &Config) -> PathBuf {
+    config
+        .analysis
+        .cache_dir
+        .clone()
+        .unwrap_or_else(|| analyzer::cache::DEFAULT_CACHE_DIR.into())
+}
+
+/// Templates URL options of the configuration
+fn remote_options(config: &Config) -> analyzer::remote_templates::RemoteOptions {
+    analyzer::remote_templates::RemoteOptions {
+        cache_dir: cache_dir(config),
+        sha256: config.analysis.templates_sha256.clone(),
+        offline: config.analysis.offline,
+    }
+}
+
+/// Validate a configuration file, with the command line overrides applied, and
+/// print a checklist without running the analysis
+pub fn check(config_path: PathBuf, overrides: ConfigOverrides) -> Result<()> {
+    let mut config = load(&config_path)?;
+    config.merge(&overrides, false, false)?;
+
     println!(
-        "\n{} Using configuration: {}\n",
+        "\n{} Checking configuration: {}\n",
         "⚙".cyan().bold(),
         config_path.display().to_string().bright_blue()
     );
 
-    // Prepare parameters for analyze command
-    let path = PathBuf::from(&config.analysis.path);
-    let templates = None; // TODO: Add templates to config file
-    let output = Some(PathBuf::from(&config.output.report_file));
-    let ast = config.analysis.generate_ast;
-
-    let ignore = if config.rules.ignore_severities.is_empty() {
-        None
-    } else {
-        Some(config.rules.ignore_severities.join(","))
+    let mut problems = 0;
+    let mut report = |ok: bool, message: String| {
+        if ok {
+            println!("  {} {}", "✓".green().bold(), message);
+        } else {
+            println!("  {} {}", "✗".red().bold(), message.red());
+            problems += 1;
+        }
     };
 
-    let ignore_rules = if config.rules.ignore_rules.is_empty() {
-        None
-    } else {
-        Some(config.rules.ignore_rules.join(","))
+    report(true, "Configuration file parsed".to_string());
+
+    let path = Path::new(&config.analysis.path);
+    report(
+        path.exists(),
+        format!("analysis.path '{}' {}", path.display(), if path.exists() { "exists" } else { "does not exist" }),
+    );
+
+    let rule_conflicts = match &config.analysis.rule_conflicts {
+        Some(policy) => match policy.parse::<analyzer::RuleConflictPolicy>() {
+            Ok(parsed) => {
+                report(true, format!("analysis.rule_conflicts '{policy}' is a known policy"));
+                parsed
+            }
+            Err(e) => {
+                report(false, format!("analysis.rule_conflicts: {e}"));
+                analyzer::RuleConflictPolicy::default()
+            }
+        },
+        None => analyzer::RuleConflictPolicy::default(),
     };
 
-    // CLI flags override config
-    let verbose = cli_verbose || config.display.verbose;
-    let quiet = cli_quiet || config.display.quiet;
+    let analyzer_instance = analyzer::create_analyzer();
+    let mut template_ids = Vec::new();
+    if let Some(location) = templates_path(&config_path, &config) {
+        let templates = if is_url(&location) {
+            match analyzer::remote_templates::fetch(&location.to_string_lossy(), &remote_options(&config)) {
+                Ok(dir) => {
+                    report(true, format!("analysis.templates '{}' is available", location.display()));
+                    Some(dir)
+                }
+                Err(e) => {
+                    report(false, format!("analysis.templates: {e:#}"));
+                    None
+                }
+            }
+        } else {
+            let found = location.is_dir();
+            report(
+                found,
+                format!(
+                    "analysis.templates '{}' {}",
+                    location.display(),
+                    if found { "exists" } else { "is not a directory" }
+                ),
+            );
+            found.then_some(location)
+        };
 
-    // Run analysis
-    super::analyze::run(path, templates, output, ast, ignore, ignore_rules, verbose, quiet)
+        if let Some(templates) = templates {
+            match analyzer::templates::load_templates(&templates) {
+                Ok(rules) => {
+                    report(true, format!("analysis.templates defines {} valid rule(s)", rules.len()));
+                    template_ids.extend(rules.iter().map(|rule| rule.id().to_string()));
+                    for rule in &rules {
+                        if analyzer_instance.rules().iter().any(|builtin| builtin.id() == rule.id()) {
+                            let (ok, outcome) = match (config.analysis.allow_override, rule_conflicts) {
+                                (true, _) | (_, analyzer::RuleConflictPolicy::Override) => (true, "overrides the built-in rule"),
+                                (false, analyzer::RuleConflictPolicy::Warn) => (true, "is skipped, a built-in rule has the same id"),
+                                (false, analyzer::RuleConflictPolicy::Error) => (false, "conflicts with the built-in rule of the same id"),
+                            };
+                            report(ok, format!("analysis.templates rule '{}' {outcome}", rule.id()));
+                        }
+                    }
+                }
+                Err(e) => report(false, format!("{e:#}")),
+            }
+        }
+    }
+
+    for severity in &config.rules.ignore_severities {
+        match severity.parse::<analyzer::Severity>() {
+            Ok(_) => report(true, format!("rules.ignore_severities '{severity}' is a known severity")),
+            Err(e) => report(false, format!("rules.ignore_severities: {e} (expected high, medium, low or informational)")),
+        }
+    }
+
+    if let Some(fail_on) = &config.output.fail_on {
+        match fail_on.parse::<analyzer::Severity>() {
+            Ok(_) => report(true, format!("output.fail_on '{fail_on}' is a known severity")),
+            Err(e) => report(false, format!("output.fail_on: {e} (expected high, medium, low or informational)")),
+        }
+    }
+
+    for rule_type in &config.rules.include_rule_types {
+        match rule_type.parse::<analyzer::RuleType>() {
+            Ok(_) => report(true, format!("rules.include_rule_types '{rule_type}' is a known rule type")),
+            Err(e) => report(false, format!("rules.include_rule_types: {e} (expected solana, anchor or general)")),
+        }
+    }
+
+    for category in &config.rules.include_categories {
+        match analyzer::engine::parse_categories(category) {
+            Ok(_) => report(true, format!("rules.include_categories '{category}' is a known category")),
+            Err(e) => report(false, format!("rules.include_categories: {e}")),
+        }
+    }
+
+    for (severity, color) in config.display.colors.entries() {
+        if let Some(color) = color {
+            let key = format!("display.colors.{}", format!("{severity:?}").to_lowercase());
+            match theme::parse_color(color) {
+                Ok(_) => report(true, format!("{key} '{color}' is a known color")),
+                Err(e) => report(false, format!("{key}: {e}")),
+            }
+        }
+    }
+
+    let rule_ids: Vec<&str> = analyzer_instance
+        .rules()
+        .iter()
+        .map(|rule| rule.id())
+        .chain(template_ids.iter().map(String::as_str))
+        .collect();
+    for rule_id in &config.rules.ignore_rules {
+        let known = rule_ids.iter().any(|id| id.eq_ignore_ascii_case(rule_id));
+        report(
+            known,
+            format!(
+                "rules.ignore_rules '{rule_id}' {}",
+                if known { "is a known rule" } else { "is not a known rule (see eloizer list-rules)" }
+            ),
+        );
+    }
+
+    println!();
+    if problems > 0 {
+        anyhow::bail!("Configuration has {problems} problem(s)");
+    }
+    println!("{} Configuration is valid\n", "✓".green().bold());
+    Ok(())
 }
@@ -1,9 +1,23 @@
 use anyhow::Result;
+use clap::ValueEnum;
 use colored::*;
 use std::fs;
 use std::path::PathBuf;
 
-pub fn run(output: PathBuf) -> Result<()> {
+/// Project shape the generated configuration is tailored to
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InitPreset {
+    /// Anchor workspace: analyzes programs/ and skips the instruction inventory
+    Anchor,
+    /// Native Solana program: leaves out Anchor-only rules
+    Native,
+    /// Bare skeleton with only the required settings
+    Minimal,
+    /// Every rule, failing the run on low severity findings and above
+    Strict,
+}
+
+pub fn run(output: PathBuf, preset: Option<InitPreset>) -> Result<()> {
     if output.exists() {
         eprintln!(
             "{} Configuration file already exists: {}",
@@ -14,7 +28,34 @@ pub fn run(output: PathBuf) -> Result<()> {
         anyhow::bail!("Configuration file already exists");
     }
 
-    let config_template = r#"# ELOIZER Configuration File
+    let config_template = match preset {
+        None => DEFAULT_CONFIG,
+        Some(InitPreset::Anchor) => ANCHOR_CONFIG,
+        Some(InitPreset::Native) => NATIVE_CONFIG,
+        Some(InitPreset::Minimal) => MINIMAL_CONFIG,
+        Some(InitPreset::Strict) => STRICT_CONFIG,
+    };
+
+    fs::write(&output, config_template)?;
+
+    println!(
+        "\n{} Configuration file created: {}\n",
+        "✓".green().bold(),
+        output.display().to_string().bright_green()
+    );
+
+    println!("Edit the file to customize your analysis settings.");
+    println!(
+        "Run analysis with: {}\n",
+        format!("eloizer config --config {}", output.display())
+            .cyan()
+            .bold()
+    );
+
+    Ok(())
+}
+
+const DEFAULT_CONFIG: &str = r#"# ELOIZER Configuration File
 # Generated by: eloizer init
 
 [analysis]
@@ -24,6 +65,22 @@ path = "src/"
 # Generate AST JSON files
 generate_ast = false
 
+# Custom templates directory (absolute or relative to this file), or an
+# https:// URL of a template or a .tar.gz/.zip archive of templates
+# templates = "templates/"
+# Pin the download of a templates URL, and only use its cached copy
+# templates_sha256 = "..."
+# offline = false
+# Directory of the analysis cache and of downloaded templates
+# cache_dir = ".eloizer-cache"
+
+# Custom rules reusing a built-in rule id: "warn" skips them, "error" aborts
+# rule_conflicts = "warn"
+# Let custom rules replace built-in rules with the same id
+# allow_override = false
+# Analyze files under tests/, benches/ and examples/ (skipped by default)
+# include_tests = false
+
 [output]
 # Output report file path
 report_file = "security-report.md"
@@ -38,6 +95,9 @@ ignore_rules = []
 # Rule types to include (options: solana, anchor, general)
 include_rule_types = ["solana", "anchor", "general"]
 
+# Only run rules in these categories (empty runs all), e.g. ["access-control", "cpi"]
+include_categories = []
+
 [display]
 # Enable verbose output
 verbose = false
@@ -47,23 +107,165 @@ quiet = false
 
 # Disable colored output
 no_color = false
+
+# Hide the ASCII banner (e.g. in git hooks)
+# no_banner = false
+# Report file paths relative to the analysis path
+# relative_paths = false
 "#;
 
-    fs::write(&output, config_template)?;
+const ANCHOR_CONFIG: &str = r#"# ELOIZER Configuration File
+# Generated by: eloizer init --preset anchor
 
-    println!(
-        "\n{} Configuration file created: {}\n",
-        "✓".green().bold(),
-        output.display().to_string().bright_green()
-    );
+[analysis]
+# Anchor workspaces keep their programs under programs/
+path = "programs/"
 
-    println!("Edit the file to customize your analysis settings.");
-    println!(
-        "Run analysis with: {}\n",
-        format!("eloizer config --config {}", output.display())
-            .cyan()
-            .bold()
-    );
+# Generate AST JSON files
+generate_ast = false
 
-    Ok(())
-}
+# Custom templates directory (absolute or relative to this file), or an
+# https:// URL of a template or a .tar.gz/.zip archive of templates
+# templates = "templates/"
+# Pin the download of a templates URL, and only use its cached copy
+# templates_sha256 = "..."
+# offline = false
+# Directory of the analysis cache and of downloaded templates
+# cache_dir = ".eloizer-cache"
+
+# Custom rules reusing a built-in rule id: "warn" skips them, "error" aborts
+# rule_conflicts = "warn"
+# Let custom rules replace built-in rules with the same id
+# allow_override = false
+# Analyze files under tests/, benches/ and examples/ (skipped by default)
+# include_tests = false
+
+[output]
+# Output report file path
+report_file = "security-report.md"
+
+[rules]
+# Severities to ignore (options: high, medium, low, informational)
+ignore_severities = []
+
+# The instruction inventory lists every handler; re-enable it for a first review
+ignore_rules = ["anchor-instructions"]
+
+# Anchor programs are Solana programs: keep Solana and general rules too
+include_rule_types = ["anchor", "solana", "general"]
+
+# Only run rules in these categories (empty runs all), e.g. ["access-control", "cpi"]
+include_categories = []
+
+[display]
+verbose = false
+quiet = false
+no_color = false
+"#;
+
+const NATIVE_CONFIG: &str = r#"# ELOIZER Configuration File
+# Generated by: eloizer init --preset native
+
+[analysis]
+# Path to analyze (can be overridden via CLI)
+path = "src/"
+
+# Generate AST JSON files
+generate_ast = false
+
+# Custom templates directory (absolute or relative to this file), or an
+# https:// URL of a template or a .tar.gz/.zip archive of templates
+# templates = "templates/"
+# Pin the download of a templates URL, and only use its cached copy
+# templates_sha256 = "..."
+# offline = false
+# Directory of the analysis cache and of downloaded templates
+# cache_dir = ".eloizer-cache"
+
+# Custom rules reusing a built-in rule id: "warn" skips them, "error" aborts
+# rule_conflicts = "warn"
+# Let custom rules replace built-in rules with the same id
+# allow_override = false
+# Analyze files under tests/, benches/ and examples/ (skipped by default)
+# include_tests = false
+
+[output]
+# Output report file path
+report_file = "security-report.md"
+
+[rules]
+# Severities to ignore (options: high, medium, low, informational)
+ignore_severities = []
+
+# Specific rule IDs to ignore
+ignore_rules = []
+
+# Anchor rules only apply to #[derive(Accounts)] programs
+include_rule_types = ["solana", "general"]
+
+# Only run rules in these categories (empty runs all), e.g. ["access-control", "cpi"]
+include_categories = []
+
+[display]
+verbose = false
+quiet = false
+no_color = false
+"#;
+
+const MINIMAL_CONFIG: &str = r#"# ELOIZER Configuration File
+# Generated by: eloizer init --preset minimal
+
+[analysis]
+path = "src/"
+
+[output]
+report_file = "security-report.md"
+
+[rules]
+"#;
+
+const STRICT_CONFIG: &str = r#"# ELOIZER Configuration File
+# Generated by: eloizer init --preset strict
+
+[analysis]
+# Path to analyze (can be overridden via CLI)
+path = "src/"
+
+# Generate AST JSON files
+generate_ast = false
+
+# Custom templates directory (absolute or relative to this file), or an
+# https:// URL of a template or a .tar.gz/.zip archive of templates
+# templates = "templates/"
+# Pin the download of a templates URL, and only use its cached copy
+# templates_sha256 = "..."
+# offline = false
+# Directory of the analysis cache and of downloaded templates
+# cache_dir = ".eloizer-cache"
+
+# Custom rules reusing a built-in rule id: "warn" skips them, "error" aborts
+# rule_conflicts = "warn"
+# Let custom rules replace built-in rules with the same id
+# allow_override = false
+# Analyze files under tests/, benches/ and examples/ (skipped by default)
+# include_tests = false
+
+[output]
+# Output report file path
+report_file = "security-report.md"
+
+# Exit with code 2 if any finding at or above this severity is reported
+fail_on = "low"
+
+[rules]
+# Every severity, rule and rule type is enabled
+ignore_severities = []
+ignore_rules = []
+include_rule_types = ["solana", "anchor", "general"]
+include_categories = []
+
+[display]
+verbose = false
+quiet = false
+no_color = false
+"#;
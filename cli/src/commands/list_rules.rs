@@ -1,9 +1,44 @@
 use anyhow::Result;
+use clap::ValueEnum;
 use colored::*;
 use rust_solana_analyzer::analyzer;
+use serde::Serialize;
 
-pub fn run(severity_filter: Option<String>, detailed: bool) -> Result<()> {
-    println!("\n{}\n", "📋 Available Detection Rules".bright_cyan().bold());
+use crate::theme::{severity_icon, severity_style};
+
+/// Output format of the `list-rules` command
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ListFormat {
+    /// Human-readable listing grouped by severity
+    #[default]
+    Text,
+    /// JSON array of rule summaries
+    Json,
+    /// Bordered table with the id, severity, categories and title of each rule
+    Table,
+}
+
+/// Rule entry of the JSON listing; field names are a stable interface
+#[derive(Serialize)]
+struct RuleSummary<'a> {
+    id: &'a str,
+    title: &'a str,
+    severity: analyzer::Severity,
+    description: &'a str,
+    rule_type: analyzer::RuleType,
+    categories: &'a [String],
+    references: &'a [String],
+}
+
+pub fn run(
+    severity_filter: Option<String>,
+    category_filter: Option<String>,
+    detailed: bool,
+    format: ListFormat,
+) -> Result<()> {
+    if format != ListFormat::Json {
+        println!("\n{}\n", "📋 Available Detection Rules".bright_cyan().bold());
+    }
 
     // Create analyzer to get rules
     let analyzer_instance = analyzer::create_analyzer();
@@ -29,11 +64,51 @@ pub fn run(severity_filter: Option<String>, detailed: bool) -> Result<()> {
         rules.iter().collect()
     };
 
+    // Filter by category if specified
+    let filtered_rules: Vec<_> = if let Some(categories) = category_filter {
+        let categories = analyzer::engine::parse_categories(&categories).map_err(|e| {
+            eprintln!("{} {}", "✗".red().bold(), e);
+            anyhow::Error::msg(e)
+        })?;
+        filtered_rules
+            .into_iter()
+            .filter(|r| r.categories().iter().any(|c| categories.contains(c)))
+            .collect()
+    } else {
+        filtered_rules
+    };
+
+    if format == ListFormat::Json {
+        let summaries: Vec<RuleSummary> = filtered_rules
+            .iter()
+            .map(|rule| RuleSummary {
+                id: rule.id(),
+                title: rule.title(),
+                severity: rule.severity(),
+                description: rule.description(),
+                rule_type: rule.rule_type(),
+                categories: rule.categories(),
+                references: rule.references(),
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&summaries)?);
+        return Ok(());
+    }
+
     if filtered_rules.is_empty() {
         println!("  {} No rules found", "⚠".yellow());
         return Ok(());
     }
 
+    if format == ListFormat::Table {
+        print_table(&filtered_rules);
+        println!(
+            "\nTotal: {} rules\n",
+            filtered_rules.len().to_string().bold()
+        );
+        return Ok(());
+    }
+
     // Group by severity
     for severity in &[
         analyzer::Severity::High,
@@ -50,22 +125,24 @@ pub fn run(severity_filter: Option<String>, detailed: bool) -> Result<()> {
             continue;
         }
 
-        let (icon, color_fn): (&str, fn(&str) -> ColoredString) = match severity {
-            analyzer::Severity::High => ("🔴", |s: &str| s.red().bold()),
-            analyzer::Severity::Medium => ("🟡", |s: &str| s.yellow().bold()),
-            analyzer::Severity::Low => ("🟢", |s: &str| s.blue().bold()),
-            analyzer::Severity::Informational => ("ℹ️", |s: &str| s.cyan()),
-        };
-
         println!(
             "{} {} ({} rules)\n",
-            icon,
-            color_fn(&format!("{:?} Severity", severity)),
+            severity_icon(severity),
+            severity_style(severity).emphasize(&format!("{:?} Severity", severity)),
             severity_rules.len()
         );
 
         for rule in severity_rules {
-            println!("  • {} - {}", rule.id().bold(), rule.title());
+            if rule.categories().is_empty() {
+                println!("  • {} - {}", rule.id().bold(), rule.title());
+            } else {
+                println!(
+                    "  • {} - {} {}",
+                    rule.id().bold(),
+                    rule.title(),
+                    format!("[{}]", rule.categories().join(", ")).dimmed()
+                );
+            }
 
             if detailed {
                 println!("    {}", rule.description().dimmed());
@@ -83,3 +160,72 @@ pub fn run(severity_filter: Option<String>, detailed: bool) -> Result<()> {
 
     Ok(())
 }
+
+/// Print the rules as a bordered table, ordered by severity. Cells are padded
+/// before being colored so escape codes don't break the alignment.
+fn print_table(rules: &[&std::sync::Arc<dyn analyzer::Rule>]) {
+    const HEADERS: [&str; 4] = ["ID", "Severity", "Category", "Title"];
+
+    let mut rows: Vec<(analyzer::Severity, [String; 4])> = Vec::new();
+    for severity in [
+        analyzer::Severity::High,
+        analyzer::Severity::Medium,
+        analyzer::Severity::Low,
+        analyzer::Severity::Informational,
+    ] {
+        for rule in rules.iter().filter(|r| r.severity() == severity) {
+            rows.push((
+                severity.clone(),
+                [
+                    rule.id().to_string(),
+                    format!("{:?}", severity),
+                    rule.categories().join(", "),
+                    rule.title().to_string(),
+                ],
+            ));
+        }
+    }
+
+    let mut widths = HEADERS.map(|header| header.chars().count());
+    for (_, cells) in &rows {
+        for (width, cell) in widths.iter_mut().zip(cells) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let border = |left: &str, middle: &str, right: &str| {
+        let segments: Vec<String> = widths.iter().map(|width| "─".repeat(width + 2)).collect();
+        format!("{left}{}{right}", segments.join(middle))
+    };
+    let pad = |cell: &str, width: usize| format!("{cell}{}", " ".repeat(width - cell.chars().count()));
+
+    println!("{}", border("┌", "┬", "┐"));
+    let header: Vec<String> = HEADERS
+        .iter()
+        .zip(&widths)
+        .map(|(header, width)| pad(header, *width).bold().to_string())
+        .collect();
+    println!("│ {} │", header.join(" │ "));
+    println!("{}", border("├", "┼", "┤"));
+
+    for (severity, cells) in &rows {
+        let style = severity_style(severity);
+        let line: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(column, (cell, width))| {
+                let padded = pad(cell, *width);
+                match column {
+                    0 => padded.bold().to_string(),
+                    1 => style.emphasize(&padded).to_string(),
+                    2 => padded.dimmed().to_string(),
+                    _ => padded,
+                }
+            })
+            .collect();
+        println!("│ {} │", line.join(" │ "));
+    }
+
+    println!("{}", border("└", "┴", "┘"));
+}
@@ -0,0 +1,143 @@
+use anyhow::Result;
+use log::debug;
+use rust_solana_analyzer::analyzer::{self, Analyzer, Finding, Severity};
+use tower_lsp::jsonrpc;
+use tower_lsp::lsp_types::{
+    Diagnostic, DiagnosticSeverity, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, InitializeParams, InitializeResult, InitializedParams, MessageType,
+    NumberOrString, Position, Range, ServerCapabilities, ServerInfo, TextDocumentSyncCapability,
+    TextDocumentSyncKind, Url,
+};
+use tower_lsp::{Client, LanguageServer, LspService, Server};
+
+/// Name shown as the source of the published diagnostics
+const DIAGNOSTIC_SOURCE: &str = "eloizer";
+
+/// Serve the Language Server Protocol on stdin/stdout, publishing the findings
+/// of every open Rust document as diagnostics
+pub fn run() -> Result<()> {
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+    runtime.block_on(async {
+        let (service, socket) = LspService::new(|client| Backend {
+            client,
+            analyzer: analyzer::create_analyzer(),
+        });
+        Server::new(tokio::io::stdin(), tokio::io::stdout(), socket)
+            .serve(service)
+            .await;
+    });
+    // Don't wait for the blocking stdin read to end after the client's `exit`
+    runtime.shutdown_background();
+    Ok(())
+}
+
+struct Backend {
+    client: Client,
+    analyzer: Analyzer,
+}
+
+impl Backend {
+    /// Analyze a document and replace its diagnostics. Documents that do not
+    /// parse (e.g. while typing) keep their previous diagnostics.
+    async fn publish(&self, uri: Url, text: &str, version: Option<i32>) {
+        let path = uri
+            .to_file_path()
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|_| uri.to_string());
+
+        let result = match self.analyzer.analyze_source(text, &path) {
+            Ok(result) => result,
+            Err(e) => {
+                debug!("Skipping diagnostics for {path}: {e}");
+                return;
+            }
+        };
+
+        let diagnostics = result.findings.iter().map(diagnostic).collect();
+        self.client.publish_diagnostics(uri, diagnostics, version).await;
+    }
+}
+
+#[tower_lsp::async_trait]
+impl LanguageServer for Backend {
+    async fn initialize(&self, _params: InitializeParams) -> jsonrpc::Result<InitializeResult> {
+        Ok(InitializeResult {
+            capabilities: ServerCapabilities {
+                text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+                ..ServerCapabilities::default()
+            },
+            server_info: Some(ServerInfo {
+                name: DIAGNOSTIC_SOURCE.to_string(),
+                version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            }),
+        })
+    }
+
+    async fn initialized(&self, _params: InitializedParams) {
+        self.client
+            .log_message(
+                MessageType::INFO,
+                format!("eloizer language server ready with {} rules", self.analyzer.rules().len()),
+            )
+            .await;
+    }
+
+    async fn shutdown(&self) -> jsonrpc::Result<()> {
+        Ok(())
+    }
+
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        let document = params.text_document;
+        self.publish(document.uri, &document.text, Some(document.version)).await;
+    }
+
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        // Full sync: the last change holds the whole document
+        if let Some(change) = params.content_changes.last() {
+            let document = params.text_document;
+            self.publish(document.uri, &change.text, Some(document.version)).await;
+        }
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        self.client
+            .publish_diagnostics(params.text_document.uri, Vec::new(), None)
+            .await;
+    }
+}
+
+/// Convert a finding into a diagnostic. Locations are 1-indexed, LSP positions
+/// 0-indexed; a finding without columns covers its whole start line.
+fn diagnostic(finding: &Finding) -> Diagnostic {
+    let location = &finding.location;
+    let line = to_position(location.line);
+    let start = Position::new(line, location.column.map_or(0, to_position));
+    let end = match (location.end_line, location.end_column) {
+        (Some(end_line), Some(end_column)) => Position::new(to_position(end_line), to_position(end_column)),
+        // Past the end of the line, which clients clamp to the line length
+        _ => Position::new(line, u32::MAX),
+    };
+
+    Diagnostic {
+        range: Range::new(start, end),
+        severity: Some(diagnostic_severity(&finding.severity)),
+        code: Some(NumberOrString::String(finding.rule_id.clone())),
+        source: Some(DIAGNOSTIC_SOURCE.to_string()),
+        message: finding.description.clone(),
+        ..Diagnostic::default()
+    }
+}
+
+fn diagnostic_severity(severity: &Severity) -> DiagnosticSeverity {
+    match severity {
+        Severity::High => DiagnosticSeverity::ERROR,
+        Severity::Medium => DiagnosticSeverity::WARNING,
+        Severity::Low => DiagnosticSeverity::INFORMATION,
+        Severity::Informational => DiagnosticSeverity::HINT,
+    }
+}
+
+/// 0-indexed LSP coordinate of a 1-indexed line or column
+fn to_position(value: usize) -> u32 {
+    u32::try_from(value.saturating_sub(1)).unwrap_or(u32::MAX)
+}
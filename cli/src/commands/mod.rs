@@ -1,5 +1,8 @@
 pub mod analyze;
+pub mod baseline;
 pub mod config;
 pub mod init;
 pub mod list_rules;
+pub mod lsp;
 pub mod rule_info;
+pub mod watch;
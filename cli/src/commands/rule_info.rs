@@ -2,48 +2,133 @@ use anyhow::Result;
 use colored::*;
 use rust_solana_analyzer::analyzer;
 
-pub fn run(rule_id: String) -> Result<()> {
+use crate::theme::{severity_icon, severity_style};
+
+pub fn run(rule_ids: Vec<String>) -> Result<()> {
     let analyzer_instance = analyzer::create_analyzer();
     let rules = analyzer_instance.rules();
 
-    // Find the rule
-    let rule = rules
-        .iter()
-        .find(|r| r.id().to_lowercase() == rule_id.to_lowercase());
-
-    match rule {
-        Some(r) => {
-            println!("\n{}\n", "📖 Rule Information".bright_cyan().bold());
-            println!("  {} {}", "ID:".bold(), r.id());
-            println!("  {} {}", "Title:".bold(), r.title());
-
-            let (icon, color_fn): (&str, fn(&str) -> ColoredString) = match r.severity() {
-                analyzer::Severity::High => ("🔴", |s: &str| s.red().bold()),
-                analyzer::Severity::Medium => ("🟡", |s: &str| s.yellow().bold()),
-                analyzer::Severity::Low => ("🟢", |s: &str| s.blue().bold()),
-                analyzer::Severity::Informational => ("ℹ️", |s: &str| s.cyan()),
-            };
-
-            println!(
-                "  {} {} {}\n",
-                "Severity:".bold(),
-                icon,
-                color_fn(&format!("{:?}", r.severity()))
-            );
-
-            println!("  {}", "Description:".bold());
-            println!("  {}\n", r.description());
-
-            Ok(())
+    let mut missing = Vec::new();
+    for rule_id in &rule_ids {
+        match rules.iter().find(|r| r.id().eq_ignore_ascii_case(rule_id)) {
+            Some(rule) => print_rule(rule.as_ref()),
+            None => {
+                eprintln!(
+                    "{} Rule not found: {}",
+                    "✗".red().bold(),
+                    rule_id.yellow()
+                );
+                missing.push(rule_id.as_str());
+            }
+        }
+    }
+
+    if !missing.is_empty() {
+        eprintln!("\nUse {} to see all available rules\n", "eloizer list-rules".cyan());
+        anyhow::bail!("Rule not found: {}", missing.join(", "));
+    }
+    Ok(())
+}
+
+/// Print the details of a rule
+fn print_rule(r: &dyn analyzer::Rule) {
+    println!("\n{}\n", "📖 Rule Information".bright_cyan().bold());
+    println!("  {} {}", "ID:".bold(), r.id());
+    println!("  {} {}", "Title:".bold(), r.title());
+
+    let severity = r.severity();
+    println!(
+        "  {} {} {}",
+        "Severity:".bold(),
+        severity_icon(&severity),
+        severity_style(&severity).emphasize(&format!("{:?}", severity))
+    );
+    if !r.categories().is_empty() {
+        println!("  {} {}", "Categories:".bold(), r.categories().join(", "));
+    }
+    println!();
+
+    println!("  {}", "Description:".bold());
+    println!("  {}\n", r.description());
+
+    if let Some(example) = r.vulnerable_example() {
+        println!("  {}", "Vulnerable example:".red().bold());
+        print_code(example);
+    }
+    if let Some(example) = r.fixed_example() {
+        println!("  {}", "Fixed example:".green().bold());
+        print_code(example);
+    }
+
+    if !r.references().is_empty() {
+        println!("  {}", "References:".bold());
+        for reference in r.references() {
+            println!("    - {}", reference.bright_blue());
         }
-        None => {
-            eprintln!(
-                "{} Rule not found: {}",
-                "✗".red().bold(),
-                rule_id.yellow()
-            );
-            eprintln!("\nUse {} to see all available rules\n", "eloizer list-rules".cyan());
-            anyhow::bail!("Rule not found: {}", rule_id);
+        println!();
+    }
+}
+
+/// Rust keywords highlighted in examples
+const KEYWORDS: &[&str] = &[
+    "as", "break", "const", "continue", "crate", "else", "enum", "fn", "for", "if", "impl", "in",
+    "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "struct",
+    "trait", "type", "unsafe", "use", "where", "while",
+];
+
+/// Print an indented code example, syntax-highlighted when colors are enabled
+fn print_code(code: &str) {
+    for line in code.lines() {
+        println!("    {}", highlight_line(line));
+    }
+    println!();
+}
+
+/// Minimal Rust highlighting: comments, attributes, strings, keywords and types
+fn highlight_line(line: &str) -> String {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with("//") {
+        return line.dimmed().to_string();
+    }
+    if trimmed.starts_with("#[") {
+        return line.cyan().to_string();
+    }
+
+    let mut highlighted = String::new();
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '"' {
+            let mut literal = String::from(c);
+            while let Some(next) = chars.next() {
+                literal.push(next);
+                if next == '\\' {
+                    if let Some(escaped) = chars.next() {
+                        literal.push(escaped);
+                    }
+                } else if next == '"' {
+                    break;
+                }
+            }
+            highlighted.push_str(&literal.green().to_string());
+        } else if c.is_alphabetic() || c == '_' {
+            let mut word = String::from(c);
+            while let Some(&next) = chars.peek() {
+                if !next.is_alphanumeric() && next != '_' {
+                    break;
+                }
+                word.push(next);
+                chars.next();
+            }
+            if KEYWORDS.contains(&word.as_str()) {
+                highlighted.push_str(&word.magenta().to_string());
+            } else if word.starts_with(char::is_uppercase) {
+                highlighted.push_str(&word.yellow().to_string());
+            } else {
+                highlighted.push_str(&word);
+            }
+        } else {
+            highlighted.push(c);
         }
     }
+    highlighted
 }
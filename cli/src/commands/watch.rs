@@ -0,0 +1,129 @@
+use anyhow::{Context, Result};
+use clap::Args;
+use colored::*;
+use log::{debug, warn};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use std::io::IsTerminal;
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+use super::analyze::{self, AnalyzeArgs};
+
+/// Arguments of the `watch` command
+#[derive(Args, Debug)]
+pub struct WatchArgs {
+    #[command(flatten)]
+    pub analyze: AnalyzeArgs,
+
+    /// Milliseconds to wait for further changes before re-running the analysis
+    #[arg(long, value_name = "MS", default_value_t = 300)]
+    pub debounce_ms: u64,
+}
+
+pub fn run(args: WatchArgs, verbose: bool, quiet: bool) -> Result<()> {
+    let WatchArgs { analyze, debounce_ms } = args;
+
+    for path in &analyze.path {
+        if !path.is_dir() {
+            eprintln!(
+                "{} Path is not a directory: {}",
+                "✗".red().bold(),
+                path.display().to_string().yellow()
+            );
+            anyhow::bail!("Path {} is not a directory", path.display());
+        }
+    }
+    // A failing threshold exits the process, which would end the watch
+    if analyze.fail_on.is_some() {
+        anyhow::bail!("--fail-on is not supported in watch mode");
+    }
+    // Stdin can only be read once, so later runs would see an empty diff
+    if analyze.diff.as_deref() == Some(Path::new("-")) {
+        anyhow::bail!("--diff - is not supported in watch mode");
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |event| {
+        // The receiver only goes away when the watch ends
+        let _ = sender.send(event);
+    })
+    .context("Failed to start the file watcher")?;
+    for path in &analyze.path {
+        watcher
+            .watch(path, RecursiveMode::Recursive)
+            .with_context(|| format!("Failed to watch {}", path.display()))?;
+    }
+    let watched: Vec<String> = analyze.path.iter().map(|path| path.display().to_string()).collect();
+
+    let debounce = Duration::from_millis(debounce_ms);
+    loop {
+        if std::io::stdout().is_terminal() {
+            // Clear the screen and move the cursor home
+            print!("\x1B[2J\x1B[1;1H");
+        }
+
+        // Unchanged files are served from the analysis cache, so re-runs stay fast
+        if let Err(e) = analyze::run(analyze.clone(), verbose, quiet) {
+            debug!("Analysis failed: {e:#}");
+        }
+
+        if !quiet {
+            println!(
+                "{} Watching {} for changes (Ctrl+C to stop)",
+                "👀".bold(),
+                watched.join(", ").bright_blue()
+            );
+        }
+
+        if !wait_for_change(&receiver, debounce, &analyze.cache_dir) {
+            return Ok(());
+        }
+    }
+}
+
+/// Block until a Rust source file changes, then let the burst of events settle.
+/// Returns false once the watcher stopped.
+fn wait_for_change(
+    receiver: &Receiver<notify::Result<Event>>,
+    debounce: Duration,
+    cache_dir: &Path,
+) -> bool {
+    loop {
+        match receiver.recv() {
+            Ok(Ok(event)) if is_source_change(&event, cache_dir) => break,
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => warn!("File watcher error: {e}"),
+            Err(_) => return false,
+        }
+    }
+
+    // Editors often write a file several times in a row; wait for the writes to stop
+    loop {
+        match receiver.recv_timeout(debounce) {
+            Ok(_) => {}
+            Err(RecvTimeoutError::Timeout) => return true,
+            Err(RecvTimeoutError::Disconnected) => return false,
+        }
+    }
+}
+
+/// Whether an event creates, modifies or removes a `.rs` file outside build
+/// output and the analysis cache
+fn is_source_change(event: &Event, cache_dir: &Path) -> bool {
+    if !matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) {
+        return false;
+    }
+
+    let cache_name = cache_dir.file_name();
+    event.paths.iter().any(|path| {
+        path.extension().is_some_and(|extension| extension == "rs")
+            && !path.components().any(|component| {
+                let name = component.as_os_str();
+                name == "target" || Some(name) == cache_name
+            })
+    })
+}
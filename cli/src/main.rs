@@ -2,7 +2,11 @@ use anyhow::Result;
 use clap::{Parser, Subcommand};
 use colored::*;
 
+use commands::analyze::Outcome;
+
 mod commands;
+mod theme;
+mod tui;
 
 #[derive(Parser)]
 #[command(
@@ -20,6 +24,10 @@ struct Cli {
     #[arg(long, global = true)]
     no_color: bool,
 
+    /// Severity color theme
+    #[arg(long, global = true, value_enum, value_name = "THEME")]
+    theme: Option<theme::Theme>,
+
     /// Enable verbose output
     #[arg(short, long, global = true)]
     verbose: bool,
@@ -32,31 +40,13 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Analyze Solana smart contracts for vulnerabilities
-    Analyze {
-        /// Path to Solana project directory or Rust file
-        #[arg(short, long, value_name = "PATH")]
-        path: std::path::PathBuf,
+    Analyze(commands::analyze::AnalyzeArgs),
 
-        /// Custom templates path
-        #[arg(short, long, value_name = "DIR")]
-        templates: Option<std::path::PathBuf>,
+    /// Re-run the analysis whenever a Rust file changes
+    Watch(commands::watch::WatchArgs),
 
-        /// Output report file path (supports .md format)
-        #[arg(short, long, value_name = "FILE")]
-        output: Option<std::path::PathBuf>,
-
-        /// Generate AST JSON files
-        #[arg(long)]
-        ast: bool,
-
-        /// Severities to ignore (comma-separated: low,medium,high,informational)
-        #[arg(short, long, value_name = "SEVERITIES")]
-        ignore: Option<String>,
-
-        /// Specific rule IDs to ignore (comma-separated)
-        #[arg(long, value_name = "RULE_IDS")]
-        ignore_rules: Option<String>,
-    },
+    /// Record the current findings in a baseline file
+    Baseline(commands::baseline::BaselineArgs),
 
     /// List all available detection rules
     ListRules {
@@ -64,22 +54,39 @@ enum Commands {
         #[arg(short, long)]
         severity: Option<String>,
 
+        /// Filter by category (comma-separated, e.g. access-control,cpi)
+        #[arg(short, long)]
+        category: Option<String>,
+
         /// Show detailed information
         #[arg(short, long)]
         detailed: bool,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = commands::list_rules::ListFormat::Text)]
+        format: commands::list_rules::ListFormat,
     },
 
-    /// Show information about a specific rule
+    /// Show information about one or more rules
+    #[command(long_flag = "explain")]
     RuleInfo {
-        /// Rule ID to show information for
-        rule_id: String,
+        /// Rule IDs to show information for
+        #[arg(required = true)]
+        rule_ids: Vec<String>,
     },
 
+    /// Run a language server publishing findings as editor diagnostics (stdio)
+    Lsp,
+
     /// Initialize a new analysis configuration file
     Init {
         /// Output path for config file
         #[arg(short, long, default_value = "eloizer.toml")]
         output: std::path::PathBuf,
+
+        /// Tailor the configuration to a project shape
+        #[arg(long, value_enum)]
+        preset: Option<commands::init::InitPreset>,
     },
 
     /// Run analysis with a configuration file
@@ -87,6 +94,13 @@ enum Commands {
         /// Path to configuration file
         #[arg(short, long, default_value = "eloizer.toml")]
         config: std::path::PathBuf,
+
+        /// Validate the configuration file without running the analysis
+        #[arg(long)]
+        check: bool,
+
+        #[command(flatten)]
+        overrides: commands::config::ConfigOverrides,
     },
 }
 
@@ -110,26 +124,62 @@ fn main() -> Result<()> {
     if cli.no_color {
         colored::control::set_override(false);
     }
+    if let Some(theme) = cli.theme {
+        theme::set_palette(theme.palette());
+    }
 
     // Execute command
-    match cli.command {
-        Commands::Analyze {
-            path,
-            templates,
-            output,
-            ast,
-            ignore,
-            ignore_rules,
-        } => commands::analyze::run(path, templates, output, ast, ignore, ignore_rules, cli.verbose, cli.quiet),
-
-        Commands::ListRules { severity, detailed } => {
-            commands::list_rules::run(severity, detailed)
+    let outcome = match cli.command {
+        Commands::Analyze(args) => commands::analyze::run(args, cli.verbose, cli.quiet)?,
+
+        Commands::Watch(args) => {
+            commands::watch::run(args, cli.verbose, cli.quiet)?;
+            Outcome::Passed
+        }
+
+        Commands::Baseline(args) => {
+            commands::baseline::run(args, cli.quiet)?;
+            Outcome::Passed
+        }
+
+        Commands::ListRules {
+            severity,
+            category,
+            detailed,
+            format,
+        } => {
+            commands::list_rules::run(severity, category, detailed, format)?;
+            Outcome::Passed
         }
 
-        Commands::RuleInfo { rule_id } => commands::rule_info::run(rule_id),
+        Commands::RuleInfo { rule_ids } => {
+            commands::rule_info::run(rule_ids)?;
+            Outcome::Passed
+        }
+
+        Commands::Lsp => {
+            commands::lsp::run()?;
+            Outcome::Passed
+        }
 
-        Commands::Init { output } => commands::init::run(output),
+        Commands::Init { output, preset } => {
+            commands::init::run(output, preset)?;
+            Outcome::Passed
+        }
+
+        Commands::Config { config, check, overrides } => {
+            if check {
+                commands::config::check(config, overrides)?;
+                Outcome::Passed
+            } else {
+                commands::config::run(config, overrides, cli.verbose, cli.quiet)?
+            }
+        }
+    };
 
-        Commands::Config { config } => commands::config::run(config, cli.verbose, cli.quiet),
+    // Exit only now, after every temporary directory of the run has been removed
+    if outcome == Outcome::Failed {
+        std::process::exit(2);
     }
+    Ok(())
 }
@@ -0,0 +1,146 @@
+use clap::ValueEnum;
+use colored::{Color, ColoredString, Colorize};
+use rust_solana_analyzer::analyzer::Severity;
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+/// Built-in severity color themes
+#[derive(ValueEnum, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    /// Red, yellow, blue and cyan
+    #[default]
+    Default,
+    /// Bright colors that stay apart for red-green color blindness and on light backgrounds
+    HighContrast,
+    /// Severities in the terminal's default color
+    None,
+}
+
+/// Color of each severity; `None` leaves the text in the terminal's default color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    pub high: Option<Color>,
+    pub medium: Option<Color>,
+    pub low: Option<Color>,
+    pub informational: Option<Color>,
+}
+
+impl Theme {
+    /// Severity colors of the theme
+    pub fn palette(self) -> Palette {
+        match self {
+            Theme::Default => Palette {
+                high: Some(Color::Red),
+                medium: Some(Color::Yellow),
+                low: Some(Color::Blue),
+                informational: Some(Color::Cyan),
+            },
+            Theme::HighContrast => Palette {
+                high: Some(Color::BrightMagenta),
+                medium: Some(Color::BrightYellow),
+                low: Some(Color::BrightCyan),
+                informational: Some(Color::BrightWhite),
+            },
+            Theme::None => Palette {
+                high: None,
+                medium: None,
+                low: None,
+                informational: None,
+            },
+        }
+    }
+}
+
+impl Palette {
+    /// Slot of a severity's color
+    pub fn color_mut(&mut self, severity: &Severity) -> &mut Option<Color> {
+        match severity {
+            Severity::High => &mut self.high,
+            Severity::Medium => &mut self.medium,
+            Severity::Low => &mut self.low,
+            Severity::Informational => &mut self.informational,
+        }
+    }
+
+    fn color(&self, severity: &Severity) -> Option<Color> {
+        match severity {
+            Severity::High => self.high,
+            Severity::Medium => self.medium,
+            Severity::Low => self.low,
+            Severity::Informational => self.informational,
+        }
+    }
+}
+
+/// Parse an ANSI color name ("red", "bright cyan", "bright-cyan"); "none" keeps
+/// the terminal's default color
+pub fn parse_color(name: &str) -> Result<Option<Color>, String> {
+    let normalized = name.trim().to_lowercase().replace(['-', '_'], " ");
+    if normalized == "none" {
+        return Ok(None);
+    }
+    normalized.parse::<Color>().map(Some).map_err(|()| {
+        format!("Unknown color: {name} (expected black, red, green, yellow, blue, magenta, cyan, white, their bright variants or none)")
+    })
+}
+
+/// Palette used by `severity_style`, set once at startup
+static PALETTE: OnceLock<Palette> = OnceLock::new();
+
+/// Select the palette of the run. Only the first call takes effect, so the
+/// `--theme` flag, applied first, wins over the configuration file.
+pub fn set_palette(palette: Palette) {
+    let _ = PALETTE.set(palette);
+}
+
+/// How text about a severity is styled in the active palette
+#[derive(Debug, Clone, Copy)]
+pub struct SeverityStyle {
+    color: Option<Color>,
+    /// Informational findings are not emphasized
+    emphasized: bool,
+}
+
+impl SeverityStyle {
+    /// Color of the severity; `None` for the terminal's default color
+    pub fn color(self) -> Option<Color> {
+        self.color
+    }
+
+    /// Text in the severity color
+    pub fn paint(self, text: &str) -> ColoredString {
+        match self.color {
+            Some(color) => text.color(color),
+            None => text.normal(),
+        }
+    }
+
+    /// Text in the severity color, bold unless the severity is informational
+    pub fn emphasize(self, text: &str) -> ColoredString {
+        if self.emphasized {
+            self.paint(text).bold()
+        } else {
+            self.paint(text)
+        }
+    }
+}
+
+/// Style of a severity in the active theme
+pub fn severity_style(severity: &Severity) -> SeverityStyle {
+    let palette = PALETTE.get_or_init(|| Theme::default().palette());
+    SeverityStyle {
+        color: palette.color(severity),
+        emphasized: *severity != Severity::Informational,
+    }
+}
+
+/// Icon shown next to a severity heading
+pub fn severity_icon(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::High => "🔴",
+        Severity::Medium => "🟡",
+        Severity::Low => "🟢",
+        Severity::Informational => "ℹ️",
+    }
+}
@@ -0,0 +1,286 @@
+use anyhow::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use rust_solana_analyzer::analyzer::{Finding, Severity};
+
+use crate::theme::severity_style;
+
+/// Triage state of a finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mark {
+    #[default]
+    Unmarked,
+    /// Looked at and kept
+    Reviewed,
+    /// To be suppressed
+    Ignored,
+}
+
+/// Severity filters cycled through with `s`
+const SEVERITY_FILTERS: [Option<Severity>; 5] = [
+    None,
+    Some(Severity::High),
+    Some(Severity::Medium),
+    Some(Severity::Low),
+    Some(Severity::Informational),
+];
+
+/// Browse findings in a terminal UI and return the mark of each finding
+pub fn run(findings: &[Finding]) -> Result<Vec<Mark>> {
+    let mut app = App::new(findings);
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+    result.map(|()| app.marks)
+}
+
+struct App<'a> {
+    findings: &'a [Finding],
+    marks: Vec<Mark>,
+    /// Rule IDs of the findings, cycled through with `f`
+    rules: Vec<&'a str>,
+    /// Index in `SEVERITY_FILTERS`
+    severity_filter: usize,
+    /// Index in `rules` plus one; 0 shows every rule
+    rule_filter: usize,
+    /// Indices of the findings passing the filters
+    visible: Vec<usize>,
+    list: ListState,
+    detail_scroll: u16,
+}
+
+impl<'a> App<'a> {
+    fn new(findings: &'a [Finding]) -> Self {
+        let mut rules: Vec<&str> = findings.iter().map(|finding| finding.rule_id.as_str()).collect();
+        rules.sort_unstable();
+        rules.dedup();
+
+        let mut app = Self {
+            findings,
+            marks: vec![Mark::Unmarked; findings.len()],
+            rules,
+            severity_filter: 0,
+            rule_filter: 0,
+            visible: Vec::new(),
+            list: ListState::default(),
+            detail_scroll: 0,
+        };
+        app.apply_filters();
+        app
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+                KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+                KeyCode::PageDown => self.move_selection(10),
+                KeyCode::PageUp => self.move_selection(-10),
+                KeyCode::Home | KeyCode::Char('g') => self.select(0),
+                KeyCode::End | KeyCode::Char('G') => self.select(self.visible.len().saturating_sub(1)),
+                KeyCode::Char('J') => self.detail_scroll = self.detail_scroll.saturating_add(1),
+                KeyCode::Char('K') => self.detail_scroll = self.detail_scroll.saturating_sub(1),
+                KeyCode::Char('s') => {
+                    self.severity_filter = (self.severity_filter + 1) % SEVERITY_FILTERS.len();
+                    self.apply_filters();
+                }
+                KeyCode::Char('f') => {
+                    self.rule_filter = (self.rule_filter + 1) % (self.rules.len() + 1);
+                    self.apply_filters();
+                }
+                KeyCode::Char('r') => self.toggle(Mark::Reviewed),
+                KeyCode::Char('i') => self.toggle(Mark::Ignored),
+                _ => {}
+            }
+        }
+    }
+
+    fn apply_filters(&mut self) {
+        let severity = SEVERITY_FILTERS[self.severity_filter].as_ref();
+        let rule = self.rule_filter.checked_sub(1).map(|index| self.rules[index]);
+        self.visible = (0..self.findings.len())
+            .filter(|&index| {
+                let finding = &self.findings[index];
+                severity.is_none_or(|severity| finding.severity == *severity)
+                    && rule.is_none_or(|rule| finding.rule_id == rule)
+            })
+            .collect();
+        self.select(0);
+    }
+
+    fn select(&mut self, position: usize) {
+        self.list.select((!self.visible.is_empty()).then_some(position));
+        self.detail_scroll = 0;
+    }
+
+    fn move_selection(&mut self, offset: isize) {
+        if let Some(position) = self.list.selected() {
+            let last = self.visible.len().saturating_sub(1);
+            self.select(position.saturating_add_signed(offset).min(last));
+        }
+    }
+
+    /// Index of the selected finding
+    fn selected(&self) -> Option<usize> {
+        self.list.selected().and_then(|position| self.visible.get(position).copied())
+    }
+
+    fn toggle(&mut self, mark: Mark) {
+        if let Some(index) = self.selected() {
+            self.marks[index] = if self.marks[index] == mark { Mark::Unmarked } else { mark };
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, footer] = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+        let [list_area, detail_area] =
+            Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(main);
+
+        let items: Vec<ListItem> = self
+            .visible
+            .iter()
+            .map(|&index| {
+                let finding = &self.findings[index];
+                let mark = match self.marks[index] {
+                    Mark::Unmarked => "  ",
+                    Mark::Reviewed => "✓ ",
+                    Mark::Ignored => "✗ ",
+                };
+                let mut style = severity_color(&finding.severity);
+                if self.marks[index] != Mark::Unmarked {
+                    style = style.add_modifier(Modifier::DIM);
+                }
+                ListItem::new(Line::from(vec![
+                    Span::raw(mark),
+                    Span::styled(format!("{:<5} ", severity_label(&finding.severity)), style),
+                    Span::styled(format!("{} ", finding.rule_id), style),
+                    Span::styled(
+                        format!("{}:{}", finding.location.file, finding.location.line),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]))
+            })
+            .collect();
+        let title = format!(" Findings ({}/{}) ", self.visible.len(), self.findings.len());
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, list_area, &mut self.list);
+
+        let details = Paragraph::new(self.selected().map_or_else(Vec::new, |index| self.details(index)))
+            .block(Block::default().borders(Borders::ALL).title(" Details "))
+            .wrap(Wrap { trim: false })
+            .scroll((self.detail_scroll, 0));
+        frame.render_widget(details, detail_area);
+
+        frame.render_widget(Paragraph::new(self.footer()), footer);
+    }
+
+    fn details(&self, index: usize) -> Vec<Line<'a>> {
+        let finding = &self.findings[index];
+        let heading = Style::default().add_modifier(Modifier::BOLD);
+        let location = &finding.location;
+
+        let mut lines = vec![
+            Line::from(Span::styled(
+                finding.rule_id.clone(),
+                severity_color(&finding.severity).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(format!("Severity: {:?}", finding.severity)),
+            Line::from(match location.column {
+                Some(column) => format!("Location: {}:{}:{column}", location.file, location.line),
+                None => format!("Location: {}:{}", location.file, location.line),
+            }),
+        ];
+        match self.marks[index] {
+            Mark::Unmarked => {}
+            Mark::Reviewed => lines.push(Line::from("Status: reviewed")),
+            Mark::Ignored => lines.push(Line::from("Status: ignored (added to the baseline on exit)")),
+        }
+        lines.push(Line::default());
+        lines.push(Line::from(finding.description.clone()));
+
+        if let Some(snippet) = &finding.code_snippet {
+            lines.push(Line::default());
+            lines.push(Line::from(Span::styled("Code", heading)));
+            lines.extend(
+                snippet
+                    .lines()
+                    .map(|line| Line::from(Span::styled(line.to_string(), Style::default().fg(Color::Gray)))),
+            );
+        }
+        if !finding.recommendations.is_empty() {
+            lines.push(Line::default());
+            lines.push(Line::from(Span::styled("Recommendations", heading)));
+            lines.extend(finding.recommendations.iter().map(|rec| Line::from(format!("• {rec}"))));
+        }
+        if !finding.references.is_empty() {
+            lines.push(Line::default());
+            lines.push(Line::from(Span::styled("References", heading)));
+            lines.extend(finding.references.iter().map(|reference| Line::from(format!("• {reference}"))));
+        }
+        lines
+    }
+
+    fn footer(&self) -> Line<'static> {
+        let severity = SEVERITY_FILTERS[self.severity_filter]
+            .as_ref()
+            .map_or_else(|| "all".to_string(), |severity| format!("{severity:?}").to_lowercase());
+        let rule = self.rule_filter.checked_sub(1).map_or("all", |index| self.rules[index]);
+        let reviewed = self.marks.iter().filter(|mark| **mark == Mark::Reviewed).count();
+        let ignored = self.marks.iter().filter(|mark| **mark == Mark::Ignored).count();
+        Line::from(format!(
+            " ↑↓ move  J/K scroll  s severity: {severity}  f rule: {rule}  r reviewed  i ignore  q quit   {reviewed} reviewed, {ignored} ignored"
+        ))
+        .style(Style::default().add_modifier(Modifier::REVERSED))
+    }
+}
+
+fn severity_label(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::High => "HIGH",
+        Severity::Medium => "MED",
+        Severity::Low => "LOW",
+        Severity::Informational => "INFO",
+    }
+}
+
+/// Style of a severity in the active theme
+fn severity_color(severity: &Severity) -> Style {
+    let color = severity_style(severity).color().filter(|_| colored::control::SHOULD_COLORIZE.should_colorize());
+    let Some(color) = color else {
+        return Style::default();
+    };
+    let color = match color {
+        colored::Color::Black => Color::Black,
+        colored::Color::Red => Color::Red,
+        colored::Color::Green => Color::Green,
+        colored::Color::Yellow => Color::Yellow,
+        colored::Color::Blue => Color::Blue,
+        colored::Color::Magenta => Color::Magenta,
+        colored::Color::Cyan => Color::Cyan,
+        colored::Color::White => Color::Gray,
+        colored::Color::BrightBlack => Color::DarkGray,
+        colored::Color::BrightRed => Color::LightRed,
+        colored::Color::BrightGreen => Color::LightGreen,
+        colored::Color::BrightYellow => Color::LightYellow,
+        colored::Color::BrightBlue => Color::LightBlue,
+        colored::Color::BrightMagenta => Color::LightMagenta,
+        colored::Color::BrightCyan => Color::LightCyan,
+        colored::Color::BrightWhite => Color::White,
+        colored::Color::TrueColor { r, g, b } => Color::Rgb(r, g, b),
+    };
+    Style::default().fg(color)
+}
@@ -0,0 +1,204 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::analyzer::{AnalysisResult, Finding};
+
+/// Default file name written by `eloizer baseline`
+pub const DEFAULT_BASELINE_FILE: &str = "eloizer-baseline.json";
+
+/// Current version of the baseline file format
+const BASELINE_VERSION: u32 = 1;
+
+/// Known findings that should not be reported again
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    /// Baseline file format version
+    pub version: u32,
+    /// Fingerprinted findings
+    pub findings: Vec<BaselineEntry>,
+}
+
+/// A single known finding
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// Line-independent fingerprint of the finding
+    pub fingerprint: String,
+    /// ID of the rule that produced the finding
+    pub rule_id: String,
+    /// File of the finding, relative to the project root
+    pub file: String,
+}
+
+impl Baseline {
+    /// Build a baseline from the findings of an analysis. Repeated occurrences of
+    /// a pattern in a file each get their own entry.
+    pub fn from_findings(findings: &[Finding], project_root: &str) -> Self {
+        let findings = findings
+            .iter()
+            .map(|finding| BaselineEntry {
+                fingerprint: occurrence_fingerprint(finding, project_root),
+                rule_id: finding.rule_id.clone(),
+                file: relative_path(&finding.location.file, project_root),
+            })
+            .collect();
+
+        Self {
+            version: BASELINE_VERSION,
+            findings,
+        }
+    }
+
+    /// Load a baseline file
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read baseline {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse baseline {}", path.display()))
+    }
+
+    /// Save the baseline to a file
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content)
+            .with_context(|| format!("Failed to write baseline {}", path.display()))
+    }
+
+    /// Returns true if the finding is part of the baseline
+    pub fn contains(&self, finding: &Finding, project_root: &str) -> bool {
+        let fingerprint = occurrence_fingerprint(finding, project_root);
+        self.findings.iter().any(|entry| entry.fingerprint == fingerprint)
+    }
+
+    /// Add a finding to the baseline. Returns false if it was already known.
+    pub fn insert(&mut self, finding: &Finding, project_root: &str) -> bool {
+        if self.contains(finding, project_root) {
+            return false;
+        }
+        self.findings.push(BaselineEntry {
+            fingerprint: occurrence_fingerprint(finding, project_root),
+            rule_id: finding.rule_id.clone(),
+            file: relative_path(&finding.location.file, project_root),
+        });
+        true
+    }
+
+    /// Remove the known findings from an analysis result and update its statistics.
+    /// Returns the number of findings removed.
+    pub fn apply(&self, result: &mut AnalysisResult, project_root: &str) -> usize {
+        let known: HashSet<&str> = self.findings.iter().map(|e| e.fingerprint.as_str()).collect();
+        let before = result.findings.len();
+
+        result.retain(|finding| !known.contains(occurrence_fingerprint(finding, project_root).as_str()));
+
+        before - result.findings.len()
+    }
+}
+
+/// Fingerprint of a finding: a hash of the rule ID, the file relative to the
+/// project root and the whitespace-normalized code snippet. Line numbers are
+/// left out so unrelated edits do not change it.
+pub fn fingerprint(finding: &Finding, project_root: &str) -> String {
+    let code = finding
+        .code_snippet
+        .as_deref()
+        .unwrap_or(&finding.description);
+    let normalized = code.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let mut hasher = Sha256::new();
+    hasher.update(finding.rule_id.as_bytes());
+    hasher.update([0]);
+    hasher.update(relative_path(&finding.location.file, project_root).as_bytes());
+    hasher.update([0]);
+    hasher.update(normalized.as_bytes());
+
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Fingerprint identifying this occurrence of a finding: the one assigned by
+/// `assign_fingerprints`, or the plain fingerprint for findings built elsewhere
+fn occurrence_fingerprint(finding: &Finding, project_root: &str) -> String {
+    if finding.fingerprint.is_empty() {
+        fingerprint(finding, project_root)
+    } else {
+        finding.fingerprint.clone()
+    }
+}
+
+/// Set the fingerprint of findings of a single file, sorted by location.
+/// Findings sharing a fingerprint (the same pattern repeated in the file) are
+/// told apart by their order: the first keeps the plain fingerprint, so it
+/// still matches baselines, the next ones hash in their position.
+pub fn assign_fingerprints(findings: &mut [Finding], project_root: &str) {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for finding in findings {
+        let base = fingerprint(finding, project_root);
+        let occurrence = seen.entry(base.clone()).or_insert(0);
+        finding.fingerprint = if *occurrence == 0 {
+            base
+        } else {
+            let mut hasher = Sha256::new();
+            hasher.update(base.as_bytes());
+            hasher.update([0]);
+            hasher.update(occurrence.to_string().as_bytes());
+            hasher.finalize().iter().map(|byte| format!("{byte:02x}")).collect()
+        };
+        *occurrence += 1;
+    }
+}
+
+/// Path of a file relative to the project root, with `/` separators. Both are
+/// resolved first, so a file gets the same path whichever directory it was
+/// reached from; files outside of the root are kept as given.
+pub(crate) fn relative_path(file: &str, project_root: &str) -> String {
+    let path = Path::new(file);
+    let relative = if project_root.is_empty() {
+        None
+    } else {
+        resolve(path).strip_prefix(resolve(Path::new(project_root))).ok().map(Path::to_path_buf)
+    };
+    relative
+        .as_deref()
+        .unwrap_or(path)
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Absolute form of a path, with symbolic links resolved when it exists
+fn resolve(path: &Path) -> PathBuf {
+    fs::canonicalize(path)
+        .or_else(|_| std::path::absolute(path))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_path_independent_of_root_spelling() {
+        let cwd = std::env::current_dir().unwrap();
+        let absolute = cwd.join("src/lib.rs");
+
+        assert_eq!(relative_path("src/lib.rs", "."), "src/lib.rs");
+        assert_eq!(relative_path("./src/lib.rs", &cwd.to_string_lossy()), "src/lib.rs");
+        assert_eq!(relative_path(&absolute.to_string_lossy(), "."), "src/lib.rs",
+                "A file should get the same path whether it was reached by a relative or an absolute path");
+    }
+
+    #[test]
+    fn test_relative_path_outside_root() {
+        assert_eq!(relative_path("src/lib.rs", "/nonexistent/root"), "src/lib.rs",
+                "Files outside of the root should be kept as given");
+        assert_eq!(relative_path("src/lib.rs", ""), "src/lib.rs");
+    }
+}
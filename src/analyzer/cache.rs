@@ -0,0 +1,260 @@
+use anyhow::Context;
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::analyzer::Finding;
+use crate::analyzer::engine::{RuleEngine, RuleSource};
+
+/// Default directory of the incremental analysis cache
+pub const DEFAULT_CACHE_DIR: &str = ".eloizer-cache";
+
+/// File holding the cached findings inside the cache directory
+const CACHE_FILE: &str = "findings.json";
+
+/// Current version of the cache file format
+const CACHE_VERSION: u32 = 2;
+
+/// Modification time and content hash of a source file when it was analyzed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileStamp {
+    /// Modification time in nanoseconds since the Unix epoch
+    pub modified: u64,
+    /// SHA-256 of the file content
+    pub hash: String,
+}
+
+impl FileStamp {
+    /// Stamp of a source file, given its content
+    pub fn new(path: &Path, content: &str) -> Self {
+        Self {
+            modified: modified_nanos(path).unwrap_or_default(),
+            hash: content_hash(content),
+        }
+    }
+}
+
+/// Cached analysis of a single file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub stamp: FileStamp,
+    /// Number of source lines in the file
+    pub lines: usize,
+    /// Findings of the file, before severity filters are applied
+    pub findings: Vec<Finding>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheIndex {
+    version: u32,
+    /// Analyzer version that produced the entries
+    analyzer_version: String,
+    /// Fingerprint of the rules that produced the entries
+    rule_set: String,
+    entries: HashMap<String, CacheEntry>,
+}
+
+/// Per-file findings memoized on disk, keyed by file path, modification time and
+/// content hash. Entries are discarded when the analyzer version or the rule set change.
+#[derive(Debug)]
+pub struct AnalysisCache {
+    dir: PathBuf,
+    index: CacheIndex,
+    dirty: bool,
+}
+
+impl AnalysisCache {
+    /// Open the cache in `dir` for the given rule set. A missing, unreadable or
+    /// outdated cache starts empty.
+    pub fn open(dir: &Path, rule_set: &str) -> Self {
+        let analyzer_version = env!("CARGO_PKG_VERSION").to_string();
+        let path = dir.join(CACHE_FILE);
+
+        let index = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| match serde_json::from_str::<CacheIndex>(&content) {
+                Ok(index) => Some(index),
+                Err(e) => {
+                    warn!("Ignoring unreadable analysis cache {}: {e}", path.display());
+                    None
+                }
+            })
+            .filter(|index| {
+                let current = index.version == CACHE_VERSION
+                    && index.analyzer_version == analyzer_version
+                    && index.rule_set == rule_set;
+                if !current {
+                    debug!("Discarding analysis cache built by another analyzer version or rule set");
+                }
+                current
+            })
+            .unwrap_or_else(|| CacheIndex {
+                version: CACHE_VERSION,
+                analyzer_version,
+                rule_set: rule_set.to_string(),
+                entries: HashMap::new(),
+            });
+
+        Self {
+            dir: dir.to_path_buf(),
+            index,
+            dirty: false,
+        }
+    }
+
+    /// Number of cached files
+    pub fn len(&self) -> usize {
+        self.index.entries.len()
+    }
+
+    /// Returns true if no file is cached
+    pub fn is_empty(&self) -> bool {
+        self.index.entries.is_empty()
+    }
+
+    /// Returns the cached analysis of a file if it is unchanged. A file whose
+    /// modification time changed but whose content did not is still a hit.
+    pub fn lookup(&mut self, file_path: &str) -> Option<&CacheEntry> {
+        let modified = modified_nanos(Path::new(file_path))?;
+        let entry = self.index.entries.get_mut(file_path)?;
+
+        if entry.stamp.modified != modified {
+            let content = fs::read_to_string(file_path).ok()?;
+            if entry.stamp.hash != content_hash(&content) {
+                return None;
+            }
+            entry.stamp.modified = modified;
+            self.dirty = true;
+        }
+
+        Some(&*entry)
+    }
+
+    /// Record the analysis of a file
+    pub fn store(&mut self, file_path: &str, entry: CacheEntry) {
+        self.index.entries.insert(file_path.to_string(), entry);
+        self.dirty = true;
+    }
+
+    /// Write the cache to disk if it changed
+    pub fn save(&mut self) -> anyhow::Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create cache directory {}", self.dir.display()))?;
+        // Keep the cache out of version control without touching the project's .gitignore
+        let gitignore = self.dir.join(".gitignore");
+        if !gitignore.exists() {
+            fs::write(&gitignore, "*\n")
+                .with_context(|| format!("Failed to write {}", gitignore.display()))?;
+        }
+
+        let path = self.dir.join(CACHE_FILE);
+        let content = serde_json::to_string(&self.index)?;
+        fs::write(&path, content)
+            .with_context(|| format!("Failed to write analysis cache {}", path.display()))?;
+
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+/// Fingerprint of the rule set of an engine: changes whenever a rule is added,
+/// removed or changes its severity, description or references, and whenever
+/// the template file defining a custom rule changes (e.g. its `pattern`)
+pub fn rule_set_fingerprint(rule_engine: &RuleEngine) -> String {
+    let mut rules: Vec<String> = rule_engine
+        .get_rules()
+        .iter()
+        .map(|rule| {
+            format!(
+                "{}|{:?}|{}|{}|{}",
+                rule.id(),
+                rule.severity(),
+                rule.description(),
+                rule.references().join(" "),
+                definition_hash(rule_engine.rule_source(rule.id()))
+            )
+        })
+        .collect();
+    rules.sort();
+
+    let mut hasher = Sha256::new();
+    for rule in &rules {
+        hasher.update(rule.as_bytes());
+        hasher.update([0]);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Hash of the template file a rule was loaded from. The logic of built-in and
+/// external rules only changes with the analyzer version, which the cache already checks
+fn definition_hash(source: Option<&RuleSource>) -> String {
+    match source {
+        Some(RuleSource::Template(path)) => fs::read(path)
+            .map(|bytes| format!("{:x}", Sha256::digest(bytes)))
+            .unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+fn content_hash(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
+fn modified_nanos(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    let nanos = modified.duration_since(UNIX_EPOCH).ok()?.as_nanos();
+    u64::try_from(nanos).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = "id: my-rule\ntitle: My rule\nseverity: low\ndescription: custom\npattern:\n  regex: \"foo_one\"\n";
+
+    fn template_fingerprint(dir: &Path) -> String {
+        let mut rule_engine = RuleEngine::default();
+        rule_engine.load_template_rules(dir).unwrap();
+        rule_set_fingerprint(&rule_engine)
+    }
+
+    #[test]
+    fn test_template_pattern_changes_fingerprint() {
+        let dir = tempfile::tempdir().unwrap();
+        let template = dir.path().join("rule.yaml");
+        fs::write(&template, TEMPLATE).unwrap();
+        let before = template_fingerprint(dir.path());
+
+        assert_eq!(template_fingerprint(dir.path()), before,
+                "An unchanged template should keep the fingerprint");
+
+        fs::write(&template, TEMPLATE.replace("foo_one", "foo_two")).unwrap();
+        assert_ne!(template_fingerprint(dir.path()), before,
+                "Editing the pattern of a template should invalidate the cache");
+    }
+
+    #[test]
+    fn test_cache_discarded_for_another_rule_set() {
+        let dir = tempfile::tempdir().unwrap();
+        let entry = CacheEntry {
+            stamp: FileStamp { modified: 0, hash: content_hash("") },
+            lines: 0,
+            findings: Vec::new(),
+        };
+        let mut cache = AnalysisCache::open(dir.path(), "one");
+        cache.store("lib.rs", entry);
+        cache.save().unwrap();
+
+        assert_eq!(AnalysisCache::open(dir.path(), "one").len(), 1);
+        assert!(AnalysisCache::open(dir.path(), "two").is_empty(),
+                "Entries of another rule set should not be served");
+    }
+}
@@ -0,0 +1,378 @@
+use log::trace;
+use proc_macro2::{TokenStream, TokenTree};
+use std::collections::{HashMap, HashSet};
+use syn::visit::{self, Visit};
+use syn::{
+    Attribute, Block, Expr, Field, Fields, File, ImplItem, ImplItemFn, Item, ItemStruct, Meta, Signature, Type,
+};
+
+use crate::analyzer::dsl::query::{AstQuery, NodeData};
+
+/// A single constraint inside an `#[account(...)]` attribute
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountConstraint {
+    /// Constraint name (e.g. `mut`, `has_one`, `token::mint`)
+    pub name: String,
+    /// Constraint value, if any (e.g. `authority` in `has_one = authority`)
+    pub value: Option<String>,
+}
+
+/// Instruction handler taking a `Context<T>` parameter
+#[derive(Debug, Clone)]
+pub struct InstructionHandler<'a> {
+    /// Handler signature
+    pub sig: &'a Signature,
+    /// Handler body
+    pub block: &'a Block,
+    /// Name of the accounts struct `T` in `Context<T>`
+    pub accounts_type: String,
+}
+
+/// Parse all constraints declared in the `#[account(...)]` attributes of a field
+pub fn account_constraints(attrs: &[Attribute]) -> Vec<AccountConstraint> {
+    let mut constraints = Vec::new();
+
+    for attr in attrs {
+        if let Meta::List(meta_list) = &attr.meta
+            && meta_list.path.is_ident("account")
+        {
+            constraints.extend(split_constraints(meta_list.tokens.clone()));
+        }
+    }
+
+    constraints
+}
+
+/// Returns true if the field declares the given `#[account(...)]` constraint
+pub fn has_constraint(attrs: &[Attribute], name: &str) -> bool {
+    account_constraints(attrs).iter().any(|c| c.name == name)
+}
+
+/// Returns the value of the given `#[account(...)]` constraint, if declared
+pub fn constraint_value(attrs: &[Attribute], name: &str) -> Option<String> {
+    account_constraints(attrs)
+        .into_iter()
+        .find(|c| c.name == name)
+        .and_then(|c| c.value)
+}
+
+/// Returns the raw tokens of the value of the given `#[account(...)]` constraint,
+/// for values to parse as expressions (e.g. `seeds = [b"vault", user.key().as_ref()]`)
+pub fn constraint_tokens(attrs: &[Attribute], name: &str) -> Option<TokenStream> {
+    all_constraint_tokens(attrs, name).into_iter().next()
+}
+
+/// Returns the raw tokens of every value of the given `#[account(...)]` constraint,
+/// for constraints a field may repeat (e.g. several `constraint = ...`)
+pub fn all_constraint_tokens(attrs: &[Attribute], name: &str) -> Vec<TokenStream> {
+    attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::List(meta_list) if meta_list.path.is_ident("account") => Some(meta_list.tokens.clone()),
+            _ => None,
+        })
+        .flat_map(|tokens| {
+            let mut constraints = vec![Vec::new()];
+            for token in tokens {
+                match &token {
+                    TokenTree::Punct(punct) if punct.as_char() == ',' => constraints.push(Vec::new()),
+                    _ => constraints.last_mut().expect("constraints is never empty").push(token),
+                }
+            }
+            constraints
+        })
+        .filter_map(|constraint| match constraint.as_slice() {
+            [TokenTree::Ident(ident), TokenTree::Punct(eq), value @ ..] if ident == name && eq.as_char() == '=' => {
+                Some(value.iter().cloned().collect())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Split the tokens of an `#[account(...)]` attribute on top-level commas
+fn split_constraints(tokens: TokenStream) -> Vec<AccountConstraint> {
+    let mut constraints = Vec::new();
+    let mut name = String::new();
+    let mut value: Option<String> = None;
+
+    let mut flush = |name: &mut String, value: &mut Option<String>| {
+        if !name.is_empty() {
+            constraints.push(AccountConstraint {
+                name: std::mem::take(name),
+                value: value.take().map(|v| v.trim().to_string()),
+            });
+        }
+        *value = None;
+    };
+
+    for token in tokens {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => flush(&mut name, &mut value),
+            TokenTree::Punct(punct) if punct.as_char() == '=' && value.is_none() => {
+                value = Some(String::new());
+            }
+            _ => match &mut value {
+                Some(v) => {
+                    if !v.is_empty() {
+                        v.push(' ');
+                    }
+                    v.push_str(&token.to_string());
+                }
+                None => name.push_str(&token.to_string()),
+            },
+        }
+    }
+    flush(&mut name, &mut value);
+
+    constraints
+}
+
+/// Returns true if the struct derives `Accounts`
+pub fn is_accounts_struct(item_struct: &ItemStruct) -> bool {
+    item_struct.attrs.iter().any(|attr| {
+        if let Meta::List(meta_list) = &attr.meta {
+            meta_list.path.is_ident("derive") && meta_list.tokens.to_string().contains("Accounts")
+        } else {
+            false
+        }
+    })
+}
+
+/// Returns the argument names declared in the struct's `#[instruction(...)]` attribute
+pub fn instruction_args(item_struct: &ItemStruct) -> Vec<String> {
+    let mut args = Vec::new();
+
+    for attr in &item_struct.attrs {
+        let Meta::List(meta_list) = &attr.meta else {
+            continue;
+        };
+        if !meta_list.path.is_ident("instruction") {
+            continue;
+        }
+
+        // Arguments are `name: Type` pairs separated by top-level commas
+        let mut expect_name = true;
+        let mut previous: Option<String> = None;
+        for token in meta_list.tokens.clone() {
+            match &token {
+                TokenTree::Punct(punct) if punct.as_char() == ',' => expect_name = true,
+                TokenTree::Punct(punct) if punct.as_char() == ':' && expect_name => {
+                    if let Some(name) = previous.take() {
+                        args.push(name);
+                    }
+                    expect_name = false;
+                }
+                TokenTree::Ident(ident) if expect_name => previous = Some(ident.to_string()),
+                _ => {}
+            }
+        }
+    }
+
+    args
+}
+
+/// Returns the named fields of a struct
+pub fn named_fields(item_struct: &ItemStruct) -> Vec<&Field> {
+    match &item_struct.fields {
+        Fields::Named(fields) => fields.named.iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the last path segment of a type, looking through references
+/// (e.g. `Signer` for `Signer<'info>`, `Account` for `&Account<'info, T>`)
+pub fn type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(type_path) => type_path.path.segments.last().map(|s| s.ident.to_string()),
+        Type::Reference(reference) => type_name(&reference.elem),
+        _ => None,
+    }
+}
+
+/// Returns the generic type arguments of the last path segment of a type
+/// (e.g. `[Vault]` for `Account<'info, Vault>`)
+pub fn type_arguments(ty: &Type) -> Vec<&Type> {
+    match ty {
+        Type::Path(type_path) => match type_path.path.segments.last().map(|s| &s.arguments) {
+            Some(syn::PathArguments::AngleBracketed(args)) => args
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    syn::GenericArgument::Type(ty) => Some(ty),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        },
+        Type::Reference(reference) => type_arguments(&reference.elem),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the name of the accounts struct `T` of a `Context<T>` parameter
+pub fn context_accounts_type(sig: &Signature) -> Option<String> {
+    sig.inputs.iter().find_map(|input| {
+        let syn::FnArg::Typed(pat_type) = input else {
+            return None;
+        };
+        if type_name(&pat_type.ty).as_deref() != Some("Context") {
+            return None;
+        }
+        type_arguments(&pat_type.ty).last().and_then(|ty| type_name(ty))
+    })
+}
+
+/// Returns true if the function is an instruction handler: an Anchor handler
+/// taking `Context<T>` or a native handler taking `&[AccountInfo]`
+pub fn is_instruction_handler(sig: &Signature) -> bool {
+    context_accounts_type(sig).is_some()
+        || sig.inputs.iter().any(|input| {
+            let syn::FnArg::Typed(pat_type) = input else {
+                return false;
+            };
+            let ty = match &*pat_type.ty {
+                Type::Reference(reference) => &*reference.elem,
+                ty => ty,
+            };
+            matches!(ty, Type::Slice(slice) if type_name(&slice.elem).as_deref() == Some("AccountInfo"))
+        })
+}
+
+/// Collects every instruction handler (function with a `Context<T>` parameter) in the file
+pub fn instruction_handlers(ast: &File) -> Vec<InstructionHandler<'_>> {
+    AstQuery::new(ast)
+        .functions()
+        .collect()
+        .into_iter()
+        .filter_map(|node| {
+            let (sig, block) = match node.data {
+                NodeData::Function(func) => (&func.sig, func.block.as_ref()),
+                NodeData::ImplFunction(func) => (&func.sig, &func.block),
+                _ => return None,
+            };
+            let accounts_type = context_accounts_type(sig)?;
+            trace!("Found instruction handler {} for {}", sig.ident, accounts_type);
+            Some(InstructionHandler {
+                sig,
+                block,
+                accounts_type,
+            })
+        })
+        .collect()
+}
+
+/// Returns the handlers whose `Context<T>` uses the given accounts struct
+pub fn handlers_for<'a>(ast: &'a File, accounts_type: &str) -> Vec<InstructionHandler<'a>> {
+    instruction_handlers(ast)
+        .into_iter()
+        .filter(|handler| handler.accounts_type == accounts_type)
+        .collect()
+}
+
+/// Returns `x` if the expression is an accounts field access such as `ctx.accounts.x`
+pub fn accounts_field(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Field(field) => accounts_field_access(field),
+        _ => None,
+    }
+}
+
+fn accounts_field_access(field: &syn::ExprField) -> Option<String> {
+    if let Expr::Field(base) = &*field.base
+        && matches!(&base.member, syn::Member::Named(ident) if ident == "accounts")
+        && let syn::Member::Named(ident) = &field.member
+    {
+        return Some(ident.to_string());
+    }
+    None
+}
+
+/// Returns every accounts field referenced as `accounts.x` in a token stream
+pub fn accounts_fields_in_tokens(tokens: &TokenStream) -> Vec<String> {
+    let flat = flatten_tokens(tokens.clone());
+    let mut fields = Vec::new();
+
+    for window in flat.windows(3) {
+        if window[0] == "accounts" && window[1] == "." {
+            fields.push(window[2].clone());
+        }
+    }
+
+    fields
+}
+
+/// Returns every account referenced in a token stream as `ctx.accounts.x`, `self.x`
+/// or a local variable bound to an accounts field in `aliases`
+pub fn accounts_in(tokens: TokenStream, aliases: &HashMap<String, String>) -> Vec<String> {
+    let mut accounts = accounts_fields_in_tokens(&tokens);
+    let flat = flatten_tokens(tokens);
+    for (index, token) in flat.iter().enumerate() {
+        if token == "self" && flat.get(index + 1).is_some_and(|t| t == ".") {
+            accounts.extend(flat.get(index + 2).cloned());
+        } else if let Some(account) = aliases.get(token) {
+            accounts.push(account.clone());
+        }
+    }
+    accounts
+}
+
+/// Returns the methods of every `impl` block for the given type
+pub fn impl_methods<'a>(ast: &'a File, type_name: &str) -> Vec<&'a ImplItemFn> {
+    ast.items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Impl(item_impl) if self::type_name(&item_impl.self_ty).as_deref() == Some(type_name) => {
+                Some(item_impl)
+            }
+            _ => None,
+        })
+        .flat_map(|item_impl| {
+            item_impl.items.iter().filter_map(|item| match item {
+                ImplItem::Fn(method) => Some(method),
+                _ => None,
+            })
+        })
+        .collect()
+}
+
+/// Flatten a token stream into the string form of its leaf tokens
+pub fn flatten_tokens(tokens: TokenStream) -> Vec<String> {
+    let mut flat = Vec::new();
+    for token in tokens {
+        match token {
+            TokenTree::Group(group) => flat.extend(flatten_tokens(group.stream())),
+            other => flat.push(other.to_string()),
+        }
+    }
+    flat
+}
+
+/// Visitor that collects every accounts field referenced in an expression tree
+#[derive(Default)]
+pub struct AccountRefCollector {
+    pub fields: HashSet<String>,
+}
+
+impl AccountRefCollector {
+    /// Collect the accounts fields referenced in an expression
+    pub fn collect_expr(expr: &Expr) -> HashSet<String> {
+        let mut collector = Self::default();
+        collector.visit_expr(expr);
+        collector.fields
+    }
+}
+
+impl<'ast> Visit<'ast> for AccountRefCollector {
+    fn visit_expr_field(&mut self, expr: &'ast syn::ExprField) {
+        if let Some(field) = accounts_field_access(expr) {
+            self.fields.insert(field);
+        }
+        visit::visit_expr_field(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        self.fields.extend(accounts_fields_in_tokens(&mac.tokens));
+        visit::visit_macro(self, mac);
+    }
+}
@@ -27,8 +27,14 @@ pub struct RuleBuilder {
     references: Vec<String>,
     /// Recommendations for fixing the issue
     recommendations: Vec<String>,
+    /// Example of code the rule flags
+    vulnerable_example: Option<String>,
+    /// Fixed version of the vulnerable example
+    fixed_example: Option<String>,
     /// Tags to classify the rule
     tags: Vec<String>,
+    /// Categories to filter the rule by
+    categories: Vec<String>,
     /// Indicates if the rule is enabled by default
     enabled: bool,
 }
@@ -45,7 +51,10 @@ impl RuleBuilder {
             query_builder: None,
             references: Vec::new(),
             recommendations: Vec::new(),
+            vulnerable_example: None,
+            fixed_example: None,
             tags: Vec::new(),
+            categories: Vec::new(),
             enabled: true,
         }
     }
@@ -197,6 +206,18 @@ impl RuleBuilder {
         self
     }
 
+    /// Sets a canonical example of code the rule flags
+    pub fn vulnerable_example(mut self, code: &str) -> Self {
+        self.vulnerable_example = Some(code.trim_matches('\n').to_string());
+        self
+    }
+
+    /// Sets the fixed version of the vulnerable example
+    pub fn fixed_example(mut self, code: &str) -> Self {
+        self.fixed_example = Some(code.trim_matches('\n').to_string());
+        self
+    }
+
     /// Adds a tag to classify the rule
     pub fn tag(mut self, tag: &str) -> Self {
         self.tags.push(tag.to_string());
@@ -211,6 +232,16 @@ impl RuleBuilder {
         self
     }
 
+    /// Adds a category to filter the rule by (one of `RULE_CATEGORIES`)
+    pub fn category(mut self, category: &str) -> Self {
+        debug_assert!(
+            crate::analyzer::engine::RULE_CATEGORIES.contains(&category),
+            "Unknown rule category: {category}"
+        );
+        self.categories.push(category.to_string());
+        self
+    }
+
     /// Sets whether the rule is enabled by default
     pub fn enabled(mut self, enabled: bool) -> Self {
         self.enabled = enabled;
@@ -226,6 +257,9 @@ impl RuleBuilder {
         let references = self.references;
         let recommendations = self.recommendations;
         let tags = self.tags;
+        let categories = self.categories;
+        let vulnerable_example = self.vulnerable_example;
+        let fixed_example = self.fixed_example;
         let enabled = self.enabled;
         let id = self.id.clone();
         let title = self.title.clone();
@@ -268,6 +302,9 @@ impl RuleBuilder {
                     Ok(Vec::new())
                 }
             },
-        ))
+        )
+        .with_examples(vulnerable_example, fixed_example)
+        .with_categories(categories)
+        .with_references(references))
     }
 }
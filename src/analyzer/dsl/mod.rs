@@ -1,3 +1,4 @@
+pub mod anchor;
 pub mod builders;
 pub mod query;
 
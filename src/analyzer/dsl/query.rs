@@ -1,7 +1,7 @@
 use log::{debug, trace};
 use std::fmt;
 use syn::visit::{self, Visit};
-use syn::{Block, Expr, File, Item, ItemEnum, ItemFn, ItemStruct};
+use syn::{Block, Expr, Field, File, Item, ItemEnum, ItemFn, ItemStruct};
 
 use crate::analyzer::{Finding, Severity};
 
@@ -14,12 +14,16 @@ pub enum NodeType {
     Function,
     /// Struct
     Struct,
+    /// Struct field
+    Field,
     /// Enum
     Enum,
     /// Block
     Block,
     /// Expression
     Expression,
+    /// Macro invocation
+    Macro,
     /// Other
     Other,
 }
@@ -30,9 +34,11 @@ impl fmt::Display for NodeType {
             NodeType::File => write!(f, "File"),
             NodeType::Function => write!(f, "Function"),
             NodeType::Struct => write!(f, "Struct"),
+            NodeType::Field => write!(f, "Field"),
             NodeType::Enum => write!(f, "Enum"),
             NodeType::Block => write!(f, "Block"),
             NodeType::Expression => write!(f, "Expression"),
+            NodeType::Macro => write!(f, "Macro"),
             NodeType::Other => write!(f, "Other"),
         }
     }
@@ -49,12 +55,16 @@ pub enum NodeData<'a> {
     ImplFunction(&'a syn::ImplItemFn),
     /// Struct
     Struct(&'a ItemStruct),
+    /// Struct field
+    Field(&'a Field),
     /// Enum
     Enum(&'a ItemEnum),
     /// Block
     Block(&'a Block),
     /// Expression
     Expression(&'a Expr),
+    /// Macro invocation (expression or statement position)
+    Macro(&'a syn::Macro),
     /// Other
     Other,
 }
@@ -107,6 +117,33 @@ impl<'a> AstNode<'a> {
         }
     }
 
+    /// Create a new node from a struct field
+    pub fn from_field(field: &'a Field) -> Self {
+        Self {
+            node_type: NodeType::Field,
+            data: NodeData::Field(field),
+            name: field.ident.as_ref().map(ToString::to_string),
+        }
+    }
+
+    /// Create a new node from an expression with a descriptive name
+    pub fn from_expression(expr: &'a Expr, name: &str) -> Self {
+        Self {
+            node_type: NodeType::Expression,
+            data: NodeData::Expression(expr),
+            name: Some(name.to_string()),
+        }
+    }
+
+    /// Create a new node from a macro invocation with a descriptive name
+    pub fn from_macro(mac: &'a syn::Macro, name: &str) -> Self {
+        Self {
+            node_type: NodeType::Macro,
+            data: NodeData::Macro(mac),
+            name: Some(name.to_string()),
+        }
+    }
+
     /// Get the node type
     pub fn node_type(&self) -> NodeType {
         self.node_type.clone()
@@ -123,9 +160,14 @@ impl<'a> AstNode<'a> {
             NodeData::Function(func) => format!("fn {}(...)", func.sig.ident),
             NodeData::ImplFunction(func) => format!("fn {}(...)", func.sig.ident),
             NodeData::Struct(struct_item) => format!("struct {}", struct_item.ident),
+            NodeData::Field(field) => match &field.ident {
+                Some(ident) => format!("{ident}: ..."),
+                None => "...".to_string(),
+            },
             NodeData::Enum(enum_item) => format!("enum {}", enum_item.ident),
             NodeData::Block(_) => "{ ... }".to_string(),
             NodeData::Expression(_) => "...".to_string(),
+            NodeData::Macro(mac) => format!("{}!(...)", quote::ToTokens::to_token_stream(&mac.path)),
             _ => "...".to_string(),
         }
     }
@@ -138,9 +180,11 @@ impl<'a> AstNode<'a> {
             NodeData::Function(func) => Some(func as &dyn Spanned),
             NodeData::ImplFunction(func) => Some(func as &dyn Spanned),
             NodeData::Struct(struct_item) => Some(struct_item as &dyn Spanned),
+            NodeData::Field(field) => Some(field as &dyn Spanned),
             NodeData::Enum(enum_item) => Some(enum_item as &dyn Spanned),
             NodeData::Block(block) => Some(block as &dyn Spanned),
             NodeData::Expression(expr) => Some(expr as &dyn Spanned),
+            NodeData::Macro(mac) => Some(mac as &dyn Spanned),
             NodeData::File(file) => Some(file as &dyn Spanned),
             NodeData::Other => None,
         }
@@ -437,11 +481,15 @@ impl<'a> AstQuery<'a> {
                 };
 
                 Finding {
+                    rule_id: String::new(),
                     description,
                     severity: severity.clone(),
                     location: Self::create_fallback_location(file_path),
                     code_snippet: Some(node.snippet()),
+                    context: None,
                     recommendations: recommendations.to_vec(),
+                    references: Vec::new(),
+                    fingerprint: String::new(),
                 }
             })
             .collect()
@@ -483,11 +531,15 @@ impl<'a> AstQuery<'a> {
                 };
 
                 Finding {
+                    rule_id: String::new(),
                     description: finding_description,
                     severity: severity.clone(),
                     location,
                     code_snippet: Some(code_snippet),
+                    context: None,
                     recommendations: recommendations.to_vec(),
+                    references: Vec::new(),
+                    fingerprint: String::new(),
                 }
             })
             .collect()
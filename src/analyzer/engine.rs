@@ -1,14 +1,17 @@
+use std::collections::HashMap;
+use std::fmt;
 use std::path::Path;
 use std::sync::Arc;
 
 use anyhow::Result;
 use log::{debug, info, warn};
+use serde::Serialize;
 use syn::File;
 
-use crate::analyzer::{Finding, Severity};
+use crate::analyzer::{Finding, Severity, SourceContext};
 
 /// Type of rule
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 pub enum RuleType {
     /// Rules specific to Solana
     Solana,
@@ -18,7 +21,113 @@ pub enum RuleType {
     General,
 }
 
+impl std::str::FromStr for RuleType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "solana" => Ok(RuleType::Solana),
+            "anchor" => Ok(RuleType::Anchor),
+            "general" => Ok(RuleType::General),
+            _ => Err(format!("Unknown rule type: {s}")),
+        }
+    }
+}
+
+/// What to do with a custom rule whose ID is already used by a built-in rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RuleConflictPolicy {
+    /// Keep the built-in rule and skip the custom one with a warning
+    #[default]
+    Warn,
+    /// Fail loading the custom rules
+    Error,
+    /// Replace the built-in rule with the custom one
+    Override,
+}
+
+impl std::str::FromStr for RuleConflictPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "warn" => Ok(RuleConflictPolicy::Warn),
+            "error" => Ok(RuleConflictPolicy::Error),
+            "override" => Ok(RuleConflictPolicy::Override),
+            _ => Err(format!("Unknown rule conflict policy: {s} (expected warn, error or override)")),
+        }
+    }
+}
+
+/// Where a rule was loaded from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleSource {
+    /// Shipped with the analyzer
+    BuiltIn,
+    /// Custom rule template file
+    Template(String),
+    /// Registered by an embedding crate
+    External,
+}
+
+impl fmt::Display for RuleSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleSource::BuiltIn => write!(f, "built-in rules"),
+            RuleSource::Template(path) => write!(f, "template {path}"),
+            RuleSource::External => write!(f, "external registration"),
+        }
+    }
+}
+
+/// Categories rules are classified under, for focused analysis passes
+pub const RULE_CATEGORIES: [&str; 11] = [
+    "access-control",
+    "accounts",
+    "arithmetic",
+    "best-practices",
+    "configuration",
+    "cpi",
+    "error-handling",
+    "initialization",
+    "logging",
+    "pda",
+    "unsafe",
+];
+
+/// Parse a comma-separated list of rule categories, rejecting unknown ones
+pub fn parse_categories(list: &str) -> std::result::Result<Vec<String>, String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|category| !category.is_empty())
+        .map(|category| {
+            let category = category.to_lowercase();
+            if RULE_CATEGORIES.contains(&category.as_str()) {
+                Ok(category)
+            } else {
+                Err(format!(
+                    "Unknown rule category: {category} (expected one of {})",
+                    RULE_CATEGORIES.join(", ")
+                ))
+            }
+        })
+        .collect()
+}
+
 /// A rule that can be applied to an AST
+///
+/// Built-in rules are declared with `RuleBuilder`. External crates can also
+/// implement the trait directly and add their rules to an analyzer with
+/// `Analyzer::register_rule`. Implementors must provide:
+///
+/// - `id`: unique, stable identifier used by `--ignore-rules`, baselines and
+///   suppression comments (e.g. `my-org-unchecked-oracle`)
+/// - `title` and `description`: shown in reports and `rule-info`
+/// - `severity` and `rule_type`: used by the severity and rule type filters
+/// - `execute`: the analysis hook, returning the findings of one parsed file
+///
+/// Override `execute_with_source` instead of relying on its default when the
+/// rule needs the source code for precise locations and snippets.
 pub trait Rule: Send + Sync {
     /// Returns the unique ID of the rule
     fn id(&self) -> &str;
@@ -40,6 +149,27 @@ pub trait Rule: Send + Sync {
         Vec::new()
     }
 
+    /// Returns the categories of the rule (see `RULE_CATEGORIES`)
+    fn categories(&self) -> &[String] {
+        &[]
+    }
+
+    /// Returns external references (CWE ids, advisories, exploit writeups)
+    /// attached to the findings of the rule
+    fn references(&self) -> &[String] {
+        &[]
+    }
+
+    /// Returns a canonical example of code the rule flags, if provided
+    fn vulnerable_example(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the fixed version of the vulnerable example, if provided
+    fn fixed_example(&self) -> Option<&str> {
+        None
+    }
+
     /// Execute the rule on the given AST and return findings
     fn execute(&self, ast: &File, file_path: &str) -> Result<Vec<Finding>>;
 
@@ -65,6 +195,15 @@ pub struct RuleEngineConfig {
 
     /// Rule types to include
     pub include_rule_types: Vec<RuleType>,
+
+    /// Only include rules in one of these categories (all rules when empty)
+    pub include_categories: Vec<String>,
+
+    /// Only include these rule IDs, whatever their type or categories (all rules when empty)
+    pub only_rules: Vec<String>,
+
+    /// How custom rules reusing the ID of a built-in rule are handled
+    pub rule_conflicts: RuleConflictPolicy,
 }
 
 impl Default for RuleEngineConfig {
@@ -74,15 +213,22 @@ impl Default for RuleEngineConfig {
             ignore_severities: Vec::new(),
             ignore_rules: Vec::new(),
             include_rule_types: vec![RuleType::Solana, RuleType::Anchor, RuleType::General],
+            include_categories: Vec::new(),
+            only_rules: Vec::new(),
+            rule_conflicts: RuleConflictPolicy::default(),
         }
     }
 }
 
 /// Engine for loading and executing rules
+#[derive(Clone)]
 pub struct RuleEngine {
     /// Rules loaded in the engine
     rules: Vec<Arc<dyn Rule>>,
 
+    /// Source of every rule offered to the engine, including filtered out ones
+    sources: HashMap<String, RuleSource>,
+
     /// Configuration for the engine
     config: RuleEngineConfig,
 }
@@ -92,6 +238,7 @@ impl RuleEngine {
     pub fn new(config: RuleEngineConfig) -> Self {
         Self {
             rules: Vec::new(),
+            sources: HashMap::new(),
             config,
         }
     }
@@ -115,18 +262,80 @@ impl RuleEngine {
         Ok(())
     }
 
-    /// Loads rules from YAML templates
-    pub fn load_yaml_rules(&mut self, templates_path: &Path) -> Result<()> {
-        debug!("Loading YAML rules from {}", templates_path.display());
+    /// Loads custom rules from the YAML and TOML templates of a directory.
+    /// Templates reusing the ID of a built-in rule are handled according to
+    /// the `rule_conflicts` policy of the configuration.
+    pub fn load_template_rules(&mut self, templates_path: &Path) -> Result<()> {
+        debug!("Loading rule templates from {}", templates_path.display());
+
+        let rules = crate::analyzer::templates::load_template_files(templates_path)?;
+
+        let conflicts: Vec<String> = rules
+            .iter()
+            .filter(|(_, rule)| self.is_builtin(rule.id()))
+            .map(|(path, rule)| format!("{path}: rule id '{}' is already used by a built-in rule", rule.id()))
+            .collect();
+        if self.config.rule_conflicts == RuleConflictPolicy::Error && !conflicts.is_empty() {
+            anyhow::bail!(
+                "{} custom rule(s) conflict with built-in rules (allow overrides to replace them):\n{}",
+                conflicts.len(),
+                conflicts.join("\n")
+            );
+        }
+
+        let mut count = 0;
+        for (path, rule) in rules {
+            if self.is_builtin(rule.id()) {
+                if self.config.rule_conflicts == RuleConflictPolicy::Override {
+                    info!("Template rule {} from {path} overrides the built-in rule", rule.id());
+                    self.rules.retain(|existing| existing.id() != rule.id());
+                } else {
+                    warn!(
+                        "Skipping template rule {} from {path}: the id is already used by a built-in rule",
+                        rule.id()
+                    );
+                    continue;
+                }
+            }
+            self.add_rule_from(rule, RuleSource::Template(path));
+            count += 1;
+        }
 
-        //@todo => implement YAML rule loading
-        info!("YAML rule loading not implemented yet");
+        info!("Loaded {count} template rules from {}", templates_path.display());
 
         Ok(())
     }
 
-    /// Adds a rule to the engine
+    /// Whether a rule ID belongs to a built-in rule, even one filtered out
+    fn is_builtin(&self, id: &str) -> bool {
+        self.sources.get(id) == Some(&RuleSource::BuiltIn)
+    }
+
+    /// Returns where the rule with the given ID was loaded from
+    pub fn rule_source(&self, id: &str) -> Option<&RuleSource> {
+        self.sources.get(id)
+    }
+
+    /// Rule IDs of the allowlist that no rule offered to the engine has
+    pub fn unknown_allowed_rules(&self) -> Vec<&str> {
+        self.config
+            .only_rules
+            .iter()
+            .filter(|id| !self.sources.contains_key(id.as_str()))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Adds a built-in rule to the engine
     pub fn add_rule(&mut self, rule: Arc<dyn Rule>) {
+        self.add_rule_from(rule, RuleSource::BuiltIn);
+    }
+
+    /// Adds a rule loaded from the given source to the engine
+    pub fn add_rule_from(&mut self, rule: Arc<dyn Rule>, source: RuleSource) {
+        debug!("Rule {} comes from {source}", rule.id());
+        self.sources.insert(rule.id().to_string(), source);
+
         // Check if the rule should be ignored based on severity
         if self.config.ignore_severities.contains(&rule.severity()) {
             debug!(
@@ -143,6 +352,17 @@ impl RuleEngine {
             return;
         }
 
+        // An allowlist of rule IDs replaces the rule type and category filters
+        if !self.config.only_rules.is_empty() {
+            if self.config.only_rules.contains(&rule.id().to_string()) {
+                debug!("Adding rule: {}", rule.id());
+                self.rules.push(rule);
+            } else {
+                debug!("Ignoring rule {} as it is not in the allowlist", rule.id());
+            }
+            return;
+        }
+
         // Check if the rule type is included
         if !self.config.include_rule_types.contains(&rule.rule_type()) {
             debug!(
@@ -153,6 +373,21 @@ impl RuleEngine {
             return;
         }
 
+        // Check if the rule is in one of the included categories
+        if !self.config.include_categories.is_empty()
+            && !rule
+                .categories()
+                .iter()
+                .any(|category| self.config.include_categories.contains(category))
+        {
+            debug!(
+                "Ignoring rule {} due to categories {:?}",
+                rule.id(),
+                rule.categories()
+            );
+            return;
+        }
+
         debug!("Adding rule: {}", rule.id());
         self.rules.push(rule);
     }
@@ -175,8 +410,22 @@ impl RuleEngine {
 
         for rule in &self.rules {
             match rule.execute_with_source(ast, file_path, source_code) {
-                Ok(rule_findings) => {
+                Ok(mut rule_findings) => {
                     debug!("Rule {} found {} issues", rule.id(), rule_findings.len());
+
+                    // Tag findings with the rule that produced them
+                    for finding in &mut rule_findings {
+                        if finding.rule_id.is_empty() {
+                            finding.rule_id = rule.id().to_string();
+                        }
+                        if finding.references.is_empty() {
+                            finding.references = rule.references().to_vec();
+                        }
+                        if finding.context.is_none() {
+                            finding.context = SourceContext::capture(source_code, &finding.location);
+                        }
+                    }
+
                     findings.extend(rule_findings);
                 }
                 Err(e) => {
@@ -208,6 +457,18 @@ pub struct RustRule {
     /// Recommendations for fixing the issue
     recommendations: Vec<String>,
 
+    /// Example of code the rule flags
+    vulnerable_example: Option<String>,
+
+    /// Fixed version of the vulnerable example
+    fixed_example: Option<String>,
+
+    /// Categories of the rule
+    categories: Vec<String>,
+
+    /// External references of the rule
+    references: Vec<String>,
+
     /// Function that implements the rule check with `SpanExtractor` support
     check_fn: Box<dyn Fn(&File, &str, &crate::analyzer::span_utils::SpanExtractor) -> Result<Vec<Finding>> + Send + Sync>,
 }
@@ -233,9 +494,32 @@ impl RustRule {
             severity,
             rule_type,
             recommendations,
+            vulnerable_example: None,
+            fixed_example: None,
+            categories: Vec::new(),
+            references: Vec::new(),
             check_fn: Box::new(check_fn),
         }
     }
+
+    /// Attaches examples of vulnerable and fixed code
+    pub fn with_examples(mut self, vulnerable: Option<String>, fixed: Option<String>) -> Self {
+        self.vulnerable_example = vulnerable;
+        self.fixed_example = fixed;
+        self
+    }
+
+    /// Attaches the categories of the rule
+    pub fn with_categories(mut self, categories: Vec<String>) -> Self {
+        self.categories = categories;
+        self
+    }
+
+    /// Attaches external references (CWE ids, advisories, exploit writeups)
+    pub fn with_references(mut self, references: Vec<String>) -> Self {
+        self.references = references;
+        self
+    }
 }
 
 impl Rule for RustRule {
@@ -263,6 +547,22 @@ impl Rule for RustRule {
         self.recommendations.clone()
     }
 
+    fn categories(&self) -> &[String] {
+        &self.categories
+    }
+
+    fn references(&self) -> &[String] {
+        &self.references
+    }
+
+    fn vulnerable_example(&self) -> Option<&str> {
+        self.vulnerable_example.as_deref()
+    }
+
+    fn fixed_example(&self) -> Option<&str> {
+        self.fixed_example.as_deref()
+    }
+
     fn execute(&self, ast: &File, file_path: &str) -> Result<Vec<Finding>> {
         // Fallback: create SpanExtractor with empty source for backward compatibility
         let span_extractor = crate::analyzer::span_utils::SpanExtractor::new(String::new(), file_path.to_string());
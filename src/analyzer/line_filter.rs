@@ -0,0 +1,134 @@
+use log::debug;
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+use crate::analyzer::Finding;
+
+/// Inclusive range of lines of a file, written `file:start-end` or `file:line`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineRange {
+    pub file: PathBuf,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl std::str::FromStr for LineRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || format!("Invalid line range: {s} (expected file:start-end)");
+
+        let (file, lines) = s.rsplit_once(':').ok_or_else(invalid)?;
+        let (start, end) = lines.split_once('-').unwrap_or((lines, lines));
+        let start: usize = start.trim().parse().map_err(|_| invalid())?;
+        let end: usize = end.trim().parse().map_err(|_| invalid())?;
+
+        if file.is_empty() || start == 0 || end < start {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            file: PathBuf::from(file),
+            start,
+            end,
+        })
+    }
+}
+
+/// Restricts reported findings to line ranges, keyed by file path. Paths match
+/// when one is a suffix of the other, so ranges relative to the repository root
+/// (as in a diff) apply to findings of any analysis root.
+#[derive(Debug, Clone, Default)]
+pub struct LineFilter {
+    ranges: HashMap<PathBuf, Vec<(usize, usize)>>,
+}
+
+impl LineFilter {
+    /// Build a filter from explicit line ranges
+    pub fn from_ranges(ranges: impl IntoIterator<Item = LineRange>) -> Self {
+        let mut filter = Self::default();
+        for range in ranges {
+            filter.add(range);
+        }
+        filter
+    }
+
+    /// Build a filter from the hunks of a unified diff (e.g. `git diff -U0`).
+    /// Each hunk covers its lines in the new version of the file; deleted files are skipped.
+    pub fn from_unified_diff(diff: &str) -> Self {
+        let mut filter = Self::default();
+        let mut file: Option<PathBuf> = None;
+
+        for line in diff.lines() {
+            if let Some(target) = line.strip_prefix("+++ ") {
+                // Timestamps are separated from the path by a tab
+                let target = target.split('\t').next().unwrap_or_default().trim();
+                file = (target != "/dev/null")
+                    .then(|| PathBuf::from(target.strip_prefix("b/").unwrap_or(target)));
+            } else if let Some(hunk) = line.strip_prefix("@@ ")
+                && let Some(file) = &file
+                && let Some((start, count)) = hunk_new_lines(hunk)
+                && count > 0
+            {
+                filter.add(LineRange {
+                    file: file.clone(),
+                    start,
+                    end: start + count - 1,
+                });
+            }
+        }
+
+        debug!("Diff touches {} file(s)", filter.file_count());
+        filter
+    }
+
+    /// Add a line range
+    pub fn add(&mut self, range: LineRange) {
+        self.ranges
+            .entry(normalize(&range.file))
+            .or_default()
+            .push((range.start, range.end));
+    }
+
+    /// Merge the ranges of another filter
+    pub fn extend(&mut self, other: LineFilter) {
+        for (file, ranges) in other.ranges {
+            self.ranges.entry(file).or_default().extend(ranges);
+        }
+    }
+
+    /// Returns true if no range was given
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of files with ranges
+    pub fn file_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Whether a finding starts inside one of the ranges of its file
+    pub fn contains(&self, finding: &Finding) -> bool {
+        let file = normalize(Path::new(&finding.location.file));
+        let line = finding.location.line;
+
+        self.ranges
+            .iter()
+            .filter(|(path, _)| file.ends_with(path) || path.ends_with(&file))
+            .any(|(_, ranges)| ranges.iter().any(|(start, end)| (*start..=*end).contains(&line)))
+    }
+}
+
+/// Start and length of the new side of a hunk header such as `-12,3 +14,5 @@`
+fn hunk_new_lines(hunk: &str) -> Option<(usize, usize)> {
+    let new = hunk.split_whitespace().find_map(|part| part.strip_prefix('+'))?;
+    let (start, count) = new.split_once(',').unwrap_or((new, "1"));
+    Some((start.parse().ok()?, count.parse().ok()?))
+}
+
+/// Drop `.` components so `./src/lib.rs` and `src/lib.rs` compare equal
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect()
+}
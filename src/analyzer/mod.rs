@@ -1,20 +1,34 @@
 // Declare submodules
+pub mod baseline;
+pub mod cache;
 pub mod dsl;
 pub mod engine;
+pub mod line_filter;
 pub mod rules;
+pub mod remote_templates;
 pub mod reporting;
 pub mod span_utils;
+pub mod templates;
 
 // Standard imports
 use anyhow::Context;
+use rayon::prelude::*;
 use log::{debug, info, warn};
-use std::collections::HashMap;
-use std::path::Path;
-use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, mpsc};
+use std::time::Duration;
 use syn::File;
 
+use crate::analyzer::cache::{AnalysisCache, CacheEntry, FileStamp};
+use crate::analyzer::line_filter::LineFilter;
+use crate::ast::parser::WalkOptions;
+use crate::ast::suppressions::parse_suppressions;
+
 /// Severity level of a vulnerability
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Severity {
     /// High severity vulnerability that must be fixed immediately
     High,
@@ -26,8 +40,39 @@ pub enum Severity {
     Informational,
 }
 
+impl Severity {
+    /// Numeric rank of the severity, higher is more severe
+    fn rank(&self) -> u8 {
+        match self {
+            Severity::High => 3,
+            Severity::Medium => 2,
+            Severity::Low => 1,
+            Severity::Informational => 0,
+        }
+    }
+
+    /// Returns true if this severity is at least as severe as `threshold`
+    pub fn is_at_least(&self, threshold: &Severity) -> bool {
+        self.rank() >= threshold.rank()
+    }
+}
+
+impl std::str::FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "high" => Ok(Severity::High),
+            "medium" => Ok(Severity::Medium),
+            "low" => Ok(Severity::Low),
+            "informational" => Ok(Severity::Informational),
+            _ => Err(format!("Unknown severity level: {s}")),
+        }
+    }
+}
+
 /// Location of a vulnerability in the source code
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Location {
     /// File path
     pub file: String,
@@ -41,9 +86,68 @@ pub struct Location {
     pub end_column: Option<usize>,
 }
 
+/// Source lines surrounding a finding
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceContext {
+    /// Line number of the first captured line (1-indexed)
+    pub start_line: usize,
+    /// Captured lines, without line terminators
+    pub lines: Vec<String>,
+}
+
+impl SourceContext {
+    /// Lines before and after the offending lines captured with a finding
+    pub const SURROUNDING_LINES: usize = 2;
+
+    /// Offending lines captured at most, longer spans are cut after them
+    pub const MAX_MARKED_LINES: usize = 5;
+
+    /// Captures the lines of `location` plus `SURROUNDING_LINES` on each side.
+    /// Returns `None` if the location is outside of the source.
+    pub fn capture(source: &str, location: &Location) -> Option<Self> {
+        let lines: Vec<&str> = source.lines().collect();
+        if location.line == 0 || location.line > lines.len() {
+            return None;
+        }
+
+        let marked_end = *Self::marked_lines(location).end();
+        let start_line = location.line.saturating_sub(Self::SURROUNDING_LINES).max(1);
+        let end_line = (marked_end + Self::SURROUNDING_LINES).min(lines.len());
+
+        Some(Self {
+            start_line,
+            lines: lines[start_line - 1..end_line].iter().map(|line| line.to_string()).collect(),
+        })
+    }
+
+    /// Offending lines of a location, cut after `MAX_MARKED_LINES`
+    pub fn marked_lines(location: &Location) -> RangeInclusive<usize> {
+        let end_line = location
+            .end_line
+            .unwrap_or(location.line)
+            .clamp(location.line, location.line + Self::MAX_MARKED_LINES - 1);
+        location.line..=end_line
+    }
+
+    /// Width of the widest line number, to right-align the gutter
+    pub fn gutter_width(&self) -> usize {
+        (self.start_line + self.lines.len().saturating_sub(1)).to_string().len()
+    }
+
+    /// Iterates the captured lines with their 1-indexed line number
+    pub fn numbered_lines(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.lines
+            .iter()
+            .enumerate()
+            .map(|(offset, line)| (self.start_line + offset, line.as_str()))
+    }
+}
+
 /// Finding of a vulnerability
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Finding {
+    /// ID of the rule that produced the finding
+    pub rule_id: String,
     /// Description of the vulnerability
     pub description: String,
     /// Severity level of the vulnerability
@@ -52,15 +156,25 @@ pub struct Finding {
     pub location: Location,
     /// Code snippet containing the vulnerability (optional)
     pub code_snippet: Option<String>,
+    /// Source lines around the vulnerability, for diagnostics-style rendering
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<SourceContext>,
     /// Recommendations for fixing the vulnerability
     pub recommendations: Vec<String>,
+    /// External references of the rule (CWE ids, advisories, exploit writeups)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<String>,
+    /// Stable identifier of the finding across runs, independent of line numbers
+    /// (see `baseline::fingerprint`). Empty until the analyzer assigns it
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub fingerprint: String,
 }
 
 /// Custom result type for analyzer operations
 pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
 pub use engine::{
-    Rule, RuleEngine, RuleEngineConfig, RuleType, create_rule_engine,
+    Rule, RuleConflictPolicy, RuleEngine, RuleEngineConfig, RuleSource, RuleType, create_rule_engine,
     create_rule_engine_with_config,
 };
 
@@ -69,13 +183,25 @@ pub fn create_analyzer() -> Analyzer {
     Analyzer::new()
 }
 
+/// Creates an analyzer without any rule, for embedders composing their own rule
+/// set with `Analyzer::register_rule`
+pub fn create_analyzer_empty() -> Analyzer {
+    Analyzer::empty()
+}
+
+/// Analyzes in-memory source code with the default rule set, without touching
+/// the filesystem. `virtual_path` is only used as the file of the finding locations.
+pub fn analyze_source(source: &str, virtual_path: &str) -> Result<AnalysisResult> {
+    create_analyzer().analyze_source(source, virtual_path)
+}
+
 /// Creates an analyzer with custom options
 pub fn create_analyzer_with_options(options: AnalysisOptions) -> Analyzer {
     Analyzer::with_options(options)
 }
 
 /// Result of an analysis
-#[derive(Debug)]
+#[derive(Debug, Default, Serialize)]
 pub struct AnalysisResult {
     /// Findings found during the analysis
     pub findings: Vec<Finding>,
@@ -83,21 +209,56 @@ pub struct AnalysisResult {
     pub stats: AnalysisStats,
 }
 
+impl AnalysisResult {
+    /// Keep only the findings matching `predicate`, recomputing the severity and
+    /// rule breakdowns of `stats` so they stay consistent with the findings
+    pub fn retain<F: Fn(&Finding) -> bool>(&mut self, predicate: F) {
+        self.findings.retain(|finding| predicate(finding));
+        self.stats.recount(&self.findings);
+    }
+}
+
 /// Statistics of an analysis
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct AnalysisStats {
     /// Number of files analyzed
     pub files_analyzed: usize,
+    /// Number of source lines in the analyzed files
+    pub lines_analyzed: usize,
     /// Number of rules executed
     pub rules_executed: usize,
     /// Total analysis time in milliseconds
     pub total_time_ms: u64,
     /// Breakdown of findings by severity
     pub findings_by_severity: HashMap<Severity, usize>,
+    /// Breakdown of findings by rule ID
+    pub findings_by_rule: BTreeMap<String, usize>,
+    /// Number of files skipped because their analysis exceeded the per-file timeout
+    pub files_skipped_timeout: usize,
+}
+
+impl AnalysisStats {
+    /// Count a reported finding in the severity and rule breakdowns
+    pub fn record(&mut self, finding: &Finding) {
+        *self
+            .findings_by_severity
+            .entry(finding.severity.clone())
+            .or_insert(0) += 1;
+        *self.findings_by_rule.entry(finding.rule_id.clone()).or_insert(0) += 1;
+    }
+
+    /// Recompute the severity and rule breakdowns from a set of findings
+    pub fn recount(&mut self, findings: &[Finding]) {
+        self.findings_by_severity.clear();
+        self.findings_by_rule.clear();
+        for finding in findings {
+            self.record(finding);
+        }
+    }
 }
 
 /// Options for analysis
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct AnalysisOptions {
     /// Whether to generate AST JSON files
     pub generate_ast: bool,
@@ -108,11 +269,96 @@ pub struct AnalysisOptions {
     /// Severities to ignore
     pub ignore_severities: Vec<Severity>,
 
+    /// Only report findings at or above this severity
+    pub min_severity: Option<Severity>,
+
     /// Rule IDs to ignore
     pub ignore_rules: Vec<String>,
 
     /// Rule types to include
     pub include_rule_types: Vec<RuleType>,
+
+    /// Only run rules in one of these categories (all rules when empty)
+    pub include_categories: Vec<String>,
+
+    /// Only run these rule IDs, taking precedence over `include_rule_types` and
+    /// `include_categories` (all rules when empty)
+    pub only_rules: Vec<String>,
+
+    /// Only analyze files matching one of these globs (relative to the analysis root)
+    pub include_globs: Vec<String>,
+
+    /// Skip files and directories matching one of these globs (relative to the analysis root)
+    pub exclude_globs: Vec<String>,
+
+    /// Whether to skip files ignored by `.gitignore`
+    pub respect_gitignore: bool,
+
+    /// Whether to analyze files under `tests/`, `benches/` and `examples/` directories
+    pub include_tests: bool,
+
+    /// Only analyze files changed since this git ref
+    pub since: Option<String>,
+
+    /// Maximum number of worker threads (defaults to the number of CPUs)
+    pub max_threads: Option<usize>,
+
+    /// Directory of the incremental analysis cache; unchanged files reuse their
+    /// cached findings. Disabled when `None`
+    pub cache_dir: Option<String>,
+
+    /// Only report findings starting inside these line ranges (e.g. the hunks of a diff)
+    pub line_filter: Option<LineFilter>,
+
+    /// How custom rules reusing the ID of a built-in rule are handled
+    pub rule_conflicts: RuleConflictPolicy,
+
+    /// Time budget for parsing and analyzing a single file; files exceeding it
+    /// are skipped with a warning. Unbounded when `None`; a zero budget is rejected
+    pub per_file_timeout: Option<Duration>,
+
+    /// Root the file paths of finding fingerprints are relative to; paths are
+    /// used as given when `None`
+    pub project_root: Option<String>,
+}
+
+impl Default for AnalysisOptions {
+    fn default() -> Self {
+        Self {
+            generate_ast: false,
+            custom_templates_path: None,
+            ignore_severities: Vec::new(),
+            min_severity: None,
+            ignore_rules: Vec::new(),
+            include_rule_types: Vec::new(),
+            include_categories: Vec::new(),
+            only_rules: Vec::new(),
+            include_globs: Vec::new(),
+            exclude_globs: Vec::new(),
+            respect_gitignore: true,
+            include_tests: false,
+            since: None,
+            max_threads: None,
+            cache_dir: None,
+            line_filter: None,
+            rule_conflicts: RuleConflictPolicy::default(),
+            per_file_timeout: None,
+            project_root: None,
+        }
+    }
+}
+
+impl AnalysisOptions {
+    /// Directory walk options derived from the file filters
+    pub fn walk_options(&self) -> WalkOptions {
+        WalkOptions {
+            include_globs: self.include_globs.clone(),
+            exclude_globs: self.exclude_globs.clone(),
+            respect_gitignore: self.respect_gitignore,
+            since: self.since.clone(),
+            include_tests: self.include_tests,
+        }
+    }
 }
 
 /// Analyzer for Solana contracts
@@ -120,8 +366,8 @@ pub struct Analyzer {
     /// Options for analysis
     options: AnalysisOptions,
 
-    /// Rule engine
-    rule_engine: RuleEngine,
+    /// Rule engine, shared with the workers of timed file analyses
+    rule_engine: Arc<RuleEngine>,
 }
 
 impl Analyzer {
@@ -136,18 +382,81 @@ impl Analyzer {
         
         Self {
             options: AnalysisOptions::default(),
-            rule_engine,
+            rule_engine: Arc::new(rule_engine),
+        }
+    }
+
+    /// Creates a new analyzer with default options and no rules
+    pub fn empty() -> Self {
+        Self {
+            options: AnalysisOptions::default(),
+            rule_engine: Arc::new(create_rule_engine()),
+        }
+    }
+
+    /// Creates a new analyzer with the given options. Custom rule templates
+    /// that fail to load are skipped with a warning
+    pub fn with_options(mut options: AnalysisOptions) -> Self {
+        if options.per_file_timeout.is_some_and(|timeout| timeout.is_zero()) {
+            warn!("Ignoring the per-file timeout of 0s, which would skip every file");
+            options.per_file_timeout = None;
+        }
+
+        let mut rule_engine = Self::builtin_engine(&options);
+
+        if let Some(templates_path) = Self::templates_dir(&options)
+            && let Err(e) = rule_engine.load_template_rules(templates_path)
+        {
+            warn!("Failed to load rule templates from {}: {}", templates_path.display(), e);
+        }
+
+        let unknown = rule_engine.unknown_allowed_rules();
+        if !unknown.is_empty() {
+            warn!("Unknown rule IDs to run: {}", unknown.join(", "));
+        }
+
+        Self {
+            options,
+            rule_engine: Arc::new(rule_engine),
+        }
+    }
+
+    /// Creates a new analyzer with the given options, failing if the custom
+    /// rule templates are malformed or conflict with built-in rules, if
+    /// `only_rules` names an unknown rule, or if the per-file timeout is zero
+    pub fn try_with_options(options: AnalysisOptions) -> anyhow::Result<Self> {
+        if options.per_file_timeout.is_some_and(|timeout| timeout.is_zero()) {
+            anyhow::bail!("The per-file timeout must be greater than zero");
+        }
+
+        let mut rule_engine = Self::builtin_engine(&options);
+
+        if let Some(templates_path) = Self::templates_dir(&options) {
+            rule_engine.load_template_rules(templates_path)?;
+        }
+
+        let unknown = rule_engine.unknown_allowed_rules();
+        if !unknown.is_empty() {
+            anyhow::bail!("Unknown rule IDs to run: {}", unknown.join(", "));
         }
+
+        Ok(Self {
+            options,
+            rule_engine: Arc::new(rule_engine),
+        })
     }
 
-    /// Creates a new analyzer with the given options
-    pub fn with_options(options: AnalysisOptions) -> Self {
+    /// Rule engine configured from the analysis options, with the built-in rules
+    fn builtin_engine(options: &AnalysisOptions) -> RuleEngine {
         // Convert analysis options to rule engine config
         let config = RuleEngineConfig {
             custom_templates_path: options.custom_templates_path.clone(),
             ignore_severities: options.ignore_severities.clone(),
             ignore_rules: options.ignore_rules.clone(),
             include_rule_types: options.include_rule_types.clone(),
+            include_categories: options.include_categories.clone(),
+            only_rules: options.only_rules.clone(),
+            rule_conflicts: options.rule_conflicts,
         };
 
         let mut rule_engine = create_rule_engine_with_config(config);
@@ -157,27 +466,29 @@ impl Analyzer {
             warn!("Failed to load built-in rules: {e}");
         }
 
-        // Load custom rules if specified
-        if let Some(templates_path) = &options.custom_templates_path {
-            let path = Path::new(templates_path);
-            if path.exists() && path.is_dir() {
-                if let Err(e) = rule_engine.load_yaml_rules(path) {
-                    warn!("Failed to load YAML rules from {}: {}", path.display(), e);
-                }
-            } else {
-                warn!(
-                    "Custom templates path does not exist or is not a directory: {}",
-                    path.display()
-                );
-            }
-        }
+        rule_engine
+    }
 
-        Self {
-            options,
-            rule_engine,
+    /// Custom templates directory of the options, if it exists
+    fn templates_dir(options: &AnalysisOptions) -> Option<&Path> {
+        let path = Path::new(options.custom_templates_path.as_deref()?);
+        if path.is_dir() {
+            Some(path)
+        } else {
+            warn!(
+                "Custom templates path does not exist or is not a directory: {}",
+                path.display()
+            );
+            None
         }
     }
 
+    /// Registers a rule, such as one shipped by an external crate. The rule is
+    /// subject to the same severity, ID, type and category filters as built-in rules
+    pub fn register_rule(&mut self, rule: Box<dyn Rule>) {
+        Arc::make_mut(&mut self.rule_engine).add_rule_from(Arc::from(rule), RuleSource::External);
+    }
+
     /// Returns a reference to all loaded rules
     pub fn rules(&self) -> &[Arc<dyn Rule>] {
         self.rule_engine.get_rules()
@@ -185,67 +496,383 @@ impl Analyzer {
 
     /// Analyzes a single file
     pub fn analyze_file(&self, file_path: &str, ast: &File) -> Result<Vec<Finding>> {
+        Ok(self.analyze_parsed(file_path, ast)?.findings)
+    }
+
+    /// Analyzes an already parsed file, reading its source for precise locations
+    fn analyze_parsed(&self, file_path: &str, ast: &File) -> anyhow::Result<FileAnalysis> {
         debug!("Analyzing file: {file_path}");
 
         // Read source code for precise locations
         let source_code = std::fs::read_to_string(file_path)
             .with_context(|| format!("Failed to read source code from {file_path}"))?;
-
-        // Execute rules on the AST with source code for precise locations
-        let findings = self
-            .rule_engine
-            .execute_rules(ast, file_path, &source_code)
-            .with_context(|| format!("Failed to execute rules on {file_path}"))?;
+        let findings = run_rules(&self.rule_engine, file_path, ast, &source_code)?;
 
         debug!("Found {} issues in {}", findings.len(), file_path);
 
-        Ok(findings)
+        Ok(FileAnalysis::new(file_path, &source_code, findings))
+    }
+
+    /// Parses and analyzes a single file from disk, within the per-file timeout if set
+    fn analyze_path(&self, file_path: &str) -> FileResult {
+        match self.options.per_file_timeout {
+            Some(timeout) => self.analyze_path_within(file_path, timeout),
+            None => analyze_path(&self.rule_engine, file_path).map_err(FileError::failed),
+        }
+    }
+
+    /// Parses and analyzes a single file from disk on a worker thread, giving up
+    /// after `timeout`. A worker that overruns is left to finish in the background.
+    fn analyze_path_within(&self, file_path: &str, timeout: Duration) -> FileResult {
+        let rule_engine = Arc::clone(&self.rule_engine);
+        let path = file_path.to_string();
+        let (sender, receiver) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("eloizer-file".to_string())
+            .spawn(move || {
+                // The receiver is gone once the timeout expired
+                let _ = sender.send(analyze_path(&rule_engine, &path).map_err(FileError::failed));
+            })
+            .context("Failed to spawn a file analysis worker")
+            .map_err(FileError::failed)?;
+
+        receiver.recv_timeout(timeout).unwrap_or(Err(FileError::TimedOut(timeout)))
+    }
+
+    /// Drops the findings filtered out by the severity and line range options;
+    /// a finding must pass every filter
+    fn retain_reported(&self, findings: &mut Vec<Finding>) {
+        findings.retain(|f| {
+            !self.options.ignore_severities.contains(&f.severity)
+                && self
+                    .options
+                    .min_severity
+                    .as_ref()
+                    .is_none_or(|min| f.severity.is_at_least(min))
+                && self
+                    .options
+                    .line_filter
+                    .as_ref()
+                    .is_none_or(|filter| filter.contains(f))
+        });
+    }
+
+    /// Analyzes in-memory source code, such as an unsaved editor buffer, without
+    /// touching the filesystem. `virtual_path` is only used as the file of the
+    /// finding locations.
+    pub fn analyze_source(&self, source: &str, virtual_path: &str) -> Result<AnalysisResult> {
+        debug!("Analyzing in-memory source: {virtual_path}");
+
+        let start_time = std::time::Instant::now();
+        let ast = crate::ast::parser::parse_rust_code(source)
+            .with_context(|| format!("Failed to parse {virtual_path}"))?;
+        let mut findings = run_rules(&self.rule_engine, virtual_path, &ast, source)?;
+        sort_findings(&mut findings);
+        // Occurrences are numbered before filtering, so --lines and --diff cannot renumber them
+        baseline::assign_fingerprints(&mut findings, self.options.project_root.as_deref().unwrap_or_default());
+        self.retain_reported(&mut findings);
+
+        let mut stats = AnalysisStats {
+            files_analyzed: 1,
+            lines_analyzed: source.lines().count(),
+            rules_executed: self.rule_engine.rule_count(),
+            ..AnalysisStats::default()
+        };
+        stats.recount(&findings);
+        stats.total_time_ms = u64::try_from(start_time.elapsed().as_millis())?;
+
+        Ok(AnalysisResult { findings, stats })
+    }
+
+    /// Analyzes multiple already parsed Rust files. Prefer [`Analyzer::analyze_paths`]:
+    /// files analyzed in parallel or within a timeout are parsed again by the worker
+    pub fn analyze_files(&self, files: &[(PathBuf, File)]) -> Result<AnalysisResult> {
+        let mut all_findings = Vec::new();
+        let stats = self.analyze_files_with(files, |finding| all_findings.push(finding.clone()))?;
+
+        // Keep reports reproducible regardless of scheduling
+        sort_findings(&mut all_findings);
+
+        Ok(AnalysisResult {
+            findings: all_findings,
+            stats,
+        })
+    }
+
+    /// Analyzes multiple already parsed Rust files like [`Analyzer::analyze_paths_with`]
+    pub fn analyze_files_with<F>(&self, files: &[(PathBuf, File)], callback: F) -> Result<AnalysisStats>
+    where
+        F: FnMut(&Finding),
+    {
+        let files: Vec<(&Path, Option<&File>)> = files.iter().map(|(path, ast)| (path.as_path(), Some(ast))).collect();
+        self.analyze_inputs_with(&files, callback)
     }
 
-    /// Analyzes multiple Rust files
-    pub fn analyze_files(&self, files: &[(std::path::PathBuf, File)]) -> Result<AnalysisResult> {
+    /// Analyzes multiple Rust files from disk. Each file is parsed once, by the
+    /// worker analyzing it and within the per-file timeout; unchanged files
+    /// served from the cache are not parsed at all.
+    pub fn analyze_paths(&self, paths: &[PathBuf]) -> Result<AnalysisResult> {
+        let mut all_findings = Vec::new();
+        let stats = self.analyze_paths_with(paths, |finding| all_findings.push(finding.clone()))?;
+
+        // Keep reports reproducible regardless of scheduling
+        sort_findings(&mut all_findings);
+
+        Ok(AnalysisResult {
+            findings: all_findings,
+            stats,
+        })
+    }
+
+    /// Analyzes multiple Rust files from disk, invoking the callback for each
+    /// finding as soon as the file producing it has been analyzed.
+    ///
+    /// The callback always runs on the calling thread. Findings of a file are
+    /// delivered together and in order, but files may complete in any order
+    /// when analyzed in parallel.
+    pub fn analyze_paths_with<F>(&self, paths: &[PathBuf], callback: F) -> Result<AnalysisStats>
+    where
+        F: FnMut(&Finding),
+    {
+        let files: Vec<(&Path, Option<&File>)> = paths.iter().map(|path| (path.as_path(), None)).collect();
+        self.analyze_inputs_with(&files, callback)
+    }
+
+    /// Analyzes files given by path, with their AST when already parsed
+    fn analyze_inputs_with<F>(&self, files: &[(&Path, Option<&File>)], mut callback: F) -> Result<AnalysisStats>
+    where
+        F: FnMut(&Finding),
+    {
         info!("Starting analysis of {} files", files.len());
 
         let start_time = std::time::Instant::now();
         let mut stats = AnalysisStats::default();
         stats.files_analyzed = files.len();
+        stats.rules_executed = self.rule_engine.rule_count();
+
+        let threads = self
+            .options
+            .max_threads
+            .unwrap_or_else(rayon::current_num_threads)
+            .max(1);
+
+        // Unchanged files are served from the cache without being re-parsed or re-analyzed
+        let mut cache = self.options.cache_dir.as_ref().map(|dir| {
+            AnalysisCache::open(Path::new(dir), &cache::rule_set_fingerprint(&self.rule_engine))
+        });
+        let mut cached = Vec::new();
+        let mut pending = Vec::new();
+        for file in files {
+            let file_path = file.0.to_string_lossy().to_string();
+            match cache.as_mut().and_then(|cache| cache.lookup(&file_path)) {
+                Some(entry) => {
+                    let analysis = FileAnalysis {
+                        findings: entry.findings.clone(),
+                        lines: entry.lines,
+                        stamp: None,
+                    };
+                    cached.push((file_path, analysis));
+                }
+                None => pending.push(file),
+            }
+        }
+        if cache.is_some() {
+            debug!("{} of {} files served from the analysis cache", cached.len(), files.len());
+        }
 
-        let mut all_findings = Vec::new();
+        let mut findings_count = 0;
+        let mut deliver = |file_path: String, result: FileResult, stats: &mut AnalysisStats| {
+            match result {
+                Ok(FileAnalysis { mut findings, lines, stamp }) => {
+                    stats.lines_analyzed += lines;
+                    if let (Some(cache), Some(stamp)) = (cache.as_mut(), stamp) {
+                        let entry = CacheEntry {
+                            stamp,
+                            lines,
+                            findings: findings.clone(),
+                        };
+                        cache.store(&file_path, entry);
+                    }
 
-        for (path, ast) in files {
-            let file_path = path.to_string_lossy().to_string();
-            match self.analyze_file(&file_path, ast) {
-                Ok(mut findings) => {
-                    // Filter findings by severity
-                    findings.retain(|f| !self.options.ignore_severities.contains(&f.severity));
+                    sort_findings(&mut findings);
+                    // Occurrences are numbered before filtering, so --lines and --diff cannot renumber them
+                    baseline::assign_fingerprints(&mut findings, self.options.project_root.as_deref().unwrap_or_default());
+                    self.retain_reported(&mut findings);
 
                     // Update statistics
                     for finding in &findings {
-                        *stats
-                            .findings_by_severity
-                            .entry(finding.severity.clone())
-                            .or_insert(0) += 1;
+                        stats.record(finding);
+                        callback(finding);
                     }
-
-                    all_findings.extend(findings);
+                    findings_count += findings.len();
                 }
-                Err(e) => {
+                Err(FileError::TimedOut(timeout)) => {
+                    warn!("Skipping {file_path}: analysis exceeded the {}s timeout", timeout.as_secs_f64());
+                    stats.files_analyzed -= 1;
+                    stats.files_skipped_timeout += 1;
+                }
+                Err(FileError::Failed(e)) => {
                     warn!("Error analyzing {file_path}: {e}");
+                    stats.files_analyzed -= 1;
                 }
             }
+        };
+
+        for (file_path, analysis) in cached {
+            deliver(file_path, Ok(analysis), &mut stats);
+        }
+
+        if threads == 1 || pending.len() < 2 {
+            for (path, ast) in pending {
+                let file_path = path.to_string_lossy().to_string();
+                // Files analyzed within a time budget are parsed on a worker,
+                // since syn ASTs cannot be sent to other threads
+                let result = match (ast, self.options.per_file_timeout) {
+                    (Some(ast), None) => self.analyze_parsed(&file_path, ast).map_err(FileError::failed),
+                    _ => self.analyze_path(&file_path),
+                };
+                deliver(file_path, result, &mut stats);
+            }
+        } else {
+            // syn ASTs hold thread-local spans and cannot be shared across threads,
+            // so each worker parses its file from source
+            let paths: Vec<String> = pending
+                .iter()
+                .map(|(path, _)| path.to_string_lossy().to_string())
+                .collect();
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .context("Failed to build analysis thread pool")?;
+
+            debug!("Analyzing {} files on {} threads", paths.len(), threads);
+            let (sender, receiver) = std::sync::mpsc::channel::<(String, FileResult)>();
+            std::thread::scope(|scope| {
+                scope.spawn(|| {
+                    pool.install(|| {
+                        paths.into_par_iter().for_each_with(sender, |sender, file_path| {
+                            let result = self.analyze_path(&file_path);
+                            // The receiver only goes away once every result was delivered
+                            let _ = sender.send((file_path, result));
+                        });
+                    });
+                });
+
+                for (file_path, result) in receiver {
+                    deliver(file_path, result, &mut stats);
+                }
+            });
+        }
+
+        if let Some(cache) = cache.as_mut()
+            && let Err(e) = cache.save()
+        {
+            warn!("Failed to save the analysis cache: {e:#}");
         }
 
         stats.total_time_ms = u64::try_from(start_time.elapsed().as_millis())?;
 
         info!(
             "Analysis completed: {} findings in {}ms",
-            all_findings.len(),
+            findings_count,
             stats.total_time_ms
         );
 
-        Ok(AnalysisResult {
-            findings: all_findings,
-            stats,
-        })
+        Ok(stats)
+    }
+}
+
+/// Findings and size of a single analyzed file
+struct FileAnalysis {
+    findings: Vec<Finding>,
+    lines: usize,
+    /// Stamp of the analyzed content, `None` when served from the cache
+    stamp: Option<FileStamp>,
+}
+
+impl FileAnalysis {
+    fn new(file_path: &str, source_code: &str, findings: Vec<Finding>) -> Self {
+        Self {
+            findings,
+            lines: source_code.lines().count(),
+            stamp: Some(FileStamp::new(Path::new(file_path), source_code)),
+        }
+    }
+}
+
+/// Why a file could not be analyzed; errors are kept as strings since boxed
+/// errors cannot leave the worker threads
+enum FileError {
+    Failed(String),
+    /// The analysis exceeded the per-file timeout
+    TimedOut(Duration),
+}
+
+impl FileError {
+    fn failed(error: anyhow::Error) -> Self {
+        FileError::Failed(format!("{error:#}"))
+    }
+}
+
+/// Analysis of a single file
+type FileResult = std::result::Result<FileAnalysis, FileError>;
+
+/// Parses and analyzes a single file from disk
+fn analyze_path(rule_engine: &RuleEngine, file_path: &str) -> anyhow::Result<FileAnalysis> {
+    let source_code = std::fs::read_to_string(file_path)
+        .with_context(|| format!("Failed to read source code from {file_path}"))?;
+    let ast = crate::ast::parser::parse_rust_code(&source_code)
+        .with_context(|| format!("Failed to parse {file_path}"))?;
+    let findings = run_rules(rule_engine, file_path, &ast, &source_code)?;
+
+    Ok(FileAnalysis::new(file_path, &source_code, findings))
+}
+
+/// Executes the rules on a parsed file and applies its suppression comments
+fn run_rules(rule_engine: &RuleEngine, file_path: &str, ast: &File, source_code: &str) -> anyhow::Result<Vec<Finding>> {
+    let mut findings = rule_engine
+        .execute_rules(ast, file_path, source_code)
+        .with_context(|| format!("Failed to execute rules on {file_path}"))?;
+    apply_suppressions(file_path, source_code, &mut findings);
+    Ok(findings)
+}
+
+/// Sort findings by file, position and rule
+fn sort_findings(findings: &mut [Finding]) {
+    findings.sort_by(|a, b| {
+        (&a.location.file, a.location.line, a.location.column, &a.rule_id)
+            .cmp(&(&b.location.file, b.location.line, b.location.column, &b.rule_id))
+    });
+}
+
+/// Drop the findings silenced by inline `// eloizer-ignore` directives
+fn apply_suppressions(file_path: &str, source_code: &str, findings: &mut Vec<Finding>) {
+    let suppressions = parse_suppressions(source_code);
+    if suppressions.is_empty() {
+        return;
+    }
+
+    let mut used = vec![false; suppressions.len()];
+    findings.retain(|finding| {
+        let mut suppressed = false;
+        for (index, suppression) in suppressions.iter().enumerate() {
+            if suppression.matches(&finding.rule_id, finding.location.line) {
+                used[index] = true;
+                suppressed = true;
+            }
+        }
+        if suppressed {
+            debug!("Suppressed {} at {}:{}", finding.rule_id, file_path, finding.location.line);
+        }
+        !suppressed
+    });
+
+    for (suppression, used) in suppressions.iter().zip(used) {
+        if !used {
+            warn!(
+                "Unused eloizer-ignore directive at {}:{} matches no finding",
+                file_path, suppression.line
+            );
+        }
     }
 }
@@ -0,0 +1,274 @@
+use anyhow::{Context, Result, bail};
+use log::{debug, info, warn};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::analyzer::templates;
+use crate::ast::archive;
+
+/// Directory of downloaded templates inside the cache directory
+const REMOTE_DIR: &str = "remote-templates";
+
+/// Time allowed for downloading a template URL
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How a template URL is fetched
+#[derive(Debug, Clone, Default)]
+pub struct RemoteOptions {
+    /// Directory the download is cached in
+    pub cache_dir: PathBuf,
+    /// Expected SHA-256 of the download (hex); a different download is rejected
+    pub sha256: Option<String>,
+    /// Never download, only use the cached copy
+    pub offline: bool,
+}
+
+/// Whether a templates location is a URL rather than a local directory
+pub fn is_remote(location: &str) -> bool {
+    location.starts_with("https://") || location.starts_with("http://")
+}
+
+/// Download the templates at an `https://` URL, a single YAML/TOML template or a
+/// `.tar.gz`/`.zip` archive of templates, and return the local directory
+/// holding them. The download is cached: with a pinned checksum a matching
+/// cached copy is used without network access, and when the download fails
+/// the cached copy is used instead.
+pub fn fetch(url: &str, options: &RemoteOptions) -> Result<PathBuf> {
+    if !url.starts_with("https://") {
+        bail!("Only https:// template URLs are supported: {url}");
+    }
+    let file_name = url_file_name(url).with_context(|| format!("Template URL {url} does not name a file"))?;
+    let download_path = Path::new(&file_name);
+    if !templates::is_template_file(download_path) && !archive::is_archive(download_path) {
+        bail!("Template URL {url} must point to a .yaml, .yml or .toml template or a .tar.gz or .zip archive");
+    }
+
+    let key = format!("{:x}", Sha256::digest(url.as_bytes()));
+    let root = options.cache_dir.join(REMOTE_DIR);
+    let download = root.join(format!("{}-{file_name}", &key[..16]));
+    let dir = root.join(&key[..16]);
+
+    let cached = fs::read(&download).ok();
+    let cached_ok = cached.as_deref().is_some_and(|bytes| matches_pin(bytes, options.sha256.as_deref()));
+
+    if options.offline {
+        let Some(bytes) = cached else {
+            bail!("Templates at {url} are not cached; run once without --offline to download them");
+        };
+        verify(&bytes, options.sha256.as_deref(), url)?;
+        if !dir.is_dir() {
+            unpack(&download, &dir)?;
+        }
+        return Ok(templates_root(&dir));
+    }
+
+    // A pinned download never changes, so a matching copy is reused as is
+    if options.sha256.is_some() && cached_ok && dir.is_dir() {
+        debug!("Using cached templates for {url}");
+        return Ok(templates_root(&dir));
+    }
+
+    let bytes = match download_bytes(url) {
+        Ok(bytes) => bytes,
+        Err(e) if cached_ok => {
+            warn!("Failed to download {url}, using the cached templates: {e:#}");
+            if !dir.is_dir() {
+                unpack(&download, &dir)?;
+            }
+            return Ok(templates_root(&dir));
+        }
+        Err(e) => return Err(e),
+    };
+    verify(&bytes, options.sha256.as_deref(), url)?;
+
+    fs::create_dir_all(&root).with_context(|| format!("Failed to create {}", root.display()))?;
+    fs::write(&download, &bytes).with_context(|| format!("Failed to write {}", download.display()))?;
+    unpack(&download, &dir)?;
+    info!("Downloaded templates from {url} to {}", dir.display());
+    Ok(templates_root(&dir))
+}
+
+fn download_bytes(url: &str) -> Result<Vec<u8>> {
+    debug!("Downloading {url}");
+    let client = reqwest::blocking::Client::builder()
+        .timeout(DOWNLOAD_TIMEOUT)
+        .user_agent(concat!("eloizer/", env!("CARGO_PKG_VERSION")))
+        .build()?;
+    let response = client
+        .get(url)
+        .send()
+        .and_then(reqwest::blocking::Response::error_for_status)
+        .with_context(|| format!("Failed to download {url}"))?;
+    Ok(response.bytes().with_context(|| format!("Failed to download {url}"))?.to_vec())
+}
+
+/// Last path segment of a URL, without query or fragment
+fn url_file_name(url: &str) -> Option<String> {
+    let path = url.split(['?', '#']).next()?;
+    let name = path.rsplit('/').next()?;
+    (!name.is_empty() && path.matches('/').count() > 2).then(|| name.to_string())
+}
+
+fn matches_pin(bytes: &[u8], sha256: Option<&str>) -> bool {
+    sha256.is_none_or(|expected| format!("{:x}", Sha256::digest(bytes)).eq_ignore_ascii_case(expected.trim()))
+}
+
+fn verify(bytes: &[u8], sha256: Option<&str>, url: &str) -> Result<()> {
+    if !matches_pin(bytes, sha256) {
+        bail!(
+            "Checksum mismatch for {url}: expected {}, got {:x}",
+            sha256.unwrap_or_default(),
+            Sha256::digest(bytes)
+        );
+    }
+    Ok(())
+}
+
+/// Replace the templates directory with the content of a download
+fn unpack(download: &Path, dir: &Path) -> Result<()> {
+    if dir.exists() {
+        fs::remove_dir_all(dir).with_context(|| format!("Failed to clear {}", dir.display()))?;
+    }
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+    if archive::is_archive(download) {
+        archive::extract_to(download, dir)
+    } else {
+        // Drop the cache key prefix of the download
+        let name = download
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.split_once('-'))
+            .map_or("template.yaml", |(_, name)| name);
+        fs::copy(download, dir.join(name)).with_context(|| format!("Failed to copy {}", download.display()))?;
+        Ok(())
+    }
+}
+
+/// Directory holding the templates: archives wrapping everything in a single
+/// top-level directory (as source host tarballs do) are looked into
+fn templates_root(dir: &Path) -> PathBuf {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return dir.to_path_buf();
+    };
+    let entries: Vec<PathBuf> = entries.filter_map(|entry| entry.ok().map(|entry| entry.path())).collect();
+    match entries.as_slice() {
+        [only] if only.is_dir() => only.clone(),
+        _ => dir.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Nothing listens on port 1, so downloads from it fail right away
+    const UNREACHABLE_URL: &str = "https://127.0.0.1:1/policy/rules.yaml";
+
+    const TEMPLATE: &str = "id: central-rule\n";
+
+    /// Store `content` as the cached download of `url`, as `fetch` would
+    fn cache_download(cache_dir: &Path, url: &str, content: &str) {
+        let key = format!("{:x}", Sha256::digest(url.as_bytes()));
+        let root = cache_dir.join(REMOTE_DIR);
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join(format!("{}-{}", &key[..16], url_file_name(url).unwrap())), content).unwrap();
+    }
+
+    fn sha256(content: &str) -> String {
+        format!("{:x}", Sha256::digest(content.as_bytes()))
+    }
+
+    fn options(cache_dir: &Path, sha256: Option<String>, offline: bool) -> RemoteOptions {
+        RemoteOptions { cache_dir: cache_dir.to_path_buf(), sha256, offline }
+    }
+
+    #[test]
+    fn test_url_file_name() {
+        assert_eq!(url_file_name("https://example.com/policy/rules.yaml").as_deref(), Some("rules.yaml"));
+        assert_eq!(url_file_name("https://example.com/rules.tar.gz?token=abc#top").as_deref(), Some("rules.tar.gz"),
+                "The query and fragment should not be part of the file name");
+        assert_eq!(url_file_name("https://example.com/policy/"), None);
+        assert_eq!(url_file_name("https://example.com"), None, "A bare host does not name a file");
+    }
+
+    #[test]
+    fn test_matches_pin() {
+        let digest = sha256(TEMPLATE);
+
+        assert!(matches_pin(TEMPLATE.as_bytes(), None), "Unpinned downloads always match");
+        assert!(matches_pin(TEMPLATE.as_bytes(), Some(&digest)));
+        assert!(matches_pin(TEMPLATE.as_bytes(), Some(&format!(" {} ", digest.to_uppercase()))),
+                "The pin should be case and whitespace insensitive");
+        assert!(!matches_pin(b"id: other-rule\n", Some(&digest)));
+    }
+
+    #[test]
+    fn test_verify() {
+        assert!(verify(TEMPLATE.as_bytes(), Some(&sha256(TEMPLATE)), UNREACHABLE_URL).is_ok());
+
+        let error = verify(TEMPLATE.as_bytes(), Some(&sha256("other")), UNREACHABLE_URL).unwrap_err();
+        assert!(error.to_string().contains("Checksum mismatch"), "Unexpected error: {error}");
+    }
+
+    #[test]
+    fn test_rejects_unsupported_urls() {
+        let cache_dir = tempfile::tempdir().unwrap();
+
+        assert!(fetch("http://example.com/rules.yaml", &options(cache_dir.path(), None, true)).is_err(),
+                "Plain http:// URLs should be rejected");
+        assert!(fetch("https://example.com/rules.txt", &options(cache_dir.path(), None, true)).is_err(),
+                "URLs that are neither templates nor archives should be rejected");
+    }
+
+    #[test]
+    fn test_offline_without_cached_copy() {
+        let cache_dir = tempfile::tempdir().unwrap();
+
+        let error = fetch(UNREACHABLE_URL, &options(cache_dir.path(), None, true)).unwrap_err();
+        assert!(error.to_string().contains("not cached"), "Unexpected error: {error}");
+    }
+
+    #[test]
+    fn test_offline_uses_cached_copy() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        cache_download(cache_dir.path(), UNREACHABLE_URL, TEMPLATE);
+
+        let dir = fetch(UNREACHABLE_URL, &options(cache_dir.path(), Some(sha256(TEMPLATE)), true)).unwrap();
+        assert_eq!(fs::read_to_string(dir.join("rules.yaml")).unwrap(), TEMPLATE,
+                "The cached download should be unpacked under its own file name");
+    }
+
+    #[test]
+    fn test_offline_rejects_cached_copy_with_another_checksum() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        cache_download(cache_dir.path(), UNREACHABLE_URL, TEMPLATE);
+
+        let error = fetch(UNREACHABLE_URL, &options(cache_dir.path(), Some(sha256("other")), true)).unwrap_err();
+        assert!(error.to_string().contains("Checksum mismatch"), "Unexpected error: {error}");
+    }
+
+    #[test]
+    fn test_failed_download_falls_back_to_cache() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        cache_download(cache_dir.path(), UNREACHABLE_URL, TEMPLATE);
+
+        let dir = fetch(UNREACHABLE_URL, &options(cache_dir.path(), None, false)).unwrap();
+        assert_eq!(fs::read_to_string(dir.join("rules.yaml")).unwrap(), TEMPLATE,
+                "A failed download should use the cached copy");
+    }
+
+    #[test]
+    fn test_failed_download_without_usable_cache() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        assert!(fetch(UNREACHABLE_URL, &options(cache_dir.path(), None, false)).is_err(),
+                "A failed download without a cached copy should fail");
+
+        // A cached copy that does not match the pin cannot stand in for the download
+        cache_download(cache_dir.path(), UNREACHABLE_URL, TEMPLATE);
+        assert!(fetch(UNREACHABLE_URL, &options(cache_dir.path(), Some(sha256("other")), false)).is_err(),
+                "A cached copy with another checksum should not be used");
+    }
+}
@@ -1,11 +1,208 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 
-use crate::analyzer::{Finding, Severity};
+use serde::Serialize;
+use serde_json::json;
+
+use crate::analyzer::{AnalysisStats, Finding, Severity, SourceContext, baseline};
+
+/// Output format of a report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown,
+    Json,
+    Sarif,
+    Text,
+    Github,
+    Csv,
+    Html,
+    Junit,
+    Count,
+    Ndjson,
+}
+
+impl ReportFormat {
+    /// Infer the format from an output file extension, if recognized
+    pub fn from_extension(path: &str) -> Option<Self> {
+        let extension = std::path::Path::new(path).extension()?.to_str()?;
+        match extension.to_lowercase().as_str() {
+            "md" | "markdown" => Some(ReportFormat::Markdown),
+            "json" => Some(ReportFormat::Json),
+            "sarif" => Some(ReportFormat::Sarif),
+            "txt" => Some(ReportFormat::Text),
+            "csv" => Some(ReportFormat::Csv),
+            "html" | "htm" => Some(ReportFormat::Html),
+            "xml" => Some(ReportFormat::Junit),
+            "ndjson" | "jsonl" => Some(ReportFormat::Ndjson),
+            _ => None,
+        }
+    }
+}
+
+impl std::str::FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "md" | "markdown" => Ok(ReportFormat::Markdown),
+            "json" => Ok(ReportFormat::Json),
+            "sarif" => Ok(ReportFormat::Sarif),
+            "text" | "txt" => Ok(ReportFormat::Text),
+            "github" => Ok(ReportFormat::Github),
+            "csv" => Ok(ReportFormat::Csv),
+            "html" => Ok(ReportFormat::Html),
+            "junit" => Ok(ReportFormat::Junit),
+            "count" => Ok(ReportFormat::Count),
+            "ndjson" | "jsonl" => Ok(ReportFormat::Ndjson),
+            _ => Err(format!(
+                "Unknown report format: {s} (expected md, json, sarif, text, github, csv, html, junit, count or ndjson)"
+            )),
+        }
+    }
+}
+
+/// How findings are grouped in human-readable reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    /// One entry per rule listing every location it fired at
+    Rule,
+}
+
+impl std::str::FromStr for GroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "rule" => Ok(GroupBy::Rule),
+            _ => Err(format!("Unknown grouping: {s} (expected rule)")),
+        }
+    }
+}
+
+/// Order in which findings are listed in the terminal output and in reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortOrder {
+    /// Most severe first, then by file and position
+    #[default]
+    Severity,
+    /// By file and position, then most severe first
+    File,
+    /// By rule id, then by file and position
+    Rule,
+}
+
+impl std::str::FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "severity" => Ok(SortOrder::Severity),
+            "file" => Ok(SortOrder::File),
+            "rule" => Ok(SortOrder::Rule),
+            _ => Err(format!("Unknown sort order: {s} (expected severity, file or rule)")),
+        }
+    }
+}
+
+/// Sort findings in the given order. Ties keep file, position and rule order so
+/// the output is stable across runs.
+pub fn sort_findings(findings: &mut [Finding], order: SortOrder) {
+    fn position(finding: &Finding) -> (&str, usize, Option<usize>) {
+        (&finding.location.file, finding.location.line, finding.location.column)
+    }
+
+    findings.sort_by(|a, b| {
+        match order {
+            SortOrder::Severity => (severity_rank(&a.severity), position(a), &a.rule_id)
+                .cmp(&(severity_rank(&b.severity), position(b), &b.rule_id)),
+            SortOrder::File => (position(a), severity_rank(&a.severity), &a.rule_id)
+                .cmp(&(position(b), severity_rank(&b.severity), &b.rule_id)),
+            SortOrder::Rule => (&a.rule_id, position(a)).cmp(&(&b.rule_id, position(b))),
+        }
+    });
+}
+
+/// Keep at most `limit` findings of each severity, in their current order.
+/// Returns how many findings of each severity were left out.
+pub fn limit_per_severity(findings: &mut Vec<Finding>, limit: usize) -> HashMap<Severity, usize> {
+    let mut kept: HashMap<Severity, usize> = HashMap::new();
+    let mut hidden: HashMap<Severity, usize> = HashMap::new();
+    findings.retain(|finding| {
+        let count = kept.entry(finding.severity.clone()).or_insert(0);
+        if *count < limit {
+            *count += 1;
+            true
+        } else {
+            *hidden.entry(finding.severity.clone()).or_insert(0) += 1;
+            false
+        }
+    });
+    hidden
+}
+
+/// One line of an NDJSON report
+#[derive(Serialize)]
+struct NdjsonRecord<'a> {
+    rule_id: &'a str,
+    severity: &'a Severity,
+    file: String,
+    line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    column: Option<usize>,
+    description: &'a str,
+    recommendations: &'a [String],
+    #[serde(skip_serializing_if = "str::is_empty")]
+    fingerprint: &'a str,
+}
+
+/// Serialize a finding as one compact JSON object, without the trailing newline.
+/// The file is reported relative to `project_path`, as in the other formats
+pub fn ndjson_line(finding: &Finding, project_path: &str) -> Result<String, serde_json::Error> {
+    serde_json::to_string(&NdjsonRecord {
+        rule_id: &finding.rule_id,
+        severity: &finding.severity,
+        file: baseline::relative_path(&finding.location.file, project_path),
+        line: finding.location.line,
+        column: finding.location.column,
+        description: &finding.description,
+        recommendations: &finding.recommendations,
+        fingerprint: &finding.fingerprint,
+    })
+}
+
+/// Locations listed per grouped entry before the rest are summarized as "and N more"
+pub const DEFAULT_GROUP_LIMIT: usize = 10;
 
 pub struct ReportGenerator {
     findings: Vec<Finding>,
     project_path: String,
+    stats: AnalysisStats,
+    group_by: Option<GroupBy>,
+    group_limit: usize,
+    sort: SortOrder,
+    /// Maximum findings listed per severity, when limited
+    top: Option<usize>,
+    /// Findings of each severity left out by `top`
+    hidden: HashMap<Severity, usize>,
+}
+
+/// Structured report written by `save_json_report`
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    findings: &'a [Finding],
+    stats: &'a AnalysisStats,
+}
+
+/// Finding counts written by `save_summary` for trend dashboards
+#[derive(Serialize)]
+struct SummaryReport {
+    high: usize,
+    medium: usize,
+    low: usize,
+    informational: usize,
+    total: usize,
+    files_analyzed: usize,
+    duration_ms: u64,
 }
 
 impl ReportGenerator {
@@ -13,7 +210,50 @@ impl ReportGenerator {
         Self {
             findings,
             project_path,
+            stats: AnalysisStats::default(),
+            group_by: None,
+            group_limit: DEFAULT_GROUP_LIMIT,
+            sort: SortOrder::Severity,
+            top: None,
+            hidden: HashMap::new(),
+        }
+    }
+
+    /// Attach the analysis statistics to include in structured reports
+    pub fn with_stats(mut self, stats: AnalysisStats) -> Self {
+        self.stats = stats;
+        self
+    }
+
+    /// Collapse findings in the text, Markdown and HTML reports. Machine-readable
+    /// formats always keep one entry per finding.
+    pub fn with_group_by(mut self, group_by: Option<GroupBy>) -> Self {
+        self.group_by = group_by;
+        self
+    }
+
+    /// Order findings in every format. Reports with severity sections (Markdown,
+    /// JUnit) keep them and apply the order within each section.
+    pub fn with_sort(mut self, order: SortOrder) -> Self {
+        sort_findings(&mut self.findings, order);
+        self.sort = order;
+        self
+    }
+
+    /// List at most `top` findings per severity, the first ones in the sort
+    /// order. Apply after `with_sort`; totals still count every finding.
+    pub fn with_top(mut self, top: Option<usize>) -> Self {
+        if let Some(limit) = top {
+            self.hidden = limit_per_severity(&mut self.findings, limit);
         }
+        self.top = top;
+        self
+    }
+
+    /// Maximum number of locations listed per grouped entry
+    pub fn with_group_limit(mut self, limit: usize) -> Self {
+        self.group_limit = limit;
+        self
     }
 
     pub fn generate_markdown_report(&self) -> String {
@@ -21,6 +261,9 @@ impl ReportGenerator {
         
         // Header
         report.push_str(&self.generate_header());
+
+        // Severity badges
+        report.push_str(&self.generate_severity_badges());
         
         // Table of Contents
         report.push_str(&self.generate_table_of_contents());
@@ -42,6 +285,498 @@ impl ReportGenerator {
         Ok(())
     }
 
+    /// Generate the JSON report with all findings and the analysis statistics
+    pub fn generate_json_report(&self) -> Result<String, serde_json::Error> {
+        let report = JsonReport {
+            findings: &self.findings,
+            stats: &self.stats,
+        };
+        serde_json::to_string_pretty(&report)
+    }
+
+    /// Save the JSON report to a file
+    pub fn save_json_report(&self, output_path: &str) -> Result<(), std::io::Error> {
+        let report = self.generate_json_report()?;
+        fs::write(output_path, report)?;
+        Ok(())
+    }
+
+    /// Generate the summary JSON: finding counts per severity, files analyzed and duration
+    pub fn generate_summary_json(&self) -> Result<String, serde_json::Error> {
+        let severity_counts = self.get_severity_counts();
+        let count = |severity: Severity| severity_counts.get(&severity).copied().unwrap_or(0);
+        let summary = SummaryReport {
+            high: count(Severity::High),
+            medium: count(Severity::Medium),
+            low: count(Severity::Low),
+            informational: count(Severity::Informational),
+            total: self.findings.len(),
+            files_analyzed: self.stats.files_analyzed,
+            duration_ms: self.stats.total_time_ms,
+        };
+        serde_json::to_string_pretty(&summary)
+    }
+
+    /// Save the summary JSON to a file
+    pub fn save_summary(&self, output_path: &str) -> Result<(), std::io::Error> {
+        let summary = self.generate_summary_json()?;
+        fs::write(output_path, summary)?;
+        Ok(())
+    }
+
+    /// Generate JSON Lines: one compact finding object per line
+    pub fn generate_ndjson_report(&self) -> Result<String, serde_json::Error> {
+        let mut report = String::new();
+        for finding in &self.findings {
+            report.push_str(&ndjson_line(finding, &self.project_path)?);
+            report.push('\n');
+        }
+        Ok(report)
+    }
+
+    /// Generate a SARIF 2.1.0 log for code scanning integrations
+    pub fn generate_sarif_report(&self) -> Result<String, serde_json::Error> {
+        let mut rule_ids: Vec<&str> = self.findings.iter().map(|f| f.rule_id.as_str()).collect();
+        rule_ids.sort();
+        rule_ids.dedup();
+
+        let rules: Vec<_> = rule_ids
+            .iter()
+            .map(|rule_id| {
+                let finding = self.findings.iter().find(|f| f.rule_id == *rule_id);
+                let title = finding
+                    .map(|f| extract_title_and_description(&f.description).0)
+                    .unwrap_or_default();
+                json!({
+                    "id": rule_id,
+                    "shortDescription": { "text": title },
+                })
+            })
+            .collect();
+
+        let results: Vec<_> = self
+            .findings
+            .iter()
+            .map(|finding| {
+                let mut region = json!({ "startLine": finding.location.line });
+                if let Some(column) = finding.location.column {
+                    region["startColumn"] = json!(column);
+                }
+                if let Some(end_line) = finding.location.end_line {
+                    region["endLine"] = json!(end_line);
+                }
+                if let Some(end_column) = finding.location.end_column {
+                    region["endColumn"] = json!(end_column);
+                }
+
+                let mut result = json!({
+                    "ruleId": finding.rule_id,
+                    "level": sarif_level(&finding.severity),
+                    "message": { "text": finding.description },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": self.display_path(&finding.location.file) },
+                            "region": region,
+                        }
+                    }],
+                });
+                // Lets code scanning platforms track the finding across runs
+                if !finding.fingerprint.is_empty() {
+                    result["partialFingerprints"] = json!({ "eloizerFingerprint/v1": finding.fingerprint });
+                }
+                result
+            })
+            .collect();
+
+        let log = json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "eloizer",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules,
+                    }
+                },
+                "results": results,
+            }],
+        });
+        serde_json::to_string_pretty(&log)
+    }
+
+    /// Generate a plain text report, one finding per entry (or per rule when grouped)
+    pub fn generate_text_report(&self) -> String {
+        if self.group_by.is_some() {
+            return self.generate_grouped_text_report();
+        }
+
+        let mut report = String::new();
+
+        for finding in &self.findings {
+            report.push_str(&format!(
+                "[{:?}] {}\n  --> {}:{}\n",
+                finding.severity,
+                finding.description,
+                self.display_path(&finding.location.file),
+                finding.location.position()
+            ));
+            if let Some(context) = &finding.context {
+                let marked = SourceContext::marked_lines(&finding.location);
+                let width = context.gutter_width();
+                for (number, line) in context.numbered_lines() {
+                    let marker = if marked.contains(&number) { '>' } else { ' ' };
+                    report.push_str(&format!(" {marker} {number:>width$} | {line}\n"));
+                }
+            } else if let Some(snippet) = &finding.code_snippet {
+                for line in snippet.lines() {
+                    report.push_str(&format!("   | {line}\n"));
+                }
+            }
+            report.push('\n');
+        }
+
+        report.push_str(&format!("{} finding(s)\n", self.findings.len()));
+        report.push_str(&self.top_note());
+        report
+    }
+
+    /// Plain text report with one entry per rule listing its locations
+    fn generate_grouped_text_report(&self) -> String {
+        let groups = group_findings_by_rule(&self.findings);
+        let mut report = String::new();
+
+        for group in &groups {
+            report.push_str(&format!(
+                "[{:?}] {} ({}): {} occurrence(s)\n  {}\n",
+                group.severity,
+                group.title,
+                group.rule_id,
+                group.findings.len(),
+                group.description
+            ));
+            for finding in group.findings.iter().take(self.location_limit()) {
+                report.push_str(&format!(
+                    "  --> {}:{}\n",
+                    self.display_path(&finding.location.file),
+                    finding.location.position()
+                ));
+            }
+            if let Some(more) = self.remaining_locations(group.findings.len()) {
+                report.push_str(&format!("  ... and {more} more\n"));
+            }
+            report.push('\n');
+        }
+
+        report.push_str(&format!(
+            "{} finding(s) from {} rule(s)\n",
+            self.findings.len(),
+            groups.len()
+        ));
+        report.push_str(&self.top_note());
+        report
+    }
+
+    /// Generate GitHub Actions workflow commands, one annotation per finding.
+    /// File paths are kept as reported so they stay relative to the workspace.
+    pub fn generate_github_annotations(&self) -> String {
+        let mut report = String::new();
+
+        for finding in &self.findings {
+            let level = match finding.severity {
+                Severity::High => "error",
+                Severity::Medium => "warning",
+                Severity::Low | Severity::Informational => "notice",
+            };
+            let file = finding.location.file.trim_start_matches("./");
+
+            let mut properties = format!(
+                "file={},line={}",
+                escape_github_property(file),
+                finding.location.line
+            );
+            if let Some(column) = finding.location.column {
+                properties.push_str(&format!(",col={column}"));
+            }
+
+            report.push_str(&format!(
+                "::{level} {properties}::{}\n",
+                escape_github_data(&format!("{} {}", finding.rule_id, finding.description))
+            ));
+        }
+
+        report
+    }
+
+    /// Generate a CSV report with one row per finding.
+    /// The column order is part of the format and must stay stable.
+    pub fn generate_csv_report(&self) -> String {
+        let mut report = String::from("rule_id,severity,file,line,description,recommendation\n");
+
+        for finding in &self.findings {
+            let row = [
+                finding.rule_id.clone(),
+                format!("{:?}", finding.severity),
+                self.display_path(&finding.location.file).to_string(),
+                finding.location.line.to_string(),
+                finding.description.clone(),
+                finding.recommendations.join("; "),
+            ];
+            let row: Vec<String> = row.iter().map(|field| escape_csv_field(field)).collect();
+            report.push_str(&row.join(","));
+            report.push('\n');
+        }
+
+        report
+    }
+
+    /// Save the CSV report to a file
+    pub fn save_csv_report(&self, output_path: &str) -> Result<(), std::io::Error> {
+        fs::write(output_path, self.generate_csv_report())
+    }
+
+    /// Generate a self-contained HTML page (inline CSS, no external assets) with a
+    /// severity summary and one collapsible section per finding
+    pub fn generate_html_report(&self) -> String {
+        let severities = [
+            Severity::High,
+            Severity::Medium,
+            Severity::Low,
+            Severity::Informational,
+        ];
+        let severity_counts = self.get_severity_counts();
+        let mut report = String::from(HTML_HEADER);
+
+        report.push_str("<h1>Eloizer Report</h1>\n");
+        report.push_str(&format!(
+            "<p class=\"meta\">{} finding(s) in {} file(s)</p>\n",
+            self.findings.len() + self.hidden_count(),
+            self.get_unique_file_count()
+        ));
+
+        // Severity summary
+        report.push_str("<div class=\"summary\">\n");
+        for severity in &severities {
+            report.push_str(&format!(
+                "<div class=\"card {}\"><span class=\"count\">{}</span>{:?}</div>\n",
+                html_severity_class(severity),
+                severity_counts.get(severity).unwrap_or(&0) + self.hidden.get(severity).unwrap_or(&0),
+                severity
+            ));
+        }
+        report.push_str("</div>\n");
+        let note = self.top_note();
+        if !note.is_empty() {
+            report.push_str(&format!("<p class=\"meta\">{}</p>\n", escape_markup(note.trim_end())));
+        }
+
+        if self.findings.is_empty() {
+            report.push_str("<p>No vulnerabilities found.</p>\n");
+        }
+
+        // Findings, most severe first unless sorted by file or rule
+        if self.group_by.is_some() {
+            report.push_str(&self.generate_grouped_html_findings());
+            report.push_str("</body>\n</html>\n");
+            return report;
+        }
+        let ordered: Vec<&Finding> = match self.sort {
+            SortOrder::Severity => severities
+                .iter()
+                .flat_map(|severity| self.findings.iter().filter(move |f| &f.severity == severity))
+                .collect(),
+            SortOrder::File | SortOrder::Rule => self.findings.iter().collect(),
+        };
+        for finding in ordered {
+            let severity = &finding.severity;
+            let (title, description) = extract_title_and_description(&finding.description);
+            let location = format!(
+                "{}:{}",
+                self.display_path(&finding.location.file),
+                finding.location.position()
+            );
+
+            report.push_str(&format!(
+                "<details class=\"finding {}\">\n<summary><span class=\"badge\">{:?}</span> {} <code>{}</code></summary>\n",
+                html_severity_class(severity),
+                severity,
+                escape_markup(&title),
+                escape_markup(&location)
+            ));
+            report.push_str(&format!(
+                "<p class=\"rule\">{}</p>\n<p>{}</p>\n",
+                escape_markup(&finding.rule_id),
+                escape_markup(&description)
+            ));
+            if let Some(snippet) = &finding.code_snippet {
+                report.push_str(&format!("<pre><code>{}</code></pre>\n", escape_markup(snippet)));
+            }
+            if !finding.recommendations.is_empty() {
+                report.push_str("<h4>Recommendations</h4>\n<ol>\n");
+                for recommendation in &finding.recommendations {
+                    report.push_str(&format!("<li>{}</li>\n", escape_markup(recommendation)));
+                }
+                report.push_str("</ol>\n");
+            }
+            report.push_str("</details>\n");
+        }
+
+        report.push_str("</body>\n</html>\n");
+        report
+    }
+
+    /// One collapsible HTML section per rule listing its locations
+    fn generate_grouped_html_findings(&self) -> String {
+        let mut html = String::new();
+
+        for group in group_findings_by_rule(&self.findings) {
+            html.push_str(&format!(
+                "<details class=\"finding {}\">\n<summary><span class=\"badge\">{:?}</span> {} <code>{} occurrence(s)</code></summary>\n",
+                html_severity_class(&group.severity),
+                group.severity,
+                escape_markup(&group.title),
+                group.findings.len()
+            ));
+            html.push_str(&format!(
+                "<p class=\"rule\">{}</p>\n<p>{}</p>\n<ul>\n",
+                escape_markup(group.rule_id),
+                escape_markup(&group.description)
+            ));
+            for finding in group.findings.iter().take(self.location_limit()) {
+                let location = format!(
+                    "{}:{}",
+                    self.display_path(&finding.location.file),
+                    finding.location.position()
+                );
+                html.push_str(&format!("<li><code>{}</code></li>\n", escape_markup(&location)));
+            }
+            if let Some(more) = self.remaining_locations(group.findings.len()) {
+                html.push_str(&format!("<li>and {more} more</li>\n"));
+            }
+            html.push_str("</ul>\n");
+            if let Some(first) = group.findings.first()
+                && !first.recommendations.is_empty()
+            {
+                html.push_str("<h4>Recommendations</h4>\n<ol>\n");
+                for recommendation in &first.recommendations {
+                    html.push_str(&format!("<li>{}</li>\n", escape_markup(recommendation)));
+                }
+                html.push_str("</ol>\n");
+            }
+            html.push_str("</details>\n");
+        }
+
+        html
+    }
+
+    /// Save the HTML report to a file
+    pub fn save_html_report(&self, output_path: &str) -> Result<(), std::io::Error> {
+        fs::write(output_path, self.generate_html_report())
+    }
+
+    /// Generate a JUnit XML report for CI test panels: one `<testsuite>` per
+    /// severity, one `<testcase>` per rule and one `<failure>` per finding
+    pub fn generate_junit_report(&self) -> String {
+        let severities = [
+            Severity::High,
+            Severity::Medium,
+            Severity::Low,
+            Severity::Informational,
+        ];
+        let mut suites = String::new();
+        let mut total_tests = 0;
+
+        for severity in &severities {
+            let mut by_rule: BTreeMap<&str, Vec<&Finding>> = BTreeMap::new();
+            for finding in self.findings.iter().filter(|f| &f.severity == severity) {
+                by_rule.entry(&finding.rule_id).or_default().push(finding);
+            }
+            // Only rules with findings are listed, so every test case fails
+            total_tests += by_rule.len();
+
+            suites.push_str(&format!(
+                "  <testsuite name=\"{severity:?}\" tests=\"{0}\" failures=\"{0}\">\n",
+                by_rule.len()
+            ));
+            for (rule_id, findings) in by_rule {
+                suites.push_str(&format!(
+                    "    <testcase classname=\"eloizer.{}\" name=\"{}\">\n",
+                    html_severity_class(severity),
+                    escape_markup(rule_id)
+                ));
+                for finding in findings {
+                    let message = format!(
+                        "{}:{}: {}",
+                        self.display_path(&finding.location.file),
+                        finding.location.position(),
+                        finding.description
+                    );
+                    let mut details = finding.code_snippet.clone().unwrap_or_default();
+                    for recommendation in &finding.recommendations {
+                        details.push_str(&format!("\n- {recommendation}"));
+                    }
+                    suites.push_str(&format!(
+                        "      <failure message=\"{}\" type=\"{severity:?}\">{}</failure>\n",
+                        escape_markup(&message),
+                        escape_markup(details.trim())
+                    ));
+                }
+                suites.push_str("    </testcase>\n");
+            }
+            suites.push_str("  </testsuite>\n");
+        }
+
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"eloizer\" tests=\"{total_tests}\" failures=\"{total_tests}\">\n{suites}</testsuites>\n"
+        )
+    }
+
+    /// Save the JUnit XML report to a file
+    pub fn save_junit_report(&self, output_path: &str) -> Result<(), std::io::Error> {
+        fs::write(output_path, self.generate_junit_report())
+    }
+
+    /// Generate the report in the given format
+    pub fn generate_report(&self, format: ReportFormat) -> Result<String, serde_json::Error> {
+        match format {
+            ReportFormat::Markdown => Ok(self.generate_markdown_report()),
+            ReportFormat::Json => self.generate_json_report(),
+            ReportFormat::Sarif => self.generate_sarif_report(),
+            ReportFormat::Text => Ok(self.generate_text_report()),
+            ReportFormat::Github => Ok(self.generate_github_annotations()),
+            ReportFormat::Csv => Ok(self.generate_csv_report()),
+            ReportFormat::Html => Ok(self.generate_html_report()),
+            ReportFormat::Junit => Ok(self.generate_junit_report()),
+            ReportFormat::Count => Ok(self.findings.len().to_string()),
+            ReportFormat::Ndjson => self.generate_ndjson_report(),
+        }
+    }
+
+    /// Save the report in the given format to a file
+    pub fn save_report(&self, format: ReportFormat, output_path: &str) -> Result<(), std::io::Error> {
+        let report = self.generate_report(format)?;
+        fs::write(output_path, report)?;
+        Ok(())
+    }
+
+    /// Number of locations listed per entry: capped only when grouping
+    fn location_limit(&self) -> usize {
+        match self.group_by {
+            Some(_) => self.group_limit,
+            None => usize::MAX,
+        }
+    }
+
+    /// Number of locations left out of an entry with `total` occurrences, if any
+    fn remaining_locations(&self, total: usize) -> Option<usize> {
+        total.checked_sub(self.location_limit()).filter(|&more| more > 0)
+    }
+
+    /// Path of a file relative to the project root
+    fn display_path(&self, file: &str) -> String {
+        baseline::relative_path(file, &self.project_path)
+    }
+
     fn generate_header(&self) -> String {
         "# Rust Solana Analyzer Report\n\n\
             This report was generated by Rust Solana Analyzer, a static analysis tool for Solana smart contracts. \
@@ -57,26 +792,44 @@ impl ReportGenerator {
         toc.push_str("  - [Files Details](#files-details)\n");
         toc.push_str("  - [Issue Summary](#issue-summary)\n");
 
-        // Add sections for each severity level that has findings
-        let severity_counts = self.get_severity_counts();
-        
-        if severity_counts.get(&Severity::High).unwrap_or(&0) > &0 {
-            toc.push_str("- [High Issues](#high-issues)\n");
-        }
-        if severity_counts.get(&Severity::Medium).unwrap_or(&0) > &0 {
-            toc.push_str("- [Medium Issues](#medium-issues)\n");
-        }
-        if severity_counts.get(&Severity::Low).unwrap_or(&0) > &0 {
-            toc.push_str("- [Low Issues](#low-issues)\n");
-        }
-        if severity_counts.get(&Severity::Informational).unwrap_or(&0) > &0 {
-            toc.push_str("- [Informational Issues](#informational-issues)\n");
+        // Add sections for each severity level that has findings, listing its issues
+        for severity in &SEVERITIES {
+            let groups = self.severity_groups(severity);
+            if groups.is_empty() {
+                continue;
+            }
+            toc.push_str(&format!("- [{severity:?} Issues](#{}-issues)\n", html_severity_class(severity)));
+            for (index, (title, _)) in groups.iter().enumerate() {
+                let id = issue_id(severity, index + 1);
+                toc.push_str(&format!("  - [{id}: {title}](#{})\n", id.to_lowercase()));
+            }
         }
 
         toc.push_str("\n\n");
         toc
     }
 
+    /// One shields.io badge per severity with its finding count, linking to its section
+    fn generate_severity_badges(&self) -> String {
+        let severity_counts = self.get_severity_counts();
+        let badges: Vec<String> = SEVERITIES
+            .iter()
+            .map(|severity| {
+                let shown = severity_counts.get(severity).copied().unwrap_or(0);
+                let total = shown + self.hidden.get(severity).copied().unwrap_or(0);
+                let badge = format!(
+                    "![{severity:?}: {total}](https://img.shields.io/badge/{severity:?}-{total}-{})",
+                    severity_color(severity)
+                );
+                match shown {
+                    0 => badge,
+                    _ => format!("[{badge}](#{}-issues)", html_severity_class(severity)),
+                }
+            })
+            .collect();
+        format!("\n{}\n\n", badges.join(" "))
+    }
+
     fn generate_summary(&self) -> String {
         let mut summary = String::from("# Summary\n\n");
         
@@ -87,7 +840,7 @@ impl ReportGenerator {
         
         let file_count = self.get_unique_file_count();
         summary.push_str(&format!("| .rs Files | {file_count} |\n"));
-        summary.push_str(&format!("| Total Issues | {} |\n\n", self.findings.len()));
+        summary.push_str(&format!("| Total Issues | {} |\n\n", self.findings.len() + self.hidden_count()));
 
         // Files Details
         summary.push_str("## Files Details\n\n");
@@ -96,9 +849,7 @@ impl ReportGenerator {
         
         let file_counts = self.get_file_issue_counts();
         for (file, count) in file_counts {
-            let display_file = file.strip_prefix(&self.project_path)
-                .unwrap_or(&file)
-                .trim_start_matches('/');
+            let display_file = self.display_path(&file);
             summary.push_str(&format!("| {display_file} | {count} |\n"));
         }
         summary.push_str(&format!("| **Total** | **{}** |\n\n", self.findings.len()));
@@ -109,10 +860,18 @@ impl ReportGenerator {
         summary.push_str("| --- | --- |\n");
         
         let severity_counts = self.get_severity_counts();
-        summary.push_str(&format!("| High | {} |\n", severity_counts.get(&Severity::High).unwrap_or(&0)));
-        summary.push_str(&format!("| Medium | {} |\n", severity_counts.get(&Severity::Medium).unwrap_or(&0)));
-        summary.push_str(&format!("| Low | {} |\n", severity_counts.get(&Severity::Low).unwrap_or(&0)));
-        summary.push_str(&format!("| Informational | {} |\n\n", severity_counts.get(&Severity::Informational).unwrap_or(&0)));
+        let total = |severity: Severity| {
+            severity_counts.get(&severity).unwrap_or(&0) + self.hidden.get(&severity).unwrap_or(&0)
+        };
+        summary.push_str(&format!("| High | {} |\n", total(Severity::High)));
+        summary.push_str(&format!("| Medium | {} |\n", total(Severity::Medium)));
+        summary.push_str(&format!("| Low | {} |\n", total(Severity::Low)));
+        summary.push_str(&format!("| Informational | {} |\n\n", total(Severity::Informational)));
+
+        let note = self.top_note();
+        if !note.is_empty() {
+            summary.push_str(&format!("_{}_\n\n", note.trim_end()));
+        }
 
         summary
     }
@@ -149,31 +908,44 @@ impl ReportGenerator {
         issues
     }
 
-    fn generate_severity_section(&self, severity: &Severity) -> String {
-        let mut section = String::new();
-        
-        // Group findings by title (extract title from description)
-        let mut grouped_findings: HashMap<String, (String, Vec<&Finding>)> = HashMap::new();
-        for finding in &self.findings {
-            if &finding.severity == severity {
-                let (title, description) = self.extract_title_and_description(&finding.description);
-                grouped_findings.entry(title.clone())
-                    .or_insert_with(|| (description, Vec::new()))
-                    .1.push(finding);
+    /// Issues of a severity in report order: the title, description and findings of each
+    fn severity_groups(&self, severity: &Severity) -> Vec<(String, (String, Vec<&Finding>))> {
+        match self.group_by {
+            Some(GroupBy::Rule) => group_findings_by_rule(&self.findings)
+                .into_iter()
+                .filter(|group| &group.severity == severity)
+                .map(|group| (group.title, (group.description, group.findings)))
+                .collect(),
+            None => {
+                // Group findings by title (extract title from description), in report order
+                let mut grouped_findings: Vec<(String, (String, Vec<&Finding>))> = Vec::new();
+                let mut index: HashMap<String, usize> = HashMap::new();
+                for finding in &self.findings {
+                    if &finding.severity == severity {
+                        let (title, description) = extract_title_and_description(&finding.description);
+                        match index.get(&title) {
+                            Some(&i) => grouped_findings[i].1.1.push(finding),
+                            None => {
+                                index.insert(title.clone(), grouped_findings.len());
+                                grouped_findings.push((title, (description, vec![finding])));
+                            }
+                        }
+                    }
+                }
+                grouped_findings
             }
         }
+    }
 
-        let mut issue_counter = 1;
-        for (title, (description, findings)) in grouped_findings {
-            let severity_prefix = match severity {
-                Severity::High => "H",
-                Severity::Medium => "M", 
-                Severity::Low => "L",
-                Severity::Informational => "I",
-            };
-            
-            // Title (concise)
-            section.push_str(&format!("## {severity_prefix}-{issue_counter}: {title}\n\n"));
+    fn generate_severity_section(&self, severity: &Severity) -> String {
+        let mut section = String::new();
+
+        for (issue_counter, (title, (description, findings))) in (1..).zip(self.severity_groups(severity)) {
+            let id = issue_id(severity, issue_counter);
+
+            // Title (concise), with a stable anchor for the table of contents
+            section.push_str(&format!("<a id=\"{}\"></a>\n\n", id.to_lowercase()));
+            section.push_str(&format!("## {id}: {title}\n\n"));
             
             // Description (detailed explanation)
             section.push_str(&format!("{description}\n\n"));
@@ -184,12 +956,10 @@ impl ReportGenerator {
                 if findings.len() == 1 { "" } else { "s" }
             ));
             
-            for finding in &findings {
-                let display_location = finding.location.file.strip_prefix(&self.project_path)
-                    .unwrap_or(&finding.location.file)
-                    .trim_start_matches('/');
+            for finding in findings.iter().take(self.location_limit()) {
+                let display_location = self.display_path(&finding.location.file);
                 
-                section.push_str(&format!("- Found in {} [Line: {}]\n\n", display_location, finding.location.line));
+                section.push_str(&format!("- Found in {}:{}\n\n", display_location, finding.location.position()));
                 
                 if let Some(ref code) = finding.code_snippet {
                     section.push_str("\t```rust\n");
@@ -199,6 +969,9 @@ impl ReportGenerator {
                     section.push_str("\t```\n\n");
                 }
             }
+            if let Some(more) = self.remaining_locations(findings.len()) {
+                section.push_str(&format!("- ... and {more} more\n\n"));
+            }
             
             // Recommendations
             if let Some(first_finding) = findings.first() {
@@ -209,41 +982,35 @@ impl ReportGenerator {
                     }
                     section.push_str("\n</details>\n");
                 }
+
+                if !first_finding.references.is_empty() {
+                    section.push_str("\n<details><summary>References</summary>\n\n");
+                    for reference in &first_finding.references {
+                        section.push_str(&format!("- {reference}\n"));
+                    }
+                    section.push_str("\n</details>\n");
+                }
             }
-            
+
             section.push_str("</details>\n\n\n\n");
-            issue_counter += 1;
         }
         
         section
     }
 
-    /// Extract title and description from finding description
-    /// Format: "Title. Description with more details."
-    fn extract_title_and_description(&self, full_description: &str) -> (String, String) {
-        // Find the first sentence as title
-        if let Some(first_dot) = full_description.find(". ") {
-            let title = full_description[..first_dot].trim().to_string();
-            let description = full_description[first_dot + 2..].trim().to_string();
-            (title, description)
-        } else {
-            // If no clear separation, use a simplified approach
-            let parts: Vec<&str> = full_description.splitn(2, " has ").collect();
-            if parts.len() == 2 {
-                let title = format!("{} Issue", parts[0]);
-                let description = format!("This {} has {}. Consider reviewing and fixing this issue.", parts[0].to_lowercase(), parts[1]);
-                (title, description)
-            } else {
-                // Fallback: use first part as title, rest as description
-                let words: Vec<&str> = full_description.split_whitespace().collect();
-                if words.len() > 6 {
-                    let title = words[..6].join(" ");
-                    let description = words[6..].join(" ");
-                    (title, description)
-                } else {
-                    (full_description.to_string(), "Review and fix this issue.".to_string())
-                }
-            }
+    /// Findings left out by `with_top`
+    fn hidden_count(&self) -> usize {
+        self.hidden.values().sum()
+    }
+
+    /// Line telling how many findings `with_top` left out, empty when none were
+    fn top_note(&self) -> String {
+        match self.top {
+            Some(top) if self.hidden_count() > 0 => format!(
+                "Showing at most {top} finding(s) per severity, {} more not listed\n",
+                self.hidden_count()
+            ),
+            _ => String::new(),
         }
     }
 
@@ -274,3 +1041,191 @@ impl ReportGenerator {
         sorted_counts
     }
 }
+
+/// Extract title and description from finding description
+/// Format: "Title. Description with more details."
+fn extract_title_and_description(full_description: &str) -> (String, String) {
+    // Find the first sentence as title
+    if let Some(first_dot) = full_description.find(". ") {
+        let title = full_description[..first_dot].trim().to_string();
+        let description = full_description[first_dot + 2..].trim().to_string();
+        (title, description)
+    } else {
+        // If no clear separation, use a simplified approach
+        let parts: Vec<&str> = full_description.splitn(2, " has ").collect();
+        if parts.len() == 2 {
+            let title = format!("{} Issue", parts[0]);
+            let description = format!("This {} has {}. Consider reviewing and fixing this issue.", parts[0].to_lowercase(), parts[1]);
+            (title, description)
+        } else {
+            // Fallback: use first part as title, rest as description
+            let words: Vec<&str> = full_description.split_whitespace().collect();
+            if words.len() > 6 {
+                let title = words[..6].join(" ");
+                let description = words[6..].join(" ");
+                (title, description)
+            } else {
+                (full_description.to_string(), "Review and fix this issue.".to_string())
+            }
+        }
+    }
+}
+
+/// Findings of a single rule collapsed into one report entry
+#[derive(Debug)]
+pub struct FindingGroup<'a> {
+    pub rule_id: &'a str,
+    pub severity: Severity,
+    /// Rule title, without the per-finding `in '<name>'` suffix
+    pub title: String,
+    pub description: String,
+    pub findings: Vec<&'a Finding>,
+}
+
+/// Group findings by rule id, most severe first. Rules keep the order in which
+/// they were first reported and occurrences keep their original order.
+pub fn group_findings_by_rule(findings: &[Finding]) -> Vec<FindingGroup<'_>> {
+    let mut groups: Vec<FindingGroup> = Vec::new();
+    let mut index: HashMap<&str, usize> = HashMap::new();
+
+    for finding in findings {
+        match index.get(finding.rule_id.as_str()) {
+            Some(&i) => groups[i].findings.push(finding),
+            None => {
+                let (title, description) = extract_title_and_description(&finding.description);
+                index.insert(&finding.rule_id, groups.len());
+                groups.push(FindingGroup {
+                    rule_id: &finding.rule_id,
+                    severity: finding.severity.clone(),
+                    title: rule_title(&title).to_string(),
+                    description,
+                    findings: vec![finding],
+                });
+            }
+        }
+    }
+
+    groups.sort_by_key(|group| severity_rank(&group.severity));
+    groups
+}
+
+/// Strip the `in '<name>'` suffix findings append to the rule title
+fn rule_title(title: &str) -> &str {
+    match title.rsplit_once(" in '") {
+        Some((rule, name)) if name.ends_with('\'') => rule,
+        _ => title,
+    }
+}
+
+/// Severities in report order
+const SEVERITIES: [Severity; 4] = [Severity::High, Severity::Medium, Severity::Low, Severity::Informational];
+
+/// Identifier of the n-th issue of a severity in the Markdown report (`H-1`, `M-2`)
+fn issue_id(severity: &Severity, number: usize) -> String {
+    let prefix = match severity {
+        Severity::High => "H",
+        Severity::Medium => "M",
+        Severity::Low => "L",
+        Severity::Informational => "I",
+    };
+    format!("{prefix}-{number}")
+}
+
+/// Badge color of a severity, the same as in the HTML report
+fn severity_color(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::High => "cf222e",
+        Severity::Medium => "bf8700",
+        Severity::Low => "0969da",
+        Severity::Informational => "1b7c83",
+    }
+}
+
+fn severity_rank(severity: &Severity) -> u8 {
+    match severity {
+        Severity::High => 0,
+        Severity::Medium => 1,
+        Severity::Low => 2,
+        Severity::Informational => 3,
+    }
+}
+
+/// Page header of the HTML report. Severity colors match the CLI output.
+const HTML_HEADER: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Eloizer Report</title>
+<style>
+body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; max-width: 960px; margin: 2em auto; padding: 0 1em; color: #24292f; }
+h1 { margin-bottom: 0.2em; }
+.meta { color: #57606a; }
+.summary { display: flex; gap: 1em; margin: 1.5em 0; }
+.card { flex: 1; padding: 1em; border-radius: 6px; color: #fff; font-weight: 600; }
+.card .count { display: block; font-size: 2em; }
+.finding { border: 1px solid #d0d7de; border-left-width: 6px; border-radius: 6px; margin: 0.6em 0; padding: 0.5em 1em; }
+.finding summary { cursor: pointer; font-weight: 600; }
+.finding summary code { font-weight: normal; color: #57606a; }
+.badge { display: inline-block; min-width: 7em; padding: 0.1em 0.5em; border-radius: 4px; color: #fff; font-size: 0.85em; text-align: center; }
+.rule { font-family: monospace; color: #57606a; }
+pre { background: #f6f8fa; padding: 0.8em; border-radius: 6px; overflow-x: auto; }
+.high.card, .high .badge { background: #cf222e; } .high.finding { border-left-color: #cf222e; }
+.medium.card, .medium .badge { background: #bf8700; } .medium.finding { border-left-color: #bf8700; }
+.low.card, .low .badge { background: #0969da; } .low.finding { border-left-color: #0969da; }
+.informational.card, .informational .badge { background: #1b7c83; } .informational.finding { border-left-color: #1b7c83; }
+</style>
+</head>
+<body>
+"#;
+
+/// CSS class of a severity in the HTML report
+fn html_severity_class(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::High => "high",
+        Severity::Medium => "medium",
+        Severity::Low => "low",
+        Severity::Informational => "informational",
+    }
+}
+
+/// Escape text for inclusion in HTML or XML
+fn escape_markup(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// SARIF result level for a severity
+fn sarif_level(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::High => "error",
+        Severity::Medium => "warning",
+        Severity::Low | Severity::Informational => "note",
+    }
+}
+
+/// Escape the message of a GitHub workflow command
+fn escape_github_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a property value of a GitHub workflow command
+fn escape_github_property(value: &str) -> String {
+    escape_github_data(value)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+/// Quote a CSV field if it contains a delimiter, quote or line break
+fn escape_csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -17,15 +17,61 @@ fn register_solana_rules(engine: &mut RuleEngine) -> Result<()> {
     // High severity rules
     engine.add_rule(solana::high::unsafe_code::create_rule());
     engine.add_rule(solana::high::missing_signer_check::create_rule());
+    engine.add_rule(solana::high::missing_owner_check::create_rule());
+    engine.add_rule(solana::high::privileged_instruction_signer::create_rule());
+    engine.add_rule(solana::high::bump_seed_canonicalization::create_rule());
+    engine.add_rule(solana::high::insecure_account_close::create_rule());
+    engine.add_rule(solana::high::arbitrary_cpi::create_rule());
+    engine.add_rule(solana::high::duplicate_mutable_accounts::create_rule());
+    engine.add_rule(solana::high::type_cosplay::create_rule());
+    engine.add_rule(solana::high::unsafe_account_cast::create_rule());
 
     // Medium severity rules
-    engine.add_rule(solana::medium::duplicate_mutable_accounts::create_rule());
     engine.add_rule(solana::medium::division_by_zero::create_rule());
     engine.add_rule(solana::medium::owner_check::create_rule());
+    engine.add_rule(solana::medium::unchecked_arithmetic::create_rule());
+    engine.add_rule(solana::medium::missing_has_one::create_rule());
+    engine.add_rule(solana::medium::unchecked_sysvar_account::create_rule());
+    engine.add_rule(solana::medium::missing_rent_exemption::create_rule());
+    engine.add_rule(solana::medium::unsafe_realloc::create_rule());
+    engine.add_rule(solana::medium::unbalanced_lamport_transfer::create_rule());
+    engine.add_rule(solana::medium::unsigned_init_payer::create_rule());
+    engine.add_rule(solana::medium::missing_invoke_signed::create_rule());
+    engine.add_rule(solana::medium::unguarded_init_if_needed::create_rule());
+    engine.add_rule(solana::medium::stale_account_after_cpi::create_rule());
+    engine.add_rule(solana::medium::ambiguous_pda_seeds::create_rule());
+    engine.add_rule(solana::medium::unconstrained_token_account::create_rule());
+    engine.add_rule(solana::medium::unchecked_data_slice::create_rule());
+    engine.add_rule(solana::medium::missing_mut_cpi_account::create_rule());
+    engine.add_rule(solana::medium::borrow_across_cpi::create_rule());
+    engine.add_rule(solana::medium::permissionless_handler::create_rule());
+    engine.add_rule(solana::medium::unverified_pda_authority::create_rule());
+    engine.add_rule(solana::medium::state_check_after_external_cpi::create_rule());
+    engine.add_rule(solana::medium::float_financial_math::create_rule());
 
     // Low severity rules
     engine.add_rule(solana::low::missing_error_handling::create_rule());
     engine.add_rule(solana::low::anchor_instructions::create_rule());
+    engine.add_rule(solana::low::panic_in_handler::create_rule());
+    engine.add_rule(solana::low::unchecked_optional_account::create_rule());
+    engine.add_rule(solana::low::init_space_mismatch::create_rule());
+    engine.add_rule(solana::low::unchecked_token_instruction::create_rule());
+    engine.add_rule(solana::low::large_init_space::create_rule());
+    engine.add_rule(solana::low::unchecked_account_usage::create_rule());
+    engine.add_rule(solana::low::unchecked_program_account::create_rule());
+    engine.add_rule(solana::low::unix_timestamp_gate::create_rule());
+    engine.add_rule(solana::low::ignored_result::create_rule());
+    engine.add_rule(solana::low::zero_account_read::create_rule());
+    engine.add_rule(solana::low::unbounded_cpi_loop::create_rule());
+    engine.add_rule(solana::low::account_index_mismatch::create_rule());
+
+    // Informational rules
+    engine.add_rule(solana::informational::unnecessary_mut_account::create_rule());
+    engine.add_rule(solana::informational::sensitive_log::create_rule());
+    engine.add_rule(solana::informational::init_if_needed_usage::create_rule());
+    engine.add_rule(solana::informational::hardcoded_program_id::create_rule());
+    engine.add_rule(solana::informational::tautological_constraint::create_rule());
+    engine.add_rule(solana::informational::non_idempotent_ata_creation::create_rule());
 
     Ok(())
 }
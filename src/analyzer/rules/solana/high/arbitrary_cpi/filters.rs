@@ -0,0 +1,230 @@
+use log::{debug, trace};
+use std::collections::{HashMap, HashSet};
+use syn::visit::{self, Visit};
+use syn::{Expr, File, Item};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Anchor account types that validate the program id themselves
+const VALIDATED_PROGRAM_TYPES: [&str; 2] = ["Program", "Interface"];
+
+pub trait ArbitraryCpiFilters<'a> {
+    fn invokes_unchecked_program(self, ast: &'a File) -> AstQuery<'a>;
+}
+
+impl<'a> ArbitraryCpiFilters<'a> for AstQuery<'a> {
+    fn invokes_unchecked_program(self, ast: &'a File) -> AstQuery<'a> {
+        debug!("Filtering CPIs to unchecked program accounts");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let (sig, block) = match node.data {
+                NodeData::Function(func) => (&func.sig, func.block.as_ref()),
+                NodeData::ImplFunction(func) => (&func.sig, &func.block),
+                _ => continue,
+            };
+
+            let mut finder = CpiFinder::default();
+            finder.visit_block(block);
+            if finder.invokes.is_empty() {
+                continue;
+            }
+
+            let constrained = anchor::context_accounts_type(sig)
+                .map(|accounts_type| constrained_program_fields(ast, &accounts_type))
+                .unwrap_or_default();
+
+            for (invoke, instruction) in &finder.invokes {
+                // Instructions built elsewhere in the function are matched by local name
+                let programs: Vec<&String> = match instruction {
+                    Some(Instruction::Program(program)) => vec![program],
+                    Some(Instruction::Local(local)) => finder.instruction_locals.get(local).into_iter().collect(),
+                    Some(Instruction::Constant) => Vec::new(),
+                    None => finder.instruction_locals.values().collect(),
+                };
+
+                if let Some(program) = programs
+                    .into_iter()
+                    .find(|program| !finder.validated.contains(*program) && !constrained.contains(*program))
+                {
+                    trace!("Found CPI to unchecked program account '{program}'");
+                    new_results.push(AstNode::from_expression(invoke, program));
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Program accounts of the accounts struct that Anchor validates
+/// (`Program<'info, T>`, `Interface<...>` or an `address = ...` constraint)
+fn constrained_program_fields(ast: &File, accounts_type: &str) -> HashSet<String> {
+    ast.items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Struct(item_struct) if item_struct.ident == accounts_type => Some(item_struct),
+            _ => None,
+        })
+        .flat_map(anchor::named_fields)
+        .filter(|field| {
+            anchor::type_name(&field.ty).is_some_and(|ty| VALIDATED_PROGRAM_TYPES.contains(&ty.as_str()))
+                || anchor::has_constraint(&field.attrs, "address")
+        })
+        .filter_map(|field| field.ident.as_ref().map(ToString::to_string))
+        .collect()
+}
+
+/// Program targeted by an instruction
+enum Instruction {
+    /// Program id read from an account
+    Program(String),
+    /// Instruction stored in a local variable
+    Local(String),
+    /// Hardcoded program id
+    Constant,
+}
+
+/// Visitor that collects CPIs, the program accounts of the instructions they
+/// execute and the accounts whose key is validated
+#[derive(Default)]
+struct CpiFinder<'ast> {
+    /// `invoke`/`invoke_signed` calls with the instruction they execute
+    invokes: Vec<(&'ast Expr, Option<Instruction>)>,
+    /// Instruction locals and the program account they target
+    instruction_locals: HashMap<String, String>,
+    /// Accounts whose key is compared or checked
+    validated: HashSet<String>,
+}
+
+impl CpiFinder<'_> {
+    /// Classify the program targeted by an instruction expression
+    fn instruction_target(&self, expr: &Expr) -> Option<Instruction> {
+        match expr {
+            Expr::Reference(reference) => self.instruction_target(&reference.expr),
+            Expr::Paren(paren) => self.instruction_target(&paren.expr),
+            Expr::Try(try_expr) => self.instruction_target(&try_expr.expr),
+            Expr::MethodCall(call) if call.method == "unwrap" || call.method == "expect" => {
+                self.instruction_target(&call.receiver)
+            }
+            Expr::Path(path) => path
+                .path
+                .get_ident()
+                .map(|ident| Instruction::Local(ident.to_string())),
+            _ => program_id_expr(expr).map(|program_id| match key_owner(program_id) {
+                Some(account) => Instruction::Program(account),
+                None => Instruction::Constant,
+            }),
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for CpiFinder<'ast> {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        if let syn::Pat::Ident(pat_ident) = &local.pat
+            && let Some(init) = &local.init
+            && let Some(Instruction::Program(program)) = self.instruction_target(&init.expr)
+        {
+            self.instruction_locals.insert(pat_ident.ident.to_string(), program);
+        }
+        visit::visit_local(self, local);
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let Expr::Call(call) = expr
+            && let Expr::Path(path) = &*call.func
+        {
+            let name = path.path.segments.last().map(|s| s.ident.to_string()).unwrap_or_default();
+            if name == "invoke" || name == "invoke_signed" {
+                let instruction = call.args.first().and_then(|arg| self.instruction_target(arg));
+                self.invokes.push((expr, instruction));
+            } else if ["check", "assert", "validate", "verify"].iter().any(|word| name.contains(word)) {
+                for arg in &call.args {
+                    if let Some(account) = key_owner(arg).or_else(|| account_ident(arg)) {
+                        self.validated.insert(account);
+                    }
+                }
+            }
+        }
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_expr_binary(&mut self, expr: &'ast syn::ExprBinary) {
+        if matches!(expr.op, syn::BinOp::Eq(_) | syn::BinOp::Ne(_)) {
+            for side in [&expr.left, &expr.right] {
+                if let Some(account) = key_owner(side) {
+                    self.validated.insert(account);
+                }
+            }
+        }
+        visit::visit_expr_binary(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let macro_name = mac.path.segments.last().map(|s| s.ident.to_string()).unwrap_or_default();
+        if macro_name.starts_with("require") || macro_name.starts_with("assert") {
+            let tokens = anchor::flatten_tokens(mac.tokens.clone());
+            for window in tokens.windows(3) {
+                if window[1] == "." && window[2] == "key" {
+                    self.validated.insert(window[0].clone());
+                }
+            }
+        }
+        visit::visit_macro(self, mac);
+    }
+}
+
+/// Returns the program id expression of an instruction constructor:
+/// `Instruction { program_id, .. }`, `Instruction::new_with_*(program_id, ..)`
+/// or a `*::instruction::*(program_id, ..)` builder
+fn program_id_expr(expr: &Expr) -> Option<&Expr> {
+    match expr {
+        Expr::Struct(literal) if literal.path.segments.last().is_some_and(|s| s.ident == "Instruction") => literal
+            .fields
+            .iter()
+            .find(|field| matches!(&field.member, syn::Member::Named(m) if m == "program_id"))
+            .map(|field| &field.expr),
+        Expr::Call(call) => {
+            let Expr::Path(path) = &*call.func else {
+                return None;
+            };
+            let segments: Vec<String> = path.path.segments.iter().map(|s| s.ident.to_string()).collect();
+            let is_constructor = segments.len() >= 2
+                && segments[segments.len() - 2] == "Instruction"
+                && segments.last().is_some_and(|s| s.starts_with("new"));
+            let is_builder = segments.iter().rev().skip(1).any(|s| s == "instruction");
+            if is_constructor || is_builder {
+                call.args.first()
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns the account of a key expression such as `*x.key`, `x.key()` or
+/// `ctx.accounts.x.key()`
+fn key_owner(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Unary(unary) => key_owner(&unary.expr),
+        Expr::Reference(reference) => key_owner(&reference.expr),
+        Expr::Paren(paren) => key_owner(&paren.expr),
+        Expr::Field(field) if matches!(&field.member, syn::Member::Named(m) if m == "key") => {
+            account_ident(&field.base)
+        }
+        Expr::MethodCall(call) if call.method == "key" => account_ident(&call.receiver),
+        _ => None,
+    }
+}
+
+/// Name of an account expression, looking through `ctx.accounts` and `to_account_info()`
+fn account_ident(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Reference(reference) => account_ident(&reference.expr),
+        Expr::MethodCall(call) if call.method == "to_account_info" => account_ident(&call.receiver),
+        Expr::Path(path) => path.path.get_ident().map(ToString::to_string),
+        _ => anchor::accounts_field(expr),
+    }
+}
@@ -0,0 +1,59 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::ArbitraryCpiFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("arbitrary-cpi")
+        .title("Arbitrary CPI")
+        .description("Detects invoke/invoke_signed calls whose target program id is read from an account that is never validated, letting an attacker substitute a malicious program")
+        .severity(Severity::High)
+        .rule_type(RuleType::Solana)
+        .category("cpi")
+        .tag("security")
+        .tag("cpi")
+        .reference("https://github.com/coral-xyz/sealevel-attacks/tree/master/programs/5-arbitrary-cpi")
+        .recommendations(vec![
+            "Check the program account before invoking it: if program.key != &spl_token::ID { return Err(ProgramError::IncorrectProgramId) }",
+            "In Anchor, type the account as Program<'info, Token> or add #[account(address = token::ID)]",
+            "Prefer building instructions with the hardcoded program id of the target program",
+        ])
+        .vulnerable_example(r#"
+pub fn process(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let token_program = next_account_info(&mut accounts.iter())?;
+    let ix = spl_token::instruction::transfer(
+        token_program.key, source.key, destination.key, authority.key, &[], amount,
+    )?;
+    invoke(&ix, &[source.clone(), destination.clone(), authority.clone()])
+}
+"#)
+        .fixed_example(r#"
+pub fn process(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let token_program = next_account_info(&mut accounts.iter())?;
+    if token_program.key != &spl_token::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let ix = spl_token::instruction::transfer(
+        token_program.key, source.key, destination.key, authority.key, &[], amount,
+    )?;
+    invoke(&ix, &[source.clone(), destination.clone(), authority.clone()])
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing CPIs to unchecked program ids");
+
+            AstQuery::new(ast)
+                .functions()
+                .invokes_unchecked_program(ast)
+        })
+        .build()
+}
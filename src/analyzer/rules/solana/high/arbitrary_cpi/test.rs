@@ -0,0 +1,82 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::high::arbitrary_cpi::filters::ArbitraryCpiFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_programs(ast: &File) -> Vec<String> {
+        AstQuery::new(ast)
+            .functions()
+            .invokes_unchecked_program(ast)
+            .collect()
+            .into_iter()
+            .map(|node| node.name())
+            .collect()
+    }
+
+    #[test]
+    fn test_unchecked_token_program() {
+        let ast: File = parse_quote! {
+            pub fn process_transfer(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+                let account_info_iter = &mut accounts.iter();
+                let source = next_account_info(account_info_iter)?;
+                let destination = next_account_info(account_info_iter)?;
+                let authority = next_account_info(account_info_iter)?;
+                let token_program = next_account_info(account_info_iter)?;
+
+                let ix = spl_token::instruction::transfer(
+                    token_program.key, source.key, destination.key, authority.key, &[], amount,
+                )?;
+                invoke(&ix, &[source.clone(), destination.clone(), authority.clone()])
+            }
+        };
+
+        assert_eq!(flagged_programs(&ast), vec!["token_program"],
+                "Should detect a CPI to an unvalidated program account");
+    }
+
+    #[test]
+    fn test_checked_token_program() {
+        let ast: File = parse_quote! {
+            pub fn process_transfer(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+                let account_info_iter = &mut accounts.iter();
+                let token_program = next_account_info(account_info_iter)?;
+                if token_program.key != &spl_token::ID {
+                    return Err(ProgramError::IncorrectProgramId);
+                }
+                let ix = spl_token::instruction::transfer(token_program.key, a.key, b.key, c.key, &[], amount)?;
+                invoke(&ix, &[])
+            }
+        };
+
+        assert!(flagged_programs(&ast).is_empty(), "A validated program account should not be flagged");
+    }
+
+    #[test]
+    fn test_anchor_program_type() {
+        let ast: File = parse_quote! {
+            pub fn forward(ctx: Context<Forward>, data: Vec<u8>) -> Result<()> {
+                let ix = Instruction {
+                    program_id: ctx.accounts.target.key(),
+                    accounts: vec![],
+                    data,
+                };
+                invoke(&ix, &[])?;
+                let ix2 = Instruction::new_with_bytes(ctx.accounts.token_program.key(), &[], vec![]);
+                invoke(&ix2, &[])?;
+                Ok(())
+            }
+
+            #[derive(Accounts)]
+            pub struct Forward<'info> {
+                pub target: UncheckedAccount<'info>,
+                pub token_program: Program<'info, Token>,
+            }
+        };
+
+        assert_eq!(flagged_programs(&ast), vec!["target"],
+                "Only the unchecked program account should be flagged");
+    }
+}
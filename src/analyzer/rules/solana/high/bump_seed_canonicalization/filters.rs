@@ -0,0 +1,221 @@
+use log::{debug, trace};
+use std::collections::HashSet;
+use syn::visit::{self, Visit};
+use syn::{Expr, File, FnArg, Item, Pat};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+pub trait BumpSeedCanonicalizationFilters<'a> {
+    fn uncanonical_bump_accounts(self, ast: &'a File) -> AstQuery<'a>;
+    fn create_program_address_with_input_bump(self) -> AstQuery<'a>;
+}
+
+impl<'a> BumpSeedCanonicalizationFilters<'a> for AstQuery<'a> {
+    /// Seeds-validated account fields whose bump is instruction input, or that
+    /// use a bare `bump` although the account persists its canonical bump
+    fn uncanonical_bump_accounts(self, ast: &'a File) -> AstQuery<'a> {
+        debug!("Filtering accounts with uncanonical bump constraints");
+        let structs_with_bump = structs_with_bump_field(ast);
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let NodeData::Struct(item_struct) = node.data else {
+                continue;
+            };
+            let instruction_args = anchor::instruction_args(item_struct);
+
+            for field in anchor::named_fields(item_struct) {
+                let constraints = anchor::account_constraints(&field.attrs);
+                if !constraints.iter().any(|c| c.name == "seeds") {
+                    continue;
+                }
+                let Some(bump) = constraints.iter().find(|c| c.name == "bump") else {
+                    continue;
+                };
+
+                let flagged = match &bump.value {
+                    // `bump = arg` trusts a bump supplied by the caller
+                    Some(value) => instruction_args.iter().any(|arg| arg == value),
+                    // A bare `bump` on an existing account ignores the persisted canonical bump
+                    None => {
+                        let initializes = constraints
+                            .iter()
+                            .any(|c| c.name == "init" || c.name == "init_if_needed");
+                        let stores_bump = anchor::type_arguments(&field.ty)
+                            .last()
+                            .and_then(|ty| anchor::type_name(ty))
+                            .is_some_and(|name| structs_with_bump.contains(&name));
+                        !initializes && stores_bump
+                    }
+                };
+
+                if flagged {
+                    trace!("Found uncanonical bump constraint on field: {:?}", field.ident);
+                    new_results.push(AstNode::from_field(field));
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+
+    /// `create_program_address` calls whose bump seed comes from instruction input
+    fn create_program_address_with_input_bump(self) -> AstQuery<'a> {
+        debug!("Filtering create_program_address calls with attacker-controlled bumps");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let (sig, block) = match node.data {
+                NodeData::Function(func) => (&func.sig, func.block.as_ref()),
+                NodeData::ImplFunction(func) => (&func.sig, &func.block),
+                _ => continue,
+            };
+
+            let mut finder = InputBumpFinder::default();
+            for input in &sig.inputs {
+                if let FnArg::Typed(pat_type) = input
+                    && let Pat::Ident(pat_ident) = &*pat_type.pat
+                    && is_input_type(&pat_type.ty)
+                {
+                    finder.tainted.insert(pat_ident.ident.to_string());
+                }
+            }
+            finder.visit_block(block);
+
+            let function_name = sig.ident.to_string();
+            for expr in finder.found {
+                trace!("Found create_program_address with input bump in: {function_name}");
+                new_results.push(AstNode::from_expression(expr, &function_name));
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Names of the structs in the file declaring a `bump` field
+fn structs_with_bump_field(ast: &File) -> HashSet<String> {
+    ast.items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Struct(item_struct) => Some(item_struct),
+            _ => None,
+        })
+        .filter(|item_struct| {
+            anchor::named_fields(item_struct)
+                .iter()
+                .any(|field| field.ident.as_ref().is_some_and(|ident| ident == "bump"))
+        })
+        .map(|item_struct| item_struct.ident.to_string())
+        .collect()
+}
+
+/// Returns true if a parameter carries caller-supplied data rather than accounts
+fn is_input_type(ty: &syn::Type) -> bool {
+    if let syn::Type::Reference(reference) = ty
+        && let syn::Type::Slice(slice) = &*reference.elem
+    {
+        return anchor::type_name(&slice.elem).as_deref() != Some("AccountInfo");
+    }
+
+    !matches!(
+        anchor::type_name(ty).as_deref(),
+        Some("Context" | "AccountInfo" | "Pubkey")
+    )
+}
+
+/// Visitor that tracks values derived from instruction input and collects
+/// `create_program_address` calls whose last seed is such a value
+#[derive(Default)]
+struct InputBumpFinder<'ast> {
+    tainted: HashSet<String>,
+    found: Vec<&'ast Expr>,
+}
+
+impl<'ast> InputBumpFinder<'ast> {
+    fn is_tainted(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Path(path) => path
+                .path
+                .get_ident()
+                .is_some_and(|ident| self.tainted.contains(&ident.to_string())),
+            Expr::Field(field) => self.is_tainted(&field.base),
+            Expr::Index(index) => self.is_tainted(&index.expr),
+            Expr::Reference(reference) => self.is_tainted(&reference.expr),
+            Expr::Paren(paren) => self.is_tainted(&paren.expr),
+            Expr::Cast(cast) => self.is_tainted(&cast.expr),
+            Expr::Try(try_expr) => self.is_tainted(&try_expr.expr),
+            Expr::MethodCall(call) => self.is_tainted(&call.receiver),
+            Expr::Array(array) => array.elems.iter().any(|elem| self.is_tainted(elem)),
+            _ => false,
+        }
+    }
+
+    /// Returns the bump seed (`x` in `&[x]`) of a seeds array literal
+    fn bump_seed(seeds: &Expr) -> Option<&Expr> {
+        match seeds {
+            Expr::Reference(reference) => Self::bump_seed(&reference.expr),
+            Expr::Array(array) => match array.elems.last()? {
+                Expr::Reference(reference) => match &*reference.expr {
+                    Expr::Array(bump) if bump.elems.len() == 1 => bump.elems.first(),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn check_call(&mut self, expr: &'ast Expr, seeds: Option<&Expr>) {
+        if let Some(bump) = seeds.and_then(Self::bump_seed)
+            && self.is_tainted(bump)
+        {
+            self.found.push(expr);
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for InputBumpFinder<'ast> {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        if let Some(init) = &local.init
+            && self.is_tainted(&init.expr)
+        {
+            let mut names = Vec::new();
+            collect_pattern_names(&local.pat, &mut names);
+            self.tainted.extend(names);
+        }
+        visit::visit_local(self, local);
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::Call(call) => {
+                if let Expr::Path(path) = &*call.func
+                    && path
+                        .path
+                        .segments
+                        .last()
+                        .is_some_and(|s| s.ident == "create_program_address")
+                {
+                    self.check_call(expr, call.args.first());
+                }
+            }
+            Expr::MethodCall(call) if call.method == "create_program_address" => {
+                self.check_call(expr, call.args.first());
+            }
+            _ => {}
+        }
+        visit::visit_expr(self, expr);
+    }
+}
+
+fn collect_pattern_names(pat: &Pat, names: &mut Vec<String>) {
+    match pat {
+        Pat::Ident(pat_ident) => names.push(pat_ident.ident.to_string()),
+        Pat::Type(pat_type) => collect_pattern_names(&pat_type.pat, names),
+        Pat::Tuple(tuple) => tuple.elems.iter().for_each(|elem| collect_pattern_names(elem, names)),
+        Pat::Reference(reference) => collect_pattern_names(&reference.pat, names),
+        _ => {}
+    }
+}
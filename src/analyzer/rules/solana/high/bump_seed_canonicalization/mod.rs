@@ -0,0 +1,59 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::BumpSeedCanonicalizationFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("bump-seed-canonicalization")
+        .title("Unchecked PDA Bump Seed")
+        .description("Detects PDAs validated with a caller-supplied bump or without the persisted canonical bump, which lets an attacker pass a different valid PDA for the same seeds")
+        .severity(Severity::High)
+        .rule_type(RuleType::Anchor)
+        .category("pda")
+        .tag("security")
+        .tag("pda")
+        .reference("https://github.com/coral-xyz/sealevel-attacks/tree/master/programs/7-bump-seed-canonicalization")
+        .recommendations(vec![
+            "Persist the canonical bump when the account is initialized (ctx.bumps) and validate it afterwards with #[account(seeds = [...], bump = account.bump)]",
+            "Never accept the bump as instruction data; derive it with Pubkey::find_program_address or read the stored canonical bump",
+            "Replace manual Pubkey::create_program_address calls that take an input bump with find_program_address",
+        ])
+        .vulnerable_example(r#"
+pub fn set_value(ctx: Context<SetValue>, bump: u8) -> Result<()> {
+    let address = Pubkey::create_program_address(
+        &[b"vault", ctx.accounts.user.key.as_ref(), &[bump]],
+        ctx.program_id,
+    )?;
+    // ...
+}
+"#)
+        .fixed_example(r#"
+#[derive(Accounts)]
+pub struct SetValue<'info> {
+    #[account(mut, seeds = [b"vault", user.key().as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+    pub user: Signer<'info>,
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing PDA bump seed canonicalization");
+
+            AstQuery::new(ast)
+                .structs()
+                .derives_accounts()
+                .uncanonical_bump_accounts(ast)
+                .or(AstQuery::new(ast)
+                    .functions()
+                    .create_program_address_with_input_bump())
+        })
+        .build()
+}
@@ -0,0 +1,92 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::high::bump_seed_canonicalization::filters::BumpSeedCanonicalizationFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_accounts(ast: &File) -> Vec<String> {
+        AstQuery::new(ast)
+            .structs()
+            .derives_accounts()
+            .uncanonical_bump_accounts(ast)
+            .collect()
+            .into_iter()
+            .map(|node| node.name())
+            .collect()
+    }
+
+    fn flagged_calls(ast: &File) -> usize {
+        AstQuery::new(ast)
+            .functions()
+            .create_program_address_with_input_bump()
+            .collect()
+            .len()
+    }
+
+    #[test]
+    fn test_bump_from_instruction_args() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            #[instruction(bump: u8)]
+            pub struct Withdraw<'info> {
+                #[account(seeds = [b"vault", authority.key().as_ref()], bump = bump)]
+                pub vault: Account<'info, Vault>,
+                pub authority: Signer<'info>,
+            }
+        };
+
+        assert_eq!(flagged_accounts(&ast), vec!["vault"],
+                "Should detect a bump supplied as instruction input");
+    }
+
+    #[test]
+    fn test_bare_bump_with_stored_bump() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Initialize<'info> {
+                #[account(init, payer = payer, space = 8 + 1, seeds = [b"vault"], bump)]
+                pub new_vault: Account<'info, Vault>,
+                #[account(seeds = [b"vault"], bump)]
+                pub vault: Account<'info, Vault>,
+                #[account(seeds = [b"vault"], bump = vault.bump)]
+                pub checked: Account<'info, Vault>,
+            }
+
+            #[account]
+            pub struct Vault {
+                pub bump: u8,
+            }
+        };
+
+        assert_eq!(flagged_accounts(&ast), vec!["vault"],
+                "Should only flag the existing account ignoring its stored bump");
+    }
+
+    #[test]
+    fn test_create_program_address_with_input_bump() {
+        let ast: File = parse_quote! {
+            pub fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+                let bump = instruction_data[0];
+                let pda = Pubkey::create_program_address(&[b"vault", &[bump]], program_id)?;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_calls(&ast), 1, "Should detect a bump read from instruction data");
+    }
+
+    #[test]
+    fn test_create_program_address_with_stored_bump() {
+        let ast: File = parse_quote! {
+            pub fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+                let vault = Vault::try_from_slice(&accounts[0].data.borrow())?;
+                let pda = Pubkey::create_program_address(&[b"vault", &[vault.bump]], program_id)?;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_calls(&ast), 0, "A persisted canonical bump should not be flagged");
+    }
+}
@@ -0,0 +1,119 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use std::collections::HashSet;
+use syn::visit::{self, Visit};
+use syn::File;
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Account types an attacker cannot pass twice for two different roles
+const NON_DUPLICABLE_TYPES: [&str; 4] = ["Signer", "Program", "Sysvar", "Interface"];
+
+pub trait DuplicateMutableAccountsFilters<'a> {
+    fn duplicate_mutable_accounts(self, ast: &'a File) -> AstQuery<'a>;
+}
+
+impl<'a> DuplicateMutableAccountsFilters<'a> for AstQuery<'a> {
+    fn duplicate_mutable_accounts(self, ast: &'a File) -> AstQuery<'a> {
+        debug!("Filtering mutable accounts of the same type without a key disequality check");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let NodeData::Struct(struct_item) = node.data else {
+                continue;
+            };
+
+            // Mutable accounts an attacker chooses freely, with their type
+            let mutable: Vec<(String, String, &syn::Field)> = anchor::named_fields(struct_item)
+                .into_iter()
+                .filter(|field| {
+                    // PDAs are pinned to their seeds, new accounts cannot alias existing ones
+                    anchor::has_constraint(&field.attrs, "mut")
+                        && !anchor::has_constraint(&field.attrs, "init")
+                        && !anchor::has_constraint(&field.attrs, "seeds")
+                        && !anchor::type_name(&field.ty)
+                            .is_some_and(|ty| NON_DUPLICABLE_TYPES.contains(&ty.as_str()))
+                })
+                .filter_map(|field| {
+                    let name = field.ident.as_ref()?.to_string();
+                    Some((name, field.ty.to_token_stream().to_string(), field))
+                })
+                .collect();
+            if mutable.len() < 2 {
+                continue;
+            }
+
+            // Disequality checks declared as constraints or performed by the handlers
+            let mut checks: Vec<Vec<String>> = anchor::named_fields(struct_item)
+                .iter()
+                .flat_map(|field| anchor::account_constraints(&field.attrs))
+                .filter(|c| c.name == "constraint")
+                .filter_map(|c| c.value)
+                .filter(|value| value.replace(' ', "").contains("!="))
+                .map(|value| {
+                    value
+                        .split(|c: char| !c.is_alphanumeric() && c != '_')
+                        .filter(|ident| !ident.is_empty())
+                        .map(ToString::to_string)
+                        .collect()
+                })
+                .collect();
+            let mut collector = KeyCheckCollector::default();
+            for handler in anchor::handlers_for(ast, &struct_item.ident.to_string()) {
+                collector.visit_block(handler.block);
+            }
+            checks.extend(collector.checks);
+
+            let mut flagged = HashSet::new();
+            for (index, (first, first_ty, _)) in mutable.iter().enumerate() {
+                for (second, second_ty, field) in &mutable[index + 1..] {
+                    if first_ty != second_ty || flagged.contains(second) {
+                        continue;
+                    }
+                    let distinct = checks
+                        .iter()
+                        .any(|tokens| tokens.contains(first) && tokens.contains(second));
+                    if !distinct {
+                        trace!("Found duplicable mutable accounts '{first}' and '{second}'");
+                        flagged.insert(second.clone());
+                        new_results.push(AstNode::from_field(field));
+                    }
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Visitor that collects the tokens of key comparisons made in a handler
+#[derive(Default)]
+struct KeyCheckCollector {
+    checks: Vec<Vec<String>>,
+}
+
+impl<'ast> Visit<'ast> for KeyCheckCollector {
+    fn visit_expr_binary(&mut self, expr: &'ast syn::ExprBinary) {
+        if matches!(expr.op, syn::BinOp::Eq(_) | syn::BinOp::Ne(_)) {
+            self.checks.push(anchor::flatten_tokens(expr.to_token_stream()));
+        }
+        visit::visit_expr_binary(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let macro_name = mac
+            .path
+            .segments
+            .last()
+            .map(|s| s.ident.to_string())
+            .unwrap_or_default();
+        if matches!(
+            macro_name.as_str(),
+            "require_keys_neq" | "require_neq" | "assert_ne" | "require" | "assert"
+        ) {
+            self.checks.push(anchor::flatten_tokens(mac.tokens.clone()));
+        }
+        visit::visit_macro(self, mac);
+    }
+}
@@ -0,0 +1,56 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::DuplicateMutableAccountsFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("duplicate-mutable-accounts")
+        .title("Duplicate Mutable Accounts")
+        .description("Detects accounts structs with two or more mutable accounts of the same type and no check that their keys differ, allowing an attacker to pass the same account twice (e.g. to double-credit a transfer)")
+        .severity(Severity::High)
+        .rule_type(RuleType::Anchor)
+        .category("accounts")
+        .tag("security")
+        .tag("account-validation")
+        .reference("https://github.com/coral-xyz/sealevel-attacks/tree/master/programs/6-duplicate-mutable-accounts")
+        .recommendations(vec![
+            "Require the accounts to differ: #[account(mut, constraint = from.key() != to.key() @ ErrorCode::DuplicateAccount)]",
+            "Or check it in the handler: require_keys_neq!(ctx.accounts.from.key(), ctx.accounts.to.key())",
+        ])
+        .vulnerable_example(r#"
+#[derive(Accounts)]
+pub struct Transfer<'info> {
+    #[account(mut)]
+    pub from: Account<'info, Wallet>,
+    #[account(mut)]
+    pub to: Account<'info, Wallet>,
+}
+"#)
+        .fixed_example(r#"
+#[derive(Accounts)]
+pub struct Transfer<'info> {
+    #[account(mut)]
+    pub from: Account<'info, Wallet>,
+    #[account(mut, constraint = from.key() != to.key() @ ErrorCode::DuplicateAccount)]
+    pub to: Account<'info, Wallet>,
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing accounts structs for duplicate mutable accounts");
+
+            AstQuery::new(ast)
+                .structs()
+                .derives_accounts()
+                .duplicate_mutable_accounts(ast)
+        })
+        .build()
+}
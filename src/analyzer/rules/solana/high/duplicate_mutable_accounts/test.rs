@@ -0,0 +1,73 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::high::duplicate_mutable_accounts::filters::DuplicateMutableAccountsFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_fields(ast: &File) -> Vec<String> {
+        AstQuery::new(ast)
+            .structs()
+            .derives_accounts()
+            .duplicate_mutable_accounts(ast)
+            .collect()
+            .into_iter()
+            .map(|node| node.name())
+            .collect()
+    }
+
+    #[test]
+    fn test_same_type_mutable_accounts() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Transfer<'info> {
+                #[account(mut)]
+                pub from: Account<'info, Wallet>,
+                #[account(mut)]
+                pub to: Account<'info, Wallet>,
+                #[account(mut)]
+                pub fee_vault: Account<'info, FeeVault>,
+                pub authority: Signer<'info>,
+            }
+        };
+
+        assert_eq!(flagged_fields(&ast), vec!["to"],
+                "Should flag mutable accounts of the same type");
+    }
+
+    #[test]
+    fn test_key_disequality_constraint() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Transfer<'info> {
+                #[account(mut)]
+                pub from: Account<'info, Wallet>,
+                #[account(mut, constraint = from.key() != to.key())]
+                pub to: Account<'info, Wallet>,
+            }
+        };
+
+        assert!(flagged_fields(&ast).is_empty(), "A key disequality constraint should prevent the finding");
+    }
+
+    #[test]
+    fn test_key_check_in_handler() {
+        let ast: File = parse_quote! {
+            pub fn transfer(ctx: Context<Transfer>, amount: u64) -> Result<()> {
+                require_keys_neq!(ctx.accounts.from.key(), ctx.accounts.to.key());
+                Ok(())
+            }
+
+            #[derive(Accounts)]
+            pub struct Transfer<'info> {
+                #[account(mut)]
+                pub from: Account<'info, Wallet>,
+                #[account(mut)]
+                pub to: Account<'info, Wallet>,
+            }
+        };
+
+        assert!(flagged_fields(&ast).is_empty(), "A handler key check should prevent the finding");
+    }
+}
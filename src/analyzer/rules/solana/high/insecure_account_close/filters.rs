@@ -0,0 +1,202 @@
+use log::{debug, trace};
+use std::collections::HashSet;
+use syn::visit::{self, Visit};
+use syn::{Expr, File, Item};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+pub trait InsecureAccountCloseFilters<'a> {
+    fn manual_closes_without_wipe(self, ast: &'a File) -> AstQuery<'a>;
+    fn reinitializable_closed_accounts(self, ast: &'a File) -> AstQuery<'a>;
+}
+
+impl<'a> InsecureAccountCloseFilters<'a> for AstQuery<'a> {
+    /// Lamport-draining closes (`**account.lamports.borrow_mut() = 0`) in functions
+    /// that never wipe the account data, leaving it open to revival
+    fn manual_closes_without_wipe(self, ast: &'a File) -> AstQuery<'a> {
+        debug!("Filtering manual account closes without data wipe");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let (sig, block) = match node.data {
+                NodeData::Function(func) => (&func.sig, func.block.as_ref()),
+                NodeData::ImplFunction(func) => (&func.sig, &func.block),
+                _ => continue,
+            };
+
+            let mut finder = CloseFinder::default();
+            finder.visit_block(block);
+            if finder.wipes_data {
+                continue;
+            }
+
+            // Accounts already closed through Anchor's `close` constraint
+            let closed_by_constraint = anchor::context_accounts_type(sig)
+                .map(|accounts_type| closed_fields(ast, &accounts_type))
+                .unwrap_or_default();
+
+            for (account, expr) in finder.drains {
+                if closed_by_constraint.contains(&account) {
+                    continue;
+                }
+                trace!("Found manual close of '{account}' without data wipe");
+                new_results.push(AstNode::from_expression(expr, &account));
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+
+    /// `init_if_needed` accounts whose type is closed elsewhere in the program,
+    /// which can be revived and re-initialized within the same transaction
+    fn reinitializable_closed_accounts(self, ast: &'a File) -> AstQuery<'a> {
+        debug!("Filtering init_if_needed accounts of closable types");
+        let closed_types = closed_account_types(ast);
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let NodeData::Struct(item_struct) = node.data else {
+                continue;
+            };
+
+            for field in anchor::named_fields(item_struct) {
+                if !anchor::has_constraint(&field.attrs, "init_if_needed") {
+                    continue;
+                }
+                if let Some(data_type) = account_data_type(&field.ty)
+                    && closed_types.contains(&data_type)
+                {
+                    trace!("Found init_if_needed on closable account type '{data_type}'");
+                    new_results.push(AstNode::from_field(field));
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Accounts structs in the file
+fn accounts_structs(ast: &File) -> impl Iterator<Item = &syn::ItemStruct> {
+    ast.items.iter().filter_map(|item| match item {
+        Item::Struct(item_struct) if anchor::is_accounts_struct(item_struct) => Some(item_struct),
+        _ => None,
+    })
+}
+
+/// Fields of the given accounts struct declaring a `close` constraint
+fn closed_fields(ast: &File, accounts_type: &str) -> HashSet<String> {
+    accounts_structs(ast)
+        .filter(|item_struct| item_struct.ident == accounts_type)
+        .flat_map(anchor::named_fields)
+        .filter(|field| anchor::has_constraint(&field.attrs, "close"))
+        .filter_map(|field| field.ident.as_ref().map(ToString::to_string))
+        .collect()
+}
+
+/// Account data types (`T` in `Account<'info, T>`) closed with a `close` constraint
+fn closed_account_types(ast: &File) -> HashSet<String> {
+    accounts_structs(ast)
+        .flat_map(anchor::named_fields)
+        .filter(|field| anchor::has_constraint(&field.attrs, "close"))
+        .filter_map(|field| account_data_type(&field.ty))
+        .collect()
+}
+
+fn account_data_type(ty: &syn::Type) -> Option<String> {
+    anchor::type_arguments(ty)
+        .last()
+        .and_then(|ty| anchor::type_name(ty))
+}
+
+/// Visitor that collects lamport drains and data wipes in a function body
+#[derive(Default)]
+struct CloseFinder<'ast> {
+    /// Accounts whose lamports are set to zero, with the assignment
+    drains: Vec<(String, &'ast Expr)>,
+    /// The function zeroes, shrinks or marks the account data as closed
+    wipes_data: bool,
+}
+
+impl<'ast> Visit<'ast> for CloseFinder<'ast> {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let Expr::Assign(assign) = expr
+            && is_zero(&assign.right)
+            && let Some(account) = drained_account(&assign.left)
+        {
+            self.drains.push((account, expr));
+        }
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        let method = call.method.to_string();
+        let wipes = match method.as_str() {
+            "fill" => call.args.first().is_some_and(is_zero),
+            "realloc" | "resize" => call.args.first().is_some_and(is_zero),
+            _ => false,
+        };
+        self.wipes_data |= wipes;
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        if let Expr::Path(path) = &*call.func
+            && path.path.segments.last().is_some_and(|s| s.ident == "sol_memset")
+        {
+            self.wipes_data = true;
+        }
+        visit::visit_expr_call(self, call);
+    }
+
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+        if path
+            .segments
+            .last()
+            .is_some_and(|s| s.ident == "CLOSED_ACCOUNT_DISCRIMINATOR")
+        {
+            self.wipes_data = true;
+        }
+        visit::visit_path(self, path);
+    }
+}
+
+fn is_zero(expr: &Expr) -> bool {
+    matches!(expr, Expr::Lit(lit) if matches!(&lit.lit, syn::Lit::Int(int) if int.base10_digits() == "0"))
+}
+
+/// Returns the account of a `**account.lamports.borrow_mut()` or
+/// `**account.try_borrow_mut_lamports()?` place expression
+fn drained_account(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Unary(unary) if matches!(unary.op, syn::UnOp::Deref(_)) => drained_account(&unary.expr),
+        Expr::Paren(paren) => drained_account(&paren.expr),
+        Expr::Try(try_expr) => drained_account(&try_expr.expr),
+        Expr::MethodCall(call) if call.method == "borrow_mut" => match &*call.receiver {
+            Expr::Field(field) if matches!(&field.member, syn::Member::Named(m) if m == "lamports") => {
+                account_name(&field.base)
+            }
+            _ => None,
+        },
+        Expr::MethodCall(call) if call.method == "try_borrow_mut_lamports" => account_name(&call.receiver),
+        _ => None,
+    }
+}
+
+/// Name of the account an expression refers to, looking through `to_account_info()`
+fn account_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::MethodCall(call) if matches!(call.method.to_string().as_str(), "to_account_info" | "as_ref") => {
+            account_name(&call.receiver)
+        }
+        Expr::Field(field) => match &field.member {
+            syn::Member::Named(member) => Some(member.to_string()),
+            syn::Member::Unnamed(_) => None,
+        },
+        Expr::Path(path) => path.path.get_ident().map(ToString::to_string),
+        Expr::Reference(reference) => account_name(&reference.expr),
+        Expr::Paren(paren) => account_name(&paren.expr),
+        _ => None,
+    }
+}
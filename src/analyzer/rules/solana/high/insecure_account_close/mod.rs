@@ -0,0 +1,62 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::InsecureAccountCloseFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("insecure-account-close")
+        .title("Insecure Account Closing")
+        .description("Detects accounts closed by draining their lamports without wiping the data, and closable account types that can be re-initialized with init_if_needed. A closed account can be revived by refunding rent within the same transaction")
+        .severity(Severity::High)
+        .rule_type(RuleType::Anchor)
+        .category("accounts")
+        .tag("security")
+        .tag("account-lifecycle")
+        .reference("CWE-672: Operation on a Resource after Expiration or Release (https://cwe.mitre.org/data/definitions/672.html)")
+        .reference("https://github.com/coral-xyz/sealevel-attacks/tree/master/programs/9-closing-accounts")
+        .recommendations(vec![
+            "Close accounts with Anchor's close constraint: #[account(mut, close = destination)]",
+            "When closing manually, zero the account data or write the CLOSED_ACCOUNT_DISCRIMINATOR after draining the lamports, and reassign the account to the system program",
+            "Avoid init_if_needed on account types that can be closed; use init and reject reinitialization explicitly",
+        ])
+        .vulnerable_example(r#"
+pub fn close(ctx: Context<Close>) -> Result<()> {
+    let vault = ctx.accounts.vault.to_account_info();
+    let destination = ctx.accounts.destination.to_account_info();
+    **destination.lamports.borrow_mut() += vault.lamports();
+    **vault.lamports.borrow_mut() = 0;
+    Ok(())
+}
+"#)
+        .fixed_example(r#"
+#[derive(Accounts)]
+pub struct Close<'info> {
+    #[account(mut, close = destination, has_one = authority)]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub destination: SystemAccount<'info>,
+    pub authority: Signer<'info>,
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing account closing patterns");
+
+            AstQuery::new(ast)
+                .functions()
+                .manual_closes_without_wipe(ast)
+                .or(AstQuery::new(ast)
+                    .structs()
+                    .derives_accounts()
+                    .reinitializable_closed_accounts(ast))
+        })
+        .build()
+}
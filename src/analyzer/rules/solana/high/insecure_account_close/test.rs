@@ -0,0 +1,79 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::high::insecure_account_close::filters::InsecureAccountCloseFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_closes(ast: &File) -> Vec<String> {
+        AstQuery::new(ast)
+            .functions()
+            .manual_closes_without_wipe(ast)
+            .collect()
+            .into_iter()
+            .map(|node| node.name())
+            .collect()
+    }
+
+    #[test]
+    fn test_lamport_drain_without_wipe() {
+        let ast: File = parse_quote! {
+            pub fn close(ctx: Context<Close>) -> Result<()> {
+                let dest_starting_lamports = ctx.accounts.destination.lamports();
+                **ctx.accounts.destination.lamports.borrow_mut() =
+                    dest_starting_lamports.checked_add(ctx.accounts.vault.to_account_info().lamports()).unwrap();
+                **ctx.accounts.vault.to_account_info().lamports.borrow_mut() = 0;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_closes(&ast), vec!["vault"],
+                "Should detect lamports drained without wiping the data");
+    }
+
+    #[test]
+    fn test_lamport_drain_with_wipe() {
+        let ast: File = parse_quote! {
+            pub fn close(ctx: Context<Close>) -> Result<()> {
+                let account = ctx.accounts.vault.to_account_info();
+                **account.try_borrow_mut_lamports()? = 0;
+                account.try_borrow_mut_data()?.fill(0);
+                Ok(())
+            }
+        };
+
+        assert!(flagged_closes(&ast).is_empty(), "Zeroed data should not be flagged");
+    }
+
+    #[test]
+    fn test_init_if_needed_on_closable_type() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Close<'info> {
+                #[account(mut, close = destination)]
+                pub vault: Account<'info, Vault>,
+                pub destination: SystemAccount<'info>,
+            }
+
+            #[derive(Accounts)]
+            pub struct Open<'info> {
+                #[account(init_if_needed, payer = payer, space = 8 + 8)]
+                pub vault: Account<'info, Vault>,
+                #[account(mut)]
+                pub payer: Signer<'info>,
+            }
+        };
+
+        let flagged: Vec<String> = AstQuery::new(&ast)
+            .structs()
+            .derives_accounts()
+            .reinitializable_closed_accounts(&ast)
+            .collect()
+            .into_iter()
+            .map(|node| node.name())
+            .collect();
+
+        assert_eq!(flagged, vec!["vault"], "Should detect a closable type opened with init_if_needed");
+    }
+}
@@ -0,0 +1,272 @@
+use log::{debug, trace};
+use std::collections::HashSet;
+use syn::visit::{self, Visit};
+use syn::{Expr, File, FnArg, Item, Pat};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Functions that deserialize account data from raw bytes
+const DESERIALIZERS: [&str; 7] = [
+    "try_from_slice",
+    "try_from_slice_unchecked",
+    "deserialize",
+    "try_deserialize",
+    "try_deserialize_unchecked",
+    "unpack",
+    "unpack_unchecked",
+];
+
+/// Anchor account types that verify the owner when the context is deserialized
+const OWNER_CHECKED_TYPES: [&str; 3] = ["Account", "InterfaceAccount", "AccountLoader"];
+
+pub trait MissingOwnerCheckFilters<'a> {
+    fn deserializes_without_owner_check(self, ast: &'a File) -> AstQuery<'a>;
+}
+
+impl<'a> MissingOwnerCheckFilters<'a> for AstQuery<'a> {
+    fn deserializes_without_owner_check(self, ast: &'a File) -> AstQuery<'a> {
+        debug!("Filtering account deserializations without owner checks");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let (sig, block) = match node.data {
+                NodeData::Function(func) => (&func.sig, func.block.as_ref()),
+                NodeData::ImplFunction(func) => (&func.sig, &func.block),
+                _ => continue,
+            };
+
+            let mut finder = OwnerCheckFinder {
+                owner_checked_fields: anchor::context_accounts_type(sig)
+                    .map(|accounts_type| owner_checked_fields(ast, &accounts_type))
+                    .unwrap_or_default(),
+                ..Default::default()
+            };
+            for input in &sig.inputs {
+                if let FnArg::Typed(pat_type) = input
+                    && let Pat::Ident(pat_ident) = &*pat_type.pat
+                    && is_account_info_type(&pat_type.ty)
+                {
+                    finder.account_infos.insert(pat_ident.ident.to_string());
+                }
+            }
+            finder.visit_block(block);
+
+            for (expr, account) in finder.unchecked {
+                trace!("Found deserialization of '{account}' without owner check");
+                new_results.push(AstNode::from_expression(expr, &account));
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+fn is_account_info_type(ty: &syn::Type) -> bool {
+    anchor::type_name(ty).as_deref() == Some("AccountInfo")
+}
+
+/// Fields of the accounts struct whose owner Anchor verifies
+/// (`Account<'info, T>` and friends, optionally boxed, or an `owner = ...` constraint)
+fn owner_checked_fields(ast: &File, accounts_type: &str) -> HashSet<String> {
+    ast.items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Struct(item_struct) if item_struct.ident == accounts_type => Some(item_struct),
+            _ => None,
+        })
+        .flat_map(anchor::named_fields)
+        .filter(|field| {
+            let ty = match anchor::type_name(&field.ty).as_deref() {
+                Some("Box") => anchor::type_arguments(&field.ty).first().and_then(|ty| anchor::type_name(ty)),
+                _ => anchor::type_name(&field.ty),
+            };
+            ty.is_some_and(|ty| OWNER_CHECKED_TYPES.contains(&ty.as_str()))
+                || anchor::has_constraint(&field.attrs, "owner")
+        })
+        .filter_map(|field| field.ident.as_ref().map(ToString::to_string))
+        .collect()
+}
+
+/// Visitor that tracks `AccountInfo` locals, owner checks and deserializations in source order
+#[derive(Default)]
+struct OwnerCheckFinder<'ast> {
+    /// Local variables known to hold an `AccountInfo`
+    account_infos: HashSet<String>,
+    /// Accounts struct fields whose owner Anchor already verified
+    owner_checked_fields: HashSet<String>,
+    /// Accounts whose owner has been compared so far
+    owner_checked: HashSet<String>,
+    /// Deserializations of accounts with no preceding owner check
+    unchecked: Vec<(&'ast Expr, String)>,
+}
+
+impl<'ast> Visit<'ast> for OwnerCheckFinder<'ast> {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        if let Some(init) = &local.init {
+            let name = match &local.pat {
+                Pat::Ident(pat_ident) => Some(pat_ident.ident.to_string()),
+                Pat::Type(pat_type) => match &*pat_type.pat {
+                    Pat::Ident(pat_ident) if is_account_info_type(&pat_type.ty) => {
+                        self.account_infos.insert(pat_ident.ident.to_string());
+                        None
+                    }
+                    Pat::Ident(pat_ident) => Some(pat_ident.ident.to_string()),
+                    _ => None,
+                },
+                _ => None,
+            };
+
+            if let Some(name) = name
+                && is_account_info_source(&init.expr)
+            {
+                // `ctx.accounts.x.to_account_info()` of an owner-checked field needs no further check
+                if accounts_source(&init.expr).is_some_and(|field| self.owner_checked_fields.contains(&field)) {
+                    self.owner_checked.insert(name.clone());
+                }
+                self.account_infos.insert(name);
+            }
+        }
+        visit::visit_local(self, local);
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::Binary(binary) if matches!(binary.op, syn::BinOp::Eq(_) | syn::BinOp::Ne(_)) => {
+                for side in [&binary.left, &binary.right] {
+                    if let Some(account) = owner_access(side) {
+                        self.owner_checked.insert(account);
+                    }
+                }
+            }
+            Expr::Call(call) => {
+                let name = call_name(&call.func);
+                if name.contains("owner") {
+                    // Helpers such as `check_account_owner(account, program_id)`
+                    for arg in &call.args {
+                        if let Some(account) = root_ident(arg) {
+                            self.owner_checked.insert(account);
+                        }
+                    }
+                } else if DESERIALIZERS.contains(&name.as_str()) {
+                    for arg in &call.args {
+                        if let Some(account) = data_source(arg)
+                            && self.account_infos.contains(&account)
+                            && !self.owner_checked.contains(&account)
+                        {
+                            self.unchecked.push((expr, account));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let tokens = anchor::flatten_tokens(mac.tokens.clone());
+        for window in tokens.windows(3) {
+            if window[1] == "." && window[2] == "owner" {
+                self.owner_checked.insert(window[0].clone());
+            }
+        }
+        visit::visit_macro(self, mac);
+    }
+}
+
+/// Returns true if the expression yields an `AccountInfo`
+/// (`next_account_info(iter)?`, `&accounts[0]`, `accounts.get(0)`)
+fn is_account_info_source(expr: &Expr) -> bool {
+    match expr {
+        Expr::Try(expr_try) => is_account_info_source(&expr_try.expr),
+        Expr::Reference(reference) => is_account_info_source(&reference.expr),
+        Expr::Call(call) => call_name(&call.func) == "next_account_info",
+        Expr::Index(index) => root_ident(&index.expr).is_some_and(|name| name == "accounts"),
+        Expr::MethodCall(call) => {
+            root_ident(&call.receiver).is_some_and(|name| name == "accounts")
+                || call.method == "next"
+                || call.method == "to_account_info"
+        }
+        _ => false,
+    }
+}
+
+/// Returns `x` if the expression reads the accounts field `ctx.accounts.x`
+fn accounts_source(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Try(expr_try) => accounts_source(&expr_try.expr),
+        Expr::Reference(reference) => accounts_source(&reference.expr),
+        Expr::MethodCall(call) => accounts_source(&call.receiver),
+        _ => anchor::accounts_field(expr),
+    }
+}
+
+/// Returns the last segment of a called function path
+fn call_name(func: &Expr) -> String {
+    match func {
+        Expr::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|s| s.ident.to_string())
+            .unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+/// Returns `x` for expressions such as `x.owner` or `*x.owner`
+fn owner_access(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Unary(unary) => owner_access(&unary.expr),
+        Expr::Reference(reference) => owner_access(&reference.expr),
+        Expr::Paren(paren) => owner_access(&paren.expr),
+        Expr::Field(field) if matches!(&field.member, syn::Member::Named(m) if m == "owner") => {
+            root_ident(&field.base)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the root variable of an expression such as `x.data.borrow()`
+fn root_ident(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Path(path) => path.path.get_ident().map(ToString::to_string),
+        Expr::Field(field) => root_ident(&field.base),
+        Expr::MethodCall(call) => root_ident(&call.receiver),
+        Expr::Reference(reference) => root_ident(&reference.expr),
+        Expr::Unary(unary) => root_ident(&unary.expr),
+        Expr::Index(index) => root_ident(&index.expr),
+        Expr::Try(expr_try) => root_ident(&expr_try.expr),
+        Expr::Paren(paren) => root_ident(&paren.expr),
+        _ => None,
+    }
+}
+
+/// Returns the account whose data is read by the expression
+/// (`&x.data.borrow()`, `&mut &x.data.borrow()[..]`, `&x.try_borrow_data()?`)
+fn data_source(expr: &Expr) -> Option<String> {
+    struct DataFinder {
+        account: Option<String>,
+    }
+
+    impl<'ast> Visit<'ast> for DataFinder {
+        fn visit_expr_field(&mut self, field: &'ast syn::ExprField) {
+            if matches!(&field.member, syn::Member::Named(m) if m == "data") {
+                self.account = self.account.take().or_else(|| root_ident(&field.base));
+            }
+            visit::visit_expr_field(self, field);
+        }
+
+        fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+            if call.method == "try_borrow_data" || call.method == "data" {
+                self.account = self.account.take().or_else(|| root_ident(&call.receiver));
+            }
+            visit::visit_expr_method_call(self, call);
+        }
+    }
+
+    let mut finder = DataFinder { account: None };
+    finder.visit_expr(expr);
+    finder.account
+}
@@ -0,0 +1,56 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::MissingOwnerCheckFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("missing-owner-check")
+        .title("Account Deserialized Without Owner Check")
+        .description("Detects AccountInfo data deserialized without first verifying that the account is owned by the expected program, which allows account substitution attacks")
+        .severity(Severity::High)
+        .rule_type(RuleType::Solana)
+        .category("accounts")
+        .tag("security")
+        .tag("accounts")
+        .reference("CWE-345: Insufficient Verification of Data Authenticity (https://cwe.mitre.org/data/definitions/345.html)")
+        .reference("https://github.com/coral-xyz/sealevel-attacks/tree/master/programs/2-owner-checks")
+        .recommendations(vec![
+            "Verify the account owner before deserializing: if account.owner != program_id { return Err(ProgramError::IncorrectProgramId) }",
+            "Use Anchor's Account<'info, T> wrapper, which checks the owner and discriminator automatically",
+            "Centralize owner validation in a helper and call it for every account read from raw bytes",
+        ])
+        .vulnerable_example(r#"
+pub fn process(accounts: &[AccountInfo]) -> ProgramResult {
+    let vault_info = next_account_info(&mut accounts.iter())?;
+    let vault = Vault::try_from_slice(&vault_info.data.borrow())?;
+    // ...
+}
+"#)
+        .fixed_example(r#"
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let vault_info = next_account_info(&mut accounts.iter())?;
+    if vault_info.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let vault = Vault::try_from_slice(&vault_info.data.borrow())?;
+    // ...
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing account deserialization without owner checks");
+
+            AstQuery::new(ast)
+                .functions()
+                .deserializes_without_owner_check(ast)
+        })
+        .build()
+}
@@ -0,0 +1,143 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::high::missing_owner_check::filters::MissingOwnerCheckFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_accounts(ast: &File) -> Vec<String> {
+        AstQuery::new(ast)
+            .functions()
+            .deserializes_without_owner_check(ast)
+            .collect()
+            .into_iter()
+            .map(|node| node.name())
+            .collect()
+    }
+
+    #[test]
+    fn test_native_deserialization_without_owner_check() {
+        let ast: File = parse_quote! {
+            pub fn process(accounts: &[AccountInfo]) -> ProgramResult {
+                let vault_info = next_account_info(&mut accounts.iter())?;
+                let vault = Vault::try_from_slice(&vault_info.data.borrow())?;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_accounts(&ast), vec!["vault_info"],
+                "Should flag account data deserialized without an owner check");
+    }
+
+    #[test]
+    fn test_native_owner_comparison() {
+        let ast: File = parse_quote! {
+            pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+                let vault_info = next_account_info(&mut accounts.iter())?;
+                if vault_info.owner != program_id {
+                    return Err(ProgramError::IncorrectProgramId);
+                }
+                let vault = Vault::try_from_slice(&vault_info.data.borrow())?;
+                Ok(())
+            }
+        };
+
+        assert!(flagged_accounts(&ast).is_empty(), "An owner comparison should prevent the finding");
+    }
+
+    #[test]
+    fn test_owner_check_after_deserialization() {
+        let ast: File = parse_quote! {
+            pub fn process(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+                let vault_info = next_account_info(&mut accounts.iter())?;
+                let vault = Vault::try_from_slice(&vault_info.data.borrow())?;
+                if vault_info.owner != program_id {
+                    return Err(ProgramError::IncorrectProgramId);
+                }
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_accounts(&ast), vec!["vault_info"],
+                "An owner check after deserialization should not count");
+    }
+
+    #[test]
+    fn test_unchecked_account_in_anchor_context() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Withdraw<'info> {
+                pub vault: UncheckedAccount<'info>,
+            }
+
+            pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
+                let vault_info = ctx.accounts.vault.to_account_info();
+                let vault = Vault::try_deserialize(&mut &vault_info.data.borrow()[..])?;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_accounts(&ast), vec!["vault_info"],
+                "Should flag an unchecked account deserialized in a handler");
+    }
+
+    #[test]
+    fn test_owner_constraint() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Withdraw<'info> {
+                #[account(owner = crate::ID)]
+                pub vault: UncheckedAccount<'info>,
+            }
+
+            pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
+                let vault_info = ctx.accounts.vault.to_account_info();
+                let vault = Vault::try_deserialize(&mut &vault_info.data.borrow()[..])?;
+                Ok(())
+            }
+        };
+
+        assert!(flagged_accounts(&ast).is_empty(), "An owner constraint should prevent the finding");
+    }
+
+    #[test]
+    fn test_account_wrapper() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Withdraw<'info> {
+                pub vault: Account<'info, Vault>,
+                pub config: Box<Account<'info, Config>>,
+            }
+
+            pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
+                let vault_info = ctx.accounts.vault.to_account_info();
+                let vault = Vault::try_deserialize(&mut &vault_info.data.borrow()[..])?;
+                let config_info = ctx.accounts.config.to_account_info();
+                let config = Config::try_deserialize(&mut &config_info.data.borrow()[..])?;
+                Ok(())
+            }
+        };
+
+        assert!(flagged_accounts(&ast).is_empty(), "Account<'info, T> already verifies the owner");
+    }
+
+    #[test]
+    fn test_require_keys_eq_owner() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Withdraw<'info> {
+                pub vault: UncheckedAccount<'info>,
+            }
+
+            pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
+                let vault_info = ctx.accounts.vault.to_account_info();
+                require_keys_eq!(*vault_info.owner, crate::ID, ErrorCode::InvalidOwner);
+                let vault = Vault::try_deserialize(&mut &vault_info.data.borrow()[..])?;
+                Ok(())
+            }
+        };
+
+        assert!(flagged_accounts(&ast).is_empty(), "require_keys_eq! on the owner should prevent the finding");
+    }
+}
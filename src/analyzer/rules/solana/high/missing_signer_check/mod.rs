@@ -14,6 +14,9 @@ pub fn create_rule() -> Arc<dyn Rule> {
         .title("Missing Signer Check")
         .description("Detects Anchor account fields that may need signer verification")
         .severity(Severity::High)
+        .category("access-control")
+        .reference("CWE-862: Missing Authorization (https://cwe.mitre.org/data/definitions/862.html)")
+        .reference("https://github.com/coral-xyz/sealevel-attacks/tree/master/programs/0-signer-authorization")
         .recommendations(vec![
             "Add signer constraint to account fields that should be signed: #[account(signer)]",
             "Use Signer<'info> type for accounts that must be signers of the transaction",
@@ -21,6 +24,23 @@ pub fn create_rule() -> Arc<dyn Rule> {
             "Consider using #[account(constraint = account.key() == signer.key())] for explicit signer validation",
             "Review all account fields to ensure proper authorization and access control"
         ])
+        .vulnerable_example(r#"
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut, has_one = authority)]
+    pub vault: Account<'info, Vault>,
+    /// CHECK: compared against vault.authority
+    pub authority: AccountInfo<'info>,
+}
+"#)
+        .fixed_example(r#"
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut, has_one = authority)]
+    pub vault: Account<'info, Vault>,
+    pub authority: Signer<'info>,
+}
+"#)
         .dsl_query(|ast, file_path, span_extractor| {
             debug!("Analyzing missing signer checks using DSL with specialized filters");
             
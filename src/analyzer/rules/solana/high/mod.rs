@@ -1,3 +1,11 @@
+pub mod arbitrary_cpi;
+pub mod bump_seed_canonicalization;
+pub mod duplicate_mutable_accounts;
+pub mod insecure_account_close;
+pub mod missing_owner_check;
 pub mod missing_signer_check;
+pub mod privileged_instruction_signer;
+pub mod type_cosplay;
+pub mod unsafe_account_cast;
 pub mod unsafe_code;
 
@@ -0,0 +1,182 @@
+use log::{debug, trace};
+use std::collections::HashSet;
+use syn::visit::{self, Visit};
+use syn::{Expr, File};
+
+use crate::analyzer::dsl::anchor::{self, AccountRefCollector};
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Account field names that usually hold privileged roles
+const PRIVILEGED_NAMES: [&str; 3] = ["authority", "owner", "admin"];
+
+/// Unsigned account types already reported by `missing-signer-check`
+const RAW_ACCOUNT_TYPES: [&str; 3] = ["AccountInfo", "UncheckedAccount", "SystemAccount"];
+
+pub trait PrivilegedInstructionSignerFilters<'a> {
+    fn unsigned_privileged_accounts(self, ast: &'a File) -> AstQuery<'a>;
+}
+
+impl<'a> PrivilegedInstructionSignerFilters<'a> for AstQuery<'a> {
+    fn unsigned_privileged_accounts(self, ast: &'a File) -> AstQuery<'a> {
+        debug!("Filtering privileged accounts used by handlers without signer verification");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let NodeData::Struct(struct_item) = node.data else {
+                continue;
+            };
+
+            let mut usage = HandlerUsage::default();
+            for handler in anchor::handlers_for(ast, &struct_item.ident.to_string()) {
+                let mut handler_usage = HandlerUsage::default();
+                handler_usage.visit_block(handler.block);
+
+                // Only handlers that change state are privileged operations
+                if handler_usage.mutates {
+                    usage.privileged.extend(handler_usage.privileged);
+                    usage.signer_checked.extend(handler_usage.signer_checked);
+                }
+            }
+
+            for field in anchor::named_fields(struct_item) {
+                let Some(ident) = &field.ident else {
+                    continue;
+                };
+                let name = ident.to_string();
+
+                if is_privileged_name(&name)
+                    && usage.privileged.contains(&name)
+                    && !usage.signer_checked.contains(&name)
+                    && !is_signer_field(field)
+                    && !is_raw_account(field)
+                {
+                    trace!("Found privileged account '{name}' without signer verification");
+                    new_results.push(AstNode::from_field(field));
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Returns true if the account name suggests a privileged role
+fn is_privileged_name(name: &str) -> bool {
+    let name = name.to_lowercase();
+    PRIVILEGED_NAMES.iter().any(|privileged| name.contains(privileged))
+}
+
+/// Returns true if the field is declared as a signer
+fn is_signer_field(field: &syn::Field) -> bool {
+    anchor::type_name(&field.ty).as_deref() == Some("Signer")
+        || anchor::has_constraint(&field.attrs, "signer")
+}
+
+/// Returns true if `missing-signer-check` already reports the field
+fn is_raw_account(field: &syn::Field) -> bool {
+    anchor::type_name(&field.ty).is_some_and(|ty| RAW_ACCOUNT_TYPES.contains(&ty.as_str()))
+}
+
+/// Usage of the accounts inside an instruction handler
+#[derive(Default)]
+struct HandlerUsage {
+    /// Handler writes state, moves lamports or performs a CPI
+    mutates: bool,
+    /// Accounts used in comparisons or CPIs
+    privileged: HashSet<String>,
+    /// Accounts whose `is_signer` flag is checked
+    signer_checked: HashSet<String>,
+}
+
+impl<'ast> Visit<'ast> for HandlerUsage {
+    fn visit_expr_assign(&mut self, expr: &'ast syn::ExprAssign) {
+        self.mutates = true;
+        visit::visit_expr_assign(self, expr);
+    }
+
+    fn visit_expr_binary(&mut self, expr: &'ast syn::ExprBinary) {
+        match expr.op {
+            syn::BinOp::AddAssign(_)
+            | syn::BinOp::SubAssign(_)
+            | syn::BinOp::MulAssign(_)
+            | syn::BinOp::DivAssign(_) => self.mutates = true,
+            syn::BinOp::Eq(_) | syn::BinOp::Ne(_) => {
+                self.privileged.extend(AccountRefCollector::collect_expr(&expr.left));
+                self.privileged.extend(AccountRefCollector::collect_expr(&expr.right));
+            }
+            _ => {}
+        }
+        visit::visit_expr_binary(self, expr);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        if let Expr::Path(path) = &*call.func {
+            let segments: Vec<String> = path
+                .path
+                .segments
+                .iter()
+                .map(|s| s.ident.to_string())
+                .collect();
+            let last = segments.last().map(String::as_str).unwrap_or_default();
+
+            let is_cpi = matches!(last, "invoke" | "invoke_signed" | "transfer")
+                || segments.iter().any(|s| s == "CpiContext");
+            if is_cpi {
+                self.mutates = true;
+                for arg in &call.args {
+                    self.privileged.extend(AccountRefCollector::collect_expr(arg));
+                }
+            }
+        }
+        visit::visit_expr_call(self, call);
+    }
+
+    fn visit_expr_struct(&mut self, expr: &'ast syn::ExprStruct) {
+        // Struct literals in handlers are typically CPI account lists
+        for field in &expr.fields {
+            self.privileged.extend(AccountRefCollector::collect_expr(&field.expr));
+        }
+        visit::visit_expr_struct(self, expr);
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        let method = call.method.to_string();
+        if matches!(
+            method.as_str(),
+            "borrow_mut" | "try_borrow_mut_lamports" | "try_borrow_mut_data" | "exit"
+        ) {
+            self.mutates = true;
+        }
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_field(&mut self, expr: &'ast syn::ExprField) {
+        if let syn::Member::Named(member) = &expr.member
+            && member == "is_signer"
+            && let Some(account) = anchor::accounts_field(&expr.base)
+        {
+            self.signer_checked.insert(account);
+        }
+        visit::visit_expr_field(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let macro_name = mac
+            .path
+            .segments
+            .last()
+            .map(|s| s.ident.to_string())
+            .unwrap_or_default();
+
+        if macro_name.starts_with("require") || macro_name.starts_with("assert") {
+            let tokens = anchor::flatten_tokens(mac.tokens.clone());
+            for window in tokens.windows(5) {
+                if window[0] == "accounts" && window[1] == "." && window[3] == "." && window[4] == "is_signer" {
+                    self.signer_checked.insert(window[2].clone());
+                }
+            }
+            self.privileged.extend(anchor::accounts_fields_in_tokens(&mac.tokens));
+        }
+        visit::visit_macro(self, mac);
+    }
+}
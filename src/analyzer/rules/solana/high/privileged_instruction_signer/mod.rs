@@ -0,0 +1,40 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::PrivilegedInstructionSignerFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("privileged-instruction-missing-signer")
+        .title("Privileged Account Without Signer Check")
+        .description("Detects typed authority, owner or admin accounts that gate state-changing instructions but are never verified to have signed the transaction; raw AccountInfo, UncheckedAccount and SystemAccount fields are left to missing-signer-check")
+        .severity(Severity::High)
+        .rule_type(RuleType::Anchor)
+        .category("access-control")
+        .tag("security")
+        .tag("access-control")
+        .reference("CWE-862: Missing Authorization (https://cwe.mitre.org/data/definitions/862.html)")
+        .reference("https://github.com/coral-xyz/sealevel-attacks/tree/master/programs/0-signer-authorization")
+        .recommendations(vec![
+            "Declare the privileged account as Signer<'info> so Anchor verifies the signature",
+            "Use a has_one constraint on the state account together with a Signer authority: #[account(has_one = authority)]",
+            "If the account must stay an AccountInfo, add #[account(signer)] or check ctx.accounts.authority.is_signer in the handler",
+        ])
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing privileged instructions for missing signer checks");
+
+            AstQuery::new(ast)
+                .structs()
+                .derives_accounts()
+                .unsigned_privileged_accounts(ast)
+        })
+        .build()
+}
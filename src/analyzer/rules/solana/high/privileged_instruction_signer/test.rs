@@ -0,0 +1,125 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::high::privileged_instruction_signer::filters::PrivilegedInstructionSignerFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_fields(ast: &File) -> Vec<String> {
+        AstQuery::new(ast)
+            .structs()
+            .derives_accounts()
+            .unsigned_privileged_accounts(ast)
+            .collect()
+            .into_iter()
+            .map(|node| node.name())
+            .collect()
+    }
+
+    #[test]
+    fn test_unsigned_authority_in_comparison() {
+        let ast: File = parse_quote! {
+            pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+                require_keys_eq!(ctx.accounts.vault.authority, ctx.accounts.authority.key());
+                ctx.accounts.vault.balance -= amount;
+                Ok(())
+            }
+
+            #[derive(Accounts)]
+            pub struct Withdraw<'info> {
+                #[account(mut)]
+                pub vault: Account<'info, Vault>,
+                pub authority: Account<'info, Authority>,
+            }
+        };
+
+        assert_eq!(flagged_fields(&ast), vec!["authority"],
+                "Should detect authority compared but never verified as signer");
+    }
+
+    #[test]
+    fn test_raw_account_left_to_missing_signer_check() {
+        let ast: File = parse_quote! {
+            pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+                require_keys_eq!(ctx.accounts.vault.authority, ctx.accounts.authority.key());
+                ctx.accounts.vault.balance -= amount;
+                Ok(())
+            }
+
+            #[derive(Accounts)]
+            pub struct Withdraw<'info> {
+                #[account(mut)]
+                pub vault: Account<'info, Vault>,
+                pub authority: AccountInfo<'info>,
+            }
+        };
+
+        assert!(flagged_fields(&ast).is_empty(),
+                "AccountInfo authorities are reported by missing-signer-check");
+    }
+
+    #[test]
+    fn test_signer_type_is_safe() {
+        let ast: File = parse_quote! {
+            pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+                require_keys_eq!(ctx.accounts.vault.authority, ctx.accounts.authority.key());
+                ctx.accounts.vault.balance -= amount;
+                Ok(())
+            }
+
+            #[derive(Accounts)]
+            pub struct Withdraw<'info> {
+                #[account(mut)]
+                pub vault: Account<'info, Vault>,
+                pub authority: Signer<'info>,
+            }
+        };
+
+        assert!(flagged_fields(&ast).is_empty(),
+                "Should not detect Signer<'info> authority");
+    }
+
+    #[test]
+    fn test_manual_is_signer_check_is_safe() {
+        let ast: File = parse_quote! {
+            pub fn set_admin(ctx: Context<SetAdmin>, new_admin: Pubkey) -> Result<()> {
+                require!(ctx.accounts.admin.is_signer, ErrorCode::Unauthorized);
+                if ctx.accounts.config.admin != ctx.accounts.admin.key() {
+                    return err!(ErrorCode::Unauthorized);
+                }
+                ctx.accounts.config.admin = new_admin;
+                Ok(())
+            }
+
+            #[derive(Accounts)]
+            pub struct SetAdmin<'info> {
+                #[account(mut)]
+                pub config: Account<'info, Config>,
+                pub admin: UncheckedAccount<'info>,
+            }
+        };
+
+        assert!(flagged_fields(&ast).is_empty(),
+                "Should not detect admin checked through is_signer");
+    }
+
+    #[test]
+    fn test_read_only_handler_is_ignored() {
+        let ast: File = parse_quote! {
+            pub fn check(ctx: Context<Check>) -> Result<()> {
+                require_keys_eq!(ctx.accounts.vault.owner, ctx.accounts.owner.key());
+                Ok(())
+            }
+
+            #[derive(Accounts)]
+            pub struct Check<'info> {
+                pub vault: Account<'info, Vault>,
+                pub owner: Account<'info, Owner>,
+            }
+        };
+
+        assert!(flagged_fields(&ast).is_empty(),
+                "Should not detect handlers that do not change state");
+    }
+}
@@ -0,0 +1,151 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use syn::visit::{self, Visit};
+use syn::Expr;
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Deserializers that do not check an account discriminator
+const UNCHECKED_DESERIALIZERS: [&str; 4] = [
+    "try_from_slice",
+    "try_from_slice_unchecked",
+    "deserialize",
+    "try_deserialize_unchecked",
+];
+
+/// Sysvar types, whose data carries no discriminator
+const SYSVAR_TYPES: [&str; 6] = [
+    "Clock",
+    "Rent",
+    "EpochSchedule",
+    "EpochRewards",
+    "SlotHashes",
+    "StakeHistory",
+];
+
+/// Identifiers that indicate a discriminator or manual type tag check
+const TYPE_TAG_NAMES: [&str; 6] = [
+    "discriminator",
+    "discriminant",
+    "account_type",
+    "account_kind",
+    "type_tag",
+    "tag",
+];
+
+pub trait TypeCosplayFilters<'a> {
+    fn deserializes_without_discriminator(self) -> AstQuery<'a>;
+}
+
+impl<'a> TypeCosplayFilters<'a> for AstQuery<'a> {
+    fn deserializes_without_discriminator(self) -> AstQuery<'a> {
+        debug!("Filtering account deserializations without discriminator checks");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let block = match node.data {
+                NodeData::Function(func) => func.block.as_ref(),
+                NodeData::ImplFunction(func) => &func.block,
+                _ => continue,
+            };
+
+            let mut finder = TypeCheckFinder::default();
+            finder.visit_block(block);
+            if finder.type_checked {
+                continue;
+            }
+
+            for (account, expr) in finder.deserializations {
+                trace!("Found deserialization of '{account}' without discriminator check");
+                new_results.push(AstNode::from_expression(expr, &account));
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Visitor that collects raw account deserializations and type tag checks
+#[derive(Default)]
+struct TypeCheckFinder<'ast> {
+    /// Deserializations of account data, with the source account
+    deserializations: Vec<(String, &'ast Expr)>,
+    /// The function compares a discriminator or type tag
+    type_checked: bool,
+}
+
+impl<'ast> Visit<'ast> for TypeCheckFinder<'ast> {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        // `Vault::try_from_slice(&info.data.borrow())`
+        if let Expr::Call(call) = expr
+            && let Expr::Path(path) = &*call.func
+            && path.path.segments.len() >= 2
+        {
+            let segments: Vec<String> = path
+                .path
+                .segments
+                .iter()
+                .map(|s| s.ident.to_string())
+                .collect();
+            let method = &segments[segments.len() - 1];
+            let type_name = &segments[segments.len() - 2];
+            let is_account_type = type_name.starts_with(char::is_uppercase)
+                && !SYSVAR_TYPES.contains(&type_name.as_str());
+
+            if UNCHECKED_DESERIALIZERS.contains(&method.as_str())
+                && is_account_type
+                && let Some(account) = call.args.iter().find_map(data_source_account)
+            {
+                self.deserializations.push((account, expr));
+            }
+        }
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_expr_binary(&mut self, expr: &'ast syn::ExprBinary) {
+        if matches!(expr.op, syn::BinOp::Eq(_) | syn::BinOp::Ne(_))
+            && (is_type_check(&expr.left.to_token_stream()) || is_type_check(&expr.right.to_token_stream()))
+        {
+            self.type_checked = true;
+        }
+        visit::visit_expr_binary(self, expr);
+    }
+
+    fn visit_expr_match(&mut self, expr: &'ast syn::ExprMatch) {
+        if is_type_check(&expr.expr.to_token_stream()) {
+            self.type_checked = true;
+        }
+        visit::visit_expr_match(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let macro_name = mac.path.segments.last().map(|s| s.ident.to_string()).unwrap_or_default();
+        if (macro_name.starts_with("require") || macro_name.starts_with("assert"))
+            && is_type_check(&mac.tokens)
+        {
+            self.type_checked = true;
+        }
+        visit::visit_macro(self, mac);
+    }
+}
+
+/// Returns true if the tokens read a discriminator, a type tag or the first 8 bytes of data
+fn is_type_check(tokens: &proc_macro2::TokenStream) -> bool {
+    let flat = anchor::flatten_tokens(tokens.clone());
+    let names_tag = flat.iter().any(|token| {
+        let token = token.to_lowercase();
+        TYPE_TAG_NAMES.iter().any(|name| token == *name || token.ends_with(&format!("_{name}")))
+    });
+    let reads_prefix = flat.windows(2).any(|window| window[0] == ".." && window[1] == "8");
+    names_tag || reads_prefix
+}
+
+/// Account whose data is deserialized (`x` in `&x.data.borrow()` or `&mut &**x.try_borrow_data()?`)
+fn data_source_account(expr: &Expr) -> Option<String> {
+    let tokens = anchor::flatten_tokens(expr.to_token_stream());
+    tokens
+        .windows(3)
+        .find(|window| window[1] == "." && matches!(window[2].as_str(), "data" | "try_borrow_data"))
+        .map(|window| window[0].clone())
+}
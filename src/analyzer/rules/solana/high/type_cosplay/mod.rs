@@ -0,0 +1,54 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::TypeCosplayFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("type-cosplay")
+        .title("Type Cosplay")
+        .description("Detects program accounts deserialized from raw bytes without validating the Anchor discriminator or a manual type tag, letting an attacker pass an account of one type where another is expected")
+        .severity(Severity::High)
+        .rule_type(RuleType::Solana)
+        .category("accounts")
+        .tag("security")
+        .tag("account-validation")
+        .reference("CWE-843: Access of Resource Using Incompatible Type ('Type Confusion') (https://cwe.mitre.org/data/definitions/843.html)")
+        .reference("https://github.com/coral-xyz/sealevel-attacks/tree/master/programs/3-type-cosplay")
+        .recommendations(vec![
+            "Use typed Anchor accounts (Account<'info, T>), which verify the discriminator when deserializing",
+            "Deserialize with T::try_deserialize, which checks the discriminator, instead of try_from_slice",
+            "In native programs, store a type tag in every account and check it before trusting the data",
+        ])
+        .vulnerable_example(r#"
+pub fn update(ctx: Context<Update>) -> Result<()> {
+    let user = User::try_from_slice(&ctx.accounts.user.data.borrow())?;
+    // `user` may actually be a Metadata account with the same layout
+    Ok(())
+}
+"#)
+        .fixed_example(r#"
+#[derive(Accounts)]
+pub struct Update<'info> {
+    #[account(has_one = authority)]
+    pub user: Account<'info, User>,
+    pub authority: Signer<'info>,
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing account deserializations for discriminator checks");
+
+            AstQuery::new(ast)
+                .functions()
+                .deserializes_without_discriminator()
+        })
+        .build()
+}
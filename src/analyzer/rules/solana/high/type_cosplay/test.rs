@@ -0,0 +1,63 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::high::type_cosplay::filters::TypeCosplayFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_accounts(ast: &File) -> Vec<String> {
+        AstQuery::new(ast)
+            .functions()
+            .deserializes_without_discriminator()
+            .collect()
+            .into_iter()
+            .map(|node| node.name())
+            .collect()
+    }
+
+    #[test]
+    fn test_try_from_slice_without_discriminator() {
+        let ast: File = parse_quote! {
+            pub fn update(ctx: Context<Update>) -> Result<()> {
+                let user = User::try_from_slice(&ctx.accounts.user.data.borrow())?;
+                msg!("{}", user.authority);
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_accounts(&ast), vec!["user"],
+                "Should flag raw deserialization without discriminator check");
+    }
+
+    #[test]
+    fn test_manual_type_tag_check() {
+        let ast: File = parse_quote! {
+            fn process(accounts: &[AccountInfo]) -> ProgramResult {
+                let user = User::try_from_slice(&user_info.data.borrow())?;
+                if user.account_type != AccountType::User {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                Ok(())
+            }
+        };
+
+        assert!(flagged_accounts(&ast).is_empty(), "A type tag check should prevent the finding");
+    }
+
+    #[test]
+    fn test_discriminator_prefix_check() {
+        let ast: File = parse_quote! {
+            fn process(accounts: &[AccountInfo]) -> ProgramResult {
+                let data = user_info.try_borrow_data()?;
+                require!(data[..8] == User::DISCRIMINATOR, ErrorCode::WrongType);
+                let user = User::deserialize(&mut &user_info.data.borrow()[8..])?;
+                let checked = Vault::try_deserialize(&mut &vault_info.data.borrow()[..])?;
+                Ok(())
+            }
+        };
+
+        assert!(flagged_accounts(&ast).is_empty(),
+                "Discriminator checks and try_deserialize should not be flagged");
+    }
+}
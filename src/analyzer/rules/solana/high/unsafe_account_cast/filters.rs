@@ -0,0 +1,113 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use syn::visit::{self, Visit};
+use syn::{Expr, Type};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Tokens showing that a function reads or writes raw account data
+const ACCOUNT_DATA_MARKERS: [&str; 6] = [
+    "AccountInfo", "data", "try_borrow_data", "try_borrow_mut_data", "borrow_data", "borrow_mut_data",
+];
+
+/// Functions reinterpreting memory as a different type
+const TRANSMUTE_FUNCTIONS: [&str; 2] = ["transmute", "transmute_copy"];
+
+/// Functions building a typed view over a raw pointer
+const RAW_VIEW_FUNCTIONS: [&str; 2] = ["from_raw_parts", "from_raw_parts_mut"];
+
+/// Tokens of a length check against the size of the target type
+const SIZE_CHECKS: [&str; 2] = ["size_of", "size_of_val"];
+
+/// Tokens of an alignment check on the source pointer
+const ALIGNMENT_CHECKS: [&str; 3] = ["align_of", "align_offset", "is_aligned"];
+
+pub trait UnsafeAccountCastFilters<'a> {
+    fn unsafe_account_casts(self) -> AstQuery<'a>;
+}
+
+impl<'a> UnsafeAccountCastFilters<'a> for AstQuery<'a> {
+    fn unsafe_account_casts(self) -> AstQuery<'a> {
+        debug!("Filtering transmutes and raw pointer casts of account data");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let (sig, block) = match node.data {
+                NodeData::Function(func) => (&func.sig, func.block.as_ref()),
+                NodeData::ImplFunction(func) => (&func.sig, &func.block),
+                _ => continue,
+            };
+
+            let mut tokens = anchor::flatten_tokens(sig.to_token_stream());
+            tokens.extend(anchor::flatten_tokens(block.to_token_stream()));
+            if !tokens.iter().any(|token| ACCOUNT_DATA_MARKERS.contains(&token.as_str())) {
+                continue;
+            }
+
+            // Pointer views are accepted once both the length and the alignment are checked
+            let has_size_check = tokens.iter().any(|token| SIZE_CHECKS.contains(&token.as_str()));
+            let has_alignment_check = tokens.iter().any(|token| ALIGNMENT_CHECKS.contains(&token.as_str()));
+            let validated = has_size_check && has_alignment_check;
+
+            let mut finder = CastFinder::default();
+            finder.visit_block(block);
+
+            for (expr, kind) in finder.casts {
+                if kind == CastKind::Transmute || !validated {
+                    trace!("Found unchecked {kind:?} in function: {}", sig.ident);
+                    new_results.push(AstNode::from_expression(expr, &sig.ident.to_string()));
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CastKind {
+    /// `mem::transmute` or `mem::transmute_copy`
+    Transmute,
+    /// `as *const T` or `as *mut T`
+    PointerCast,
+    /// `slice::from_raw_parts` or `slice::from_raw_parts_mut`
+    RawView,
+}
+
+/// Visitor that collects transmutes, pointer casts and raw slice views
+#[derive(Default)]
+struct CastFinder<'ast> {
+    casts: Vec<(&'ast Expr, CastKind)>,
+}
+
+impl<'ast> Visit<'ast> for CastFinder<'ast> {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::Call(call) => {
+                if let Expr::Path(path) = &*call.func
+                    && let Some(last) = path.path.segments.last()
+                {
+                    let name = last.ident.to_string();
+                    if TRANSMUTE_FUNCTIONS.contains(&name.as_str()) {
+                        self.casts.push((expr, CastKind::Transmute));
+                    } else if RAW_VIEW_FUNCTIONS.contains(&name.as_str()) {
+                        self.casts.push((expr, CastKind::RawView));
+                    }
+                }
+            }
+            Expr::Cast(cast) if matches!(*cast.ty, Type::Ptr(_)) => {
+                self.casts.push((expr, CastKind::PointerCast));
+                // Chained casts (`as *const u8 as *const T`) are reported once
+                let mut inner = &*cast.expr;
+                while let Expr::Cast(nested) = inner {
+                    inner = &nested.expr;
+                }
+                self.visit_expr(inner);
+                return;
+            }
+            _ => {}
+        }
+        visit::visit_expr(self, expr);
+    }
+}
@@ -0,0 +1,56 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::UnsafeAccountCastFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("unsafe-account-cast")
+        .title("Unsafe Account Data Cast")
+        .description("Detects mem::transmute, `as *const`/`as *mut` casts and slice::from_raw_parts in functions handling account data. Reinterpreting account bytes without validating their length and alignment reads out of bounds or through misaligned pointers, which is undefined behavior and lets crafted account data forge state")
+        .severity(Severity::High)
+        .rule_type(RuleType::General)
+        .category("unsafe")
+        .category("accounts")
+        .tag("security")
+        .tag("unsafe")
+        .tag("deserialization")
+        .reference("https://docs.rs/bytemuck/latest/bytemuck/fn.try_from_bytes.html")
+        .reference("CWE-704: Incorrect Type Conversion or Cast (https://cwe.mitre.org/data/definitions/704.html)")
+        .recommendations(vec![
+            "Use bytemuck::try_from_bytes or try_from_bytes_mut, which check the length and alignment",
+            "Derive Pod and Zeroable on zero-copy account types, or use Anchor's AccountLoader",
+            "If a raw cast is unavoidable, check the data length against size_of and the pointer alignment first",
+        ])
+        .vulnerable_example(r#"
+pub fn load_state(account: &AccountInfo) -> Result<&State> {
+    let data = account.try_borrow_data()?;
+    let state = unsafe { &*(data.as_ptr() as *const State) };
+    Ok(state)
+}
+"#)
+        .fixed_example(r#"
+pub fn load_state(account: &AccountInfo) -> Result<State> {
+    let data = account.try_borrow_data()?;
+    let state = bytemuck::try_from_bytes::<State>(&data[..std::mem::size_of::<State>()])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    Ok(*state)
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing transmutes and raw pointer casts of account data");
+
+            AstQuery::new(ast)
+                .functions()
+                .unsafe_account_casts()
+        })
+        .build()
+}
@@ -0,0 +1,84 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::high::unsafe_account_cast::filters::UnsafeAccountCastFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast)
+            .functions()
+            .unsafe_account_casts()
+            .collect()
+            .len()
+    }
+
+    #[test]
+    fn test_transmute_of_account_data() {
+        let ast: File = parse_quote! {
+            pub fn load_state(account: &AccountInfo) -> State {
+                let data = account.try_borrow_data().unwrap();
+                let bytes: [u8; 40] = data[..40].try_into().unwrap();
+                unsafe { std::mem::transmute::<[u8; 40], State>(bytes) }
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Should flag transmute of account data");
+    }
+
+    #[test]
+    fn test_pointer_cast_of_account_data() {
+        let ast: File = parse_quote! {
+            pub fn load_state(account: &AccountInfo) -> Result<&mut State> {
+                let mut data = account.try_borrow_mut_data()?;
+                let state = unsafe { &mut *(data.as_mut_ptr() as *mut u8 as *mut State) };
+                Ok(state)
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Should flag a chained pointer cast once");
+    }
+
+    #[test]
+    fn test_validated_pointer_cast() {
+        let ast: File = parse_quote! {
+            pub fn load_state(account: &AccountInfo) -> Result<&State> {
+                let data = account.try_borrow_data()?;
+                if data.len() < std::mem::size_of::<State>()
+                    || data.as_ptr().align_offset(std::mem::align_of::<State>()) != 0
+                {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                Ok(unsafe { &*(data.as_ptr() as *const State) })
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Length and alignment checks validate the cast");
+    }
+
+    #[test]
+    fn test_bytemuck_cast() {
+        let ast: File = parse_quote! {
+            pub fn load_state(account: &AccountInfo) -> Result<State> {
+                let data = account.try_borrow_data()?;
+                let state = bytemuck::try_from_bytes::<State>(&data[..std::mem::size_of::<State>()])
+                    .map_err(|_| ProgramError::InvalidAccountData)?;
+                Ok(*state)
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "bytemuck::try_from_bytes is checked");
+    }
+
+    #[test]
+    fn test_pointer_cast_outside_account_handling() {
+        let ast: File = parse_quote! {
+            pub fn address_of(value: &u64) -> usize {
+                value as *const u64 as usize
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Functions not touching account data are not flagged");
+    }
+}
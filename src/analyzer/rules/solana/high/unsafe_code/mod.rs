@@ -16,9 +16,9 @@ pub fn create_rule() -> Arc<dyn Rule> {
         .description("Using unsafe code in Solana programs can lead to security vulnerabilities")
         .severity(Severity::High)
         .rule_type(RuleType::Solana)
+        .category("unsafe")
         .tag("security")
         .tag("unsafe")
-        .reference(".")
         .reference("https://doc.rust-lang.org/book/ch20-01-unsafe-rust.html")
         .recommendations(vec![
             "Avoid using unsafe code in Solana programs unless absolutely necessary",
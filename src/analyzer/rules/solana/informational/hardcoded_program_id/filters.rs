@@ -0,0 +1,263 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use syn::punctuated::Punctuated;
+use syn::visit::{self, Visit};
+use syn::{Expr, File, Lit, Token};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Macros comparing their first two arguments
+const COMPARISON_MACROS: [&str; 4] = ["require_keys_eq", "require_keys_neq", "assert_eq", "assert_ne"];
+
+/// Expressions naming the executing program's id
+const OWN_ID_EXPRESSIONS: [&str; 4] = ["ID", "crate::ID", "id()", "crate::id()"];
+
+pub trait HardcodedProgramIdFilters<'a> {
+    fn hardcoded_program_ids(self, file_path: &str) -> AstQuery<'a>;
+}
+
+impl<'a> HardcodedProgramIdFilters<'a> for AstQuery<'a> {
+    fn hardcoded_program_ids(self, file_path: &str) -> AstQuery<'a> {
+        debug!("Filtering pubkey literals that duplicate or diverge from declare_id!");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let NodeData::File(file) = node.data else {
+                continue;
+            };
+
+            // Modules other than the crate root read the id declared in src/lib.rs
+            let Some(declared) = declared_id(file).or_else(|| crate_declared_id(file_path)) else {
+                continue;
+            };
+
+            let mut finder = PubkeyLiteralFinder::default();
+            finder.visit_file(file);
+
+            for (site, value) in finder.literals {
+                let duplicated = value == declared;
+                let diverging = !duplicated && finder.compared_with_own_id.contains(&value);
+                if duplicated || diverging {
+                    trace!("Found hardcoded program id {value} (duplicated: {duplicated})");
+                    new_results.push(match site {
+                        Site::Expr(expr) => AstNode::from_expression(expr, &value),
+                        Site::Macro(mac) => AstNode::from_macro(mac, &value),
+                    });
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Program id declared with `declare_id!` at the top level of a file
+fn declared_id(file: &File) -> Option<String> {
+    file.items.iter().find_map(|item| match item {
+        syn::Item::Macro(item_macro) if item_macro.mac.path.segments.last()?.ident == "declare_id" => {
+            string_literal(&item_macro.mac.tokens)
+        }
+        _ => None,
+    })
+}
+
+/// Program id declared in the `src/lib.rs` of the crate containing a file
+fn crate_declared_id(file_path: &str) -> Option<String> {
+    let crate_root = Path::new(file_path)
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.join("Cargo.toml").is_file())?;
+    let source = fs::read_to_string(crate_root.join("src").join("lib.rs")).ok()?;
+    declared_id(&syn::parse_file(&source).ok()?)
+}
+
+/// First string literal of a token stream, without quotes
+fn string_literal(tokens: &proc_macro2::TokenStream) -> Option<String> {
+    tokens.clone().into_iter().find_map(|token| match syn::parse2::<Lit>(token.into_token_stream()) {
+        Ok(Lit::Str(lit)) => Some(lit.value()),
+        _ => None,
+    })
+}
+
+/// Where a pubkey literal was found
+enum Site<'ast> {
+    Expr(&'ast Expr),
+    Macro(&'ast syn::Macro),
+}
+
+/// Visitor that collects pubkey literals and those compared with the program's own id
+#[derive(Default)]
+struct PubkeyLiteralFinder<'ast> {
+    /// Every pubkey literal with its base58 value
+    literals: Vec<(Site<'ast>, String)>,
+    /// Values of literals compared with `program_id`, `crate::ID` or `id()`
+    compared_with_own_id: HashSet<String>,
+}
+
+impl PubkeyLiteralFinder<'_> {
+    fn compare(&mut self, left: &Expr, right: &Expr) {
+        for (side, other) in [(left, right), (right, left)] {
+            if is_own_id(other) {
+                self.compared_with_own_id.extend(literal_values(side));
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for PubkeyLiteralFinder<'ast> {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let Some(value) = pubkey_literal(expr) {
+            self.literals.push((Site::Expr(expr), value));
+        }
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_expr_binary(&mut self, expr: &'ast syn::ExprBinary) {
+        if matches!(expr.op, syn::BinOp::Eq(_) | syn::BinOp::Ne(_)) {
+            self.compare(&expr.left, &expr.right);
+        }
+        visit::visit_expr_binary(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let name = mac.path.segments.last().map(|s| s.ident.to_string()).unwrap_or_default();
+        if name == "pubkey" {
+            if let Some(value) = string_literal(&mac.tokens) {
+                self.literals.push((Site::Macro(mac), value));
+            }
+        } else if COMPARISON_MACROS.contains(&name.as_str())
+            && let Ok(args) = mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+            && args.len() >= 2
+        {
+            // Literals inside the macro are reported on the macro itself
+            for arg in args.iter().take(2) {
+                for value in literal_values(arg) {
+                    self.literals.push((Site::Macro(mac), value));
+                }
+            }
+            self.compare(&args[0], &args[1]);
+        }
+        visit::visit_macro(self, mac);
+    }
+}
+
+/// Collects the values of the pubkey literals inside an expression
+#[derive(Default)]
+struct LiteralValues {
+    values: Vec<String>,
+}
+
+impl<'ast> Visit<'ast> for LiteralValues {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let Some(value) = pubkey_literal(expr) {
+            self.values.push(value);
+        }
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        if mac.path.segments.last().is_some_and(|s| s.ident == "pubkey")
+            && let Some(value) = string_literal(&mac.tokens)
+        {
+            self.values.push(value);
+        }
+        visit::visit_macro(self, mac);
+    }
+}
+
+fn literal_values(expr: &Expr) -> Vec<String> {
+    let mut collector = LiteralValues::default();
+    collector.visit_expr(expr);
+    collector.values
+}
+
+/// Base58 value of `Pubkey::from_str("...")` or `Pubkey::new_from_array([...])`
+fn pubkey_literal(expr: &Expr) -> Option<String> {
+    let Expr::Call(call) = expr else {
+        return None;
+    };
+    let Expr::Path(path) = &*call.func else {
+        return None;
+    };
+    let segments: Vec<String> = path.path.segments.iter().map(|s| s.ident.to_string()).collect();
+    if segments.len() < 2 || segments[segments.len() - 2] != "Pubkey" {
+        return None;
+    }
+
+    match (segments.last()?.as_str(), call.args.first()?) {
+        ("from_str", Expr::Lit(lit)) => match &lit.lit {
+            Lit::Str(value) => Some(value.value()),
+            _ => None,
+        },
+        ("new_from_array", array) => byte_array(array).map(|bytes| base58(&bytes)),
+        _ => None,
+    }
+}
+
+/// Bytes of a 32-byte array literal, written out or as `[byte; 32]`
+fn byte_array(expr: &Expr) -> Option<Vec<u8>> {
+    let byte = |expr: &Expr| match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Int(int) => int.base10_parse::<u8>().ok(),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    let bytes = match expr {
+        Expr::Array(array) => array.elems.iter().map(byte).collect::<Option<Vec<u8>>>()?,
+        Expr::Repeat(repeat) => {
+            let len = match &*repeat.len {
+                Expr::Lit(lit) => match &lit.lit {
+                    Lit::Int(int) => int.base10_parse::<usize>().ok()?,
+                    _ => return None,
+                },
+                _ => return None,
+            };
+            vec![byte(&repeat.expr)?; len]
+        }
+        _ => return None,
+    };
+
+    (bytes.len() == 32).then_some(bytes)
+}
+
+/// Base58 encoding (Bitcoin alphabet), as used for Solana pubkeys
+fn base58(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+    let mut digits: Vec<u8> = Vec::new();
+    for &byte in bytes {
+        let mut carry = u32::from(byte);
+        for digit in &mut digits {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    // Leading zero bytes are encoded as leading '1's
+    let zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+    std::iter::repeat_n('1', zeros)
+        .chain(digits.iter().rev().map(|&digit| ALPHABET[digit as usize] as char))
+        .collect()
+}
+
+/// Whether an expression names the executing program's id
+fn is_own_id(expr: &Expr) -> bool {
+    let tokens = anchor::flatten_tokens(expr.to_token_stream());
+    let compact: String = tokens
+        .iter()
+        .filter(|token| !matches!(token.as_str(), "&" | "*"))
+        .map(String::as_str)
+        .collect();
+    tokens.iter().any(|token| token == "program_id") || OWN_ID_EXPRESSIONS.contains(&compact.as_str())
+}
@@ -0,0 +1,50 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::HardcodedProgramIdFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("hardcoded-program-id")
+        .title("Hardcoded Program ID")
+        .description("Detects Pubkey::from_str, pubkey! and byte-array literals that repeat the declare_id! value or are compared with the program's own id while holding a different key. Copies of the program id drift apart when the program is redeployed under a new address")
+        .severity(Severity::Informational)
+        .rule_type(RuleType::General)
+        .category("configuration")
+        .tag("configuration")
+        .recommendations(vec![
+            "Reference crate::ID or crate::id() instead of repeating the program id",
+            "Keep declare_id! as the single source of the program address",
+        ])
+        .vulnerable_example(r#"
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub fn check_owner(account: &AccountInfo) -> Result<()> {
+    let program_id = Pubkey::from_str("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS").unwrap();
+    require_keys_eq!(*account.owner, program_id);
+    Ok(())
+}
+"#)
+        .fixed_example(r#"
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub fn check_owner(account: &AccountInfo) -> Result<()> {
+    require_keys_eq!(*account.owner, crate::ID);
+    Ok(())
+}
+"#)
+        .dsl_query(|ast, file_path, _span_extractor| {
+            debug!("Analyzing hardcoded program ids");
+
+            AstQuery::new(ast).hardcoded_program_ids(file_path)
+        })
+        .build()
+}
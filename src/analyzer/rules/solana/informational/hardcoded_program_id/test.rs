@@ -0,0 +1,69 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::informational::hardcoded_program_id::filters::HardcodedProgramIdFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast).hardcoded_program_ids("lib.rs").collect().len()
+    }
+
+    #[test]
+    fn test_duplicated_program_id() {
+        let ast: File = parse_quote! {
+            declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+            pub fn check_owner(account: &AccountInfo) -> Result<()> {
+                let expected = Pubkey::from_str("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS").unwrap();
+                require_keys_eq!(*account.owner, expected);
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Should flag a copy of the declared program id");
+    }
+
+    #[test]
+    fn test_diverging_program_id() {
+        let ast: File = parse_quote! {
+            declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+            pub fn process(program_id: &Pubkey) -> ProgramResult {
+                if *program_id != pubkey!("9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin") {
+                    return Err(ProgramError::IncorrectProgramId);
+                }
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Should flag a different id compared with program_id");
+    }
+
+    #[test]
+    fn test_other_program_id() {
+        let ast: File = parse_quote! {
+            declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+            pub fn check_token_program(account: &AccountInfo) -> Result<()> {
+                let token_program = Pubkey::from_str("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA").unwrap();
+                require_keys_eq!(account.key(), token_program);
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Ids of other programs are not flagged");
+    }
+
+    #[test]
+    fn test_byte_array_program_id() {
+        let ast: File = parse_quote! {
+            declare_id!("11111111111111111111111111111111");
+
+            pub const PROGRAM: Pubkey = Pubkey::new_from_array([0; 32]);
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Should decode byte arrays before comparing");
+    }
+}
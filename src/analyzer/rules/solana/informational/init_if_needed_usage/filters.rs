@@ -0,0 +1,33 @@
+use log::{debug, trace};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+pub trait InitIfNeededUsageFilters<'a> {
+    fn init_if_needed_accounts(self) -> AstQuery<'a>;
+}
+
+impl<'a> InitIfNeededUsageFilters<'a> for AstQuery<'a> {
+    fn init_if_needed_accounts(self) -> AstQuery<'a> {
+        debug!("Filtering init_if_needed accounts");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let NodeData::Struct(struct_item) = node.data else {
+                continue;
+            };
+
+            for field in anchor::named_fields(struct_item) {
+                if anchor::has_constraint(&field.attrs, "init_if_needed") {
+                    trace!(
+                        "Found init_if_needed account '{}'",
+                        field.ident.as_ref().map(ToString::to_string).unwrap_or_default()
+                    );
+                    new_results.push(AstNode::from_field(field));
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
@@ -0,0 +1,57 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::InitIfNeededUsageFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("init-if-needed")
+        .title("init_if_needed Used")
+        .description("Notes every #[account(init_if_needed, ...)] account. The same instruction both creates the account and accepts an existing one, so every handler using it must tell the two cases apart to avoid re-initialization")
+        .severity(Severity::Informational)
+        .rule_type(RuleType::Anchor)
+        .category("initialization")
+        .tag("initialization")
+        .reference("https://github.com/coral-xyz/sealevel-attacks/tree/master/programs/4-initialization")
+        .recommendations(vec![
+            "Prefer a separate instruction with init when the account is only created once",
+            "Otherwise check an initialization flag or field in the handler before writing initial state",
+        ])
+        .vulnerable_example(r#"
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(init_if_needed, payer = user, space = 8 + Vault::INIT_SPACE)]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+"#)
+        .fixed_example(r#"
+#[derive(Accounts)]
+pub struct CreateVault<'info> {
+    #[account(init, payer = user, space = 8 + Vault::INIT_SPACE)]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing init_if_needed usage");
+
+            AstQuery::new(ast)
+                .structs()
+                .derives_accounts()
+                .init_if_needed_accounts()
+        })
+        .build()
+}
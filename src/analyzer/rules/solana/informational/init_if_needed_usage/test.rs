@@ -0,0 +1,34 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::informational::init_if_needed_usage::filters::InitIfNeededUsageFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast)
+            .structs()
+            .derives_accounts()
+            .init_if_needed_accounts()
+            .collect()
+            .len()
+    }
+
+    #[test]
+    fn test_init_if_needed_is_noted() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Deposit<'info> {
+                #[account(init_if_needed, payer = user, space = 8 + 40)]
+                pub vault: Account<'info, Vault>,
+                #[account(init, payer = user, space = 8 + 40)]
+                pub receipt: Account<'info, Receipt>,
+                #[account(mut)]
+                pub user: Signer<'info>,
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Only the init_if_needed account should be noted");
+    }
+}
@@ -0,0 +1,6 @@
+pub mod hardcoded_program_id;
+pub mod init_if_needed_usage;
+pub mod non_idempotent_ata_creation;
+pub mod sensitive_log;
+pub mod tautological_constraint;
+pub mod unnecessary_mut_account;
@@ -0,0 +1,69 @@
+use log::{debug, trace};
+use syn::visit::{self, Visit};
+use syn::Expr;
+
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Instruction builders of spl_associated_token_account failing on an existing account
+const SPL_CREATE_FUNCTIONS: [&str; 1] = ["create_associated_token_account"];
+
+/// Module of the Anchor associated token program CPIs
+const ANCHOR_MODULE: &str = "associated_token";
+
+/// Anchor CPI failing on an existing account
+const ANCHOR_CREATE_FUNCTION: &str = "create";
+
+pub trait NonIdempotentAtaCreationFilters<'a> {
+    fn non_idempotent_ata_creations(self) -> AstQuery<'a>;
+}
+
+impl<'a> NonIdempotentAtaCreationFilters<'a> for AstQuery<'a> {
+    fn non_idempotent_ata_creations(self) -> AstQuery<'a> {
+        debug!("Filtering associated token account creation without the idempotent instruction");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let block = match node.data {
+                NodeData::Function(func) => func.block.as_ref(),
+                NodeData::ImplFunction(func) => &func.block,
+                _ => continue,
+            };
+
+            let mut finder = AtaCreationFinder::default();
+            finder.visit_block(block);
+
+            for (call, name) in finder.creations {
+                trace!("Found non-idempotent associated token account creation: {name}");
+                new_results.push(AstNode::from_expression(call, &name));
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Visitor that collects calls creating an associated token account
+#[derive(Default)]
+struct AtaCreationFinder<'ast> {
+    creations: Vec<(&'ast Expr, String)>,
+}
+
+impl<'ast> Visit<'ast> for AtaCreationFinder<'ast> {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let Expr::Call(call) = expr
+            && let Expr::Path(path) = &*call.func
+        {
+            let segments: Vec<String> = path.path.segments.iter().map(|s| s.ident.to_string()).collect();
+            let name = segments.join("::");
+            let creates = match segments.as_slice() {
+                [.., module, function] if module == ANCHOR_MODULE => function == ANCHOR_CREATE_FUNCTION,
+                [.., function] => SPL_CREATE_FUNCTIONS.contains(&function.as_str()),
+                [] => false,
+            };
+            if creates {
+                self.creations.push((expr, name));
+            }
+        }
+        visit::visit_expr(self, expr);
+    }
+}
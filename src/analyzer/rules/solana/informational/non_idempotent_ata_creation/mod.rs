@@ -0,0 +1,66 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::NonIdempotentAtaCreationFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("non-idempotent-ata-creation")
+        .title("Associated Token Account Created Without the Idempotent Instruction")
+        .description("Detects associated token account creation through anchor_spl::associated_token::create or spl_associated_token_account's create_associated_token_account. These instructions fail when the account already exists, so anyone can front-run the transaction by creating the ATA first and make the instruction fail")
+        .severity(Severity::Informational)
+        .rule_type(RuleType::Anchor)
+        .category("cpi")
+        .category("initialization")
+        .tag("token")
+        .tag("dos")
+        .reference("https://github.com/solana-program/associated-token-account")
+        .recommendations(vec![
+            "Use associated_token::create_idempotent or create_associated_token_account_idempotent, which succeed when the account exists",
+            "Alternatively, declare the account with init_if_needed and associated_token::mint / associated_token::authority constraints",
+        ])
+        .vulnerable_example(r#"
+pub fn open(ctx: Context<Open>) -> Result<()> {
+    associated_token::create(CpiContext::new(
+        ctx.accounts.associated_token_program.to_account_info(),
+        associated_token::Create {
+            payer: ctx.accounts.payer.to_account_info(),
+            associated_token: ctx.accounts.user_ata.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+    ))
+}
+"#)
+        .fixed_example(r#"
+pub fn open(ctx: Context<Open>) -> Result<()> {
+    associated_token::create_idempotent(CpiContext::new(
+        ctx.accounts.associated_token_program.to_account_info(),
+        associated_token::Create {
+            payer: ctx.accounts.payer.to_account_info(),
+            associated_token: ctx.accounts.user_ata.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+    ))
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing associated token account creation");
+
+            AstQuery::new(ast).functions().non_idempotent_ata_creations()
+        })
+        .build()
+}
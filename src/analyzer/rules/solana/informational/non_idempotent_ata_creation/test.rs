@@ -0,0 +1,88 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::informational::non_idempotent_ata_creation::filters::NonIdempotentAtaCreationFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_names(ast: &File) -> Vec<String> {
+        AstQuery::new(ast)
+            .functions()
+            .non_idempotent_ata_creations()
+            .collect()
+            .iter()
+            .filter_map(|node| node.name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_anchor_create() {
+        let ast: File = parse_quote! {
+            pub fn open(ctx: Context<Open>) -> Result<()> {
+                associated_token::create(CpiContext::new(
+                    ctx.accounts.associated_token_program.to_account_info(),
+                    associated_token::Create {
+                        payer: ctx.accounts.payer.to_account_info(),
+                        associated_token: ctx.accounts.user_ata.to_account_info(),
+                        authority: ctx.accounts.user.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        system_program: ctx.accounts.system_program.to_account_info(),
+                        token_program: ctx.accounts.token_program.to_account_info(),
+                    },
+                ))
+            }
+        };
+
+        assert_eq!(flagged_names(&ast), vec!["associated_token::create"], "Anchor create CPI should be flagged");
+    }
+
+    #[test]
+    fn test_spl_instruction() {
+        let ast: File = parse_quote! {
+            pub fn open(accounts: &[AccountInfo]) -> ProgramResult {
+                let ix = spl_associated_token_account::instruction::create_associated_token_account(
+                    payer.key, wallet.key, mint.key, &spl_token::id(),
+                );
+                invoke(&ix, accounts)?;
+                let ix = create_associated_token_account(payer.key, wallet.key, mint.key, &spl_token::id());
+                invoke(&ix, accounts)
+            }
+        };
+
+        assert_eq!(
+            flagged_names(&ast),
+            vec![
+                "spl_associated_token_account::instruction::create_associated_token_account",
+                "create_associated_token_account",
+            ],
+            "SPL instruction builders should be flagged"
+        );
+    }
+
+    #[test]
+    fn test_idempotent_variants() {
+        let ast: File = parse_quote! {
+            pub fn open(ctx: Context<Open>) -> Result<()> {
+                associated_token::create_idempotent(cpi_ctx)?;
+                let ix = create_associated_token_account_idempotent(payer.key, wallet.key, mint.key, &spl_token::id());
+                invoke(&ix, &accounts)
+            }
+        };
+
+        assert!(flagged_names(&ast).is_empty(), "Idempotent creation should not be flagged");
+    }
+
+    #[test]
+    fn test_unrelated_create_calls() {
+        let ast: File = parse_quote! {
+            pub fn open(ctx: Context<Open>) -> Result<()> {
+                let pool = Pool::create(ctx.accounts.pool.key());
+                system_program::create_account(cpi_ctx, lamports, space, ctx.program_id)?;
+                create(&mut ctx.accounts.registry)
+            }
+        };
+
+        assert!(flagged_names(&ast).is_empty(), "Other create calls should not be flagged");
+    }
+}
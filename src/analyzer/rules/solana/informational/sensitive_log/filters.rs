@@ -0,0 +1,128 @@
+use log::{debug, trace};
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::visit::{self, Visit};
+use syn::Expr;
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Name fragments of values that should never reach the public logs
+const SENSITIVE_NAMES: [&str; 7] = [
+    "secret", "seed", "private", "password", "passphrase", "mnemonic", "keypair",
+];
+
+/// Syscall wrappers that write to the program log
+const LOG_FUNCTIONS: [&str; 2] = ["sol_log", "sol_log_data"];
+
+/// Accessors of raw account data
+const DATA_ACCESSORS: [&str; 3] = ["data", "try_borrow_data", "borrow_data"];
+
+pub trait SensitiveLogFilters<'a> {
+    fn sensitive_logs(self) -> AstQuery<'a>;
+}
+
+impl<'a> SensitiveLogFilters<'a> for AstQuery<'a> {
+    fn sensitive_logs(self) -> AstQuery<'a> {
+        debug!("Filtering log calls that print sensitive values");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let block = match node.data {
+                NodeData::Function(func) => func.block.as_ref(),
+                NodeData::ImplFunction(func) => &func.block,
+                _ => continue,
+            };
+
+            let mut finder = LogFinder::default();
+            finder.visit_block(block);
+
+            for (mac, value) in finder.macros {
+                trace!("Found '{value}' logged with msg!");
+                new_results.push(AstNode::from_macro(mac, &value));
+            }
+            for (call, value) in finder.calls {
+                trace!("Found '{value}' logged with a log syscall");
+                new_results.push(AstNode::from_expression(call, &value));
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Visitor that collects log calls printing a sensitive value, with that value
+#[derive(Default)]
+struct LogFinder<'ast> {
+    macros: Vec<(&'ast syn::Macro, String)>,
+    calls: Vec<(&'ast Expr, String)>,
+}
+
+impl<'ast> Visit<'ast> for LogFinder<'ast> {
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        if mac.path.segments.last().is_some_and(|s| s.ident == "msg")
+            && let Some(value) = sensitive_value(&mac.tokens)
+        {
+            self.macros.push((mac, value));
+        }
+        visit::visit_macro(self, mac);
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let Expr::Call(call) = expr
+            && let Expr::Path(path) = &*call.func
+            && path
+                .path
+                .segments
+                .last()
+                .is_some_and(|s| LOG_FUNCTIONS.contains(&s.ident.to_string().as_str()))
+            && let Some(value) = sensitive_value(&call.args.to_token_stream())
+        {
+            self.calls.push((expr, value));
+        }
+        visit::visit_expr(self, expr);
+    }
+}
+
+/// Returns the first sensitive value logged by a token stream: an identifier
+/// with a sensitive name (also inside `{name}` format arguments) or raw account data
+fn sensitive_value(tokens: &TokenStream) -> Option<String> {
+    let flat = anchor::flatten_tokens(tokens.clone());
+
+    for (index, token) in flat.iter().enumerate() {
+        // Only inline arguments of a format string are values, the rest is text
+        let names = if token.starts_with('"') {
+            inline_arguments(token)
+        } else {
+            vec![token.clone()]
+        };
+
+        for name in names {
+            let lower = name.to_lowercase();
+            if SENSITIVE_NAMES.iter().any(|sensitive| lower.contains(sensitive)) {
+                return Some(name);
+            }
+        }
+
+        if index > 0 && flat[index - 1] == "." && DATA_ACCESSORS.contains(&token.as_str()) {
+            return Some(token.clone());
+        }
+    }
+
+    None
+}
+
+/// Names interpolated inline in a format string literal, e.g. `seed` in `"{seed:?}"`
+fn inline_arguments(literal: &str) -> Vec<String> {
+    literal
+        .split('{')
+        .skip(1)
+        .filter_map(|part| {
+            let name: String = part
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '_')
+                .collect();
+            (!name.is_empty() && !name.chars().all(|c| c.is_ascii_digit())).then_some(name)
+        })
+        .collect()
+}
@@ -0,0 +1,45 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::SensitiveLogFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("sensitive-log")
+        .title("Sensitive Value Logged")
+        .description("Heuristic: detects msg! and sol_log calls that log values named like secrets (secret, seed, private, password, mnemonic, keypair) or raw account data. Program logs are public on-chain, so logged seed material or keys are readable by anyone")
+        .severity(Severity::Informational)
+        .rule_type(RuleType::General)
+        .category("logging")
+        .tag("logging")
+        .tag("heuristic")
+        .reference("CWE-532: Insertion of Sensitive Information into Log File (https://cwe.mitre.org/data/definitions/532.html)")
+        .recommendations(vec![
+            "Remove the value from the log or log a redacted form (e.g. its length or a hash)",
+            "Silence reviewed false positives with // eloizer-ignore: sensitive-log",
+        ])
+        .vulnerable_example(r#"
+let seeds = &[b"vault", user.key.as_ref(), &[bump]];
+msg!("Signing with seeds {:?}", seeds);
+"#)
+        .fixed_example(r#"
+let seeds = &[b"vault", user.key.as_ref(), &[bump]];
+msg!("Signing for vault of {}", user.key);
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing log calls for sensitive values");
+
+            AstQuery::new(ast)
+                .functions()
+                .sensitive_logs()
+        })
+        .build()
+}
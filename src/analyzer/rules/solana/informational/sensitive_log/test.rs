@@ -0,0 +1,58 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::informational::sensitive_log::filters::SensitiveLogFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast)
+            .functions()
+            .sensitive_logs()
+            .collect()
+            .len()
+    }
+
+    #[test]
+    fn test_sensitive_names_logged() {
+        let ast: File = parse_quote! {
+            fn sign(accounts: &[AccountInfo], secret_key: [u8; 32]) -> ProgramResult {
+                let seeds = &[b"vault".as_ref(), &[bump]];
+                msg!("Signing with seeds {:?}", seeds);
+                msg!("Key: {secret_key:?}");
+                sol_log(&format!("{:?}", user_password));
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 3, "Should flag seeds, secret keys and passwords in logs");
+    }
+
+    #[test]
+    fn test_account_data_logged() {
+        let ast: File = parse_quote! {
+            fn dump(accounts: &[AccountInfo]) -> ProgramResult {
+                let vault = &accounts[0];
+                msg!("{:?}", vault.try_borrow_data()?);
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Should flag raw account data in logs");
+    }
+
+    #[test]
+    fn test_harmless_logs() {
+        let ast: File = parse_quote! {
+            fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+                msg!("Seeds verified, depositing {} lamports", amount);
+                msg!("Vault owner: {}", ctx.accounts.vault.owner);
+                sol_log("Private mode disabled");
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Words in the log text should not be flagged");
+    }
+}
@@ -0,0 +1,64 @@
+use log::{debug, trace};
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{BinOp, Expr, Lit};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+pub trait TautologicalConstraintFilters<'a> {
+    fn tautological_constraints(self) -> AstQuery<'a>;
+}
+
+impl<'a> TautologicalConstraintFilters<'a> for AstQuery<'a> {
+    fn tautological_constraints(self) -> AstQuery<'a> {
+        debug!("Filtering account constraints that are always true");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let NodeData::Struct(struct_item) = node.data else {
+                continue;
+            };
+
+            for field in anchor::named_fields(struct_item) {
+                let tautological = anchor::all_constraint_tokens(&field.attrs, "constraint")
+                    .into_iter()
+                    .filter_map(|tokens| {
+                        // The custom error after `@` is not part of the expression
+                        let expr: TokenStream = tokens
+                            .into_iter()
+                            .take_while(|token| !matches!(token, TokenTree::Punct(punct) if punct.as_char() == '@'))
+                            .collect();
+                        syn::parse2::<Expr>(expr).ok()
+                    })
+                    .any(|expr| always_true(&expr));
+
+                if tautological {
+                    trace!("Found constraint that cannot fail on field {:?}", field.ident);
+                    new_results.push(AstNode::from_field(field));
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Whether a constraint expression holds whatever the accounts are
+fn always_true(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(lit) => matches!(&lit.lit, Lit::Bool(value) if value.value),
+        Expr::Paren(paren) => always_true(&paren.expr),
+        Expr::Group(group) => always_true(&group.expr),
+        Expr::Binary(binary) => match binary.op {
+            BinOp::Or(_) => always_true(&binary.left) || always_true(&binary.right),
+            BinOp::And(_) => always_true(&binary.left) && always_true(&binary.right),
+            // A value compared to itself
+            BinOp::Eq(_) | BinOp::Le(_) | BinOp::Ge(_) => {
+                binary.left.to_token_stream().to_string() == binary.right.to_token_stream().to_string()
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
@@ -0,0 +1,56 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::TautologicalConstraintFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("tautological-constraint")
+        .title("Constraint That Cannot Fail")
+        .description("Detects #[account(constraint = ...)] expressions that are always true: `true`, a value compared to itself (`a == a`, `a.key() >= a.key()`), or a disjunction containing such a term. The constraint silently provides no protection, which usually points to a copy-paste mistake in the intended comparison")
+        .severity(Severity::Informational)
+        .rule_type(RuleType::Anchor)
+        .category("accounts")
+        .category("best-practices")
+        .tag("constraints")
+        .tag("copy-paste")
+        .reference("https://www.anchor-lang.com/docs/references/account-constraints")
+        .recommendations(vec![
+            "Review the intended comparison: one side usually should refer to another account or to stored state (vault.owner == owner.key())",
+            "Prefer has_one = field or address = expected when comparing an account to a stored key",
+            "Remove constraints that are not meant to check anything",
+        ])
+        .vulnerable_example(r#"
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut, constraint = vault.owner == vault.owner @ VaultError::Unauthorized)]
+    pub vault: Account<'info, Vault>,
+    pub owner: Signer<'info>,
+}
+"#)
+        .fixed_example(r#"
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut, constraint = vault.owner == owner.key() @ VaultError::Unauthorized)]
+    pub vault: Account<'info, Vault>,
+    pub owner: Signer<'info>,
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing account constraints that cannot fail");
+
+            AstQuery::new(ast)
+                .structs()
+                .derives_accounts()
+                .tautological_constraints()
+        })
+        .build()
+}
@@ -0,0 +1,82 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::informational::tautological_constraint::filters::TautologicalConstraintFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_fields(ast: &File) -> Vec<String> {
+        AstQuery::new(ast)
+            .structs()
+            .derives_accounts()
+            .tautological_constraints()
+            .collect()
+            .into_iter()
+            .filter_map(|node| node.name)
+            .collect()
+    }
+
+    #[test]
+    fn test_self_comparison() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Withdraw<'info> {
+                #[account(mut, constraint = vault.owner == vault.owner @ VaultError::Unauthorized)]
+                pub vault: Account<'info, Vault>,
+                #[account(constraint = (pool.key() >= pool.key()))]
+                pub pool: Account<'info, Pool>,
+                pub owner: Signer<'info>,
+            }
+        };
+
+        assert_eq!(flagged_fields(&ast), vec!["vault", "pool"], "Values compared to themselves should be flagged");
+    }
+
+    #[test]
+    fn test_literal_and_disjunction() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Update<'info> {
+                #[account(constraint = true)]
+                pub config: Account<'info, Config>,
+                #[account(constraint = admin.key() == config.admin || config.admin == config.admin)]
+                pub admin: Signer<'info>,
+            }
+        };
+
+        assert_eq!(flagged_fields(&ast), vec!["config", "admin"], "Always-true terms should be flagged");
+    }
+
+    #[test]
+    fn test_meaningful_constraints() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Withdraw<'info> {
+                #[account(mut, constraint = vault.owner == owner.key() @ VaultError::Unauthorized)]
+                pub vault: Account<'info, Vault>,
+                #[account(constraint = vault.amount >= amount && vault.amount == vault.amount)]
+                pub config: Account<'info, Config>,
+                #[account(constraint = !paused)]
+                pub owner: Signer<'info>,
+            }
+        };
+
+        assert!(flagged_fields(&ast).is_empty(), "Constraints that can fail should not be flagged");
+    }
+
+    #[test]
+    fn test_second_constraint() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Stake<'info> {
+                #[account(mut, constraint = stake.owner == owner.key())]
+                #[account(constraint = stake.mint == stake.mint)]
+                pub stake: Account<'info, Stake>,
+                pub owner: Signer<'info>,
+            }
+        };
+
+        assert_eq!(flagged_fields(&ast), vec!["stake"], "Every constraint of a field should be checked");
+    }
+}
@@ -0,0 +1,353 @@
+use log::{debug, trace};
+use std::collections::{HashMap, HashSet};
+use syn::visit::{self, Visit};
+use syn::{Expr, File, Pat};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Constraints that write to the account on their own
+const MUTATING_CONSTRAINTS: [&str; 4] = ["init", "init_if_needed", "close", "realloc"];
+
+/// Methods that write to the receiver or hand it out mutably
+const MUTATING_METHODS: [&str; 14] = [
+    "borrow_mut",
+    "try_borrow_mut_data",
+    "try_borrow_mut_lamports",
+    "load_mut",
+    "load_init",
+    "set_inner",
+    "exit",
+    "close",
+    "realloc",
+    "resize",
+    "assign",
+    "serialize",
+    "try_serialize",
+    "sub_lamports",
+];
+
+/// Conversions that still refer to the same account
+const PASS_THROUGH_METHODS: [&str; 6] = [
+    "to_account_info",
+    "clone",
+    "as_ref",
+    "as_mut",
+    "into",
+    "to_owned",
+];
+
+pub trait UnnecessaryMutAccountFilters<'a> {
+    fn unnecessary_mut_accounts(self, ast: &'a File) -> AstQuery<'a>;
+}
+
+impl<'a> UnnecessaryMutAccountFilters<'a> for AstQuery<'a> {
+    fn unnecessary_mut_accounts(self, ast: &'a File) -> AstQuery<'a> {
+        debug!("Filtering mutable accounts that are never written");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let NodeData::Struct(struct_item) = node.data else {
+                continue;
+            };
+            let struct_name = struct_item.ident.to_string();
+            let handlers = anchor::handlers_for(ast, &struct_name);
+            // Without a handler the usage of the accounts is unknown
+            if handlers.is_empty() {
+                continue;
+            }
+
+            let fields = anchor::named_fields(struct_item);
+            let mut collector = WriteCollector::default();
+
+            // Accounts written by constraints of other fields (`payer = x`, `close = x`)
+            for field in &fields {
+                for constraint in anchor::account_constraints(&field.attrs) {
+                    if matches!(constraint.name.as_str(), "payer" | "close")
+                        && let Some(value) = constraint.value
+                    {
+                        collector.written.insert(value);
+                    }
+                }
+            }
+
+            for handler in &handlers {
+                collector.aliases.clear();
+                collector.context = handler.sig.inputs.iter().find_map(context_param);
+                collector.visit_block(handler.block);
+            }
+
+            // Helper methods implemented on the accounts struct (`self.vault`)
+            for method in anchor::impl_methods(ast, &struct_name) {
+                collector.aliases.clear();
+                collector.context = None;
+                collector.in_impl = true;
+                collector.visit_block(&method.block);
+                collector.in_impl = false;
+            }
+
+            if collector.escapes {
+                trace!("Accounts of '{struct_name}' are passed to other functions, skipping");
+                continue;
+            }
+
+            for field in fields {
+                let Some(ident) = &field.ident else {
+                    continue;
+                };
+                let name = ident.to_string();
+                let constraints = anchor::account_constraints(&field.attrs);
+                let is_mut = constraints.iter().any(|c| c.name == "mut");
+                let writes_itself = constraints
+                    .iter()
+                    .any(|c| MUTATING_CONSTRAINTS.contains(&c.name.as_str()));
+
+                if is_mut && !writes_itself && !collector.written.contains(&name) {
+                    trace!("Found mutable account '{name}' that is never written");
+                    new_results.push(AstNode::from_field(field));
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Name of the `Context<T>` parameter of a handler
+fn context_param(input: &syn::FnArg) -> Option<String> {
+    let syn::FnArg::Typed(pat_type) = input else {
+        return None;
+    };
+    if anchor::type_name(&pat_type.ty).as_deref() != Some("Context") {
+        return None;
+    }
+    match &*pat_type.pat {
+        Pat::Ident(pat_ident) => Some(pat_ident.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// Visitor that records which accounts are written, borrowed mutably or
+/// handed to other code (CPIs, helpers)
+#[derive(Default)]
+struct WriteCollector {
+    /// Local variables bound to an accounts field
+    aliases: HashMap<String, String>,
+    /// Name of the handler's `Context<T>` parameter
+    context: Option<String>,
+    /// Visiting a method of the accounts struct, where `self.x` is an account
+    in_impl: bool,
+    /// Accounts that may be written
+    written: HashSet<String>,
+    /// The whole context is passed on, so any account may be written
+    escapes: bool,
+}
+
+impl WriteCollector {
+    /// Account referenced as `self.x` inside a method of the accounts struct
+    fn self_field(&self, field: &syn::ExprField) -> Option<String> {
+        match (&*field.base, &field.member) {
+            (Expr::Path(path), syn::Member::Named(member))
+                if self.in_impl && path.path.is_ident("self") =>
+            {
+                Some(member.to_string())
+            }
+            _ => None,
+        }
+    }
+
+    /// Account referenced at the root of an expression chain (`ctx.accounts.x.y`, `alias.y`, `self.x`)
+    fn root_account(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Field(field) => {
+                if let Some(account) = anchor::accounts_field(expr) {
+                    return Some(account);
+                }
+                if let Some(account) = self.self_field(field) {
+                    return Some(account);
+                }
+                self.root_account(&field.base)
+            }
+            Expr::Path(path) => path
+                .path
+                .get_ident()
+                .and_then(|ident| self.aliases.get(&ident.to_string()).cloned()),
+            Expr::MethodCall(call) => self.root_account(&call.receiver),
+            Expr::Unary(unary) => self.root_account(&unary.expr),
+            Expr::Reference(reference) => self.root_account(&reference.expr),
+            Expr::Paren(paren) => self.root_account(&paren.expr),
+            Expr::Index(index) => self.root_account(&index.expr),
+            Expr::Try(expr_try) => self.root_account(&expr_try.expr),
+            _ => None,
+        }
+    }
+
+    /// Mark the account at the root of a written expression
+    fn mark_root(&mut self, expr: &Expr) {
+        if let Some(account) = self.root_account(expr) {
+            self.written.insert(account);
+        }
+    }
+
+    /// Mark the accounts handed over as a whole inside an expression (call
+    /// arguments, CPI account structs), ignoring plain reads such as `x.key()`
+    fn mark_handed(&mut self, expr: &Expr) {
+        let mut refs = HandedAccounts {
+            collector: self,
+            found: Vec::new(),
+        };
+        refs.visit_expr(expr);
+        let found = refs.found;
+        self.written.extend(found);
+    }
+
+    /// Account passed as a whole: `ctx.accounts.x`, an alias, or a clone/conversion of them
+    fn handed_account(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Field(field) => anchor::accounts_field(expr).or_else(|| self.self_field(field)),
+            Expr::Path(path) => path
+                .path
+                .get_ident()
+                .and_then(|ident| self.aliases.get(&ident.to_string()).cloned()),
+            Expr::MethodCall(call) if PASS_THROUGH_METHODS.contains(&call.method.to_string().as_str()) => {
+                self.handed_account(&call.receiver)
+            }
+            Expr::Reference(reference) => self.handed_account(&reference.expr),
+            Expr::Paren(paren) => self.handed_account(&paren.expr),
+            _ => None,
+        }
+    }
+
+    fn is_context(&self, expr: &Expr) -> bool {
+        let expr = match expr {
+            Expr::Reference(reference) => &*reference.expr,
+            expr => expr,
+        };
+        match expr {
+            Expr::Path(path) => self
+                .context
+                .as_ref()
+                .is_some_and(|context| path.path.is_ident(context)),
+            Expr::Field(field) => {
+                matches!(&field.member, syn::Member::Named(m) if m == "accounts")
+                    && self.is_context(&field.base)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Collects the accounts handed over as a whole in an expression
+struct HandedAccounts<'c> {
+    collector: &'c WriteCollector,
+    found: Vec<String>,
+}
+
+impl<'ast> Visit<'ast> for HandedAccounts<'_> {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let Some(account) = self.collector.handed_account(expr) {
+            self.found.push(account);
+            return;
+        }
+        match expr {
+            // Reading a stored field or calling a method on an account does not hand it over
+            Expr::Field(_) => {}
+            Expr::MethodCall(call) => {
+                for arg in &call.args {
+                    self.visit_expr(arg);
+                }
+            }
+            _ => visit::visit_expr(self, expr),
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for WriteCollector {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        if let Pat::Ident(pat_ident) = &local.pat
+            && let Some(init) = &local.init
+            && let Some(account) = self.handed_account(&init.expr)
+        {
+            self.aliases.insert(pat_ident.ident.to_string(), account);
+        }
+        visit::visit_local(self, local);
+    }
+
+    fn visit_expr_assign(&mut self, expr: &'ast syn::ExprAssign) {
+        self.mark_root(&expr.left);
+        visit::visit_expr_assign(self, expr);
+    }
+
+    fn visit_expr_binary(&mut self, expr: &'ast syn::ExprBinary) {
+        use syn::BinOp::*;
+        if matches!(
+            expr.op,
+            AddAssign(_) | SubAssign(_) | MulAssign(_) | DivAssign(_) | RemAssign(_)
+                | BitXorAssign(_) | BitAndAssign(_) | BitOrAssign(_) | ShlAssign(_) | ShrAssign(_)
+        ) {
+            self.mark_root(&expr.left);
+        }
+        visit::visit_expr_binary(self, expr);
+    }
+
+    fn visit_expr_reference(&mut self, expr: &'ast syn::ExprReference) {
+        if expr.mutability.is_some() {
+            self.mark_root(&expr.expr);
+        }
+        visit::visit_expr_reference(self, expr);
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        if MUTATING_METHODS.contains(&call.method.to_string().as_str()) {
+            self.mark_root(&call.receiver);
+        }
+        // `ctx.accounts.helper()` may write any account
+        if self.is_context(&call.receiver) {
+            self.escapes = true;
+        }
+        for arg in &call.args {
+            self.mark_handed(arg);
+            if self.is_context(arg) {
+                self.escapes = true;
+            }
+        }
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        // Accounts passed to a function may be used as writable CPI accounts
+        for arg in &call.args {
+            self.mark_handed(arg);
+            if self.is_context(arg) {
+                self.escapes = true;
+            }
+        }
+        visit::visit_expr_call(self, call);
+    }
+
+    fn visit_expr_struct(&mut self, expr: &'ast syn::ExprStruct) {
+        // CPI account structs (`Transfer { from: ..., to: ... }`)
+        for field in &expr.fields {
+            self.mark_handed(&field.expr);
+        }
+        visit::visit_expr_struct(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let macro_name = mac
+            .path
+            .segments
+            .last()
+            .map(|s| s.ident.to_string())
+            .unwrap_or_default();
+        // Logging and checks only read; anything else may write
+        let read_only = macro_name == "msg"
+            || macro_name.starts_with("require")
+            || macro_name.starts_with("assert")
+            || macro_name == "emit";
+        if !read_only {
+            self.written.extend(anchor::accounts_fields_in_tokens(&mac.tokens));
+        }
+        visit::visit_macro(self, mac);
+    }
+}
@@ -0,0 +1,56 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::UnnecessaryMutAccountFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("unnecessary-mut-account")
+        .title("Unnecessary Mutable Account")
+        .description("Detects accounts declared #[account(mut)] that the instruction handlers never write, borrow mutably or pass to a CPI. Over-broad mutability widens the account model and can hide copy-paste mistakes")
+        .severity(Severity::Informational)
+        .rule_type(RuleType::Anchor)
+        .category("accounts")
+        .category("best-practices")
+        .tag("best-practices")
+        .tag("account-validation")
+        .recommendations(vec![
+            "Remove mut from accounts the instruction only reads",
+            "If the account is written in a helper the analyzer cannot see, keep mut and suppress the finding with // eloizer-ignore",
+        ])
+        .vulnerable_example(r#"
+#[derive(Accounts)]
+pub struct Show<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+}
+
+pub fn show(ctx: Context<Show>) -> Result<()> {
+    msg!("balance: {}", ctx.accounts.vault.balance);
+    Ok(())
+}
+"#)
+        .fixed_example(r#"
+#[derive(Accounts)]
+pub struct Show<'info> {
+    pub vault: Account<'info, Vault>,
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing accounts structs for unnecessary mut");
+
+            AstQuery::new(ast)
+                .structs()
+                .derives_accounts()
+                .unnecessary_mut_accounts(ast)
+        })
+        .build()
+}
@@ -0,0 +1,96 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::informational::unnecessary_mut_account::filters::UnnecessaryMutAccountFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_fields(ast: &File) -> Vec<String> {
+        AstQuery::new(ast)
+            .structs()
+            .derives_accounts()
+            .unnecessary_mut_accounts(ast)
+            .collect()
+            .into_iter()
+            .map(|node| node.name())
+            .collect()
+    }
+
+    #[test]
+    fn test_mut_account_only_read() {
+        let ast: File = parse_quote! {
+            pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+                let vault = &mut ctx.accounts.vault;
+                vault.balance += amount;
+                require_keys_eq!(ctx.accounts.config.admin, ctx.accounts.user.key());
+                msg!("Deposited into {}", ctx.accounts.config.key());
+                Ok(())
+            }
+
+            #[derive(Accounts)]
+            pub struct Deposit<'info> {
+                #[account(mut)]
+                pub vault: Account<'info, Vault>,
+                #[account(mut)]
+                pub config: Account<'info, Config>,
+                pub user: Signer<'info>,
+            }
+        };
+
+        assert_eq!(flagged_fields(&ast), vec!["config"],
+                "Should flag the mutable config that is only read");
+    }
+
+    #[test]
+    fn test_cpi_and_constraint_writes() {
+        let ast: File = parse_quote! {
+            pub fn pay(ctx: Context<Pay>, amount: u64) -> Result<()> {
+                let cpi_accounts = Transfer {
+                    from: ctx.accounts.source.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                };
+                token::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), amount)?;
+                **ctx.accounts.fees.to_account_info().try_borrow_mut_lamports()? += 1;
+                Ok(())
+            }
+
+            #[derive(Accounts)]
+            pub struct Pay<'info> {
+                #[account(mut)]
+                pub source: Account<'info, TokenAccount>,
+                #[account(mut)]
+                pub destination: Account<'info, TokenAccount>,
+                #[account(mut)]
+                pub fees: SystemAccount<'info>,
+                #[account(init, payer = user, space = 8 + 8)]
+                pub receipt: Account<'info, Receipt>,
+                #[account(mut)]
+                pub user: Signer<'info>,
+                pub token_program: Program<'info, Token>,
+            }
+        };
+
+        assert!(flagged_fields(&ast).is_empty(),
+                "CPI accounts, lamport writes and payers should not be flagged");
+    }
+
+    #[test]
+    fn test_context_passed_to_helper() {
+        let ast: File = parse_quote! {
+            pub fn settle(ctx: Context<Settle>) -> Result<()> {
+                helpers::settle(&ctx)
+            }
+
+            #[derive(Accounts)]
+            pub struct Settle<'info> {
+                #[account(mut)]
+                pub vault: Account<'info, Vault>,
+            }
+        };
+
+        assert!(flagged_fields(&ast).is_empty(),
+                "Accounts handed to other functions cannot be judged and should not be flagged");
+    }
+}
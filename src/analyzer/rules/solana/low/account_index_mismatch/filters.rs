@@ -0,0 +1,277 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use std::collections::{BTreeMap, HashMap};
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::visit::{self, Visit};
+use syn::{BinOp, Expr, File, FnArg, Item, Lit, Pat, Type};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+pub trait AccountIndexMismatchFilters<'a> {
+    fn account_index_mismatches(self, ast: &'a File) -> AstQuery<'a>;
+}
+
+impl<'a> AccountIndexMismatchFilters<'a> for AstQuery<'a> {
+    fn account_index_mismatches(self, ast: &'a File) -> AstQuery<'a> {
+        debug!("Filtering accounts[N] indices beyond the checked account count or skipping accounts");
+        let constants = integer_constants(ast);
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let (sig, block) = match node.data {
+                NodeData::Function(func) => (&func.sig, func.block.as_ref()),
+                NodeData::ImplFunction(func) => (&func.sig, &func.block),
+                _ => continue,
+            };
+            let Some(accounts) = account_slice_param(sig) else {
+                continue;
+            };
+
+            let mut finder = IndexFinder {
+                accounts: accounts.clone(),
+                constants: &constants,
+                indices: BTreeMap::new(),
+                min_len: None,
+                recognized: 0,
+            };
+            finder.visit_block(block);
+            if finder.indices.is_empty() {
+                continue;
+            }
+
+            let function_name = sig.ident.to_string();
+            let mut flagged = Vec::new();
+
+            // Indices past the count a length check guarantees
+            if let Some(min_len) = finder.min_len {
+                for (index, expr) in finder.indices.range(min_len..) {
+                    trace!("Found {accounts}[{index}] beyond the checked count {min_len} in function: {function_name}");
+                    flagged.push(*expr);
+                }
+            }
+
+            // A skipped index usually means the indices are off by one. Only
+            // judged when the slice is used through literal indices alone.
+            let uses = anchor::flatten_tokens(block.to_token_stream())
+                .iter()
+                .filter(|token| **token == accounts)
+                .count();
+            if uses == finder.recognized
+                && let Some(gap) = (0..).find(|index| !finder.indices.contains_key(index))
+                && let Some((index, expr)) = finder.indices.range(gap..).next()
+                && !flagged.contains(expr)
+            {
+                trace!("Found {accounts}[{index}] after skipped index {gap} in function: {function_name}");
+                flagged.push(*expr);
+            }
+
+            for expr in flagged {
+                new_results.push(AstNode::from_expression(expr, &function_name));
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Name of the `&[AccountInfo]` parameter of a native handler
+fn account_slice_param(sig: &syn::Signature) -> Option<String> {
+    sig.inputs.iter().find_map(|input| {
+        let FnArg::Typed(pat_type) = input else {
+            return None;
+        };
+        let Type::Reference(reference) = &*pat_type.ty else {
+            return None;
+        };
+        let Type::Slice(slice) = &*reference.elem else {
+            return None;
+        };
+        match &*pat_type.pat {
+            Pat::Ident(pat_ident) if anchor::type_name(&slice.elem).as_deref() == Some("AccountInfo") => {
+                Some(pat_ident.ident.to_string())
+            }
+            _ => None,
+        }
+    })
+}
+
+/// Integer constants of the file (`const ACCOUNT_COUNT: usize = 4;`)
+fn integer_constants(ast: &File) -> HashMap<String, usize> {
+    ast.items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Const(item_const) => Some((item_const.ident.to_string(), integer(&item_const.expr, &HashMap::new())?)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Value of an integer literal or known constant
+fn integer(expr: &Expr, constants: &HashMap<String, usize>) -> Option<usize> {
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Int(int) => int.base10_parse().ok(),
+            _ => None,
+        },
+        Expr::Path(path) => constants.get(&path.path.get_ident()?.to_string()).copied(),
+        Expr::Paren(paren) => integer(&paren.expr, constants),
+        _ => None,
+    }
+}
+
+/// Visitor that records literal indices into the accounts slice and the
+/// minimum length its length checks guarantee
+struct IndexFinder<'ast, 'c> {
+    accounts: String,
+    constants: &'c HashMap<String, usize>,
+    /// First access of each literal index
+    indices: BTreeMap<usize, &'ast Expr>,
+    /// Minimum number of accounts guaranteed by the length checks
+    min_len: Option<usize>,
+    /// Uses of the slice in literal indices and length checks
+    recognized: usize,
+}
+
+impl IndexFinder<'_, '_> {
+    fn is_accounts_len(&self, expr: &Expr) -> bool {
+        matches!(expr, Expr::MethodCall(call)
+            if call.method == "len"
+                && matches!(&*call.receiver, Expr::Path(path) if path.path.is_ident(&self.accounts)))
+    }
+
+    /// Record a condition asserted to hold (`accounts.len() >= 3`), or asserted
+    /// not to hold when `negated` (the condition of an early return)
+    fn record_check(&mut self, expr: &Expr, negated: bool) {
+        let Expr::Binary(binary) = expr else {
+            if let Expr::Paren(paren) = expr {
+                self.record_check(&paren.expr, negated);
+            }
+            return;
+        };
+        let comparison = match binary.op {
+            // Both sides hold when asserting `a && b` or rejecting `a || b`
+            BinOp::And(_) if !negated => None,
+            BinOp::Or(_) if negated => None,
+            BinOp::Lt(_) => Some(Comparison::Lt),
+            BinOp::Le(_) => Some(Comparison::Le),
+            BinOp::Gt(_) => Some(Comparison::Gt),
+            BinOp::Ge(_) => Some(Comparison::Ge),
+            BinOp::Eq(_) => Some(Comparison::Eq),
+            BinOp::Ne(_) => Some(Comparison::Ne),
+            _ => return,
+        };
+        match comparison {
+            Some(comparison) => self.record_comparison(&binary.left, comparison, &binary.right, negated),
+            None => {
+                self.record_check(&binary.left, negated);
+                self.record_check(&binary.right, negated);
+            }
+        }
+    }
+
+    /// Record the minimum length guaranteed by `left comparison right`
+    fn record_comparison(&mut self, left: &Expr, comparison: Comparison, right: &Expr, negated: bool) {
+        // Normalized to `accounts.len() comparison count`
+        let (comparison, count) = if self.is_accounts_len(left) {
+            (comparison, integer(right, self.constants))
+        } else if self.is_accounts_len(right) {
+            (comparison.flipped(), integer(left, self.constants))
+        } else {
+            return;
+        };
+        let Some(count) = count else {
+            return;
+        };
+        self.recognized += 1;
+
+        let comparison = if negated { comparison.negated() } else { comparison };
+        let guaranteed = match comparison {
+            Comparison::Ge | Comparison::Eq => count,
+            Comparison::Gt => count + 1,
+            _ => return,
+        };
+        self.min_len = Some(self.min_len.map_or(guaranteed, |min_len| min_len.max(guaranteed)));
+    }
+}
+
+/// Comparison operator of a length check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+impl Comparison {
+    /// The operator with its operands swapped (`a < b` is `b > a`)
+    fn flipped(self) -> Self {
+        match self {
+            Self::Lt => Self::Gt,
+            Self::Le => Self::Ge,
+            Self::Gt => Self::Lt,
+            Self::Ge => Self::Le,
+            other => other,
+        }
+    }
+
+    /// The operator holding when this one does not
+    fn negated(self) -> Self {
+        match self {
+            Self::Lt => Self::Ge,
+            Self::Le => Self::Gt,
+            Self::Gt => Self::Le,
+            Self::Ge => Self::Lt,
+            Self::Eq => Self::Ne,
+            Self::Ne => Self::Eq,
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for IndexFinder<'ast, '_> {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let Expr::Index(index) = expr
+            && matches!(&*index.expr, Expr::Path(path) if path.path.is_ident(&self.accounts))
+            && let Some(position) = integer(&index.index, self.constants)
+        {
+            self.recognized += 1;
+            self.indices.entry(position).or_insert(expr);
+        }
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_expr_if(&mut self, expr: &'ast syn::ExprIf) {
+        self.record_check(&expr.cond, true);
+        visit::visit_expr_if(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let name = mac.path.segments.last().map(|segment| segment.ident.to_string()).unwrap_or_default();
+        if !(name.starts_with("assert") || name.starts_with("require")) {
+            return;
+        }
+        let Ok(args) = Punctuated::<Expr, syn::Token![,]>::parse_terminated.parse2(mac.tokens.clone()) else {
+            return;
+        };
+        let mut args = args.into_iter();
+        let (Some(first), second) = (args.next(), args.next()) else {
+            return;
+        };
+
+        // assert_eq!(accounts.len(), 3) and require_gte!(accounts.len(), 3) compare their arguments
+        let comparison = match name.rsplit('_').next() {
+            Some("eq") => Some(Comparison::Eq),
+            Some("gte") => Some(Comparison::Ge),
+            Some("gt") => Some(Comparison::Gt),
+            _ => None,
+        };
+        match (comparison, second) {
+            (Some(comparison), Some(second)) => self.record_comparison(&first, comparison, &second, false),
+            _ => self.record_check(&first, false),
+        }
+    }
+}
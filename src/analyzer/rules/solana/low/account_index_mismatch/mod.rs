@@ -0,0 +1,59 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::AccountIndexMismatchFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("account-index-mismatch")
+        .title("Account Index Beyond Checked Count or Skipped")
+        .description("Detects native handlers indexing the accounts slice with constants (`accounts[3]`) past the count their length check guarantees (`if accounts.len() < 3`, `assert_eq!(accounts.len(), 3)`), or skipping an index (`accounts[0]`, `accounts[1]`, `accounts[3]`), which usually means the indices are off by one. An index past the length panics, and a shifted index reads a different account than the client passes")
+        .severity(Severity::Low)
+        .rule_type(RuleType::Solana)
+        .category("accounts")
+        .category("error-handling")
+        .tag("native")
+        .tag("panic")
+        .reference("CWE-129: Improper Validation of Array Index (https://cwe.mitre.org/data/definitions/129.html)")
+        .recommendations(vec![
+            "Read accounts in order with next_account_info(&mut accounts.iter())?, which returns NotEnoughAccountKeys instead of panicking",
+            "Keep the length check and the indices in sync, for example through a shared ACCOUNT_COUNT constant",
+            "Destructure the slice (let [payer, vault, system_program] = accounts else { ... }) so the count is checked by the pattern",
+        ])
+        .vulnerable_example(r#"
+pub fn process_withdraw(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    if accounts.len() < 3 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let authority = &accounts[0];
+    let vault = &accounts[1];
+    let destination = &accounts[3];
+    transfer(vault, destination, authority, amount)
+}
+"#)
+        .fixed_example(r#"
+pub fn process_withdraw(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let authority = next_account_info(accounts_iter)?;
+    let vault = next_account_info(accounts_iter)?;
+    let destination = next_account_info(accounts_iter)?;
+    transfer(vault, destination, authority, amount)
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing native handlers for account indices out of sync with the account count");
+
+            AstQuery::new(ast)
+                .functions()
+                .account_index_mismatches(ast)
+        })
+        .build()
+}
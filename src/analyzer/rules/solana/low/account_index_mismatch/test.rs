@@ -0,0 +1,100 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::low::account_index_mismatch::filters::AccountIndexMismatchFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast)
+            .functions()
+            .account_index_mismatches(ast)
+            .collect()
+            .len()
+    }
+
+    #[test]
+    fn test_index_beyond_length_check() {
+        let ast: File = parse_quote! {
+            pub fn process_withdraw(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+                if accounts.len() < 3 {
+                    return Err(ProgramError::NotEnoughAccountKeys);
+                }
+                let authority = &accounts[0];
+                let vault = &accounts[1];
+                let destination = &accounts[2];
+                let fee_vault = &accounts[3];
+                Ok(())
+            }
+
+            pub fn process_close(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+                assert_eq!(accounts.len(), 2);
+                let target = &accounts[0];
+                let receiver = &accounts[1];
+                let authority = &accounts[2];
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 2, "Indices past the checked count should be flagged");
+    }
+
+    #[test]
+    fn test_indices_within_checked_count() {
+        let ast: File = parse_quote! {
+            const ACCOUNT_COUNT: usize = 4;
+
+            pub fn process_deposit(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+                if accounts.len() != ACCOUNT_COUNT {
+                    return Err(ProgramError::NotEnoughAccountKeys);
+                }
+                let user = &accounts[0];
+                let vault = &accounts[1];
+                let mint = &accounts[2];
+                let token_program = &accounts[3];
+                Ok(())
+            }
+
+            pub fn process_init(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+                assert!(accounts.len() > 1);
+                let payer = &accounts[0];
+                let state = &accounts[1];
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Indices within the checked count should not be flagged");
+    }
+
+    #[test]
+    fn test_skipped_index() {
+        let ast: File = parse_quote! {
+            pub fn process_swap(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+                let user = &accounts[0];
+                let pool = &accounts[1];
+                let user_tokens = &accounts[3];
+                let pool_tokens = &accounts[4];
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "The index after a skipped one should be flagged");
+    }
+
+    #[test]
+    fn test_skipped_index_with_other_uses() {
+        let ast: File = parse_quote! {
+            pub fn process_batch(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+                let authority = &accounts[0];
+                let config = &accounts[2];
+                for recipient in accounts.iter().skip(3) {
+                    pay(recipient)?;
+                }
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Slices also used otherwise should not be judged by their literal indices");
+    }
+}
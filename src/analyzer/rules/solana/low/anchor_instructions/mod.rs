@@ -12,6 +12,7 @@ pub fn create_rule() -> Arc<dyn Rule> {
     RuleBuilder::new()
         .id("anchor-instructions")
         .severity(Severity::Low)
+        .category("best-practices")
         .title("Anchor Instructions Detection")
         .description("Detects functions that are Anchor program instructions (public functions with Context parameter)")
         .recommendations(vec![
@@ -0,0 +1,118 @@
+use log::{debug, trace};
+use syn::visit::{self, Visit};
+use syn::{Expr, Pat, Stmt};
+
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Functions performing a CPI and returning its Result
+const FALLIBLE_FUNCTIONS: [&str; 15] = [
+    "invoke",
+    "invoke_signed",
+    "invoke_unchecked",
+    "invoke_signed_unchecked",
+    "transfer",
+    "transfer_checked",
+    "mint_to",
+    "burn",
+    "approve",
+    "revoke",
+    "close_account",
+    "set_authority",
+    "sync_native",
+    "create_account",
+    "try_from_slice",
+];
+
+/// Account methods and associated functions returning a Result (deserialization,
+/// data borrows, persistence)
+const FALLIBLE_METHODS: [&str; 11] = [
+    "try_deserialize",
+    "try_deserialize_unchecked",
+    "try_serialize",
+    "try_borrow_data",
+    "try_borrow_mut_data",
+    "try_borrow_mut_lamports",
+    "reload",
+    "exit",
+    "load",
+    "load_mut",
+    "load_init",
+];
+
+/// Methods turning a Result into a value that can be dropped silently
+const DISCARDING_METHODS: [&str; 1] = ["ok"];
+
+pub trait IgnoredResultFilters<'a> {
+    fn ignored_results(self) -> AstQuery<'a>;
+}
+
+impl<'a> IgnoredResultFilters<'a> for AstQuery<'a> {
+    fn ignored_results(self) -> AstQuery<'a> {
+        debug!("Filtering discarded results of fallible account operations");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let block = match node.data {
+                NodeData::Function(func) => func.block.as_ref(),
+                NodeData::ImplFunction(func) => &func.block,
+                _ => continue,
+            };
+
+            let mut finder = DiscardFinder::default();
+            finder.visit_block(block);
+
+            for (expr, callee) in finder.discarded {
+                trace!("Found discarded result of {callee}");
+                new_results.push(AstNode::from_expression(expr, &callee));
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Name of the fallible operation an expression calls, looking through `.ok()`
+fn fallible_callee(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Call(call) => {
+            let Expr::Path(path) = &*call.func else {
+                return None;
+            };
+            let name = path.path.segments.last()?.ident.to_string();
+            (FALLIBLE_FUNCTIONS.contains(&name.as_str()) || FALLIBLE_METHODS.contains(&name.as_str()))
+                .then_some(name)
+        }
+        Expr::MethodCall(call) => {
+            let name = call.method.to_string();
+            if DISCARDING_METHODS.contains(&name.as_str()) {
+                fallible_callee(&call.receiver)
+            } else {
+                FALLIBLE_METHODS.contains(&name.as_str()).then_some(name)
+            }
+        }
+        Expr::Paren(paren) => fallible_callee(&paren.expr),
+        _ => None,
+    }
+}
+
+/// Visitor that collects fallible calls bound to `_` or used as statements
+#[derive(Default)]
+struct DiscardFinder<'ast> {
+    discarded: Vec<(&'ast Expr, String)>,
+}
+
+impl<'ast> Visit<'ast> for DiscardFinder<'ast> {
+    fn visit_stmt(&mut self, stmt: &'ast Stmt) {
+        let discarded = match stmt {
+            Stmt::Local(local) if matches!(local.pat, Pat::Wild(_)) => local.init.as_ref().map(|init| &*init.expr),
+            Stmt::Expr(expr, Some(_)) => Some(expr),
+            _ => None,
+        };
+        if let Some(expr) = discarded
+            && let Some(callee) = fallible_callee(expr)
+        {
+            self.discarded.push((expr, callee));
+        }
+        visit::visit_stmt(self, stmt);
+    }
+}
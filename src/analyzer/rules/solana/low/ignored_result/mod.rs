@@ -0,0 +1,50 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::IgnoredResultFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("ignored-result")
+        .title("Ignored Result of a Fallible Account Operation")
+        .description("Detects CPIs (invoke, invoke_signed, token and system program helpers) and account operations (deserialization, data borrows, reload, exit) whose Result is discarded with `let _ =`, `.ok();` or a bare statement instead of being propagated. The swallowed error lets the instruction succeed after a failed step, leaving state half-updated")
+        .severity(Severity::Low)
+        .rule_type(RuleType::General)
+        .category("error-handling")
+        .category("cpi")
+        .tag("error-handling")
+        .tag("security")
+        .reference("CWE-252: Unchecked Return Value (https://cwe.mitre.org/data/definitions/252.html)")
+        .recommendations(vec![
+            "Propagate the error with the ? operator",
+            "If the failure is expected, match on the Result and handle each case explicitly",
+        ])
+        .vulnerable_example(r#"
+pub fn settle(ctx: Context<Settle>, amount: u64) -> Result<()> {
+    let _ = token::transfer(ctx.accounts.transfer_ctx(), amount);
+    ctx.accounts.escrow.settled = true;
+    Ok(())
+}
+"#)
+        .fixed_example(r#"
+pub fn settle(ctx: Context<Settle>, amount: u64) -> Result<()> {
+    token::transfer(ctx.accounts.transfer_ctx(), amount)?;
+    ctx.accounts.escrow.settled = true;
+    Ok(())
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing discarded results of fallible account operations");
+
+            AstQuery::new(ast).functions().ignored_results()
+        })
+        .build()
+}
@@ -0,0 +1,88 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::low::ignored_result::filters::IgnoredResultFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_names(ast: &File) -> Vec<String> {
+        AstQuery::new(ast)
+            .functions()
+            .ignored_results()
+            .collect()
+            .iter()
+            .filter_map(|node| node.name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_discarded_cpi_results() {
+        let ast: File = parse_quote! {
+            pub fn settle(ctx: Context<Settle>, amount: u64) -> Result<()> {
+                let _ = token::transfer(ctx.accounts.transfer_ctx(), amount);
+                invoke_signed(&ix, &accounts, &[seeds]).ok();
+                invoke(&ix, &accounts);
+                ctx.accounts.escrow.settled = true;
+                Ok(())
+            }
+        };
+
+        assert_eq!(
+            flagged_names(&ast),
+            vec!["transfer", "invoke_signed", "invoke"],
+            "CPI results discarded in any form should be flagged"
+        );
+    }
+
+    #[test]
+    fn test_discarded_account_operations() {
+        let ast: File = parse_quote! {
+            impl<'info> Settle<'info> {
+                fn refresh(&mut self) -> Result<()> {
+                    let _ = self.escrow.reload();
+                    let _ = Escrow::try_deserialize(&mut &data[..]);
+                    Ok(())
+                }
+            }
+        };
+
+        assert_eq!(
+            flagged_names(&ast),
+            vec!["reload", "try_deserialize"],
+            "Discarded account operations should be flagged"
+        );
+    }
+
+    #[test]
+    fn test_propagated_results() {
+        let ast: File = parse_quote! {
+            pub fn settle(ctx: Context<Settle>, amount: u64) -> Result<()> {
+                token::transfer(ctx.accounts.transfer_ctx(), amount)?;
+                invoke(&ix, &accounts)?;
+                let escrow = Escrow::try_deserialize(&mut &data[..])?;
+                if let Err(e) = ctx.accounts.escrow.reload() {
+                    msg!("reload failed: {:?}", e);
+                    return Err(e);
+                }
+                invoke_signed(&ix, &accounts, &[seeds])
+            }
+        };
+
+        assert!(flagged_names(&ast).is_empty(), "Propagated or handled results should not be flagged");
+    }
+
+    #[test]
+    fn test_unrelated_discards() {
+        let ast: File = parse_quote! {
+            pub fn log_state(ctx: Context<Log>) -> Result<()> {
+                let _ = ctx.accounts.state.key();
+                let _guard = ctx.accounts.state.try_borrow_data()?;
+                values.push(1);
+                Ok(())
+            }
+        };
+
+        assert!(flagged_names(&ast).is_empty(), "Infallible calls bound to _ should not be flagged");
+    }
+}
@@ -0,0 +1,240 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use std::collections::HashMap;
+use syn::{Attribute, Expr, Item, ItemStruct, Lit, Type};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Bytes Anchor prepends to every account for its discriminator
+const DISCRIMINATOR_LEN: u64 = 8;
+
+/// Account wrappers whose last type argument is the stored struct
+const ACCOUNT_WRAPPERS: [&str; 3] = ["Account", "AccountLoader", "InterfaceAccount"];
+
+/// Nesting depth after which struct sizes are no longer expanded
+const MAX_DEPTH: usize = 8;
+
+pub trait InitSpaceMismatchFilters<'a> {
+    fn init_space_mismatches(self) -> AstQuery<'a>;
+}
+
+impl<'a> InitSpaceMismatchFilters<'a> for AstQuery<'a> {
+    fn init_space_mismatches(self) -> AstQuery<'a> {
+        debug!("Filtering init accounts whose space is smaller than their data");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let NodeData::File(file) = node.data else {
+                continue;
+            };
+
+            let mut structs = HashMap::new();
+            let mut enums = Vec::new();
+            let mut consts = HashMap::new();
+            for item in &file.items {
+                match item {
+                    Item::Struct(item_struct) => {
+                        structs.insert(item_struct.ident.to_string(), item_struct);
+                    }
+                    Item::Enum(item_enum) => enums.push(item_enum.ident.to_string()),
+                    Item::Const(item_const) => {
+                        consts.insert(item_const.ident.to_string(), &*item_const.expr);
+                    }
+                    _ => {}
+                }
+            }
+            let estimator = SizeEstimator { structs: &structs, enums: &enums };
+
+            let accounts_structs = file.items.iter().filter_map(|item| match item {
+                Item::Struct(item_struct) if anchor::is_accounts_struct(item_struct) => Some(item_struct),
+                _ => None,
+            });
+            for item_struct in accounts_structs {
+                for field in anchor::named_fields(item_struct) {
+                    let attrs = &field.attrs;
+                    if !anchor::has_constraint(attrs, "init") && !anchor::has_constraint(attrs, "init_if_needed") {
+                        continue;
+                    }
+                    let Some(space) = anchor::constraint_tokens(attrs, "space")
+                        .and_then(|tokens| syn::parse2::<Expr>(tokens).ok())
+                    else {
+                        continue;
+                    };
+
+                    let mismatch = match evaluate(&space, &consts, false, 0) {
+                        // Literal arithmetic: compare with the estimated size of the stored struct
+                        Some(declared) => stored_struct(&field.ty)
+                            .and_then(|name| structs.get(&name))
+                            .map(|stored| DISCRIMINATOR_LEN + estimator.struct_size(stored, 0))
+                            .filter(|&required| declared < required),
+                        // INIT_SPACE or size_of::<T>() never include the discriminator
+                        None => evaluate(&space, &consts, true, 0)
+                            .filter(|&constant| constant < DISCRIMINATOR_LEN && !names_discriminator(&space))
+                            .map(|_| DISCRIMINATOR_LEN),
+                    };
+
+                    if let Some(required) = mismatch {
+                        trace!(
+                            "Found init account {:?} with space {} below {required} bytes",
+                            field.ident,
+                            space.to_token_stream()
+                        );
+                        new_results.push(AstNode::from_field(field));
+                    }
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Name of the struct stored in an `Account<'info, T>` field, looking through `Box`
+fn stored_struct(ty: &Type) -> Option<String> {
+    let name = anchor::type_name(ty)?;
+    if name == "Box" {
+        return stored_struct(anchor::type_arguments(ty).first()?);
+    }
+    if !ACCOUNT_WRAPPERS.contains(&name.as_str()) {
+        return None;
+    }
+    anchor::type_name(anchor::type_arguments(ty).last()?)
+}
+
+/// Whether a space expression refers to a discriminator constant (e.g. `DISCRIMINATOR.len()`)
+fn names_discriminator(space: &Expr) -> bool {
+    anchor::flatten_tokens(space.to_token_stream())
+        .iter()
+        .any(|token| token.to_ascii_uppercase().contains("DISCRIMINATOR"))
+}
+
+/// Whether an expression is a size that excludes the discriminator
+/// (`T::INIT_SPACE`, `size_of::<T>()`)
+fn is_struct_size(expr: &Expr) -> bool {
+    match expr {
+        Expr::Path(path) => path.path.segments.last().is_some_and(|s| s.ident == "INIT_SPACE"),
+        Expr::Call(call) => match &*call.func {
+            Expr::Path(path) => path.path.segments.last().is_some_and(|s| s.ident == "size_of"),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Evaluates integer arithmetic over literals and constants of the file. With
+/// `constant_part`, struct sizes count as zero so that only the bytes added on
+/// top of them remain.
+fn evaluate(expr: &Expr, consts: &HashMap<String, &Expr>, constant_part: bool, depth: usize) -> Option<u64> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Int(int) => int.base10_parse().ok(),
+            _ => None,
+        },
+        Expr::Paren(paren) => evaluate(&paren.expr, consts, constant_part, depth),
+        Expr::Group(group) => evaluate(&group.expr, consts, constant_part, depth),
+        Expr::Cast(cast) => evaluate(&cast.expr, consts, constant_part, depth),
+        Expr::Binary(binary) => {
+            let left = evaluate(&binary.left, consts, constant_part, depth)?;
+            let right = evaluate(&binary.right, consts, constant_part, depth)?;
+            match binary.op {
+                syn::BinOp::Add(_) => left.checked_add(right),
+                syn::BinOp::Sub(_) => left.checked_sub(right),
+                syn::BinOp::Mul(_) => left.checked_mul(right),
+                syn::BinOp::Div(_) => left.checked_div(right),
+                _ => None,
+            }
+        }
+        _ if is_struct_size(expr) => constant_part.then_some(0),
+        Expr::Path(path) => {
+            let name = path.path.get_ident()?.to_string();
+            evaluate(consts.get(&name)?, consts, constant_part, depth + 1)
+        }
+        _ => None,
+    }
+}
+
+/// Best-effort Borsh size of the structs of a file. Types it cannot size count
+/// as zero, so the result is a lower bound of the real size.
+struct SizeEstimator<'s, 'a> {
+    structs: &'s HashMap<String, &'a ItemStruct>,
+    enums: &'s [String],
+}
+
+impl SizeEstimator<'_, '_> {
+    fn struct_size(&self, item_struct: &ItemStruct, depth: usize) -> u64 {
+        if depth > MAX_DEPTH {
+            return 0;
+        }
+        item_struct
+            .fields
+            .iter()
+            .map(|field| self.type_size(&field.ty, &max_len(&field.attrs), depth))
+            .sum()
+    }
+
+    fn type_size(&self, ty: &Type, max_len: &[u64], depth: usize) -> u64 {
+        match ty {
+            Type::Array(array) => {
+                let len = match &array.len {
+                    Expr::Lit(lit) => match &lit.lit {
+                        Lit::Int(int) => int.base10_parse().unwrap_or(0),
+                        _ => 0,
+                    },
+                    _ => 0,
+                };
+                len * self.type_size(&array.elem, max_len, depth)
+            }
+            Type::Tuple(tuple) => tuple.elems.iter().map(|elem| self.type_size(elem, max_len, depth)).sum(),
+            Type::Path(_) => {
+                let name = anchor::type_name(ty).unwrap_or_default();
+                let inner = || anchor::type_arguments(ty).first().copied();
+                match name.as_str() {
+                    "bool" | "u8" | "i8" => 1,
+                    "u16" | "i16" => 2,
+                    "u32" | "i32" | "f32" => 4,
+                    "u64" | "i64" | "f64" => 8,
+                    "u128" | "i128" => 16,
+                    "Pubkey" => 32,
+                    // Length prefix, plus the bytes reserved with #[max_len(n)]
+                    "String" => 4 + max_len.first().copied().unwrap_or(0),
+                    "Vec" => {
+                        let elem = inner().map_or(0, |elem| self.type_size(elem, max_len.get(1..).unwrap_or(&[]), depth));
+                        4 + max_len.first().copied().unwrap_or(0) * elem
+                    }
+                    "Option" => 1 + inner().map_or(0, |inner| self.type_size(inner, max_len, depth)),
+                    "Box" => inner().map_or(0, |inner| self.type_size(inner, max_len, depth)),
+                    _ if self.enums.contains(&name) => 1,
+                    _ => self
+                        .structs
+                        .get(&name)
+                        .map_or(0, |nested| self.struct_size(nested, depth + 1)),
+                }
+            }
+            _ => 0,
+        }
+    }
+}
+
+/// Lengths declared with `#[max_len(...)]` for `InitSpace`
+fn max_len(attrs: &[Attribute]) -> Vec<u64> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("max_len"))
+        .filter_map(|attr| {
+            attr.parse_args_with(syn::punctuated::Punctuated::<Lit, syn::Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flat_map(|lits| {
+            lits.into_iter()
+                .map(|lit| match lit {
+                    Lit::Int(int) => int.base10_parse().unwrap_or(0),
+                    _ => 0,
+                })
+                .collect::<Vec<u64>>()
+        })
+        .collect()
+}
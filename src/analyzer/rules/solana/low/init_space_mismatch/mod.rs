@@ -0,0 +1,69 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::InitSpaceMismatchFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("init-space-mismatch")
+        .title("Init Account Space Too Small")
+        .description("Heuristic: detects #[account(init, space = ...)] whose space leaves out the 8-byte discriminator (T::INIT_SPACE or size_of::<T>() alone) or is smaller than a best-effort size estimate of the account struct's fields. Serialization then fails or the data is truncated. The estimate is a lower bound computed from the struct declared in the same file, so a finding may still need manual confirmation")
+        .severity(Severity::Low)
+        .rule_type(RuleType::Anchor)
+        .category("accounts")
+        .category("initialization")
+        .tag("initialization")
+        .tag("anchor")
+        .recommendations(vec![
+            "Derive InitSpace and declare space = 8 + T::INIT_SPACE, with #[max_len(...)] on String and Vec fields",
+            "Or declare space = 8 + std::mem::size_of::<T>() for fixed-size structs",
+            "Always reserve the 8-byte discriminator in addition to the struct size",
+        ])
+        .vulnerable_example(r#"
+#[account]
+pub struct Vault {
+    pub authority: Pubkey,
+    pub balance: u64,
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(init, payer = payer, space = 8)]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+"#)
+        .fixed_example(r#"
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub authority: Pubkey,
+    pub balance: u64,
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(init, payer = payer, space = 8 + Vault::INIT_SPACE)]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing space of init accounts");
+
+            AstQuery::new(ast).init_space_mismatches()
+        })
+        .build()
+}
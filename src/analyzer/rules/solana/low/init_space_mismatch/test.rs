@@ -0,0 +1,105 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::low::init_space_mismatch::filters::InitSpaceMismatchFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast).init_space_mismatches().collect().len()
+    }
+
+    #[test]
+    fn test_space_smaller_than_struct() {
+        let ast: File = parse_quote! {
+            #[account]
+            pub struct Vault {
+                pub authority: Pubkey,
+                pub balance: u64,
+            }
+
+            #[derive(Accounts)]
+            pub struct Initialize<'info> {
+                #[account(init, payer = payer, space = 8)]
+                pub vault: Account<'info, Vault>,
+                #[account(init, payer = payer, space = 32 + 8)]
+                pub backup: Account<'info, Vault>,
+                #[account(mut)]
+                pub payer: Signer<'info>,
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 2, "Should flag space below discriminator + fields");
+    }
+
+    #[test]
+    fn test_space_covers_struct() {
+        let ast: File = parse_quote! {
+            const MAX_MEMBERS: usize = 10;
+
+            #[account]
+            pub struct Group {
+                pub admin: Pubkey,
+                pub name: String,
+                pub members: Vec<Pubkey>,
+                pub closed: Option<i64>,
+            }
+
+            #[derive(Accounts)]
+            pub struct CreateGroup<'info> {
+                #[account(init, payer = admin, space = 8 + 32 + (4 + 32) + (4 + MAX_MEMBERS * 32) + 9)]
+                pub group: Box<Account<'info, Group>>,
+                #[account(mut)]
+                pub admin: Signer<'info>,
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Space covering the struct should not be flagged");
+    }
+
+    #[test]
+    fn test_init_space_without_discriminator() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Initialize<'info> {
+                #[account(init, payer = payer, space = Vault::INIT_SPACE)]
+                pub vault: Account<'info, Vault>,
+                #[account(init, payer = payer, space = std::mem::size_of::<Config>())]
+                pub config: Account<'info, Config>,
+                #[account(init, payer = payer, space = 8 + Vault::INIT_SPACE)]
+                pub other: Account<'info, Vault>,
+                #[account(init, payer = payer, space = Vault::DISCRIMINATOR.len() + Vault::INIT_SPACE)]
+                pub another: Account<'info, Vault>,
+                #[account(mut)]
+                pub payer: Signer<'info>,
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 2, "Should flag struct sizes without the discriminator");
+    }
+
+    #[test]
+    fn test_max_len_fields() {
+        let ast: File = parse_quote! {
+            #[account]
+            #[derive(InitSpace)]
+            pub struct Profile {
+                #[max_len(5)]
+                pub friends: Vec<Pubkey>,
+            }
+
+            #[derive(Accounts)]
+            pub struct CreateProfile<'info> {
+                #[account(init, payer = user, space = 8 + 4 + 5 * 32)]
+                pub profile: Account<'info, Profile>,
+                #[account(init, payer = user, space = 8 + 4 + 32)]
+                pub small: Account<'info, Profile>,
+                #[account(mut)]
+                pub user: Signer<'info>,
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Only the space without room for max_len entries is too small");
+    }
+}
@@ -0,0 +1,126 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use std::collections::HashMap;
+use syn::{Attribute, Expr, File, Item, Lit};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Allocation size from which an account counts as large: the most a program
+/// can allocate in one CPI, about 0.07 SOL of rent
+const LARGE_SPACE: u64 = 10_240;
+
+/// Nesting depth after which constants are no longer expanded
+const MAX_DEPTH: usize = 8;
+
+/// Words of a doc comment showing the funding requirement is documented
+const FUNDING_WORDS: [&str; 4] = ["rent", "lamports", "sol", "fund"];
+
+pub trait LargeInitSpaceFilters<'a> {
+    fn large_init_spaces(self, ast: &'a File) -> AstQuery<'a>;
+}
+
+impl<'a> LargeInitSpaceFilters<'a> for AstQuery<'a> {
+    fn large_init_spaces(self, ast: &'a File) -> AstQuery<'a> {
+        debug!("Filtering large init allocations without a funding check");
+        let mut new_results = Vec::new();
+
+        let consts: HashMap<String, &Expr> = ast
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Const(item_const) => Some((item_const.ident.to_string(), &*item_const.expr)),
+                _ => None,
+            })
+            .collect();
+
+        for node in self.results() {
+            let NodeData::Struct(struct_item) = node.data else {
+                continue;
+            };
+            let fields = anchor::named_fields(struct_item);
+            let handler_tokens: Vec<String> = anchor::handlers_for(ast, &struct_item.ident.to_string())
+                .iter()
+                .flat_map(|handler| anchor::flatten_tokens(handler.block.to_token_stream()))
+                .collect();
+
+            for field in &fields {
+                let attrs = &field.attrs;
+                if !anchor::has_constraint(attrs, "init") && !anchor::has_constraint(attrs, "init_if_needed") {
+                    continue;
+                }
+                let Some(space) = anchor::constraint_tokens(attrs, "space")
+                    .and_then(|tokens| syn::parse2::<Expr>(tokens).ok())
+                    .and_then(|space| lower_bound(&space, &consts, 0))
+                else {
+                    continue;
+                };
+                if space < LARGE_SPACE || documents_funding(attrs) {
+                    continue;
+                }
+
+                // The payer's balance checked in its constraints or in the handler
+                let payer = anchor::constraint_value(attrs, "payer");
+                let payer_checked = payer.as_deref().is_some_and(|payer| {
+                    fields.iter().any(|other| {
+                        other.ident.as_ref().is_some_and(|ident| ident == payer)
+                            && anchor::account_constraints(&other.attrs).iter().any(|c| {
+                                c.value.as_deref().is_some_and(|value| value.contains("lamports"))
+                            })
+                    }) || handler_tokens
+                        .windows(3)
+                        .any(|window| window[0] == payer && window[1] == "." && window[2] == "lamports")
+                });
+                let rent_checked = handler_tokens.iter().any(|token| token == "minimum_balance");
+
+                if !payer_checked && !rent_checked {
+                    trace!("Found init account {:?} allocating {space} bytes without a funding check", field.ident);
+                    new_results.push(AstNode::from_field(field));
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Whether a doc comment of the field mentions rent or funding
+fn documents_funding(attrs: &[Attribute]) -> bool {
+    attrs.iter().filter(|attr| attr.path().is_ident("doc")).any(|attr| {
+        let doc = attr.meta.to_token_stream().to_string().to_lowercase();
+        doc.split(|c: char| !c.is_alphanumeric())
+            .any(|word| FUNDING_WORDS.contains(&word))
+    })
+}
+
+/// Smallest size a space expression can have: integer arithmetic over literals
+/// and constants of the file, counting terms it cannot evaluate
+/// (`T::INIT_SPACE`, `size_of::<T>()`) as zero
+fn lower_bound(expr: &Expr, consts: &HashMap<String, &Expr>, depth: usize) -> Option<u64> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Int(int) => int.base10_parse().ok(),
+            _ => None,
+        },
+        Expr::Paren(paren) => lower_bound(&paren.expr, consts, depth),
+        Expr::Group(group) => lower_bound(&group.expr, consts, depth),
+        Expr::Cast(cast) => lower_bound(&cast.expr, consts, depth),
+        Expr::Binary(binary) => {
+            let left = lower_bound(&binary.left, consts, depth).unwrap_or(0);
+            let right = lower_bound(&binary.right, consts, depth).unwrap_or(0);
+            match binary.op {
+                syn::BinOp::Add(_) => left.checked_add(right),
+                syn::BinOp::Mul(_) => left.checked_mul(right),
+                _ => None,
+            }
+        }
+        Expr::Path(path) => {
+            let name = path.path.get_ident()?.to_string();
+            lower_bound(consts.get(&name)?, consts, depth + 1)
+        }
+        _ => None,
+    }
+}
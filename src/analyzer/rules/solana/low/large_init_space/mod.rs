@@ -0,0 +1,65 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::LargeInitSpaceFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("large-init-space")
+        .title("Large Account Allocation Without Funding Check")
+        .description("Detects init constraints allocating 10 KiB or more (computed from literals and constants of the file) while neither the payer's balance nor the rent-exempt minimum is checked and the field does not document the funding requirement. Rent for large accounts is significant, and underfunded payers get an opaque system program error; from 10240 bytes on, init cannot create the account through a CPI at all")
+        .severity(Severity::Low)
+        .rule_type(RuleType::Anchor)
+        .category("initialization")
+        .category("best-practices")
+        .tag("robustness")
+        .tag("rent")
+        .reference("https://solana.com/docs/core/fees#rent")
+        .recommendations(vec![
+            "Check the payer can fund the account first: require!(payer.lamports() >= Rent::get()?.minimum_balance(SPACE), ErrorCode::InsufficientFunds)",
+            "Document the rent the instruction costs on the account field",
+            "For accounts above 10 KiB, allocate with #[account(zero)] in a separate transaction or grow them with realloc",
+        ])
+        .vulnerable_example(r#"
+const MAX_ORDERS: usize = 1024;
+
+#[derive(Accounts)]
+pub struct CreateBook<'info> {
+    #[account(init, payer = payer, space = 8 + MAX_ORDERS * 48)]
+    pub book: Account<'info, OrderBook>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+"#)
+        .fixed_example(r#"
+const MAX_ORDERS: usize = 128;
+
+#[derive(Accounts)]
+pub struct CreateBook<'info> {
+    /// Costs about 0.04 SOL of rent, paid by `payer`
+    #[account(init, payer = payer, space = 8 + MAX_ORDERS * 48)]
+    pub book: Account<'info, OrderBook>,
+    #[account(mut, constraint = payer.lamports() >= BOOK_RENT @ ErrorCode::InsufficientFunds)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing large init allocations for funding checks");
+
+            AstQuery::new(ast)
+                .structs()
+                .derives_accounts()
+                .large_init_spaces(ast)
+        })
+        .build()
+}
@@ -0,0 +1,113 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::low::large_init_space::filters::LargeInitSpaceFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast)
+            .structs()
+            .derives_accounts()
+            .large_init_spaces(ast)
+            .collect()
+            .len()
+    }
+
+    #[test]
+    fn test_large_space_from_constants() {
+        let ast: File = parse_quote! {
+            const MAX_ORDERS: usize = 1024;
+            const ORDER_SIZE: usize = 48;
+
+            #[derive(Accounts)]
+            pub struct CreateBook<'info> {
+                #[account(init, payer = payer, space = 8 + MAX_ORDERS * ORDER_SIZE)]
+                pub book: Account<'info, OrderBook>,
+                #[account(init_if_needed, payer = payer, space = 8 + 10_240)]
+                pub history: Account<'info, History>,
+                #[account(mut)]
+                pub payer: Signer<'info>,
+            }
+
+            pub fn create_book(ctx: Context<CreateBook>) -> Result<()> {
+                ctx.accounts.book.authority = ctx.accounts.payer.key();
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 2, "Large allocations without a funding check should be flagged");
+    }
+
+    #[test]
+    fn test_small_and_unknown_space() {
+        let ast: File = parse_quote! {
+            const MAX_ORDERS: usize = 16;
+
+            #[derive(Accounts)]
+            pub struct CreateBook<'info> {
+                #[account(init, payer = payer, space = 8 + MAX_ORDERS * 48)]
+                pub book: Account<'info, OrderBook>,
+                #[account(init, payer = payer, space = 8 + Config::INIT_SPACE)]
+                pub config: Account<'info, Config>,
+                #[account(init, payer = payer, space = 8 + max_len as usize * 32)]
+                pub list: Account<'info, List>,
+                #[account(mut)]
+                pub payer: Signer<'info>,
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Small or non-constant allocations should not be flagged");
+    }
+
+    #[test]
+    fn test_funding_checked() {
+        let ast: File = parse_quote! {
+            const BOOK_SPACE: usize = 8 + 1024 * 48;
+
+            #[derive(Accounts)]
+            pub struct CreateBook<'info> {
+                #[account(init, payer = payer, space = BOOK_SPACE)]
+                pub book: Account<'info, OrderBook>,
+                #[account(mut, constraint = payer.lamports() >= BOOK_RENT @ BookError::InsufficientFunds)]
+                pub payer: Signer<'info>,
+            }
+
+            #[derive(Accounts)]
+            pub struct CreateArchive<'info> {
+                #[account(init, payer = funder, space = BOOK_SPACE)]
+                pub archive: Account<'info, Archive>,
+                #[account(mut)]
+                pub funder: Signer<'info>,
+            }
+
+            pub fn create_archive(ctx: Context<CreateArchive>) -> Result<()> {
+                let rent = Rent::get()?.minimum_balance(BOOK_SPACE);
+                require!(ctx.accounts.funder.lamports() >= rent, BookError::InsufficientFunds);
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Allocations with a checked payer balance should not be flagged");
+    }
+
+    #[test]
+    fn test_documented_funding() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct CreateBook<'info> {
+                /// Costs about 0.34 SOL of rent, paid by `payer`
+                #[account(init, payer = payer, space = 8 + 48 * 1024)]
+                pub book: Account<'info, OrderBook>,
+                /// Large archive of past orders
+                #[account(init, payer = payer, space = 8 + 48 * 1024)]
+                pub archive: Account<'info, Archive>,
+                #[account(mut)]
+                pub payer: Signer<'info>,
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Only the allocation without documented funding should be flagged");
+    }
+}
@@ -15,6 +15,7 @@ pub fn create_rule() -> Arc<dyn Rule> {
         .severity(Severity::Low)
         .title("Missing Error Handling in Public Functions")
         .description("Detects public functions that don't return Result<T> and may fail silently. In Solana contracts, proper error handling is essential for security and debugging.")
+        .reference("CWE-252: Unchecked Return Value (https://cwe.mitre.org/data/definitions/252.html)")
         .recommendations(vec![
             "Change function return type to Result<T, YourErrorType> to handle potential failures",
             "Use Anchor's Result<()> for instruction handlers to properly propagate errors",
@@ -23,6 +24,7 @@ pub fn create_rule() -> Arc<dyn Rule> {
             "Consider using anchor_lang::Result for Anchor-specific error handling"
         ])
         .rule_type(RuleType::Solana)
+        .category("error-handling")
         .tag("error-handling")
         .tag("best-practices")
         .dsl_query(|ast, _file_path, _span_extractor| {
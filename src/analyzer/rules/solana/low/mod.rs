@@ -1,3 +1,14 @@
 pub mod missing_error_handling;
 pub mod anchor_instructions;
-
+pub mod panic_in_handler;
+pub mod unchecked_optional_account;
+pub mod init_space_mismatch;
+pub mod unchecked_token_instruction;
+pub mod large_init_space;
+pub mod unchecked_account_usage;
+pub mod unchecked_program_account;
+pub mod unix_timestamp_gate;
+pub mod ignored_result;
+pub mod zero_account_read;
+pub mod unbounded_cpi_loop;
+pub mod account_index_mismatch;
@@ -0,0 +1,75 @@
+use log::{debug, trace};
+use syn::visit::{self, Visit};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Macros that abort the transaction
+const PANIC_MACROS: [&str; 4] = ["panic", "unreachable", "todo", "unimplemented"];
+
+pub trait PanicInHandlerFilters<'a> {
+    fn panics_in_handlers(self) -> AstQuery<'a>;
+}
+
+impl<'a> PanicInHandlerFilters<'a> for AstQuery<'a> {
+    fn panics_in_handlers(self) -> AstQuery<'a> {
+        debug!("Filtering panicking calls in instruction handlers");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let (sig, block) = match node.data {
+                NodeData::Function(func) => (&func.sig, func.block.as_ref()),
+                NodeData::ImplFunction(func) => (&func.sig, &func.block),
+                _ => continue,
+            };
+            if !anchor::is_instruction_handler(sig) {
+                continue;
+            }
+
+            let handler = sig.ident.to_string();
+            let mut finder = PanicFinder::default();
+            finder.visit_block(block);
+
+            for call in finder.calls {
+                trace!("Found panicking call in handler '{handler}'");
+                new_results.push(AstNode::from_expression(call, &handler));
+            }
+            for mac in finder.macros {
+                trace!("Found panicking macro in handler '{handler}'");
+                new_results.push(AstNode::from_macro(mac, &handler));
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Visitor that collects `.unwrap()`/`.expect()` calls and panicking macros
+#[derive(Default)]
+struct PanicFinder<'ast> {
+    calls: Vec<&'ast syn::Expr>,
+    macros: Vec<&'ast syn::Macro>,
+}
+
+impl<'ast> Visit<'ast> for PanicFinder<'ast> {
+    fn visit_expr(&mut self, expr: &'ast syn::Expr) {
+        if let syn::Expr::MethodCall(call) = expr
+            && (call.method == "unwrap" || call.method == "expect")
+        {
+            self.calls.push(expr);
+        }
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        if mac
+            .path
+            .segments
+            .last()
+            .is_some_and(|s| PANIC_MACROS.contains(&s.ident.to_string().as_str()))
+        {
+            self.macros.push(mac);
+        }
+        visit::visit_macro(self, mac);
+    }
+}
@@ -0,0 +1,54 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::PanicInHandlerFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("panic-in-handler")
+        .title("Panicking Call")
+        .description("Detects .unwrap(), .expect() and panic!/unreachable! in instruction handlers. A panic aborts the transaction without a meaningful error and can be abused to grief users where a graceful error is expected")
+        .severity(Severity::Low)
+        .rule_type(RuleType::General)
+        .category("error-handling")
+        .tag("error-handling")
+        .tag("dos")
+        .recommendations(vec![
+            "Return a typed program error instead of panicking: value.ok_or(ErrorCode::MissingValue)?",
+            "Propagate errors with the ? operator and map them with map_err where needed",
+            "Replace panic!/unreachable! with return err!(ErrorCode::...) or Err(ProgramError::...)",
+        ])
+        .vulnerable_example(r#"
+pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.balance = vault.balance.checked_sub(amount).unwrap();
+    Ok(())
+}
+"#)
+        .fixed_example(r#"
+pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.balance = vault
+        .balance
+        .checked_sub(amount)
+        .ok_or(ErrorCode::InsufficientFunds)?;
+    Ok(())
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing instruction handlers for panicking calls");
+
+            AstQuery::new(ast)
+                .functions()
+                .panics_in_handlers()
+        })
+        .build()
+}
@@ -0,0 +1,62 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::dsl::query::NodeType;
+use crate::analyzer::rules::solana::low::panic_in_handler::filters::PanicInHandlerFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_panics_in_anchor_handler() {
+        let ast: File = parse_quote! {
+            pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+                let vault = &mut ctx.accounts.vault;
+                vault.balance = vault.balance.checked_sub(amount).unwrap();
+                let fee = compute_fee(amount).expect("fee");
+                if amount == 0 {
+                    panic!("zero amount");
+                }
+                Ok(())
+            }
+        };
+
+        let results = AstQuery::new(&ast).functions().panics_in_handlers().collect();
+        assert_eq!(results.len(), 3, "Should flag unwrap, expect and panic!");
+        assert_eq!(results.iter().filter(|node| node.node_type == NodeType::Macro).count(), 1);
+        assert!(results.iter().all(|node| node.name() == "withdraw"));
+    }
+
+    #[test]
+    fn test_native_handler() {
+        let ast: File = parse_quote! {
+            pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+                let instruction = Instruction::unpack(data).unwrap();
+                match instruction {
+                    Instruction::Deposit => Ok(()),
+                    _ => unreachable!(),
+                }
+            }
+        };
+
+        let results = AstQuery::new(&ast).functions().panics_in_handlers().collect();
+        assert_eq!(results.len(), 2, "Should flag unwrap and unreachable! in native handlers");
+    }
+
+    #[test]
+    fn test_helpers_are_ignored() {
+        let ast: File = parse_quote! {
+            fn parse_amount(data: &[u8]) -> u64 {
+                u64::from_le_bytes(data[..8].try_into().unwrap())
+            }
+
+            pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+                let total = ctx.accounts.vault.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+                Ok(())
+            }
+        };
+
+        let results = AstQuery::new(&ast).functions().panics_in_handlers().collect();
+        assert!(results.is_empty(), "Non-handler functions and error propagation should not be flagged");
+    }
+}
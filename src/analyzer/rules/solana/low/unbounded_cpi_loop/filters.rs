@@ -0,0 +1,181 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use std::collections::HashSet;
+use syn::visit::{self, Visit};
+use syn::{Expr, FnArg, Pat};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Tokens marking a cross-program invocation
+const CPI_TOKENS: [&str; 3] = ["invoke", "invoke_signed", "CpiContext"];
+
+/// Modules whose functions perform a CPI (`token::transfer`, `other_program::cpi::deposit`)
+const CPI_MODULES: [&str; 4] = ["cpi", "token", "token_interface", "system_program"];
+
+/// Methods capping a count or collection (`count.min(MAX)`, `items.iter().take(MAX)`)
+const CAPPING_METHODS: [&str; 3] = ["min", "clamp", "take"];
+
+/// Parameter types that do not come from instruction data
+const NON_DATA_TYPES: [&str; 4] = ["Context", "AccountInfo", "Pubkey", "Self"];
+
+pub trait UnboundedCpiLoopFilters<'a> {
+    fn unbounded_cpi_loops(self) -> AstQuery<'a>;
+}
+
+impl<'a> UnboundedCpiLoopFilters<'a> for AstQuery<'a> {
+    fn unbounded_cpi_loops(self) -> AstQuery<'a> {
+        debug!("Filtering loops driven by instruction data that perform CPIs without a bound");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let (sig, block) = match node.data {
+                NodeData::Function(func) => (&func.sig, func.block.as_ref()),
+                NodeData::ImplFunction(func) => (&func.sig, &func.block),
+                _ => continue,
+            };
+
+            let mut tracker = LoopTracker {
+                tainted: instruction_data_params(sig),
+                bounded: HashSet::new(),
+                loops: Vec::new(),
+            };
+            if tracker.tainted.is_empty() {
+                continue;
+            }
+            tracker.visit_block(block);
+
+            for (expr, input) in tracker.loops {
+                trace!("Found CPI loop over '{input}' without a bound in function: {}", sig.ident);
+                new_results.push(AstNode::from_expression(expr, &input));
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Parameters carrying instruction data: everything but the context, accounts
+/// and program id
+fn instruction_data_params(sig: &syn::Signature) -> HashSet<String> {
+    sig.inputs
+        .iter()
+        .filter_map(|input| match input {
+            FnArg::Typed(pat_type) => Some(pat_type),
+            FnArg::Receiver(_) => None,
+        })
+        .filter(|pat_type| outer_type_name(&pat_type.ty).is_none_or(|name| !NON_DATA_TYPES.contains(&name.as_str())))
+        .filter_map(|pat_type| match &*pat_type.pat {
+            Pat::Ident(pat_ident) => Some(pat_ident.ident.to_string()),
+            _ => None,
+        })
+        .filter(|name| !matches!(name.as_str(), "accounts" | "program_id"))
+        .collect()
+}
+
+fn performs_cpi(tokens: &[String]) -> bool {
+    tokens.iter().any(|token| CPI_TOKENS.contains(&token.as_str()))
+        || tokens
+            .windows(3)
+            .any(|window| CPI_MODULES.contains(&window[0].as_str()) && window[1] == ":" && window[2] == ":")
+}
+
+/// First type name of a parameter type past references, slices and lifetimes
+/// (`AccountInfo` for `&'a [AccountInfo<'a>]`, `Vec` for `Vec<Pubkey>`)
+fn outer_type_name(ty: &syn::Type) -> Option<String> {
+    let tokens = anchor::flatten_tokens(ty.to_token_stream());
+    tokens
+        .iter()
+        .enumerate()
+        .find(|(index, token)| {
+            token.starts_with(|c: char| c.is_alphabetic() || c == '_')
+                && !matches!(token.as_str(), "mut" | "dyn" | "impl")
+                && (*index == 0 || tokens[index - 1] != "'")
+        })
+        .map(|(_, token)| token.clone())
+}
+
+/// Visitor that follows, in source order, which values derive from instruction
+/// data and which were bounded by a check, and reports CPI loops over the others
+struct LoopTracker<'ast> {
+    /// Parameters and locals derived from instruction data
+    tainted: HashSet<String>,
+    /// Tainted values compared against a bound before the loop
+    bounded: HashSet<String>,
+    loops: Vec<(&'ast Expr, String)>,
+}
+
+impl LoopTracker<'_> {
+    /// First tainted, unbounded value a loop header refers to
+    fn unbounded_input(&self, tokens: &[String]) -> Option<String> {
+        if tokens.iter().any(|token| CAPPING_METHODS.contains(&token.as_str())) {
+            return None;
+        }
+        tokens
+            .iter()
+            .find(|token| self.tainted.contains(*token) && !self.bounded.contains(*token))
+            .cloned()
+    }
+
+    /// Record the tainted values compared in a check (`items.len() <= MAX`)
+    fn record_bounds(&mut self, tokens: &[String], comparison: bool) {
+        if comparison || tokens.iter().any(|token| matches!(token.as_str(), "<" | ">")) {
+            let checked: Vec<String> = tokens.iter().filter(|token| self.tainted.contains(*token)).cloned().collect();
+            self.bounded.extend(checked);
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for LoopTracker<'ast> {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        visit::visit_local(self, local);
+
+        if let Pat::Ident(pat_ident) = &local.pat
+            && let Some(init) = &local.init
+        {
+            let tokens = anchor::flatten_tokens(init.expr.to_token_stream());
+            let name = pat_ident.ident.to_string();
+            if tokens.iter().any(|token| CAPPING_METHODS.contains(&token.as_str())) {
+                self.tainted.remove(&name);
+            } else if let Some(source) = tokens.iter().find(|token| self.tainted.contains(*token)) {
+                // A local derived from a bounded value is bounded as well
+                if self.bounded.contains(source) {
+                    self.bounded.insert(name.clone());
+                }
+                self.tainted.insert(name);
+            }
+        }
+    }
+
+    fn visit_expr_if(&mut self, expr: &'ast syn::ExprIf) {
+        let tokens = anchor::flatten_tokens(expr.cond.to_token_stream());
+        self.record_bounds(&tokens, false);
+        visit::visit_expr_if(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let name = mac.path.segments.last().map(|segment| segment.ident.to_string()).unwrap_or_default();
+        if name.starts_with("require") || name.starts_with("assert") {
+            // require_gte!, assert_eq! and similar compare their arguments
+            let comparison = ["_gt", "_lt", "_eq"].iter().any(|suffix| name.contains(suffix));
+            self.record_bounds(&anchor::flatten_tokens(mac.tokens.clone()), comparison);
+        }
+        visit::visit_macro(self, mac);
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        let (header, body) = match expr {
+            Expr::ForLoop(for_loop) => (for_loop.expr.to_token_stream(), &for_loop.body),
+            Expr::While(while_loop) => (while_loop.cond.to_token_stream(), &while_loop.body),
+            _ => {
+                visit::visit_expr(self, expr);
+                return;
+            }
+        };
+
+        if performs_cpi(&anchor::flatten_tokens(body.to_token_stream())) && let Some(input) = self.unbounded_input(&anchor::flatten_tokens(header)) {
+            self.loops.push((expr, input));
+        }
+        visit::visit_expr(self, expr);
+    }
+}
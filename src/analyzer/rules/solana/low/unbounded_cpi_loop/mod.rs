@@ -0,0 +1,57 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::UnboundedCpiLoopFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("unbounded-cpi-loop")
+        .title("CPI Loop Bounded Only by Instruction Data")
+        .description("Detects for and while loops performing a CPI (invoke, invoke_signed or a CpiContext) on every iteration whose iteration count comes from instruction data (a collection or count argument) with no prior check against a maximum. Every CPI costs compute units, so a caller passing a large collection exhausts the compute budget and the instruction can never succeed, which can block flows such as payouts or liquidations")
+        .severity(Severity::Low)
+        .rule_type(RuleType::Solana)
+        .category("cpi")
+        .category("best-practices")
+        .tag("dos")
+        .tag("compute-budget")
+        .recommendations(vec![
+            "Reject inputs above a maximum before the loop: require!(recipients.len() <= MAX_RECIPIENTS, ErrorCode::TooManyRecipients)",
+            "Cap the iteration count with .take(MAX) or .min(MAX), or process large batches across several instructions",
+            "Measure the compute units of one iteration to choose the maximum",
+        ])
+        .vulnerable_example(r#"
+pub fn airdrop(ctx: Context<Airdrop>, amounts: Vec<u64>) -> Result<()> {
+    for (amount, recipient) in amounts.iter().zip(ctx.remaining_accounts) {
+        token::transfer(ctx.accounts.transfer_ctx(recipient), *amount)?;
+    }
+    Ok(())
+}
+"#)
+        .fixed_example(r#"
+pub const MAX_RECIPIENTS: usize = 16;
+
+pub fn airdrop(ctx: Context<Airdrop>, amounts: Vec<u64>) -> Result<()> {
+    require!(amounts.len() <= MAX_RECIPIENTS, AirdropError::TooManyRecipients);
+    for (amount, recipient) in amounts.iter().zip(ctx.remaining_accounts) {
+        token::transfer(ctx.accounts.transfer_ctx(recipient), *amount)?;
+    }
+    Ok(())
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing CPI loops bounded only by instruction data");
+
+            AstQuery::new(ast)
+                .functions()
+                .unbounded_cpi_loops()
+        })
+        .build()
+}
@@ -0,0 +1,117 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::low::unbounded_cpi_loop::filters::UnboundedCpiLoopFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast)
+            .functions()
+            .unbounded_cpi_loops()
+            .collect()
+            .len()
+    }
+
+    #[test]
+    fn test_unbounded_cpi_loops() {
+        let ast: File = parse_quote! {
+            pub fn airdrop(ctx: Context<Airdrop>, amounts: Vec<u64>) -> Result<()> {
+                for (amount, recipient) in amounts.iter().zip(ctx.remaining_accounts) {
+                    token::transfer(ctx.accounts.transfer_ctx(recipient), *amount)?;
+                }
+                Ok(())
+            }
+
+            pub fn close_all(ctx: Context<CloseAll>, owners: Vec<Pubkey>) -> Result<()> {
+                for owner in &owners {
+                    invoke_signed(&close_ix(owner), &accounts, &[seeds])?;
+                }
+                Ok(())
+            }
+
+            pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+                let count = instruction_data[0];
+                let mut i = 0;
+                while i < count {
+                    invoke(&transfer_ix, &[accounts[0].clone(), accounts[1].clone()])?;
+                    i += 1;
+                }
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 3, "CPI loops sized by instruction data should be flagged");
+    }
+
+    #[test]
+    fn test_bounded_loops() {
+        let ast: File = parse_quote! {
+            pub fn airdrop(ctx: Context<Airdrop>, amounts: Vec<u64>) -> Result<()> {
+                require!(amounts.len() <= MAX_RECIPIENTS, AirdropError::TooManyRecipients);
+                for amount in amounts.iter() {
+                    token::transfer(ctx.accounts.transfer_ctx(), *amount)?;
+                }
+                Ok(())
+            }
+
+            pub fn refund(ctx: Context<Refund>, count: u64) -> Result<()> {
+                if count > MAX_REFUNDS {
+                    return err!(RefundError::TooMany);
+                }
+                for _ in 0..count {
+                    invoke(&refund_ix, &accounts)?;
+                }
+                Ok(())
+            }
+
+            pub fn payout(ctx: Context<Payout>, recipients: Vec<Pubkey>, rounds: u8) -> Result<()> {
+                let rounds = rounds.min(MAX_ROUNDS);
+                for _ in 0..rounds {
+                    invoke(&payout_ix, &accounts)?;
+                }
+                for recipient in recipients.iter().take(MAX_RECIPIENTS) {
+                    invoke(&payout_ix, &accounts)?;
+                }
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Bounded loops should not be flagged");
+    }
+
+    #[test]
+    fn test_loops_without_cpi_or_user_input() {
+        let ast: File = parse_quote! {
+            pub fn total(ctx: Context<Total>, amounts: Vec<u64>) -> Result<()> {
+                let mut sum = 0u64;
+                for amount in amounts.iter() {
+                    sum = sum.checked_add(*amount).unwrap();
+                }
+                for recipient in ctx.accounts.config.recipients.iter() {
+                    invoke(&payout_ix, &[recipient.clone()])?;
+                }
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Loops without CPIs or not driven by instruction data should not be flagged");
+    }
+
+    #[test]
+    fn test_check_inside_loop_is_not_a_bound() {
+        let ast: File = parse_quote! {
+            pub fn distribute(ctx: Context<Distribute>, shares: Vec<u64>) -> Result<()> {
+                let entries = shares.iter();
+                for share in entries {
+                    require!(*share > 0, DistributeError::EmptyShare);
+                    token::mint_to(ctx.accounts.mint_ctx(), *share)?;
+                }
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "A check inside the loop does not bound the iteration count");
+    }
+}
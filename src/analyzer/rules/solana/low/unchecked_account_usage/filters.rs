@@ -0,0 +1,261 @@
+use log::{debug, trace};
+use std::collections::{BTreeSet, HashMap};
+use syn::visit::{self, Visit};
+use syn::punctuated::Punctuated;
+use syn::{Expr, File, Pat, Token};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Account types Anchor performs no validation on
+const UNCHECKED_TYPES: [&str; 2] = ["AccountInfo", "UncheckedAccount"];
+
+/// Methods deserializing account data into program or token state
+const DESERIALIZERS: [&str; 8] = [
+    "try_deserialize",
+    "try_deserialize_unchecked",
+    "try_from_slice",
+    "deserialize",
+    "unpack",
+    "unpack_unchecked",
+    "load",
+    "load_mut",
+];
+
+/// Account wrappers whose `try_from` deserializes an `AccountInfo`
+const TYPED_WRAPPERS: [&str; 3] = ["Account", "InterfaceAccount", "AccountLoader"];
+
+/// How an unchecked account is consumed, in the order listed in findings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Usage {
+    SignerCheck,
+    Deserialized,
+    CpiAuthority,
+}
+
+impl Usage {
+    fn label(self) -> &'static str {
+        match self {
+            Usage::SignerCheck => "is_signer read",
+            Usage::Deserialized => "data deserialized",
+            Usage::CpiAuthority => "CPI authority",
+        }
+    }
+}
+
+pub trait UncheckedAccountUsageFilters<'a> {
+    fn unchecked_account_usages(self, ast: &'a File) -> AstQuery<'a>;
+}
+
+impl<'a> UncheckedAccountUsageFilters<'a> for AstQuery<'a> {
+    fn unchecked_account_usages(self, ast: &'a File) -> AstQuery<'a> {
+        debug!("Filtering unchecked accounts used as signers or typed accounts");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let NodeData::Struct(struct_item) = node.data else {
+                continue;
+            };
+            let fields = anchor::named_fields(struct_item);
+            if !fields.iter().any(|field| is_unchecked(&field.ty)) {
+                continue;
+            }
+
+            let mut finder = UsageFinder::default();
+            for handler in anchor::handlers_for(ast, &struct_item.ident.to_string()) {
+                finder.aliases.clear();
+                finder.visit_block(handler.block);
+            }
+
+            for field in fields {
+                let Some(ident) = &field.ident else {
+                    continue;
+                };
+                let account = ident.to_string();
+                if !is_unchecked(&field.ty) {
+                    continue;
+                }
+                let Some(usages) = finder.usages.get(&account) else {
+                    continue;
+                };
+
+                // Declared signers and PDA authorities signing with their seeds are
+                // legitimate reasons for these uses
+                let is_signer = anchor::has_constraint(&field.attrs, "signer");
+                let is_pda = anchor::has_constraint(&field.attrs, "seeds");
+                let labels: Vec<&str> = usages
+                    .iter()
+                    .filter(|usage| match usage {
+                        Usage::SignerCheck => !is_signer,
+                        Usage::CpiAuthority => !is_signer && !is_pda,
+                        Usage::Deserialized => true,
+                    })
+                    .map(|usage| usage.label())
+                    .collect();
+                if labels.is_empty() {
+                    continue;
+                }
+
+                trace!("Found unchecked account {account} consumed as: {}", labels.join(", "));
+                let mut node = AstNode::from_field(field);
+                node.name = Some(format!("{account}: {}", labels.join(", ")));
+                new_results.push(node);
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Whether a field is an `AccountInfo` or `UncheckedAccount`, possibly boxed
+fn is_unchecked(ty: &syn::Type) -> bool {
+    match anchor::type_name(ty).as_deref() {
+        Some("Box") => anchor::type_arguments(ty).first().is_some_and(|inner| is_unchecked(inner)),
+        Some(name) => UNCHECKED_TYPES.contains(&name),
+        None => false,
+    }
+}
+
+/// Visitor that records how accounts are consumed in a handler
+#[derive(Default)]
+struct UsageFinder {
+    /// Local variables derived from an accounts field (`let data = ctx.accounts.x.try_borrow_data()?`)
+    aliases: HashMap<String, String>,
+    usages: HashMap<String, BTreeSet<Usage>>,
+}
+
+impl UsageFinder {
+    /// Account an expression is derived from, looking through method calls,
+    /// field accesses, references and `?`
+    fn account_of(&self, mut expr: &Expr) -> Option<String> {
+        loop {
+            if let Some(account) = anchor::accounts_field(expr) {
+                return Some(account);
+            }
+            match expr {
+                Expr::MethodCall(call) => expr = &call.receiver,
+                Expr::Field(field) => expr = &field.base,
+                Expr::Reference(reference) => expr = &reference.expr,
+                Expr::Try(try_expr) => expr = &try_expr.expr,
+                Expr::Paren(paren) => expr = &paren.expr,
+                Expr::Index(index) => expr = &index.expr,
+                Expr::Path(path) => {
+                    let name = path.path.get_ident()?.to_string();
+                    return self.aliases.get(&name).cloned();
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    fn record(&mut self, expr: &Expr, usage: Usage) {
+        if let Some(account) = self.account_of(expr) {
+            self.usages.entry(account).or_default().insert(usage);
+        }
+    }
+
+    /// Record the accounts appearing anywhere in the arguments of a deserializing call
+    fn record_deserialized<'e>(&mut self, args: impl IntoIterator<Item = &'e Expr>) {
+        let mut collector = AccountCollector { finder: self, found: Vec::new() };
+        for arg in args {
+            collector.visit_expr(arg);
+        }
+        let found = collector.found;
+        for account in found {
+            self.usages.entry(account).or_default().insert(Usage::Deserialized);
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for UsageFinder {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        // Visit the initializer first so `let x = x.foo()` does not alias itself
+        visit::visit_local(self, local);
+        if let Pat::Ident(pat_ident) = &local.pat
+            && let Some(init) = &local.init
+        {
+            let name = pat_ident.ident.to_string();
+            match self.account_of(&init.expr) {
+                Some(account) => self.aliases.insert(name, account),
+                None => self.aliases.remove(&name),
+            };
+        }
+    }
+
+    fn visit_expr_field(&mut self, expr: &'ast syn::ExprField) {
+        if matches!(&expr.member, syn::Member::Named(ident) if ident == "is_signer") {
+            self.record(&expr.base, Usage::SignerCheck);
+        }
+        visit::visit_expr_field(self, expr);
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        if DESERIALIZERS.contains(&call.method.to_string().as_str()) {
+            self.record_deserialized(std::iter::once(&*call.receiver).chain(call.args.iter()));
+        }
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        if let Expr::Path(path) = &*call.func {
+            let segments: Vec<String> = path.path.segments.iter().map(|s| s.ident.to_string()).collect();
+            let deserializes = match segments.as_slice() {
+                [.., wrapper, method] if method == "try_from" => TYPED_WRAPPERS.contains(&wrapper.as_str()),
+                [.., method] => DESERIALIZERS.contains(&method.as_str()),
+                [] => false,
+            };
+            if deserializes {
+                self.record_deserialized(&call.args);
+            }
+        }
+        visit::visit_expr_call(self, call);
+    }
+
+    fn visit_expr_struct(&mut self, expr: &'ast syn::ExprStruct) {
+        // `authority: ctx.accounts.x.to_account_info()` in the accounts of a CPI
+        for field in &expr.fields {
+            if let syn::Member::Named(ident) = &field.member
+                && (ident.to_string().ends_with("authority") || ident == "payer")
+                && passes_account(&field.expr)
+            {
+                self.record(&field.expr, Usage::CpiAuthority);
+            }
+        }
+        visit::visit_expr_struct(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        // Uses inside require!, assert! and similar
+        if let Ok(args) = mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated) {
+            for arg in &args {
+                self.visit_expr(arg);
+            }
+        }
+        visit::visit_macro(self, mac);
+    }
+}
+
+/// Whether an expression hands over the account itself rather than a value read
+/// from it (`x.to_account_info()`, not `x.key()`)
+fn passes_account(expr: &Expr) -> bool {
+    match expr {
+        Expr::MethodCall(call) => matches!(call.method.to_string().as_str(), "to_account_info" | "clone"),
+        Expr::Reference(reference) => passes_account(&reference.expr),
+        _ => anchor::accounts_field(expr).is_some(),
+    }
+}
+
+/// Collects the accounts referenced by the expressions of a call
+struct AccountCollector<'f> {
+    finder: &'f UsageFinder,
+    found: Vec<String>,
+}
+
+impl<'ast> Visit<'ast> for AccountCollector<'_> {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match self.finder.account_of(expr) {
+            Some(account) => self.found.push(account),
+            None => visit::visit_expr(self, expr),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::UncheckedAccountUsageFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("unchecked-account-usage")
+        .title("Unchecked Account Used as a Signer or Typed Account")
+        .description("Detects AccountInfo and UncheckedAccount fields that the handlers treat as a more specific account: reading is_signer, deserializing their data or passing them as the authority of a CPI. Anchor validates none of this for unchecked accounts, so each of these uses relies on hand-written checks that a typed account would perform automatically. The finding lists how the account is consumed")
+        .severity(Severity::Low)
+        .rule_type(RuleType::Anchor)
+        .category("accounts")
+        .category("best-practices")
+        .tag("security")
+        .tag("account-types")
+        .reference("https://www.anchor-lang.com/docs/references/account-types")
+        .recommendations(vec![
+            "Declare accounts whose is_signer is read, or that authorize a CPI, as Signer<'info>",
+            "Declare accounts whose data is deserialized as Account<'info, T> (or AccountLoader<'info, T> for zero-copy state) so Anchor checks the owner and discriminator",
+            "Keep UncheckedAccount for accounts that are only forwarded, and explain the remaining checks in its /// CHECK: comment",
+        ])
+        .vulnerable_example(r#"
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    /// CHECK: deserialized in the handler
+    #[account(mut)]
+    pub config: UncheckedAccount<'info>,
+    /// CHECK: signature checked in the handler
+    pub admin: AccountInfo<'info>,
+}
+
+pub fn update_config(ctx: Context<UpdateConfig>, fee: u64) -> Result<()> {
+    require!(ctx.accounts.admin.is_signer, ErrorCode::Unauthorized);
+    let mut config = Config::try_deserialize(&mut &ctx.accounts.config.data.borrow()[..])?;
+    config.fee = fee;
+    Ok(())
+}
+"#)
+        .fixed_example(r#"
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(mut, has_one = admin)]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+}
+
+pub fn update_config(ctx: Context<UpdateConfig>, fee: u64) -> Result<()> {
+    ctx.accounts.config.fee = fee;
+    Ok(())
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing how unchecked accounts are consumed");
+
+            AstQuery::new(ast)
+                .structs()
+                .derives_accounts()
+                .unchecked_account_usages(ast)
+        })
+        .build()
+}
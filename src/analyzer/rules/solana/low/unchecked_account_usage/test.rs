@@ -0,0 +1,134 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::low::unchecked_account_usage::filters::UncheckedAccountUsageFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged(ast: &File) -> Vec<String> {
+        AstQuery::new(ast)
+            .structs()
+            .derives_accounts()
+            .unchecked_account_usages(ast)
+            .collect()
+            .into_iter()
+            .filter_map(|node| node.name)
+            .collect()
+    }
+
+    #[test]
+    fn test_signer_check_and_deserialization() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct UpdateConfig<'info> {
+                /// CHECK: deserialized in the handler
+                #[account(mut)]
+                pub config: UncheckedAccount<'info>,
+                /// CHECK: signature checked in the handler
+                pub admin: AccountInfo<'info>,
+            }
+
+            pub fn update_config(ctx: Context<UpdateConfig>, fee: u64) -> Result<()> {
+                require!(ctx.accounts.admin.is_signer, ConfigError::Unauthorized);
+                let data = ctx.accounts.config.try_borrow_data()?;
+                let mut config = Config::try_deserialize(&mut &data[..])?;
+                config.fee = fee;
+                Ok(())
+            }
+        };
+
+        assert_eq!(
+            flagged(&ast),
+            vec!["config: data deserialized".to_string(), "admin: is_signer read".to_string()],
+            "Unchecked accounts used as typed accounts should be flagged with their uses"
+        );
+    }
+
+    #[test]
+    fn test_cpi_authority() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Withdraw<'info> {
+                #[account(mut)]
+                pub vault_tokens: Account<'info, TokenAccount>,
+                /// CHECK: authority of the vault
+                pub owner: UncheckedAccount<'info>,
+                /// CHECK: PDA signing with its seeds
+                #[account(seeds = [b"vault"], bump)]
+                pub vault_authority: UncheckedAccount<'info>,
+            }
+
+            pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+                let owner = &ctx.accounts.owner;
+                if !owner.is_signer {
+                    return err!(VaultError::Unauthorized);
+                }
+                token::transfer(CpiContext::new(program, Transfer {
+                    from: ctx.accounts.vault_tokens.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                }), amount)?;
+                token::transfer(CpiContext::new_with_signer(program, Transfer {
+                    from: ctx.accounts.vault_tokens.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.vault_authority.to_account_info(),
+                }, signer_seeds), amount)
+            }
+        };
+
+        assert_eq!(
+            flagged(&ast),
+            vec!["owner: is_signer read, CPI authority".to_string()],
+            "Only the non-PDA authority should be flagged"
+        );
+    }
+
+    #[test]
+    fn test_forwarded_accounts() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Register<'info> {
+                /// CHECK: only stored
+                pub delegate: UncheckedAccount<'info>,
+                /// CHECK: forwarded to the CPI
+                pub metadata_program: AccountInfo<'info>,
+                #[account(signer)]
+                /// CHECK: declared signer
+                pub admin: AccountInfo<'info>,
+                #[account(mut)]
+                pub registry: Account<'info, Registry>,
+            }
+
+            pub fn register(ctx: Context<Register>) -> Result<()> {
+                require!(ctx.accounts.admin.is_signer, RegistryError::Unauthorized);
+                let registry = &mut ctx.accounts.registry;
+                registry.entry = Entry { authority: ctx.accounts.delegate.key() };
+                invoke(&instruction, &[ctx.accounts.metadata_program.to_account_info()])?;
+                Ok(())
+            }
+        };
+
+        assert!(flagged(&ast).is_empty(), "Stored keys, forwarded accounts and declared signers should not be flagged");
+    }
+
+    #[test]
+    fn test_typed_account_wrapper() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Close<'info> {
+                /// CHECK: loaded in the handler
+                #[account(mut)]
+                pub pool: Box<UncheckedAccount<'info>>,
+            }
+
+            pub fn close(ctx: Context<Close>) -> Result<()> {
+                let pool: Account<Pool> = Account::try_from(&ctx.accounts.pool)?;
+                msg!("closing pool {}", pool.key());
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged(&ast), vec!["pool: data deserialized".to_string()]);
+    }
+}
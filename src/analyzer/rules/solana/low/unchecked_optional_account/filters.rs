@@ -0,0 +1,188 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use std::collections::{HashMap, HashSet};
+use syn::visit::{self, Visit};
+use syn::{Expr, Item, ItemStruct};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Calls that assume an `Option` holds a value
+const PRESENCE_ASSUMPTIONS: [&str; 3] = ["unwrap", "expect", "unwrap_unchecked"];
+
+/// Adapters that keep the `Option` wrapper around the same value
+const OPTION_ADAPTERS: [&str; 5] = ["as_ref", "as_mut", "as_deref", "as_deref_mut", "clone"];
+
+pub trait UncheckedOptionalAccountFilters<'a> {
+    fn unchecked_optional_reads(self) -> AstQuery<'a>;
+}
+
+impl<'a> UncheckedOptionalAccountFilters<'a> for AstQuery<'a> {
+    fn unchecked_optional_reads(self) -> AstQuery<'a> {
+        debug!("Filtering optional accounts and fields read without a presence check");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let NodeData::File(file) = node.data else {
+                continue;
+            };
+
+            // Option<...> fields of Accounts structs, and of #[account] state set by other instructions
+            let mut optional_accounts: HashMap<String, HashSet<String>> = HashMap::new();
+            let mut optional_state_fields = HashSet::new();
+            for item in &file.items {
+                let Item::Struct(item_struct) = item else {
+                    continue;
+                };
+                if anchor::is_accounts_struct(item_struct) {
+                    optional_accounts.insert(item_struct.ident.to_string(), optional_fields(item_struct));
+                } else if is_state_struct(item_struct) {
+                    optional_state_fields.extend(optional_fields(item_struct));
+                }
+            }
+
+            for handler in anchor::instruction_handlers(file) {
+                let accounts = optional_accounts.get(&handler.accounts_type).cloned().unwrap_or_default();
+                if accounts.is_empty() && optional_state_fields.is_empty() {
+                    continue;
+                }
+
+                let mut finder = OptionalReadFinder::new(accounts, &optional_state_fields);
+                finder.visit_block(handler.block);
+
+                for (expr, name) in finder.reads {
+                    if !finder.checked.contains(&name) {
+                        trace!("Found '{name}' read without a presence check in handler '{}'", handler.sig.ident);
+                        new_results.push(AstNode::from_expression(expr, &name));
+                    }
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Names of the `Option<...>` fields of a struct
+fn optional_fields(item_struct: &ItemStruct) -> HashSet<String> {
+    anchor::named_fields(item_struct)
+        .into_iter()
+        .filter(|field| anchor::type_name(&field.ty).as_deref() == Some("Option"))
+        .filter_map(|field| field.ident.as_ref().map(ToString::to_string))
+        .collect()
+}
+
+/// Returns true if the struct is program state declared with `#[account]`
+fn is_state_struct(item_struct: &ItemStruct) -> bool {
+    item_struct.attrs.iter().any(|attr| attr.path().is_ident("account"))
+}
+
+/// Visitor that collects presence assumptions on optional values and the
+/// values checked anywhere in the handler
+struct OptionalReadFinder<'ast, 's> {
+    /// Optional accounts of the handler and the locals bound to them
+    accounts: HashSet<String>,
+    /// Optional fields of the program state
+    state_fields: &'s HashSet<String>,
+    reads: Vec<(&'ast Expr, String)>,
+    checked: HashSet<String>,
+}
+
+impl<'ast, 's> OptionalReadFinder<'ast, 's> {
+    fn new(accounts: HashSet<String>, state_fields: &'s HashSet<String>) -> Self {
+        Self {
+            accounts,
+            state_fields,
+            reads: Vec::new(),
+            checked: HashSet::new(),
+        }
+    }
+
+    /// Optional value an expression refers to, looking through `as_ref()` and similar
+    fn optional_value(&self, mut expr: &Expr) -> Option<String> {
+        loop {
+            match expr {
+                Expr::MethodCall(call) if OPTION_ADAPTERS.contains(&call.method.to_string().as_str()) => {
+                    expr = &call.receiver;
+                }
+                Expr::Paren(paren) => expr = &paren.expr,
+                Expr::Reference(reference) => expr = &reference.expr,
+                _ => break,
+            }
+        }
+
+        if let Some(field) = anchor::accounts_field(expr) {
+            return self.accounts.contains(&field).then_some(field);
+        }
+        match expr {
+            Expr::Path(path) => path
+                .path
+                .get_ident()
+                .map(ToString::to_string)
+                .filter(|name| self.accounts.contains(name)),
+            Expr::Field(field) => match &field.member {
+                syn::Member::Named(ident) if self.state_fields.contains(&ident.to_string()) => {
+                    Some(ident.to_string())
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// A condition mentioning an optional value checks its presence
+    fn check(&mut self, tokens: proc_macro2::TokenStream) {
+        for token in anchor::flatten_tokens(tokens) {
+            if self.accounts.contains(&token) || self.state_fields.contains(&token) {
+                self.checked.insert(token);
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for OptionalReadFinder<'ast, '_> {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        if let Some(init) = &local.init {
+            // `let referrer = &ctx.accounts.referrer;` makes `referrer` an alias
+            if let syn::Pat::Ident(pat_ident) = &local.pat
+                && let Some(field) = anchor::accounts_field(&init.expr)
+                && self.accounts.contains(&field)
+            {
+                self.accounts.insert(pat_ident.ident.to_string());
+            }
+            // `let Some(x) = ... else { ... }` checks the value
+            if init.diverge.is_some() {
+                self.check(init.expr.to_token_stream());
+            }
+        }
+        visit::visit_local(self, local);
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let Expr::MethodCall(call) = expr
+            && PRESENCE_ASSUMPTIONS.contains(&call.method.to_string().as_str())
+            && let Some(name) = self.optional_value(&call.receiver)
+        {
+            self.reads.push((expr, name));
+        }
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_expr_if(&mut self, expr: &'ast syn::ExprIf) {
+        self.check(expr.cond.to_token_stream());
+        visit::visit_expr_if(self, expr);
+    }
+
+    fn visit_expr_match(&mut self, expr: &'ast syn::ExprMatch) {
+        self.check(expr.expr.to_token_stream());
+        visit::visit_expr_match(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let name = mac.path.segments.last().map(|s| s.ident.to_string()).unwrap_or_default();
+        if name.starts_with("require") || name.starts_with("assert") {
+            self.check(mac.tokens.clone());
+        }
+        visit::visit_macro(self, mac);
+    }
+}
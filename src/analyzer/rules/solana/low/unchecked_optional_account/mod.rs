@@ -0,0 +1,58 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::UncheckedOptionalAccountFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("unchecked-optional-account")
+        .title("Optional Value Read Without Check")
+        .description("Heuristic: detects handlers calling .unwrap()/.expect() on an optional account (Option<...> in the accounts struct) or on an Option field of program state that another instruction sets, with no presence check in the handler. The instruction then assumes a caller passed the account or that the value was initialized, and fails unexpectedly when it was not")
+        .severity(Severity::Low)
+        .rule_type(RuleType::Anchor)
+        .category("accounts")
+        .category("error-handling")
+        .tag("initialization")
+        .tag("error-handling")
+        .recommendations(vec![
+            "Check presence explicitly with is_some(), if let Some(...) or let ... else before using the value",
+            "Convert a missing value into a typed error: value.as_ref().ok_or(ErrorCode::MissingAccount)?",
+            "For values set by a prior instruction, check the uninitialized (None or zero) case before relying on them",
+        ])
+        .vulnerable_example(r#"
+#[derive(Accounts)]
+pub struct Purchase<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    #[account(mut)]
+    pub referrer: Option<SystemAccount<'info>>,
+}
+
+pub fn purchase(ctx: Context<Purchase>, amount: u64) -> Result<()> {
+    let referrer = ctx.accounts.referrer.as_ref().unwrap();
+    pay_referral(referrer, amount)
+}
+"#)
+        .fixed_example(r#"
+pub fn purchase(ctx: Context<Purchase>, amount: u64) -> Result<()> {
+    if let Some(referrer) = &ctx.accounts.referrer {
+        pay_referral(referrer, amount)?;
+    }
+    Ok(())
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing reads of optional accounts and fields");
+
+            AstQuery::new(ast).unchecked_optional_reads()
+        })
+        .build()
+}
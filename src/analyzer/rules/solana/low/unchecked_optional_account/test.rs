@@ -0,0 +1,97 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::low::unchecked_optional_account::filters::UncheckedOptionalAccountFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast).unchecked_optional_reads().collect().len()
+    }
+
+    #[test]
+    fn test_optional_account_unwrapped() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Purchase<'info> {
+                #[account(mut)]
+                pub buyer: Signer<'info>,
+                #[account(mut)]
+                pub referrer: Option<SystemAccount<'info>>,
+            }
+
+            pub fn purchase(ctx: Context<Purchase>, amount: u64) -> Result<()> {
+                let referrer = ctx.accounts.referrer.as_ref().unwrap();
+                pay_referral(referrer, amount)
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Should flag an optional account assumed present");
+    }
+
+    #[test]
+    fn test_optional_account_checked() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Purchase<'info> {
+                #[account(mut)]
+                pub buyer: Signer<'info>,
+                #[account(mut)]
+                pub referrer: Option<SystemAccount<'info>>,
+            }
+
+            pub fn purchase(ctx: Context<Purchase>, amount: u64) -> Result<()> {
+                require!(ctx.accounts.referrer.is_some(), ShopError::MissingReferrer);
+                let referrer = ctx.accounts.referrer.as_ref().unwrap();
+                pay_referral(referrer, amount)
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "A presence check guards the unwrap");
+    }
+
+    #[test]
+    fn test_optional_account_alias() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Purchase<'info> {
+                #[account(mut)]
+                pub referrer: Option<SystemAccount<'info>>,
+            }
+
+            pub fn purchase(ctx: Context<Purchase>, amount: u64) -> Result<()> {
+                let referrer = &ctx.accounts.referrer;
+                pay_referral(referrer.as_ref().expect("referrer"), amount)
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Should follow locals bound to the optional account");
+    }
+
+    #[test]
+    fn test_state_field_set_by_other_instruction() {
+        let ast: File = parse_quote! {
+            #[account]
+            pub struct Config {
+                pub admin: Pubkey,
+                pub pending_admin: Option<Pubkey>,
+            }
+
+            #[derive(Accounts)]
+            pub struct AcceptAdmin<'info> {
+                #[account(mut)]
+                pub config: Account<'info, Config>,
+                pub signer: Signer<'info>,
+            }
+
+            pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+                let config = &mut ctx.accounts.config;
+                config.admin = config.pending_admin.unwrap();
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Should flag an Option state field assumed set");
+    }
+}
@@ -0,0 +1,170 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use std::collections::{HashMap, HashSet};
+use syn::visit::{self, Visit};
+use syn::{Expr, File, Pat, Type};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Account types Anchor performs no validation on
+const UNCHECKED_TYPES: [&str; 2] = ["AccountInfo", "UncheckedAccount"];
+
+/// Fields of CPI account structs holding a well-known program
+/// (`associated_token::Create`, `token::InitializeAccount` helpers and similar)
+const PROGRAM_FIELDS: [&str; 4] = [
+    "token_program",
+    "associated_token_program",
+    "system_program",
+    "token_2022_program",
+];
+
+pub trait UncheckedProgramAccountFilters<'a> {
+    fn unchecked_program_accounts(self, ast: &'a File) -> AstQuery<'a>;
+}
+
+impl<'a> UncheckedProgramAccountFilters<'a> for AstQuery<'a> {
+    fn unchecked_program_accounts(self, ast: &'a File) -> AstQuery<'a> {
+        debug!("Filtering unchecked accounts used as CPI programs");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let NodeData::Struct(struct_item) = node.data else {
+                continue;
+            };
+            let struct_name = struct_item.ident.to_string();
+
+            // Programs of CPIs built in the handlers or in helper methods of the accounts struct
+            let mut usage = ProgramUsageCollector::default();
+            for handler in anchor::handlers_for(ast, &struct_name) {
+                usage.aliases.clear();
+                usage.visit_block(handler.block);
+            }
+            for method in anchor::impl_methods(ast, &struct_name) {
+                usage.aliases.clear();
+                usage.visit_block(&method.block);
+            }
+
+            for field in anchor::named_fields(struct_item) {
+                let Some(ident) = &field.ident else {
+                    continue;
+                };
+                let account = ident.to_string();
+                if !is_unchecked(&field.ty)
+                    || !usage.programs.contains(&account)
+                    || usage.checked.contains(&account)
+                    || anchor::has_constraint(&field.attrs, "address")
+                {
+                    continue;
+                }
+
+                trace!("Found unchecked account {account} used as a CPI program");
+                new_results.push(AstNode::from_field(field));
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Whether a field is an `AccountInfo` or `UncheckedAccount`, possibly boxed
+fn is_unchecked(ty: &Type) -> bool {
+    match anchor::type_name(ty).as_deref() {
+        Some("Box") => anchor::type_arguments(ty).first().is_some_and(|inner| is_unchecked(inner)),
+        Some(name) => UNCHECKED_TYPES.contains(&name),
+        None => false,
+    }
+}
+
+/// Visitor that records the accounts used as CPI programs and the accounts
+/// whose key is checked in code
+#[derive(Default)]
+struct ProgramUsageCollector {
+    /// Local variables bound to an accounts field
+    aliases: HashMap<String, String>,
+    /// Accounts used as the program of a CPI
+    programs: HashSet<String>,
+    /// Accounts whose key is compared in a macro (`require_keys_eq!`) or an `if` condition
+    checked: HashSet<String>,
+}
+
+impl ProgramUsageCollector {
+    /// Account an expression hands over: `ctx.accounts.x`, `self.x` or an alias,
+    /// possibly through `to_account_info()`, `clone()` or a reference
+    fn account_of(&self, expr: &Expr) -> Option<String> {
+        if let Some(account) = anchor::accounts_field(expr) {
+            return Some(account);
+        }
+        match expr {
+            Expr::MethodCall(call) if matches!(call.method.to_string().as_str(), "to_account_info" | "clone") => {
+                self.account_of(&call.receiver)
+            }
+            Expr::Reference(reference) => self.account_of(&reference.expr),
+            Expr::Paren(paren) => self.account_of(&paren.expr),
+            Expr::Field(field) => match (&*field.base, &field.member) {
+                (Expr::Path(base), syn::Member::Named(ident)) if base.path.is_ident("self") => Some(ident.to_string()),
+                _ => None,
+            },
+            Expr::Path(path) => self.aliases.get(&path.path.get_ident()?.to_string()).cloned(),
+            _ => None,
+        }
+    }
+
+    fn record_checks(&mut self, tokens: proc_macro2::TokenStream) {
+        let flat = anchor::flatten_tokens(tokens);
+        for window in flat.windows(3) {
+            if window[1] == "." && window[2] == "key" {
+                let account = self.aliases.get(&window[0]).cloned().unwrap_or_else(|| window[0].clone());
+                self.checked.insert(account);
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for ProgramUsageCollector {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        if let Pat::Ident(pat_ident) = &local.pat
+            && let Some(init) = &local.init
+            && let Some(account) = self.account_of(&init.expr)
+        {
+            self.aliases.insert(pat_ident.ident.to_string(), account);
+        }
+        visit::visit_local(self, local);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        // CpiContext::new(program, accounts) and CpiContext::new_with_signer(program, accounts, seeds)
+        if let Expr::Path(path) = &*call.func
+            && let [.., context, constructor] = path.path.segments.iter().collect::<Vec<_>>().as_slice()
+            && context.ident == "CpiContext"
+            && constructor.ident.to_string().starts_with("new")
+            && let Some(account) = call.args.first().and_then(|program| self.account_of(program))
+        {
+            self.programs.insert(account);
+        }
+        visit::visit_expr_call(self, call);
+    }
+
+    fn visit_expr_struct(&mut self, expr: &'ast syn::ExprStruct) {
+        for field in &expr.fields {
+            if let syn::Member::Named(ident) = &field.member
+                && PROGRAM_FIELDS.contains(&ident.to_string().as_str())
+                && let Some(account) = self.account_of(&field.expr)
+            {
+                self.programs.insert(account);
+            }
+        }
+        visit::visit_expr_struct(self, expr);
+    }
+
+    fn visit_expr_if(&mut self, expr: &'ast syn::ExprIf) {
+        self.record_checks(expr.cond.to_token_stream());
+        visit::visit_expr_if(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        // Checks inside require_keys_eq!, require!, assert_eq! and similar
+        self.record_checks(mac.tokens.clone());
+        visit::visit_macro(self, mac);
+    }
+}
@@ -0,0 +1,72 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::UncheckedProgramAccountFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("unchecked-program-account")
+        .title("Program Account Without Address Check")
+        .description("Detects AccountInfo and UncheckedAccount fields used as the program of a CpiContext or passed as the token, associated token or system program of a CPI, without an address constraint or a key check in the handler. An attacker can substitute a fake program that accepts the call and skips the transfer or mint")
+        .severity(Severity::Low)
+        .rule_type(RuleType::Anchor)
+        .category("cpi")
+        .category("accounts")
+        .tag("security")
+        .tag("program-id")
+        .reference("https://github.com/coral-xyz/sealevel-attacks/tree/master/programs/5-arbitrary-cpi")
+        .recommendations(vec![
+            "Declare the account as Program<'info, Token> (or System, AssociatedToken), which checks the program id",
+            "For token programs that may be SPL Token or Token-2022, use Interface<'info, TokenInterface>",
+            "Otherwise add #[account(address = token::ID)] to the field",
+        ])
+        .vulnerable_example(r#"
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut)]
+    pub user_tokens: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub vault_tokens: Account<'info, TokenAccount>,
+    pub user: Signer<'info>,
+    /// CHECK: token program
+    pub token_program: AccountInfo<'info>,
+}
+
+pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.user_tokens.to_account_info(),
+        to: ctx.accounts.vault_tokens.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    token::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), amount)
+}
+"#)
+        .fixed_example(r#"
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut)]
+    pub user_tokens: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub vault_tokens: Account<'info, TokenAccount>,
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing CPI program accounts for address checks");
+
+            AstQuery::new(ast)
+                .structs()
+                .derives_accounts()
+                .unchecked_program_accounts(ast)
+        })
+        .build()
+}
@@ -0,0 +1,111 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::low::unchecked_program_account::filters::UncheckedProgramAccountFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_fields(ast: &File) -> Vec<String> {
+        AstQuery::new(ast)
+            .structs()
+            .derives_accounts()
+            .unchecked_program_accounts(ast)
+            .collect()
+            .into_iter()
+            .filter_map(|node| node.name)
+            .collect()
+    }
+
+    #[test]
+    fn test_unchecked_cpi_program() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Deposit<'info> {
+                #[account(mut)]
+                pub user_tokens: Account<'info, TokenAccount>,
+                pub user: Signer<'info>,
+                /// CHECK: token program
+                pub token_program: AccountInfo<'info>,
+            }
+
+            pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+                let program = ctx.accounts.token_program.to_account_info();
+                token::transfer(CpiContext::new(program, Transfer {
+                    from: ctx.accounts.user_tokens.to_account_info(),
+                    to: ctx.accounts.vault_tokens.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                }), amount)
+            }
+        };
+
+        assert_eq!(flagged_fields(&ast), vec!["token_program"], "An unchecked CPI program should be flagged");
+    }
+
+    #[test]
+    fn test_program_fields_of_cpi_accounts() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct CreateVault<'info> {
+                #[account(mut)]
+                pub payer: Signer<'info>,
+                /// CHECK: system program
+                pub sys: UncheckedAccount<'info>,
+                pub token_program: Program<'info, Token>,
+                pub associated_token_program: Program<'info, AssociatedToken>,
+            }
+
+            impl<'info> CreateVault<'info> {
+                fn create_ctx(&self) -> CpiContext<'_, '_, '_, 'info, Create<'info>> {
+                    CpiContext::new(self.associated_token_program.to_account_info(), Create {
+                        payer: self.payer.to_account_info(),
+                        system_program: self.sys.to_account_info(),
+                        token_program: self.token_program.to_account_info(),
+                    })
+                }
+            }
+        };
+
+        assert_eq!(flagged_fields(&ast), vec!["sys"], "Only the unchecked system program should be flagged");
+    }
+
+    #[test]
+    fn test_address_constraint_and_key_check() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Deposit<'info> {
+                /// CHECK: checked by address
+                #[account(address = token::ID)]
+                pub token_program: AccountInfo<'info>,
+                /// CHECK: checked in the handler
+                pub system_program: UncheckedAccount<'info>,
+            }
+
+            pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+                require_keys_eq!(ctx.accounts.system_program.key(), system_program::ID);
+                system_program::transfer(CpiContext::new(ctx.accounts.system_program.to_account_info(), accounts), amount)?;
+                token::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), amount)
+            }
+        };
+
+        assert!(flagged_fields(&ast).is_empty(), "Programs with an address constraint or key check should not be flagged");
+    }
+
+    #[test]
+    fn test_unused_unchecked_account() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Register<'info> {
+                /// CHECK: only stored
+                pub delegate: UncheckedAccount<'info>,
+            }
+
+            pub fn register(ctx: Context<Register>) -> Result<()> {
+                ctx.accounts.registry.delegate = ctx.accounts.delegate.key();
+                Ok(())
+            }
+        };
+
+        assert!(flagged_fields(&ast).is_empty(), "Accounts not used as CPI programs should not be flagged");
+    }
+}
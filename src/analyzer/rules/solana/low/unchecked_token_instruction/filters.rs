@@ -0,0 +1,106 @@
+use log::{debug, trace};
+use std::collections::HashSet;
+use syn::visit::{self, Visit};
+use syn::{Expr, UseTree};
+
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Token instructions superseded by a `_checked` variant validating mint and decimals
+const UNCHECKED_INSTRUCTIONS: [&str; 4] = ["transfer", "burn", "mint_to", "approve"];
+
+/// Modules exposing the token program CPIs and instruction builders
+const TOKEN_MODULES: [&str; 5] = ["token", "token_interface", "token_2022", "spl_token", "spl_token_2022"];
+
+pub trait UncheckedTokenInstructionFilters<'a> {
+    fn unchecked_token_instructions(self) -> AstQuery<'a>;
+}
+
+impl<'a> UncheckedTokenInstructionFilters<'a> for AstQuery<'a> {
+    fn unchecked_token_instructions(self) -> AstQuery<'a> {
+        debug!("Filtering token CPIs without their _checked variant");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let NodeData::File(file) = node.data else {
+                continue;
+            };
+
+            let mut finder = TokenCallFinder::default();
+            for item in &file.items {
+                if let syn::Item::Use(item_use) = item {
+                    imported_instructions(&item_use.tree, &mut Vec::new(), &mut finder.imported);
+                }
+            }
+            finder.visit_file(file);
+
+            for (expr, name) in finder.calls {
+                trace!("Found unchecked token instruction {name}");
+                new_results.push(AstNode::from_expression(expr, &name));
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Collects the unchecked instructions imported by name from a token module
+/// (e.g. `use anchor_spl::token::{transfer, Transfer};`)
+fn imported_instructions(tree: &UseTree, prefix: &mut Vec<String>, imported: &mut HashSet<String>) {
+    match tree {
+        UseTree::Path(path) => {
+            prefix.push(path.ident.to_string());
+            imported_instructions(&path.tree, prefix, imported);
+            prefix.pop();
+        }
+        UseTree::Group(group) => {
+            for tree in &group.items {
+                imported_instructions(tree, prefix, imported);
+            }
+        }
+        UseTree::Name(name) if from_token_module(prefix) && is_unchecked(&name.ident.to_string()) => {
+            imported.insert(name.ident.to_string());
+        }
+        UseTree::Rename(rename) if from_token_module(prefix) && is_unchecked(&rename.ident.to_string()) => {
+            imported.insert(rename.rename.to_string());
+        }
+        _ => {}
+    }
+}
+
+/// Whether a path (without its last segment) ends in a token module, or its `instruction` submodule
+fn from_token_module(prefix: &[String]) -> bool {
+    match prefix {
+        [.., module, last] if last == "instruction" => TOKEN_MODULES.contains(&module.as_str()),
+        [.., last] => TOKEN_MODULES.contains(&last.as_str()),
+        [] => false,
+    }
+}
+
+fn is_unchecked(name: &str) -> bool {
+    UNCHECKED_INSTRUCTIONS.contains(&name)
+}
+
+/// Visitor that collects calls to unchecked token instructions
+#[derive(Default)]
+struct TokenCallFinder<'ast> {
+    /// Unchecked instructions imported by name
+    imported: HashSet<String>,
+    calls: Vec<(&'ast Expr, String)>,
+}
+
+impl<'ast> Visit<'ast> for TokenCallFinder<'ast> {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let Expr::Call(call) = expr
+            && let Expr::Path(path) = &*call.func
+        {
+            let segments: Vec<String> = path.path.segments.iter().map(|s| s.ident.to_string()).collect();
+            if let Some((name, prefix)) = segments.split_last()
+                && ((is_unchecked(name) && from_token_module(prefix))
+                    || (prefix.is_empty() && self.imported.contains(name)))
+            {
+                self.calls.push((expr, segments.join("::")));
+            }
+        }
+        visit::visit_expr(self, expr);
+    }
+}
@@ -0,0 +1,58 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::UncheckedTokenInstructionFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("unchecked-token-instruction")
+        .title("Token Instruction Without Decimals Check")
+        .description("Detects SPL token CPIs and instruction builders (token::transfer, spl_token::instruction::transfer, burn, mint_to, approve) used instead of their _checked variants. The unchecked instructions take neither the mint nor its decimals, so the token program cannot verify that the amount is expressed for the expected mint, and they are deprecated for Token-2022 mints with transfer fees or hooks")
+        .severity(Severity::Low)
+        .rule_type(RuleType::Solana)
+        .category("cpi")
+        .tag("token")
+        .tag("cpi")
+        .recommendations(vec![
+            "Migrate to transfer_checked, burn_checked, mint_to_checked and approve_checked",
+            "Pass the mint account and its decimals so the token program validates the amount",
+            "With Anchor, use anchor_spl::token_interface::transfer_checked to support Token-2022 mints",
+        ])
+        .vulnerable_example(r#"
+pub fn pay(ctx: Context<Pay>, amount: u64) -> Result<()> {
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.from.to_account_info(),
+        to: ctx.accounts.to.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, amount)
+}
+"#)
+        .fixed_example(r#"
+pub fn pay(ctx: Context<Pay>, amount: u64) -> Result<()> {
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.from.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.to.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing token instructions without decimals check");
+
+            AstQuery::new(ast).unchecked_token_instructions()
+        })
+        .build()
+}
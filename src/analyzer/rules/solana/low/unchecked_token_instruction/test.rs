@@ -0,0 +1,72 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::low::unchecked_token_instruction::filters::UncheckedTokenInstructionFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast).unchecked_token_instructions().collect().len()
+    }
+
+    #[test]
+    fn test_anchor_token_transfer() {
+        let ast: File = parse_quote! {
+            pub fn pay(ctx: Context<Pay>, amount: u64) -> Result<()> {
+                let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+                token::transfer(cpi_ctx, amount)?;
+                anchor_spl::token::burn(burn_ctx, amount)
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 2, "Should flag token::transfer and token::burn");
+    }
+
+    #[test]
+    fn test_native_instruction_builder() {
+        let ast: File = parse_quote! {
+            pub fn process(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+                let ix = spl_token::instruction::transfer(
+                    token_program.key,
+                    source.key,
+                    destination.key,
+                    authority.key,
+                    &[],
+                    amount,
+                )?;
+                invoke(&ix, accounts)
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Should flag spl_token::instruction::transfer");
+    }
+
+    #[test]
+    fn test_imported_instruction() {
+        let ast: File = parse_quote! {
+            use anchor_spl::token::{mint_to, MintTo, Token};
+
+            pub fn reward(ctx: Context<Reward>, amount: u64) -> Result<()> {
+                mint_to(ctx.accounts.mint_ctx(), amount)
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Should flag mint_to imported from the token module");
+    }
+
+    #[test]
+    fn test_checked_and_system_transfers() {
+        let ast: File = parse_quote! {
+            use anchor_lang::system_program::{transfer, Transfer};
+
+            pub fn pay(ctx: Context<Pay>, amount: u64, decimals: u8) -> Result<()> {
+                token_interface::transfer_checked(cpi_ctx, amount, decimals)?;
+                system_program::transfer(lamports_ctx, amount)?;
+                transfer(lamports_ctx, amount)
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Checked and system program transfers should not be flagged");
+    }
+}
@@ -0,0 +1,138 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use std::collections::HashSet;
+use syn::punctuated::Punctuated;
+use syn::visit::{self, Visit};
+use syn::{Attribute, BinOp, Expr, Pat, Token};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Field of `Clock` holding the estimated wall-clock time
+const TIMESTAMP_FIELD: &str = "unix_timestamp";
+
+/// Macro name prefixes of assertions (`require!`, `require_gte!`, `assert!`)
+const ASSERTION_MACROS: [&str; 2] = ["require", "assert"];
+
+/// Assertion macros comparing their first two arguments
+const COMPARISON_MACROS: [&str; 4] = ["require_gt", "require_gte", "assert_gt", "assert_ge"];
+
+/// Words of a doc comment showing the clock assumption is documented
+const CLOCK_WORDS: [&str; 4] = ["clock", "drift", "skew", "unix_timestamp"];
+
+pub trait UnixTimestampGateFilters<'a> {
+    fn unix_timestamp_gates(self) -> AstQuery<'a>;
+}
+
+impl<'a> UnixTimestampGateFilters<'a> for AstQuery<'a> {
+    fn unix_timestamp_gates(self) -> AstQuery<'a> {
+        debug!("Filtering comparisons of the clock timestamp gating execution");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let (sig, attrs, block) = match node.data {
+                NodeData::Function(func) => (&func.sig, &func.attrs, func.block.as_ref()),
+                NodeData::ImplFunction(func) => (&func.sig, &func.attrs, &func.block),
+                _ => continue,
+            };
+            if documents_clock(attrs) {
+                continue;
+            }
+
+            let mut finder = GateFinder::default();
+            finder.visit_block(block);
+
+            let name = sig.ident.to_string();
+            for expr in finder.conditions {
+                trace!("Found timestamp comparison gating function: {name}");
+                new_results.push(AstNode::from_expression(expr, &name));
+            }
+            for mac in finder.assertions {
+                trace!("Found timestamp assertion in function: {name}");
+                new_results.push(AstNode::from_macro(mac, &name));
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Whether a doc comment of the function mentions the clock assumption
+fn documents_clock(attrs: &[Attribute]) -> bool {
+    attrs.iter().filter(|attr| attr.path().is_ident("doc")).any(|attr| {
+        let doc = attr.meta.to_token_stream().to_string().to_lowercase();
+        CLOCK_WORDS.iter().any(|word| doc.contains(word))
+    })
+}
+
+/// Whether a binary operator orders its operands
+fn is_ordering(op: &BinOp) -> bool {
+    matches!(op, BinOp::Lt(_) | BinOp::Le(_) | BinOp::Gt(_) | BinOp::Ge(_))
+}
+
+/// Visitor that collects `if` conditions and assertions ordering a timestamp
+#[derive(Default)]
+struct GateFinder<'ast> {
+    /// Locals holding the timestamp or a value computed from it
+    timestamps: HashSet<String>,
+    conditions: Vec<&'ast Expr>,
+    assertions: Vec<&'ast syn::Macro>,
+}
+
+impl GateFinder<'_> {
+    /// Whether an expression reads the timestamp or a local derived from it
+    fn mentions_timestamp(&self, expr: &Expr) -> bool {
+        anchor::flatten_tokens(expr.to_token_stream())
+            .iter()
+            .any(|token| token == TIMESTAMP_FIELD || self.timestamps.contains(token))
+    }
+
+    /// Whether an expression contains an ordering comparison of the timestamp
+    fn compares_timestamp(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Binary(binary) if is_ordering(&binary.op) => {
+                self.mentions_timestamp(&binary.left) || self.mentions_timestamp(&binary.right)
+            }
+            Expr::Binary(binary) => self.compares_timestamp(&binary.left) || self.compares_timestamp(&binary.right),
+            Expr::Unary(unary) => self.compares_timestamp(&unary.expr),
+            Expr::Paren(paren) => self.compares_timestamp(&paren.expr),
+            _ => false,
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for GateFinder<'ast> {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        if let Pat::Ident(pat_ident) = &local.pat
+            && let Some(init) = &local.init
+            && self.mentions_timestamp(&init.expr)
+        {
+            self.timestamps.insert(pat_ident.ident.to_string());
+        }
+        visit::visit_local(self, local);
+    }
+
+    fn visit_expr_if(&mut self, expr: &'ast syn::ExprIf) {
+        if self.compares_timestamp(&expr.cond) {
+            self.conditions.push(&expr.cond);
+        }
+        visit::visit_expr_if(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let name = mac.path.segments.last().map(|s| s.ident.to_string()).unwrap_or_default();
+        if ASSERTION_MACROS.iter().any(|prefix| name.starts_with(prefix))
+            && let Ok(args) = mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+        {
+            let gated = if COMPARISON_MACROS.contains(&name.as_str()) {
+                args.iter().take(2).any(|arg| self.mentions_timestamp(arg))
+            } else {
+                args.first().is_some_and(|condition| self.compares_timestamp(condition))
+            };
+            if gated {
+                self.assertions.push(mac);
+            }
+        }
+        visit::visit_macro(self, mac);
+    }
+}
@@ -0,0 +1,53 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::UnixTimestampGateFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("unix-timestamp-gate")
+        .title("Unix Timestamp Used as an Access Gate")
+        .description("Detects if conditions and require!/assert! checks comparing Clock::unix_timestamp (directly or through a local) against a deadline, as in vesting, unlock or auction gates. The timestamp is a stake-weighted estimate of the validators' clocks and may drift from wall-clock time, so gates relying on it to the second can open early or late. Functions whose doc comment mentions the clock assumption are not reported")
+        .severity(Severity::Low)
+        .rule_type(RuleType::Solana)
+        .category("access-control")
+        .category("best-practices")
+        .tag("clock")
+        .tag("timing")
+        .reference("https://docs.solanalabs.com/implemented-proposals/bank-timestamp-correction")
+        .recommendations(vec![
+            "Document the trust assumption on the clock in the function's doc comment, including the tolerated drift",
+            "Keep time-based gates coarse (hours or days) so that clock drift cannot matter",
+            "Use Clock::slot for short or ordering-sensitive deadlines",
+        ])
+        .vulnerable_example(r#"
+pub fn claim(ctx: Context<Claim>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= ctx.accounts.vesting.unlock_ts, VestingError::Locked);
+    release_tokens(&ctx)
+}
+"#)
+        .fixed_example(r#"
+/// Unlocks at `unlock_ts`. Relies on Clock::unix_timestamp, which may drift
+/// by a few minutes; unlock times are whole days, so the drift is harmless.
+pub fn claim(ctx: Context<Claim>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= ctx.accounts.vesting.unlock_ts, VestingError::Locked);
+    release_tokens(&ctx)
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing access gates based on the clock timestamp");
+
+            AstQuery::new(ast).functions().unix_timestamp_gates()
+        })
+        .build()
+}
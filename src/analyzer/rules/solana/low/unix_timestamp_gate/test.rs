@@ -0,0 +1,78 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::low::unix_timestamp_gate::filters::UnixTimestampGateFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast).functions().unix_timestamp_gates().collect().len()
+    }
+
+    #[test]
+    fn test_timestamp_gates() {
+        let ast: File = parse_quote! {
+            pub fn claim(ctx: Context<Claim>) -> Result<()> {
+                let now = Clock::get()?.unix_timestamp;
+                require!(now >= ctx.accounts.vesting.unlock_ts, VestingError::Locked);
+                release_tokens(&ctx)
+            }
+
+            pub fn bid(ctx: Context<Bid>, amount: u64) -> Result<()> {
+                let clock = Clock::get()?;
+                if clock.unix_timestamp > ctx.accounts.auction.end_ts {
+                    return err!(AuctionError::Ended);
+                }
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 2, "Timestamp comparisons gating execution should be flagged");
+    }
+
+    #[test]
+    fn test_derived_timestamp_and_comparison_macro() {
+        let ast: File = parse_quote! {
+            pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
+                let now = Clock::get()?.unix_timestamp;
+                let elapsed = now - ctx.accounts.stake.start_ts;
+                require_gte!(elapsed, LOCK_PERIOD, StakeError::Locked);
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Values derived from the timestamp should be tracked");
+    }
+
+    #[test]
+    fn test_slot_and_recorded_timestamp() {
+        let ast: File = parse_quote! {
+            pub fn claim(ctx: Context<Claim>) -> Result<()> {
+                let clock = Clock::get()?;
+                require!(clock.slot >= ctx.accounts.vesting.unlock_slot, VestingError::Locked);
+                ctx.accounts.vesting.claimed_at = clock.unix_timestamp;
+                if ctx.accounts.vesting.amount > 0 {
+                    release_tokens(&ctx)?;
+                }
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Slot gates and stored timestamps should not be flagged");
+    }
+
+    #[test]
+    fn test_documented_assumption() {
+        let ast: File = parse_quote! {
+            /// Unlocks at `unlock_ts`. Relies on the cluster clock, which may drift by a few minutes.
+            pub fn claim(ctx: Context<Claim>) -> Result<()> {
+                let now = Clock::get()?.unix_timestamp;
+                require!(now >= ctx.accounts.vesting.unlock_ts, VestingError::Locked);
+                release_tokens(&ctx)
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Documented clock assumptions should not be flagged");
+    }
+}
@@ -0,0 +1,206 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use std::collections::{HashMap, HashSet};
+use syn::punctuated::Punctuated;
+use syn::visit::{self, Visit};
+use syn::{Expr, File, Token};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// `AccountLoader` methods that check the discriminator, which a `zero` account does not have yet
+const CHECKED_LOADS: [&str; 2] = ["load", "load_mut"];
+
+pub trait ZeroAccountReadFilters<'a> {
+    fn zero_account_reads(self, ast: &'a File) -> AstQuery<'a>;
+}
+
+impl<'a> ZeroAccountReadFilters<'a> for AstQuery<'a> {
+    fn zero_account_reads(self, ast: &'a File) -> AstQuery<'a> {
+        debug!("Filtering #[account(zero)] accounts read before they are written");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let NodeData::Struct(struct_item) = node.data else {
+                continue;
+            };
+
+            let zero_accounts: HashSet<String> = anchor::named_fields(struct_item)
+                .into_iter()
+                .filter(|field| anchor::has_constraint(&field.attrs, "zero"))
+                .filter_map(|field| field.ident.as_ref().map(ToString::to_string))
+                .collect();
+            if zero_accounts.is_empty() {
+                continue;
+            }
+
+            for handler in anchor::handlers_for(ast, &struct_item.ident.to_string()) {
+                let mut tracker = ZeroReadTracker::new(&zero_accounts);
+                tracker.visit_block(handler.block);
+
+                for (read, account) in tracker.reads {
+                    trace!("Found zero account '{account}' read before it is written in handler: {}", handler.sig.ident);
+                    new_results.push(match read {
+                        Read::Expression(expr) => AstNode::from_expression(expr, &account),
+                        Read::Macro(mac) => AstNode::from_macro(mac, &account),
+                    });
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Where a `zero` account is read: an expression, or a macro such as `require!` whose arguments read it
+enum Read<'ast> {
+    Expression(&'ast Expr),
+    Macro(&'ast syn::Macro),
+}
+
+/// Visitor that follows, in source order, which fields of the `zero` accounts
+/// were written and reports reads of the others
+struct ZeroReadTracker<'ast, 'z> {
+    zero_accounts: &'z HashSet<String>,
+    /// Locals bound to a `zero` account or its loaded data
+    aliases: HashMap<String, String>,
+    /// Fields written so far, per account
+    written: HashMap<String, HashSet<String>>,
+    /// Accounts written as a whole (`set_inner`, a struct assignment)
+    initialized: HashSet<String>,
+    /// Accounts already reported, so each is reported once per handler
+    reported: HashSet<String>,
+    reads: Vec<(Read<'ast>, String)>,
+}
+
+impl<'ast, 'z> ZeroReadTracker<'ast, 'z> {
+    fn new(zero_accounts: &'z HashSet<String>) -> Self {
+        Self {
+            zero_accounts,
+            aliases: HashMap::new(),
+            written: HashMap::new(),
+            initialized: HashSet::new(),
+            reported: HashSet::new(),
+            reads: Vec::new(),
+        }
+    }
+
+    /// `zero` account an expression refers to: `ctx.accounts.x`, an alias, or
+    /// the data loaded from it (`ctx.accounts.x.load_init()?`)
+    fn account(&self, expr: &Expr) -> Option<String> {
+        let mut expr = expr;
+        loop {
+            match expr {
+                Expr::Reference(reference) => expr = &reference.expr,
+                Expr::Unary(unary) if matches!(unary.op, syn::UnOp::Deref(_)) => expr = &unary.expr,
+                Expr::Try(try_expr) => expr = &try_expr.expr,
+                Expr::MethodCall(call) if call.method.to_string().starts_with("load") => expr = &call.receiver,
+                _ => break,
+            }
+        }
+        let account = anchor::accounts_field(expr).or_else(|| match expr {
+            Expr::Path(path) => path.path.get_ident().and_then(|ident| self.aliases.get(&ident.to_string()).cloned()),
+            _ => None,
+        })?;
+        self.zero_accounts.contains(&account).then_some(account)
+    }
+
+    /// Account and top-level field written by an assignment target (`x.f`, `x.f.g`, `x.f[i]`)
+    fn written_field(&self, mut expr: &Expr) -> Option<(String, String)> {
+        loop {
+            match expr {
+                Expr::Field(field) => {
+                    if let Some(account) = self.account(&field.base) {
+                        return Some((account, field.member.to_token_stream().to_string()));
+                    }
+                    expr = &field.base;
+                }
+                Expr::Index(index) => expr = &index.expr,
+                _ => return None,
+            }
+        }
+    }
+
+    fn record_read(&mut self, read: Read<'ast>, account: String) {
+        if self.reported.insert(account.clone()) {
+            self.reads.push((read, account));
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for ZeroReadTracker<'ast, '_> {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        visit::visit_local(self, local);
+
+        if let (syn::Pat::Ident(pat_ident), Some(init)) = (&local.pat, &local.init)
+            && let Some(account) = self.account(&init.expr)
+        {
+            self.aliases.insert(pat_ident.ident.to_string(), account);
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::Assign(assign) => {
+                self.visit_expr(&assign.right);
+                if let Some(account) = self.account(&assign.left) {
+                    // `**data = State { .. }` writes every field
+                    self.initialized.insert(account);
+                } else if let Some((account, field)) = self.written_field(&assign.left) {
+                    self.written.entry(account).or_default().insert(field);
+                } else {
+                    self.visit_expr(&assign.left);
+                }
+                return;
+            }
+            Expr::MethodCall(call) => {
+                let method = call.method.to_string();
+                if let Some(account) = self.account(&call.receiver) {
+                    if method == "set_inner" {
+                        visit::visit_expr(self, expr);
+                        self.initialized.insert(account);
+                        return;
+                    }
+                    // The discriminator of a `zero` account is only written by `load_init`
+                    if CHECKED_LOADS.contains(&method.as_str()) && !self.initialized.contains(&account) {
+                        self.record_read(Read::Expression(expr), account);
+                    }
+                }
+            }
+            Expr::Field(field) => {
+                if let Some(account) = self.account(&field.base) {
+                    let member = field.member.to_token_stream().to_string();
+                    let written = self.written.get(&account).is_some_and(|fields| fields.contains(&member));
+                    if !written && !self.initialized.contains(&account) {
+                        self.record_read(Read::Expression(expr), account);
+                    }
+                    return;
+                }
+            }
+            _ => {}
+        }
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        // Reads inside require!, msg! and similar macros
+        if let Ok(args) = mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated) {
+            for arg in &args {
+                // Parsed from the macro tokens, so it does not live as long as the AST
+                let mut nested = ZeroReadTracker {
+                    zero_accounts: self.zero_accounts,
+                    aliases: self.aliases.clone(),
+                    written: self.written.clone(),
+                    initialized: self.initialized.clone(),
+                    reported: self.reported.clone(),
+                    reads: Vec::new(),
+                };
+                nested.visit_expr(arg);
+                if let Some((_, account)) = nested.reads.into_iter().next() {
+                    self.record_read(Read::Macro(mac), account);
+                }
+            }
+        }
+        visit::visit_macro(self, mac);
+    }
+}
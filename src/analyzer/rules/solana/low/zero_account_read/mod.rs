@@ -0,0 +1,62 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::ZeroAccountReadFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("zero-account-read")
+        .title("Zero Account Read Before Initialization")
+        .description("Detects #[account(zero)] accounts whose fields the handler reads before writing them, or loads with load()/load_mut() instead of load_init(). A zero account is pre-allocated and entirely zeroed when the instruction runs, so reads only ever see zeros and load()/load_mut() fail on the missing discriminator; code expecting prior data points to a misunderstood initialization flow")
+        .severity(Severity::Low)
+        .rule_type(RuleType::Anchor)
+        .category("initialization")
+        .category("accounts")
+        .tag("correctness")
+        .tag("zero-copy")
+        .recommendations(vec![
+            "Write every field of a zero account before reading it, or initialize it with set_inner",
+            "Load zero-copy accounts created with zero through load_init(), not load() or load_mut()",
+            "If the handler needs existing data, the account is not new: drop zero and use mut with the checks it needs",
+        ])
+        .vulnerable_example(r#"
+#[derive(Accounts)]
+pub struct CreateBook<'info> {
+    #[account(zero)]
+    pub book: AccountLoader<'info, OrderBook>,
+    pub authority: Signer<'info>,
+}
+
+pub fn create_book(ctx: Context<CreateBook>) -> Result<()> {
+    let mut book = ctx.accounts.book.load_mut()?;
+    book.next_order_id += 1;
+    book.authority = ctx.accounts.authority.key();
+    Ok(())
+}
+"#)
+        .fixed_example(r#"
+pub fn create_book(ctx: Context<CreateBook>) -> Result<()> {
+    let mut book = ctx.accounts.book.load_init()?;
+    book.next_order_id = 1;
+    book.authority = ctx.accounts.authority.key();
+    Ok(())
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing #[account(zero)] accounts read before initialization");
+
+            AstQuery::new(ast)
+                .structs()
+                .derives_accounts()
+                .zero_account_reads(ast)
+        })
+        .build()
+}
@@ -0,0 +1,109 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::low::zero_account_read::filters::ZeroAccountReadFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast)
+            .structs()
+            .derives_accounts()
+            .zero_account_reads(ast)
+            .collect()
+            .len()
+    }
+
+    #[test]
+    fn test_field_read_before_write() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct CreateBook<'info> {
+                #[account(zero)]
+                pub book: AccountLoader<'info, OrderBook>,
+                pub authority: Signer<'info>,
+            }
+
+            pub fn create_book(ctx: Context<CreateBook>) -> Result<()> {
+                let mut book = ctx.accounts.book.load_init()?;
+                book.authority = ctx.accounts.authority.key();
+                book.next_order_id += 1;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "The compound assignment reads a field that was never written");
+    }
+
+    #[test]
+    fn test_checked_load_and_macro_read() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct CreateBook<'info> {
+                #[account(zero)]
+                pub book: AccountLoader<'info, OrderBook>,
+                #[account(zero)]
+                pub registry: Account<'info, Registry>,
+            }
+
+            pub fn create_book(ctx: Context<CreateBook>) -> Result<()> {
+                let book = ctx.accounts.book.load_mut()?;
+                require!(ctx.accounts.registry.count < MAX, RegistryError::Full);
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 2, "load_mut on a zero account and the require! read should be flagged");
+    }
+
+    #[test]
+    fn test_written_before_read() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Create<'info> {
+                #[account(zero)]
+                pub book: AccountLoader<'info, OrderBook>,
+                #[account(zero)]
+                pub registry: Account<'info, Registry>,
+            }
+
+            pub fn create(ctx: Context<Create>, capacity: u64) -> Result<()> {
+                let mut book = ctx.accounts.book.load_init()?;
+                book.capacity = capacity;
+                book.orders[0] = Order::default();
+                msg!("capacity {}", book.capacity);
+                book.orders[1] = book.orders[0];
+
+                let registry = &mut ctx.accounts.registry;
+                registry.set_inner(Registry { count: 0, authority: ctx.accounts.authority.key() });
+                require!(registry.count == 0, RegistryError::NotEmpty);
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Fields written before they are read should not be flagged");
+    }
+
+    #[test]
+    fn test_regular_accounts_ignored() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Update<'info> {
+                #[account(mut)]
+                pub book: AccountLoader<'info, OrderBook>,
+                #[account(init, payer = payer, space = 8 + 64)]
+                pub registry: Account<'info, Registry>,
+            }
+
+            pub fn update(ctx: Context<Update>) -> Result<()> {
+                let mut book = ctx.accounts.book.load_mut()?;
+                book.next_order_id += 1;
+                ctx.accounts.registry.count += 1;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Only zero accounts are tracked");
+    }
+}
@@ -0,0 +1,207 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use std::collections::HashSet;
+use syn::punctuated::Punctuated;
+use syn::visit::{self, Visit};
+use syn::{BareFnArg, Expr, ExprArray, FnArg, Item, ItemStruct, Pat, Token, Type};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Functions deriving a program address from a seeds slice
+const DERIVATION_FUNCTIONS: [&str; 3] = ["find_program_address", "try_find_program_address", "create_program_address"];
+
+/// Methods viewing a value as bytes without changing its length
+const BYTE_VIEWS: [&str; 6] = ["as_bytes", "as_ref", "as_slice", "as_str", "borrow", "to_vec"];
+
+pub trait AmbiguousPdaSeedsFilters<'a> {
+    fn ambiguous_pda_seeds(self) -> AstQuery<'a>;
+}
+
+impl<'a> AmbiguousPdaSeedsFilters<'a> for AstQuery<'a> {
+    fn ambiguous_pda_seeds(self) -> AstQuery<'a> {
+        debug!("Filtering PDA seeds built from variable-length user input");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let NodeData::File(file) = node.data else {
+                continue;
+            };
+
+            // Anchor `seeds = [...]` constraints using `#[instruction(...)]` arguments
+            for item in &file.items {
+                let Item::Struct(item_struct) = item else {
+                    continue;
+                };
+                if !anchor::is_accounts_struct(item_struct) {
+                    continue;
+                }
+
+                let inputs = variable_length_instruction_args(item_struct);
+                if inputs.is_empty() {
+                    continue;
+                }
+                let checked: HashSet<String> = anchor::handlers_for(file, &item_struct.ident.to_string())
+                    .iter()
+                    .flat_map(|handler| length_checked(handler.block.to_token_stream()))
+                    .collect();
+
+                for field in anchor::named_fields(item_struct) {
+                    let Some(Expr::Array(seeds)) = anchor::constraint_tokens(&field.attrs, "seeds")
+                        .and_then(|tokens| syn::parse2::<Expr>(tokens).ok())
+                    else {
+                        continue;
+                    };
+                    if is_ambiguous(&seeds, &inputs, &checked) {
+                        trace!("Found ambiguous seeds on account {:?}", field.ident);
+                        new_results.push(AstNode::from_field(field));
+                    }
+                }
+            }
+
+            // Native find_program_address(&[...]) calls using function parameters
+            let mut finder = DerivationFinder::default();
+            finder.visit_file(file);
+            for (call, seeds, inputs, checked) in finder.derivations {
+                if is_ambiguous(seeds, &inputs, &checked) {
+                    trace!("Found ambiguous seeds in {}", call.to_token_stream());
+                    new_results.push(AstNode::from_expression(call, "find_program_address"));
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// A seeds array is ambiguous when two variable-length inputs are adjacent, since
+/// seeds are concatenated without length prefix (`"ab" + "c"` equals `"a" + "bc"`),
+/// or when a variable-length input has no length check
+fn is_ambiguous(seeds: &ExprArray, inputs: &HashSet<String>, checked: &HashSet<String>) -> bool {
+    let variable: Vec<Option<String>> = seeds.elems.iter().map(|seed| variable_input(seed, inputs)).collect();
+
+    let adjacent = variable.windows(2).any(|pair| pair[0].is_some() && pair[1].is_some());
+    let unbounded = variable.iter().flatten().any(|input| !checked.contains(input));
+    adjacent || unbounded
+}
+
+/// Input a seed views as bytes, if it is one of the variable-length inputs
+fn variable_input(mut seed: &Expr, inputs: &HashSet<String>) -> Option<String> {
+    loop {
+        match seed {
+            Expr::Reference(reference) => seed = &reference.expr,
+            Expr::Paren(paren) => seed = &paren.expr,
+            Expr::MethodCall(call) if BYTE_VIEWS.contains(&call.method.to_string().as_str()) => {
+                seed = &call.receiver;
+            }
+            Expr::Path(path) => {
+                let name = path.path.get_ident()?.to_string();
+                return inputs.contains(&name).then_some(name);
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// Whether a type is a string or byte buffer of caller-chosen length
+fn is_variable_length(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(reference) => is_variable_length(&reference.elem),
+        Type::Slice(_) => true,
+        Type::Path(_) => matches!(anchor::type_name(ty).as_deref(), Some("String" | "str" | "Vec")),
+        _ => false,
+    }
+}
+
+/// Names of the `#[instruction(...)]` arguments holding strings or byte buffers
+fn variable_length_instruction_args(item_struct: &ItemStruct) -> HashSet<String> {
+    item_struct
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("instruction"))
+        .filter_map(|attr| attr.parse_args_with(Punctuated::<BareFnArg, Token![,]>::parse_terminated).ok())
+        .flatten()
+        .filter(|arg| is_variable_length(&arg.ty))
+        .filter_map(|arg| arg.name.map(|(name, _)| name.to_string()))
+        .collect()
+}
+
+/// Names whose length is read (`name.len()`), e.g. in a `require!` bound
+fn length_checked(tokens: proc_macro2::TokenStream) -> HashSet<String> {
+    anchor::flatten_tokens(tokens)
+        .windows(3)
+        .filter(|window| window[1] == "." && window[2] == "len")
+        .map(|window| window[0].clone())
+        .collect()
+}
+
+/// A derivation call with its seeds, the variable-length parameters of the
+/// enclosing function and the parameters whose length it checks
+type Derivation<'ast> = (&'ast Expr, &'ast ExprArray, HashSet<String>, HashSet<String>);
+
+/// Visitor that collects program address derivations with literal seed arrays
+#[derive(Default)]
+struct DerivationFinder<'ast> {
+    /// Variable-length parameters of the function being visited
+    inputs: HashSet<String>,
+    /// Parameters whose length the function being visited checks
+    checked: HashSet<String>,
+    derivations: Vec<Derivation<'ast>>,
+}
+
+impl DerivationFinder<'_> {
+    fn enter(&mut self, sig: &syn::Signature, block: &syn::Block) -> (HashSet<String>, HashSet<String>) {
+        let inputs = sig
+            .inputs
+            .iter()
+            .filter_map(|input| match input {
+                FnArg::Typed(pat_type) if is_variable_length(&pat_type.ty) => match &*pat_type.pat {
+                    Pat::Ident(pat_ident) => Some(pat_ident.ident.to_string()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+        let checked = length_checked(block.to_token_stream());
+        (
+            std::mem::replace(&mut self.inputs, inputs),
+            std::mem::replace(&mut self.checked, checked),
+        )
+    }
+}
+
+impl<'ast> Visit<'ast> for DerivationFinder<'ast> {
+    fn visit_item_fn(&mut self, func: &'ast syn::ItemFn) {
+        let saved = self.enter(&func.sig, &func.block);
+        visit::visit_item_fn(self, func);
+        (self.inputs, self.checked) = saved;
+    }
+
+    fn visit_impl_item_fn(&mut self, func: &'ast syn::ImplItemFn) {
+        let saved = self.enter(&func.sig, &func.block);
+        visit::visit_impl_item_fn(self, func);
+        (self.inputs, self.checked) = saved;
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let Expr::Call(call) = expr
+            && let Expr::Path(path) = &*call.func
+            && path
+                .path
+                .segments
+                .last()
+                .is_some_and(|s| DERIVATION_FUNCTIONS.contains(&s.ident.to_string().as_str()))
+            && !self.inputs.is_empty()
+        {
+            let mut seeds = call.args.first();
+            while let Some(Expr::Reference(reference)) = seeds {
+                seeds = Some(&reference.expr);
+            }
+            if let Some(Expr::Array(array)) = seeds {
+                self.derivations
+                    .push((expr, array, self.inputs.clone(), self.checked.clone()));
+            }
+        }
+        visit::visit_expr(self, expr);
+    }
+}
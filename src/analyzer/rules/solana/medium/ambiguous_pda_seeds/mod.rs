@@ -0,0 +1,60 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::AmbiguousPdaSeedsFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("ambiguous-pda-seeds")
+        .title("PDA Seeds From Variable-Length Input")
+        .description("Detects PDA seeds (seeds = [...] constraints or find_program_address calls) built from user-supplied strings or byte buffers. Seeds are concatenated without length prefix before hashing, so two adjacent variable-length seeds let distinct inputs derive the same address (\"ab\" + \"c\" and \"a\" + \"bc\"), and an input without a length check can exceed the 32-byte seed limit or collide with the seeds of another PDA")
+        .severity(Severity::Medium)
+        .rule_type(RuleType::Solana)
+        .category("pda")
+        .tag("security")
+        .tag("pda")
+        .reference("https://github.com/coral-xyz/sealevel-attacks/tree/master/programs/8-pda-sharing")
+        .recommendations(vec![
+            "Prefer fixed-length seeds: pubkeys, integers with to_le_bytes(), or a hash of the user input",
+            "Separate variable-length seeds with a fixed-length seed, or prefix each with its length",
+            "Bound the length of user-supplied seeds with require!(name.len() <= MAX_LEN, ...)",
+        ])
+        .vulnerable_example(r#"
+#[derive(Accounts)]
+#[instruction(namespace: String, name: String)]
+pub struct Register<'info> {
+    #[account(init, payer = owner, space = 8 + Entry::INIT_SPACE,
+              seeds = [b"entry", namespace.as_bytes(), name.as_bytes()], bump)]
+    pub entry: Account<'info, Entry>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+"#)
+        .fixed_example(r#"
+#[derive(Accounts)]
+#[instruction(namespace: String, name: String)]
+pub struct Register<'info> {
+    #[account(init, payer = owner, space = 8 + Entry::INIT_SPACE,
+              seeds = [b"entry", &hash(namespace.as_bytes()).to_bytes(), &hash(name.as_bytes()).to_bytes()], bump)]
+    pub entry: Account<'info, Entry>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing PDA seeds");
+
+            AstQuery::new(ast).ambiguous_pda_seeds()
+        })
+        .build()
+}
@@ -0,0 +1,96 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::medium::ambiguous_pda_seeds::filters::AmbiguousPdaSeedsFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast).ambiguous_pda_seeds().collect().len()
+    }
+
+    #[test]
+    fn test_adjacent_variable_seeds() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            #[instruction(namespace: String, name: String)]
+            pub struct Register<'info> {
+                #[account(init, payer = owner, space = 8 + Entry::INIT_SPACE,
+                          seeds = [b"entry", namespace.as_bytes(), name.as_bytes()], bump)]
+                pub entry: Account<'info, Entry>,
+                #[account(mut)]
+                pub owner: Signer<'info>,
+            }
+
+            pub fn register(ctx: Context<Register>, namespace: String, name: String) -> Result<()> {
+                require!(namespace.len() <= 16 && name.len() <= 16, RegistryError::NameTooLong);
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Adjacent variable-length seeds are ambiguous even when bounded");
+    }
+
+    #[test]
+    fn test_unbounded_seed() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            #[instruction(name: String)]
+            pub struct CreateProfile<'info> {
+                #[account(init, payer = user, space = 8 + Profile::INIT_SPACE,
+                          seeds = [b"profile", user.key().as_ref(), name.as_bytes()], bump)]
+                pub profile: Account<'info, Profile>,
+                #[account(mut)]
+                pub user: Signer<'info>,
+            }
+
+            pub fn create_profile(ctx: Context<CreateProfile>, name: String) -> Result<()> {
+                ctx.accounts.profile.name = name;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "A variable-length seed without length check should be flagged");
+    }
+
+    #[test]
+    fn test_bounded_and_fixed_seeds() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            #[instruction(name: String, index: u64)]
+            pub struct CreateProfile<'info> {
+                #[account(init, payer = user, space = 8 + Profile::INIT_SPACE,
+                          seeds = [b"profile", user.key().as_ref(), name.as_bytes()], bump)]
+                pub profile: Account<'info, Profile>,
+                #[account(seeds = [b"slot", index.to_le_bytes().as_ref()], bump)]
+                pub slot: Account<'info, Slot>,
+                #[account(mut)]
+                pub user: Signer<'info>,
+            }
+
+            pub fn create_profile(ctx: Context<CreateProfile>, name: String, index: u64) -> Result<()> {
+                require!(name.len() <= 32, ProfileError::NameTooLong);
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Bounded and fixed-length seeds should not be flagged");
+    }
+
+    #[test]
+    fn test_native_find_program_address() {
+        let ast: File = parse_quote! {
+            pub fn derive_entry(program_id: &Pubkey, namespace: &str, name: &[u8]) -> (Pubkey, u8) {
+                Pubkey::find_program_address(&[namespace.as_bytes(), name], program_id)
+            }
+
+            pub fn derive_vault(program_id: &Pubkey, owner: &Pubkey, label: &str) -> (Pubkey, u8) {
+                assert!(label.len() <= 32);
+                Pubkey::find_program_address(&[b"vault", owner.as_ref(), label.as_bytes()], program_id)
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Only the derivation with adjacent variable-length seeds is ambiguous");
+    }
+}
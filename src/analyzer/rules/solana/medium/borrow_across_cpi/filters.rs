@@ -0,0 +1,164 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use syn::visit::{self, Visit};
+use syn::{Expr, Member, Pat};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Methods returning a guard that keeps the account data or lamports mutably borrowed
+const GUARD_METHODS: [&str; 6] = [
+    "try_borrow_mut_data",
+    "try_borrow_mut_lamports",
+    "borrow_mut_data",
+    "borrow_mut_lamports",
+    "load_mut",
+    "load_init",
+];
+
+/// `RefCell` methods guarding `AccountInfo::data` or `AccountInfo::lamports`
+const REFCELL_GUARD_METHODS: [&str; 2] = ["borrow_mut", "try_borrow_mut"];
+
+/// Functions performing a cross-program invocation
+const CPI_FUNCTIONS: [&str; 4] = ["invoke", "invoke_signed", "invoke_unchecked", "invoke_signed_unchecked"];
+
+pub trait BorrowAcrossCpiFilters<'a> {
+    fn borrows_across_cpi(self) -> AstQuery<'a>;
+}
+
+impl<'a> BorrowAcrossCpiFilters<'a> for AstQuery<'a> {
+    fn borrows_across_cpi(self) -> AstQuery<'a> {
+        debug!("Filtering CPIs made while a mutable account borrow is alive");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let (sig, block) = match node.data {
+                NodeData::Function(func) => (&func.sig, func.block.as_ref()),
+                NodeData::ImplFunction(func) => (&func.sig, &func.block),
+                _ => continue,
+            };
+
+            let mut finder = BorrowFinder::default();
+            finder.visit_block(block);
+
+            for expr in finder.cpis {
+                trace!("Found CPI with a live mutable borrow in function: {}", sig.ident);
+                new_results.push(AstNode::from_expression(expr, &sig.ident.to_string()));
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Strip references, parentheses and `?` around an expression
+fn strip(mut expr: &Expr) -> &Expr {
+    loop {
+        match expr {
+            Expr::Reference(reference) => expr = &reference.expr,
+            Expr::Paren(paren) => expr = &paren.expr,
+            Expr::Try(try_expr) => expr = &try_expr.expr,
+            _ => return expr,
+        }
+    }
+}
+
+/// Name of the account a receiver refers to: `vault` for `vault`,
+/// `ctx.accounts.vault`, `vault.data` or `vault.to_account_info()`
+fn account_name(expr: &Expr) -> Option<String> {
+    match strip(expr) {
+        Expr::Path(path) => path.path.get_ident().map(|ident| ident.to_string()),
+        Expr::Field(field) => match &field.member {
+            Member::Named(name) if name == "data" || name == "lamports" => account_name(&field.base),
+            Member::Named(name) => Some(name.to_string()),
+            Member::Unnamed(_) => None,
+        },
+        Expr::MethodCall(call) => account_name(&call.receiver),
+        _ => None,
+    }
+}
+
+/// Account whose borrow guard an initializer returns, or `None` if it holds no guard.
+/// The inner `Option` is `None` when the account cannot be named.
+fn guarded_account(init: &Expr) -> Option<Option<String>> {
+    let Expr::MethodCall(call) = strip(init) else {
+        return None;
+    };
+    let method = call.method.to_string();
+    let is_guard = GUARD_METHODS.contains(&method.as_str())
+        || (REFCELL_GUARD_METHODS.contains(&method.as_str())
+            && matches!(strip(&call.receiver), Expr::Field(field)
+                if matches!(&field.member, Member::Named(name) if name == "data" || name == "lamports")));
+    is_guard.then(|| account_name(&call.receiver))
+}
+
+/// Borrow guard bound to a local variable
+struct Guard {
+    variable: String,
+    account: Option<String>,
+    /// Block nesting depth the guard was declared at
+    depth: usize,
+}
+
+/// Visitor that tracks the live borrow guards of a function and collects the
+/// CPIs made while one of them covers an account passed to the CPI
+#[derive(Default)]
+struct BorrowFinder<'ast> {
+    live: Vec<Guard>,
+    depth: usize,
+    cpis: Vec<&'ast Expr>,
+}
+
+impl<'ast> Visit<'ast> for BorrowFinder<'ast> {
+    fn visit_block(&mut self, block: &'ast syn::Block) {
+        self.depth += 1;
+        visit::visit_block(self, block);
+        // Guards are released at the end of the block that declared them
+        let depth = self.depth;
+        self.live.retain(|guard| guard.depth < depth);
+        self.depth -= 1;
+    }
+
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        visit::visit_local(self, local);
+
+        let mut pat = &local.pat;
+        if let Pat::Type(pat_type) = pat {
+            pat = &pat_type.pat;
+        }
+        if let Pat::Ident(pat_ident) = pat
+            && let Some(init) = &local.init
+            && let Some(account) = guarded_account(&init.expr)
+        {
+            self.live.push(Guard {
+                variable: pat_ident.ident.to_string(),
+                account,
+                depth: self.depth,
+            });
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let Expr::Call(call) = expr
+            && let Expr::Path(path) = &*call.func
+            && let Some(last) = path.path.segments.last()
+        {
+            let name = last.ident.to_string();
+            if name == "drop" {
+                let released: Vec<String> = call.args.iter().filter_map(account_name).collect();
+                self.live.retain(|guard| !released.contains(&guard.variable));
+            } else if !self.live.is_empty() {
+                let tokens = anchor::flatten_tokens(call.args.to_token_stream());
+                let is_cpi = CPI_FUNCTIONS.contains(&name.as_str()) || tokens.iter().any(|token| token == "CpiContext");
+                let conflicts = self
+                    .live
+                    .iter()
+                    .any(|guard| guard.account.as_ref().is_none_or(|account| tokens.contains(account)));
+                if is_cpi && conflicts {
+                    self.cpis.push(expr);
+                }
+            }
+        }
+        visit::visit_expr(self, expr);
+    }
+}
@@ -0,0 +1,58 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::BorrowAcrossCpiFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("borrow-across-cpi")
+        .title("Mutable Account Borrow Held Across CPI")
+        .description("Detects CPIs (invoke, invoke_signed or an Anchor CpiContext call) made while a mutable borrow of the same account's data or lamports is still alive (`let data = account.try_borrow_mut_data()?`, `account.data.borrow_mut()`, `loader.load_mut()?`). The runtime borrows every account passed to the CPI, so the outstanding RefMut makes the instruction fail with a borrow error")
+        .severity(Severity::Medium)
+        .rule_type(RuleType::Solana)
+        .category("cpi")
+        .category("accounts")
+        .tag("correctness")
+        .tag("cpi")
+        .reference("https://solana.com/docs/core/cpi")
+        .recommendations(vec![
+            "Scope the borrow in its own block so it is released before the CPI",
+            "Or release it explicitly with drop(data) before invoking",
+        ])
+        .vulnerable_example(r#"
+pub fn process(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let vault = &accounts[0];
+    let mut data = vault.try_borrow_mut_data()?;
+    data[0] = 1;
+    invoke(&transfer_ix, &[vault.clone(), destination.clone()])?;
+    Ok(())
+}
+"#)
+        .fixed_example(r#"
+pub fn process(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let vault = &accounts[0];
+    {
+        let mut data = vault.try_borrow_mut_data()?;
+        data[0] = 1;
+    }
+    invoke(&transfer_ix, &[vault.clone(), destination.clone()])?;
+    Ok(())
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing mutable account borrows held across CPIs");
+
+            AstQuery::new(ast)
+                .functions()
+                .borrows_across_cpi()
+        })
+        .build()
+}
@@ -0,0 +1,91 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::medium::borrow_across_cpi::filters::BorrowAcrossCpiFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast).functions().borrows_across_cpi().collect().len()
+    }
+
+    #[test]
+    fn test_borrow_held_across_invoke() {
+        let ast: File = parse_quote! {
+            pub fn process(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+                let vault = &accounts[0];
+                let destination = &accounts[1];
+                let mut data = vault.try_borrow_mut_data()?;
+                data[0] = 1;
+                invoke(&transfer_ix, &[vault.clone(), destination.clone()])?;
+
+                let mut lamports = destination.lamports.borrow_mut();
+                invoke_signed(&ix, &[destination.clone()], &[seeds])?;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 2, "Both CPIs run while a borrow of a passed account is alive");
+    }
+
+    #[test]
+    fn test_released_borrows() {
+        let ast: File = parse_quote! {
+            pub fn process(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+                let vault = &accounts[0];
+                let destination = &accounts[1];
+                {
+                    let mut data = vault.try_borrow_mut_data()?;
+                    data[0] = 1;
+                }
+                invoke(&transfer_ix, &[vault.clone(), destination.clone()])?;
+
+                let mut data = destination.data.borrow_mut();
+                data[0] = 2;
+                drop(data);
+                invoke(&transfer_ix, &[destination.clone()])?;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Borrows released before the CPI should not be flagged");
+    }
+
+    #[test]
+    fn test_borrow_of_other_account() {
+        let ast: File = parse_quote! {
+            pub fn process(accounts: &[AccountInfo]) -> ProgramResult {
+                let state = &accounts[0];
+                let source = &accounts[1];
+                let mut data = state.try_borrow_mut_data()?;
+                let value = State::try_from_slice(&source.data.borrow_mut())?;
+                invoke(&ix, &[source.clone()])?;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Borrows of accounts not passed to the CPI should not be flagged");
+    }
+
+    #[test]
+    fn test_load_mut_across_anchor_cpi() {
+        let ast: File = parse_quote! {
+            pub fn settle(ctx: Context<Settle>, amount: u64) -> Result<()> {
+                let mut pool = ctx.accounts.pool.load_mut()?;
+                pool.total -= amount;
+                token::transfer(
+                    CpiContext::new(ctx.accounts.token_program.to_account_info(), Transfer {
+                        from: ctx.accounts.pool.to_account_info(),
+                        to: ctx.accounts.user_tokens.to_account_info(),
+                        authority: ctx.accounts.pool.to_account_info(),
+                    }),
+                    amount,
+                )?;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "A zero-copy load_mut held across a CPI should be flagged");
+    }
+}
@@ -13,8 +13,10 @@ pub fn create_rule() -> Arc<dyn Rule> {
     RuleBuilder::new()
         .id("solana-division-by-zero")
         .severity(Severity::Medium)
+        .category("arithmetic")
         .title("Division Without Zero Check")
         .description("Detects division operations without zero verification")
+        .reference("CWE-369: Divide By Zero (https://cwe.mitre.org/data/definitions/369.html)")
         .recommendations(vec![
             "Add explicit zero checks before division operations: if divisor == 0 { return Err(...) }",
             "Use checked division methods: checked_div() which returns Option<T>",
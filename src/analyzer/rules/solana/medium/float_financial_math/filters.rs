@@ -0,0 +1,146 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use std::collections::HashSet;
+use syn::visit::{self, Visit};
+use syn::{BinOp, Expr, FnArg, Pat, Type};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Parts of identifiers naming token amounts, prices and balances
+const MONEY_WORDS: [&str; 14] = [
+    "amount", "price", "balance", "lamports", "fee", "reward", "supply", "collateral", "debt", "liquidity",
+    "interest", "stake", "shares", "payout",
+];
+
+pub trait FloatFinancialMathFilters<'a> {
+    fn float_financial_math(self) -> AstQuery<'a>;
+}
+
+impl<'a> FloatFinancialMathFilters<'a> for AstQuery<'a> {
+    fn float_financial_math(self) -> AstQuery<'a> {
+        debug!("Filtering floating-point arithmetic on amounts and prices in instruction handlers");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let (sig, block) = match node.data {
+                NodeData::Function(func) => (&func.sig, func.block.as_ref()),
+                NodeData::ImplFunction(func) => (&func.sig, &func.block),
+                _ => continue,
+            };
+
+            if !anchor::is_instruction_handler(sig) {
+                continue;
+            }
+
+            let mut finder = FloatMathFinder::default();
+            for input in &sig.inputs {
+                if let FnArg::Typed(pat_type) = input
+                    && let Pat::Ident(pat_ident) = &*pat_type.pat
+                {
+                    finder.record(&pat_ident.ident.to_string(), Some(&pat_type.ty), None);
+                }
+            }
+            finder.visit_block(block);
+
+            let handler_name = sig.ident.to_string();
+            for expr in finder.found {
+                trace!("Found floating-point arithmetic on amounts in handler: {handler_name}");
+                new_results.push(AstNode::from_expression(expr, &handler_name));
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+fn is_float_type(ty: &Type) -> bool {
+    matches!(anchor::type_name(ty).as_deref(), Some("f32" | "f64"))
+}
+
+fn is_money_name(name: &str) -> bool {
+    let name = name.to_lowercase();
+    MONEY_WORDS.iter().any(|word| name.contains(word))
+}
+
+/// Whether a token is a float literal (`0.5`, `1e9`, `2f64`)
+fn is_float_literal(token: &str) -> bool {
+    token.starts_with(|c: char| c.is_ascii_digit())
+        && !token.starts_with("0x")
+        && (token.contains('.') || token.contains(['e', 'E']) || token.ends_with("f32") || token.ends_with("f64"))
+}
+
+/// Visitor that collects arithmetic mixing floating-point values with amounts
+#[derive(Default)]
+struct FloatMathFinder<'ast> {
+    /// Locals and parameters holding floats
+    floats: HashSet<String>,
+    /// Locals and parameters holding amounts, or values computed from them
+    money: HashSet<String>,
+    /// Reported expressions
+    found: Vec<&'ast Expr>,
+}
+
+impl FloatMathFinder<'_> {
+    /// Record what a local or parameter holds from its type and initializer
+    fn record(&mut self, name: &str, ty: Option<&Type>, init: Option<&Expr>) {
+        let tokens = init.map(|expr| anchor::flatten_tokens(expr.to_token_stream())).unwrap_or_default();
+        if ty.is_some_and(is_float_type) || self.is_float(&tokens) {
+            self.floats.insert(name.to_string());
+        }
+        if is_money_name(name) || self.mentions_money(&tokens) {
+            self.money.insert(name.to_string());
+        }
+    }
+
+    fn is_float(&self, tokens: &[String]) -> bool {
+        tokens.iter().any(|token| {
+            token == "f32" || token == "f64" || is_float_literal(token) || self.floats.contains(token)
+        })
+    }
+
+    fn mentions_money(&self, tokens: &[String]) -> bool {
+        tokens.iter().any(|token| self.money.contains(token) || is_money_name(token))
+    }
+}
+
+impl<'ast> Visit<'ast> for FloatMathFinder<'ast> {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        visit::visit_local(self, local);
+
+        let (pat, ty) = match &local.pat {
+            Pat::Type(pat_type) => (&*pat_type.pat, Some(&*pat_type.ty)),
+            pat => (pat, None),
+        };
+        if let Pat::Ident(pat_ident) = pat {
+            let init = local.init.as_ref().map(|init| &*init.expr);
+            self.record(&pat_ident.ident.to_string(), ty, init);
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let Expr::Binary(binary) = expr
+            && matches!(
+                binary.op,
+                BinOp::Add(_)
+                    | BinOp::Sub(_)
+                    | BinOp::Mul(_)
+                    | BinOp::Div(_)
+                    | BinOp::Rem(_)
+                    | BinOp::AddAssign(_)
+                    | BinOp::SubAssign(_)
+                    | BinOp::MulAssign(_)
+                    | BinOp::DivAssign(_)
+                    | BinOp::RemAssign(_)
+            )
+        {
+            let tokens = anchor::flatten_tokens(expr.to_token_stream());
+            if self.is_float(&tokens) && self.mentions_money(&tokens) {
+                // The whole computation is reported once, not each nested operation
+                self.found.push(expr);
+                return;
+            }
+        }
+        visit::visit_expr(self, expr);
+    }
+}
@@ -0,0 +1,57 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::FloatFinancialMathFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("float-financial-math")
+        .title("Floating-Point Arithmetic on Amounts or Prices")
+        .description("Detects f32/f64 arithmetic in instruction handlers on token amounts, prices, balances or fees: float-typed locals and parameters, values cast with `as f64` and float literals combined with such values. Floating point rounds silently, loses precision above 2^53 and its results can differ between environments, so balances computed this way drift and can be gamed with crafted amounts")
+        .severity(Severity::Medium)
+        .rule_type(RuleType::General)
+        .category("arithmetic")
+        .tag("arithmetic")
+        .tag("precision")
+        .reference("CWE-1339: Insufficient Precision or Accuracy of a Real Number (https://cwe.mitre.org/data/definitions/1339.html)")
+        .recommendations(vec![
+            "Use fixed-point integer math: scale values by a constant such as 10^9 and compute in u64 or u128",
+            "Multiply before dividing and use checked_mul/checked_div so the rounding is explicit",
+            "Express rates in basis points (u16) instead of fractions",
+        ])
+        .vulnerable_example(r#"
+pub fn swap(ctx: Context<Swap>, amount_in: u64) -> Result<()> {
+    let price = ctx.accounts.pool.price as f64 / 1e6;
+    let amount_out = (amount_in as f64 * price) as u64;
+    token::transfer(ctx.accounts.transfer_ctx(), amount_out)
+}
+"#)
+        .fixed_example(r#"
+pub const PRICE_SCALE: u128 = 1_000_000;
+
+pub fn swap(ctx: Context<Swap>, amount_in: u64) -> Result<()> {
+    let amount_out = (amount_in as u128)
+        .checked_mul(ctx.accounts.pool.price as u128)
+        .and_then(|value| value.checked_div(PRICE_SCALE))
+        .and_then(|value| u64::try_from(value).ok())
+        .ok_or(SwapError::MathOverflow)?;
+    token::transfer(ctx.accounts.transfer_ctx(), amount_out)
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing instruction handlers for floating-point arithmetic on amounts");
+
+            AstQuery::new(ast)
+                .functions()
+                .float_financial_math()
+        })
+        .build()
+}
@@ -0,0 +1,68 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::medium::float_financial_math::filters::FloatFinancialMathFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast)
+            .functions()
+            .float_financial_math()
+            .collect()
+            .len()
+    }
+
+    #[test]
+    fn test_float_amount_math() {
+        let ast: File = parse_quote! {
+            pub fn swap(ctx: Context<Swap>, amount_in: u64) -> Result<()> {
+                let price = ctx.accounts.pool.price as f64 / 1e6;
+                let amount_out = (amount_in as f64 * price) as u64;
+                token::transfer(ctx.accounts.transfer_ctx(), amount_out)
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 2, "Float price and amount computations should be flagged");
+    }
+
+    #[test]
+    fn test_float_parameter_and_derived_value() {
+        let ast: File = parse_quote! {
+            pub fn set_reward(ctx: Context<SetReward>, ratio: f64) -> Result<()> {
+                let pool = ctx.accounts.vault.total_staked;
+                let share = ratio * 100.0;
+                ctx.accounts.user.reward = (share * pool as f64) as u64;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Float math reaching the reward should be flagged");
+    }
+
+    #[test]
+    fn test_integer_and_unrelated_float_math() {
+        let ast: File = parse_quote! {
+            pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+                let fee = amount.checked_mul(FEE_BPS).unwrap() / 10_000;
+                let progress = ctx.accounts.campaign.elapsed as f64 / 60.0;
+                msg!("progress {}", progress);
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Integer amount math and floats unrelated to amounts should not be flagged");
+    }
+
+    #[test]
+    fn test_non_handler_is_ignored() {
+        let ast: File = parse_quote! {
+            pub fn display_price(price: u64) -> f64 {
+                price as f64 / 1e6
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Functions that are not instruction handlers should not be flagged");
+    }
+}
@@ -0,0 +1,237 @@
+use log::{debug, trace};
+use std::collections::{HashMap, HashSet};
+use syn::visit::{self, Visit};
+use syn::{Expr, File, Pat};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Account types that do not carry deserialized data with stored pubkeys
+const NON_DATA_ACCOUNT_TYPES: [&str; 7] = [
+    "Signer",
+    "AccountInfo",
+    "UncheckedAccount",
+    "Program",
+    "SystemAccount",
+    "Sysvar",
+    "Interface",
+];
+
+/// Constraints of accounts created by the instruction, whose stored fields are
+/// still zeroed when the constraints are checked
+const CREATING_CONSTRAINTS: [&str; 3] = ["init", "init_if_needed", "zero"];
+
+pub trait MissingHasOneFilters<'a> {
+    fn missing_has_one(self, ast: &'a File) -> AstQuery<'a>;
+}
+
+impl<'a> MissingHasOneFilters<'a> for AstQuery<'a> {
+    fn missing_has_one(self, ast: &'a File) -> AstQuery<'a> {
+        debug!("Filtering accounts whose stored pubkeys are used without has_one");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let NodeData::Struct(struct_item) = node.data else {
+                continue;
+            };
+            let fields = anchor::named_fields(struct_item);
+            let field_names: HashSet<String> = fields
+                .iter()
+                .filter_map(|field| field.ident.as_ref().map(ToString::to_string))
+                .collect();
+
+            // Relationships (account, stored field) read or verified by the handlers
+            let mut relations = RelationCollector::default();
+            for handler in anchor::handlers_for(ast, &struct_item.ident.to_string()) {
+                relations.aliases.clear();
+                relations.visit_block(handler.block);
+            }
+
+            // Relationships already enforced by `constraint = ...` expressions
+            let constrained: Vec<String> = fields
+                .iter()
+                .flat_map(|field| anchor::account_constraints(&field.attrs))
+                .filter(|c| c.name == "constraint")
+                .filter_map(|c| c.value)
+                .collect();
+
+            for field in &fields {
+                let Some(ident) = &field.ident else {
+                    continue;
+                };
+                let account = ident.to_string();
+                if anchor::type_name(&field.ty)
+                    .is_some_and(|ty| NON_DATA_ACCOUNT_TYPES.contains(&ty.as_str()))
+                {
+                    continue;
+                }
+                if CREATING_CONSTRAINTS
+                    .iter()
+                    .any(|name| anchor::has_constraint(&field.attrs, name))
+                {
+                    continue;
+                }
+
+                let has_one: Vec<String> = anchor::account_constraints(&field.attrs)
+                    .into_iter()
+                    .filter(|c| c.name == "has_one")
+                    .filter_map(|c| c.value)
+                    .collect();
+
+                let missing = relations.accessed.iter().any(|(source, target)| {
+                    *source == account
+                        && target != &account
+                        && field_names.contains(target)
+                        && !relations.compared.contains(&(source.clone(), target.clone()))
+                        && !has_one.iter().any(|value| value == target)
+                        && !constrained
+                            .iter()
+                            .any(|expr| expr.contains(&format!("{account} . {target}")))
+                });
+
+                if missing {
+                    trace!("Found account '{account}' without has_one constraint");
+                    new_results.push(AstNode::from_field(field));
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Visitor that records `account.field` reads where `account` is an accounts
+/// field (directly or through a local alias)
+#[derive(Default)]
+struct RelationCollector {
+    /// Local variables bound to an accounts field (`let vault = &ctx.accounts.vault`)
+    aliases: HashMap<String, String>,
+    /// Stored fields read from accounts
+    accessed: HashSet<(String, String)>,
+    /// Stored fields compared in the handler (manual relationship checks)
+    compared: HashSet<(String, String)>,
+}
+
+impl RelationCollector {
+    fn resolve_account(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Path(path) => path
+                .path
+                .get_ident()
+                .and_then(|ident| self.aliases.get(&ident.to_string()).cloned()),
+            Expr::Paren(paren) => self.resolve_account(&paren.expr),
+            _ => anchor::accounts_field(expr),
+        }
+    }
+
+    fn relation(&self, expr: &syn::ExprField) -> Option<(String, String)> {
+        let syn::Member::Named(member) = &expr.member else {
+            return None;
+        };
+        self.resolve_account(&expr.base)
+            .map(|account| (account, member.to_string()))
+    }
+
+    /// Relations referenced in a token stream as `accounts.x.y` or `alias.y`
+    fn relations_in_tokens(&self, tokens: &proc_macro2::TokenStream) -> Vec<(String, String)> {
+        let flat = anchor::flatten_tokens(tokens.clone());
+        let mut relations = Vec::new();
+
+        for (index, window) in flat.windows(3).enumerate() {
+            if window[1] != "." {
+                continue;
+            }
+            let through_accounts = index >= 2 && flat[index - 2] == "accounts" && flat[index - 1] == ".";
+            let account = if through_accounts {
+                Some(window[0].clone())
+            } else if index == 0 || flat[index - 1] != "." {
+                self.aliases.get(&window[0]).cloned()
+            } else {
+                None
+            };
+            if let Some(account) = account {
+                relations.push((account, window[2].clone()));
+            }
+        }
+
+        relations
+    }
+}
+
+/// Collects every relation referenced in an expression
+struct ComparedRelations<'c> {
+    collector: &'c RelationCollector,
+    relations: Vec<(String, String)>,
+}
+
+impl<'ast> Visit<'ast> for ComparedRelations<'_> {
+    fn visit_expr_field(&mut self, expr: &'ast syn::ExprField) {
+        if let Some(relation) = self.collector.relation(expr) {
+            self.relations.push(relation);
+        }
+        visit::visit_expr_field(self, expr);
+    }
+}
+
+impl<'ast> Visit<'ast> for RelationCollector {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        if let Pat::Ident(pat_ident) = &local.pat
+            && let Some(init) = &local.init
+        {
+            let target = match &*init.expr {
+                Expr::Reference(reference) => &*reference.expr,
+                expr => expr,
+            };
+            if let Some(account) = anchor::accounts_field(target) {
+                self.aliases.insert(pat_ident.ident.to_string(), account);
+            }
+        }
+        visit::visit_local(self, local);
+    }
+
+    fn visit_expr_field(&mut self, expr: &'ast syn::ExprField) {
+        if let Some(relation) = self.relation(expr) {
+            self.accessed.insert(relation);
+        }
+        visit::visit_expr_field(self, expr);
+    }
+
+    fn visit_expr_assign(&mut self, expr: &'ast syn::ExprAssign) {
+        // Storing a field (`vault.authority = ...`) does not rely on the relationship
+        match &*expr.left {
+            Expr::Field(field) if self.relation(field).is_some() => self.visit_expr(&field.base),
+            left => self.visit_expr(left),
+        }
+        self.visit_expr(&expr.right);
+    }
+
+    fn visit_expr_binary(&mut self, expr: &'ast syn::ExprBinary) {
+        if matches!(expr.op, syn::BinOp::Eq(_) | syn::BinOp::Ne(_)) {
+            let mut compared = ComparedRelations {
+                collector: self,
+                relations: Vec::new(),
+            };
+            compared.visit_expr(&expr.left);
+            compared.visit_expr(&expr.right);
+            let relations = compared.relations;
+            self.compared.extend(relations);
+        }
+        visit::visit_expr_binary(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let macro_name = mac
+            .path
+            .segments
+            .last()
+            .map(|s| s.ident.to_string())
+            .unwrap_or_default();
+
+        let relations = self.relations_in_tokens(&mac.tokens);
+        if macro_name.starts_with("require") || macro_name.starts_with("assert") {
+            self.compared.extend(relations.iter().cloned());
+        }
+        self.accessed.extend(relations);
+        visit::visit_macro(self, mac);
+    }
+}
@@ -0,0 +1,56 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::MissingHasOneFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("missing-has-one")
+        .title("Missing has_one Constraint")
+        .description("Detects accounts whose stored pubkeys (e.g. vault.authority) are used by the handler while the matching account in the struct is not tied to them with has_one, allowing a different account to be substituted")
+        .severity(Severity::Medium)
+        .rule_type(RuleType::Anchor)
+        .category("access-control")
+        .category("accounts")
+        .tag("security")
+        .tag("access-control")
+        .reference("https://github.com/coral-xyz/sealevel-attacks/tree/master/programs/1-account-data-matching")
+        .reference("Cashio exploit (March 2022): unvalidated relationships between accounts allowed minting against fake collateral")
+        .recommendations(vec![
+            "Add a has_one constraint linking the accounts: #[account(has_one = authority)]",
+            "If the field name differs from the account name, use constraint = vault.admin == admin.key() @ ErrorCode::InvalidAuthority",
+        ])
+        .vulnerable_example(r#"
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    pub authority: Signer<'info>,
+}
+"#)
+        .fixed_example(r#"
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut, has_one = authority)]
+    pub vault: Account<'info, Vault>,
+    pub authority: Signer<'info>,
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing accounts for missing has_one constraints");
+
+            AstQuery::new(ast)
+                .structs()
+                .derives_accounts()
+                .missing_has_one(ast)
+        })
+        .build()
+}
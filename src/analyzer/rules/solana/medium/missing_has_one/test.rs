@@ -0,0 +1,124 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::medium::missing_has_one::filters::MissingHasOneFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_fields(ast: &File) -> Vec<String> {
+        AstQuery::new(ast)
+            .structs()
+            .derives_accounts()
+            .missing_has_one(ast)
+            .collect()
+            .into_iter()
+            .map(|node| node.name())
+            .collect()
+    }
+
+    #[test]
+    fn test_stored_authority_without_has_one() {
+        let ast: File = parse_quote! {
+            pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+                let vault = &mut ctx.accounts.vault;
+                msg!("Withdrawing for {}", vault.authority);
+                vault.balance -= amount;
+                Ok(())
+            }
+
+            #[derive(Accounts)]
+            pub struct Withdraw<'info> {
+                #[account(mut)]
+                pub vault: Account<'info, Vault>,
+                pub authority: Signer<'info>,
+            }
+        };
+
+        assert_eq!(flagged_fields(&ast), vec!["vault"],
+                "Should flag the vault using its stored authority without has_one");
+    }
+
+    #[test]
+    fn test_has_one_present() {
+        let ast: File = parse_quote! {
+            pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+                let owner = ctx.accounts.vault.authority;
+                Ok(())
+            }
+
+            #[derive(Accounts)]
+            pub struct Withdraw<'info> {
+                #[account(mut, has_one = authority)]
+                pub vault: Account<'info, Vault>,
+                pub authority: Signer<'info>,
+            }
+        };
+
+        assert!(flagged_fields(&ast).is_empty(), "has_one should satisfy the relationship");
+    }
+
+    #[test]
+    fn test_manual_comparison() {
+        let ast: File = parse_quote! {
+            pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+                require_keys_eq!(ctx.accounts.vault.authority, ctx.accounts.authority.key());
+                Ok(())
+            }
+
+            #[derive(Accounts)]
+            pub struct Withdraw<'info> {
+                #[account(mut)]
+                pub vault: Account<'info, Vault>,
+                pub authority: Signer<'info>,
+            }
+        };
+
+        assert!(flagged_fields(&ast).is_empty(), "A manual key check should not be flagged");
+    }
+
+    #[test]
+    fn test_initialize_pattern() {
+        let ast: File = parse_quote! {
+            pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+                let vault = &mut ctx.accounts.vault;
+                vault.authority = ctx.accounts.authority.key();
+                vault.bump = ctx.bumps.vault;
+                msg!("Vault created for {}", vault.authority);
+                Ok(())
+            }
+
+            #[derive(Accounts)]
+            pub struct Initialize<'info> {
+                #[account(init, payer = authority, space = 8 + Vault::INIT_SPACE, seeds = [b"vault", authority.key().as_ref()], bump)]
+                pub vault: Account<'info, Vault>,
+                #[account(mut)]
+                pub authority: Signer<'info>,
+                pub system_program: Program<'info, System>,
+            }
+        };
+
+        assert!(flagged_fields(&ast).is_empty(),
+                "Accounts created by the instruction cannot satisfy has_one yet");
+    }
+
+    #[test]
+    fn test_assignment_is_not_a_read() {
+        let ast: File = parse_quote! {
+            pub fn claim(ctx: Context<Claim>) -> Result<()> {
+                ctx.accounts.vault.authority = ctx.accounts.authority.key();
+                Ok(())
+            }
+
+            #[derive(Accounts)]
+            pub struct Claim<'info> {
+                #[account(mut)]
+                pub vault: Account<'info, Vault>,
+                pub authority: Signer<'info>,
+            }
+        };
+
+        assert!(flagged_fields(&ast).is_empty(),
+                "Storing a field does not rely on the relationship");
+    }
+}
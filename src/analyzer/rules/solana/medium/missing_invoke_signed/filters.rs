@@ -0,0 +1,367 @@
+use log::{debug, trace};
+use std::collections::{HashMap, HashSet};
+use syn::punctuated::Punctuated;
+use syn::visit::{self, Visit};
+use syn::{Expr, File, Item, Lit, Pat, Token};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Position of the signing authority in `system_instruction` builders
+const SYSTEM_SIGNERS: [(&str, usize); 4] = [
+    ("transfer", 0),
+    ("create_account", 0),
+    ("assign", 0),
+    ("allocate", 0),
+];
+
+/// Position of the signing authority in SPL token instruction builders
+const TOKEN_SIGNERS: [(&str, usize); 12] = [
+    ("transfer", 3),
+    ("transfer_checked", 4),
+    ("mint_to", 3),
+    ("mint_to_checked", 3),
+    ("burn", 3),
+    ("burn_checked", 3),
+    ("close_account", 3),
+    ("approve", 3),
+    ("revoke", 2),
+    ("set_authority", 4),
+    ("freeze_account", 3),
+    ("thaw_account", 3),
+];
+
+/// Fields of Anchor CPI account structs that must sign
+const SIGNER_FIELDS: [&str; 2] = ["authority", "current_authority"];
+
+pub trait MissingInvokeSignedFilters<'a> {
+    fn invokes_without_pda_signer(self, ast: &'a File) -> AstQuery<'a>;
+}
+
+impl<'a> MissingInvokeSignedFilters<'a> for AstQuery<'a> {
+    fn invokes_without_pda_signer(self, ast: &'a File) -> AstQuery<'a> {
+        debug!("Filtering CPIs whose PDA authority signs without seeds");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let (sig, block) = match node.data {
+                NodeData::Function(func) => (&func.sig, func.block.as_ref()),
+                NodeData::ImplFunction(func) => (&func.sig, &func.block),
+                _ => continue,
+            };
+
+            let mut finder = PdaCpiFinder::default();
+            if let Some(accounts_type) = anchor::context_accounts_type(sig) {
+                finder.pdas.extend(seeded_fields(ast, &accounts_type));
+            }
+            finder.visit_block(block);
+
+            for (call, instruction) in &finder.invokes {
+                if let Some(pda) = finder
+                    .instruction_signers(instruction)
+                    .into_iter()
+                    .find(|signer| finder.pdas.contains(signer))
+                {
+                    trace!("Found invoke with PDA authority '{pda}'");
+                    new_results.push(AstNode::from_expression(call, &pda));
+                }
+            }
+
+            for (call, accounts) in &finder.cpi_contexts {
+                if let Some(pda) = finder
+                    .context_signers(accounts)
+                    .into_iter()
+                    .find(|signer| finder.pdas.contains(signer))
+                {
+                    trace!("Found CpiContext::new with PDA authority '{pda}'");
+                    new_results.push(AstNode::from_expression(call, &pda));
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Fields of the accounts struct derived from the program's own seeds
+fn seeded_fields(ast: &File, accounts_type: &str) -> HashSet<String> {
+    ast.items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Struct(item_struct) if item_struct.ident == accounts_type => Some(item_struct),
+            _ => None,
+        })
+        .flat_map(anchor::named_fields)
+        .filter(|field| {
+            // PDAs of another program cannot be signed for by this one
+            anchor::has_constraint(&field.attrs, "seeds")
+                && !anchor::has_constraint(&field.attrs, "seeds::program")
+        })
+        .filter_map(|field| field.ident.as_ref().map(ToString::to_string))
+        .collect()
+}
+
+/// Visitor that collects PDA derivations, `invoke` calls and `CpiContext::new` calls
+#[derive(Default)]
+struct PdaCpiFinder<'ast> {
+    /// Locals and accounts known to be PDAs of the program
+    pdas: HashSet<String>,
+    /// Initializers of local bindings, to follow instructions built earlier
+    locals: HashMap<String, &'ast Expr>,
+    /// `invoke` calls with the instruction they execute
+    invokes: Vec<(&'ast Expr, &'ast Expr)>,
+    /// `CpiContext::new` calls with their accounts struct
+    cpi_contexts: Vec<(&'ast Expr, &'ast Expr)>,
+}
+
+impl<'ast> PdaCpiFinder<'ast> {
+    /// Follow references and local bindings to the expression that builds a value
+    fn resolve(&self, expr: &'ast Expr) -> &'ast Expr {
+        let mut expr = expr;
+        let mut seen = HashSet::new();
+        loop {
+            expr = match expr {
+                Expr::Reference(reference) => &reference.expr,
+                Expr::Paren(paren) => &paren.expr,
+                Expr::Try(try_expr) => &try_expr.expr,
+                Expr::MethodCall(call) if ["unwrap", "expect", "clone"].contains(&call.method.to_string().as_str()) => {
+                    &call.receiver
+                }
+                Expr::Path(path) => match path.path.get_ident().map(ToString::to_string) {
+                    Some(name) if seen.insert(name.clone()) => match self.locals.get(&name) {
+                        Some(init) => init,
+                        None => return expr,
+                    },
+                    _ => return expr,
+                },
+                _ => return expr,
+            };
+        }
+    }
+
+    /// Accounts an instruction requires to sign
+    fn instruction_signers(&self, instruction: &'ast Expr) -> Vec<String> {
+        let instruction = self.resolve(instruction);
+        let mut signers = Vec::new();
+
+        if let Expr::Call(call) = instruction
+            && let Expr::Path(path) = &*call.func
+        {
+            let segments: Vec<String> = path.path.segments.iter().map(|s| s.ident.to_string()).collect();
+            let name = segments.last().cloned().unwrap_or_default();
+            let table: &[(&str, usize)] = if segments.iter().any(|s| s == "system_instruction") {
+                &SYSTEM_SIGNERS
+            } else if segments.iter().rev().skip(1).any(|s| s == "instruction") {
+                &TOKEN_SIGNERS
+            } else {
+                &[]
+            };
+            if let Some((_, position)) = table.iter().find(|(builder, _)| *builder == name)
+                && let Some(arg) = call.args.iter().nth(*position)
+                && let Some(account) = account_name(arg)
+            {
+                signers.push(account);
+            }
+        }
+
+        // Hand-built instructions mark signers with AccountMeta::new(_, true)
+        let mut metas = SignerMetaCollector {
+            finder: self,
+            seen: HashSet::new(),
+            signers: Vec::new(),
+        };
+        metas.visit_expr(instruction);
+        signers.extend(metas.signers);
+
+        signers
+    }
+
+    /// Signing accounts of an Anchor CPI accounts struct
+    fn context_signers(&self, accounts: &'ast Expr) -> Vec<String> {
+        let Expr::Struct(literal) = self.resolve(accounts) else {
+            return Vec::new();
+        };
+        let is_system = literal.path.segments.iter().any(|s| s.ident == "system_program");
+
+        literal
+            .fields
+            .iter()
+            .filter(|field| match &field.member {
+                syn::Member::Named(member) => {
+                    SIGNER_FIELDS.iter().any(|name| member == name) || (is_system && member == "from")
+                }
+                syn::Member::Unnamed(_) => false,
+            })
+            .filter_map(|field| account_name(self.resolve(&field.expr)))
+            .collect()
+    }
+
+    /// Record accounts whose key is compared with a PDA
+    fn compare(&mut self, left: &Expr, right: &Expr) {
+        for (side, other) in [(left, right), (right, left)] {
+            if account_name(side).is_some_and(|name| self.pdas.contains(&name))
+                && let Some(account) = account_name(other)
+            {
+                self.pdas.insert(account);
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for PdaCpiFinder<'ast> {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        visit::visit_local(self, local);
+        let Some(init) = &local.init else {
+            return;
+        };
+
+        let pat = match &local.pat {
+            Pat::Type(pat_type) => &*pat_type.pat,
+            pat => pat,
+        };
+        // `let (pda, bump) = Pubkey::find_program_address(..)` binds the address first
+        let name = match pat {
+            Pat::Ident(pat_ident) => Some(pat_ident.ident.to_string()),
+            Pat::Tuple(tuple) => match tuple.elems.first() {
+                Some(Pat::Ident(pat_ident)) => Some(pat_ident.ident.to_string()),
+                _ => None,
+            },
+            _ => None,
+        };
+        let Some(name) = name else {
+            return;
+        };
+
+        if derives_pda(&init.expr) {
+            self.pdas.insert(name.clone());
+        }
+        if matches!(pat, Pat::Ident(_)) {
+            self.locals.insert(name, &init.expr);
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let Expr::Call(call) = expr
+            && let Expr::Path(path) = &*call.func
+        {
+            let segments: Vec<String> = path.path.segments.iter().map(|s| s.ident.to_string()).collect();
+            if segments.last().is_some_and(|name| name == "invoke")
+                && let Some(instruction) = call.args.first()
+            {
+                self.invokes.push((expr, instruction));
+            } else if segments.ends_with(&["CpiContext".to_string(), "new".to_string()])
+                && let Some(accounts) = call.args.iter().nth(1)
+            {
+                self.cpi_contexts.push((expr, accounts));
+            }
+        }
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_expr_binary(&mut self, expr: &'ast syn::ExprBinary) {
+        if matches!(expr.op, syn::BinOp::Eq(_) | syn::BinOp::Ne(_)) {
+            self.compare(&expr.left, &expr.right);
+        }
+        visit::visit_expr_binary(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let name = mac.path.segments.last().map(|s| s.ident.to_string()).unwrap_or_default();
+        if (name.starts_with("require_keys_eq") || name.starts_with("assert_eq"))
+            && let Ok(args) = mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+            && args.len() >= 2
+        {
+            self.compare(&args[0], &args[1]);
+        }
+        visit::visit_macro(self, mac);
+    }
+}
+
+/// Collects the accounts marked as signers by `AccountMeta::new(_, true)`,
+/// following local bindings such as an accounts vector built beforehand
+struct SignerMetaCollector<'f, 'ast> {
+    finder: &'f PdaCpiFinder<'ast>,
+    seen: HashSet<String>,
+    signers: Vec<String>,
+}
+
+impl<'v, 'ast: 'v> Visit<'v> for SignerMetaCollector<'_, 'ast> {
+    fn visit_expr(&mut self, expr: &'v Expr) {
+        match expr {
+            Expr::Call(call) => {
+                if let Expr::Path(path) = &*call.func
+                    && path.path.segments.iter().any(|s| s.ident == "AccountMeta")
+                    && call.args.len() == 2
+                    && matches!(&call.args[1], Expr::Lit(lit) if matches!(&lit.lit, Lit::Bool(value) if value.value))
+                    && let Some(account) = account_name(&call.args[0])
+                {
+                    self.signers.push(account);
+                }
+            }
+            Expr::Path(path) => {
+                if let Some(name) = path.path.get_ident().map(ToString::to_string)
+                    && self.seen.insert(name.clone())
+                    && let Some(init) = self.finder.locals.get(&name)
+                {
+                    self.visit_expr(init);
+                }
+            }
+            _ => {}
+        }
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'v syn::Macro) {
+        // Account metas are usually listed in a vec![...]
+        if mac.path.is_ident("vec")
+            && let Ok(elements) = mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+        {
+            let mut nested = SignerMetaCollector {
+                finder: self.finder,
+                seen: std::mem::take(&mut self.seen),
+                signers: Vec::new(),
+            };
+            for element in &elements {
+                nested.visit_expr(element);
+            }
+            self.seen = nested.seen;
+            self.signers.extend(nested.signers);
+        }
+        visit::visit_macro(self, mac);
+    }
+}
+
+/// Whether an expression derives a PDA with `find_program_address` or `create_program_address`
+fn derives_pda(expr: &Expr) -> bool {
+    match expr {
+        Expr::Try(try_expr) => derives_pda(&try_expr.expr),
+        Expr::MethodCall(call) if call.method == "unwrap" || call.method == "expect" => derives_pda(&call.receiver),
+        Expr::Call(call) => matches!(
+            &*call.func,
+            Expr::Path(path) if path.path.segments.last().is_some_and(|s| {
+                s.ident == "find_program_address" || s.ident == "create_program_address"
+            })
+        ),
+        _ => false,
+    }
+}
+
+/// Name of the account or local behind a key or account expression:
+/// `pda`, `&pda`, `vault.key`, `*vault.key`, `vault.key()`, `ctx.accounts.vault.to_account_info()`
+fn account_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Reference(reference) => account_name(&reference.expr),
+        Expr::Unary(unary) => account_name(&unary.expr),
+        Expr::Paren(paren) => account_name(&paren.expr),
+        Expr::Field(field) if matches!(&field.member, syn::Member::Named(m) if m == "key") => {
+            account_name(&field.base)
+        }
+        Expr::MethodCall(call)
+            if ["key", "to_account_info", "clone"].contains(&call.method.to_string().as_str()) =>
+        {
+            account_name(&call.receiver)
+        }
+        Expr::Path(path) => path.path.get_ident().map(ToString::to_string),
+        _ => anchor::accounts_field(expr),
+    }
+}
@@ -0,0 +1,81 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::MissingInvokeSignedFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("missing-invoke-signed")
+        .title("PDA Authority Without Signer Seeds")
+        .description("Detects invoke calls and CpiContext::new contexts whose signing authority is a PDA of the program (derived with find_program_address/create_program_address or declared with seeds). A PDA can only sign through invoke_signed with its seeds, so the CPI either fails at runtime or reveals that the wrong authority was passed")
+        .severity(Severity::Medium)
+        .rule_type(RuleType::Solana)
+        .category("cpi")
+        .category("pda")
+        .tag("security")
+        .tag("cpi")
+        .tag("pda")
+        .recommendations(vec![
+            "Sign for the PDA with invoke_signed(&ix, accounts, &[&[b\"vault\", owner.key.as_ref(), &[bump]]])",
+            "In Anchor, build the context with CpiContext::new_with_signer(program, accounts, signer_seeds)",
+            "Derive the signer seeds from the same seeds and canonical bump used to validate the PDA",
+        ])
+        .vulnerable_example(r#"
+pub fn withdraw(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let vault = next_account_info(iter)?;
+    let destination = next_account_info(iter)?;
+    let vault_authority = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+
+    let (authority, _bump) = Pubkey::find_program_address(&[b"vault"], program_id);
+    if vault_authority.key != &authority {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let ix = spl_token::instruction::transfer(
+        token_program.key, vault.key, destination.key, vault_authority.key, &[], amount,
+    )?;
+    invoke(&ix, &[vault.clone(), destination.clone(), vault_authority.clone()])
+}
+"#)
+        .fixed_example(r#"
+pub fn withdraw(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let vault = next_account_info(iter)?;
+    let destination = next_account_info(iter)?;
+    let vault_authority = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+
+    let (authority, bump) = Pubkey::find_program_address(&[b"vault"], program_id);
+    if vault_authority.key != &authority {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let ix = spl_token::instruction::transfer(
+        token_program.key, vault.key, destination.key, vault_authority.key, &[], amount,
+    )?;
+    invoke_signed(
+        &ix,
+        &[vault.clone(), destination.clone(), vault_authority.clone()],
+        &[&[b"vault", &[bump]]],
+    )
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing CPIs signed by PDAs");
+
+            AstQuery::new(ast)
+                .functions()
+                .invokes_without_pda_signer(ast)
+        })
+        .build()
+}
@@ -0,0 +1,149 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::medium::missing_invoke_signed::filters::MissingInvokeSignedFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_authorities(ast: &File) -> Vec<String> {
+        AstQuery::new(ast)
+            .functions()
+            .invokes_without_pda_signer(ast)
+            .collect()
+            .into_iter()
+            .map(|node| node.name())
+            .collect()
+    }
+
+    #[test]
+    fn test_token_transfer_with_pda_authority() {
+        let ast: File = parse_quote! {
+            pub fn withdraw(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+                let iter = &mut accounts.iter();
+                let vault = next_account_info(iter)?;
+                let destination = next_account_info(iter)?;
+                let vault_authority = next_account_info(iter)?;
+                let token_program = next_account_info(iter)?;
+
+                let (authority, _bump) = Pubkey::find_program_address(&[b"vault"], program_id);
+                if vault_authority.key != &authority {
+                    return Err(ProgramError::InvalidSeeds);
+                }
+
+                let ix = spl_token::instruction::transfer(
+                    token_program.key, vault.key, destination.key, vault_authority.key, &[], amount,
+                )?;
+                invoke(&ix, &[vault.clone(), destination.clone(), vault_authority.clone()])
+            }
+        };
+
+        assert_eq!(flagged_authorities(&ast), vec!["vault_authority"],
+                "Should flag invoke when the token authority is a PDA");
+    }
+
+    #[test]
+    fn test_invoke_signed_is_not_flagged() {
+        let ast: File = parse_quote! {
+            pub fn withdraw(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+                let (authority, bump) = Pubkey::find_program_address(&[b"vault"], program_id);
+                let ix = spl_token::instruction::transfer(
+                    token_program.key, vault.key, destination.key, &authority, &[], amount,
+                )?;
+                invoke_signed(&ix, &[vault.clone(), destination.clone()], &[&[b"vault", &[bump]]])
+            }
+        };
+
+        assert!(flagged_authorities(&ast).is_empty(), "invoke_signed should not be flagged");
+    }
+
+    #[test]
+    fn test_pda_destination_is_not_flagged() {
+        let ast: File = parse_quote! {
+            pub fn deposit(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+                let (vault, _bump) = Pubkey::find_program_address(&[b"vault", user.key.as_ref()], program_id);
+                let ix = system_instruction::transfer(user.key, &vault, amount);
+                invoke(&ix, &[user.clone(), vault_info.clone()])
+            }
+        };
+
+        assert!(flagged_authorities(&ast).is_empty(),
+                "Sending to a PDA does not require its signature");
+    }
+
+    #[test]
+    fn test_account_meta_signer() {
+        let ast: File = parse_quote! {
+            pub fn forward(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+                let signer = Pubkey::create_program_address(&[b"signer", &[bump]], program_id)?;
+                let metas = vec![
+                    AccountMeta::new(*target.key, false),
+                    AccountMeta::new_readonly(signer, true),
+                ];
+                let ix = Instruction { program_id: *callee.key, accounts: metas, data: vec![] };
+                invoke(&ix, accounts)
+            }
+        };
+
+        assert_eq!(flagged_authorities(&ast), vec!["signer"],
+                "Should flag a PDA marked as signer in the account metas");
+    }
+
+    #[test]
+    fn test_anchor_context_without_signer() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Withdraw<'info> {
+                #[account(mut)]
+                pub vault: Account<'info, TokenAccount>,
+                #[account(seeds = [b"authority"], bump)]
+                pub vault_authority: UncheckedAccount<'info>,
+                pub token_program: Program<'info, Token>,
+            }
+
+            pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+                let accounts = Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.vault_authority.to_account_info(),
+                };
+                token::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), accounts), amount)
+            }
+        };
+
+        assert_eq!(flagged_authorities(&ast), vec!["vault_authority"],
+                "Should flag CpiContext::new with a seeded authority");
+    }
+
+    #[test]
+    fn test_anchor_context_with_signer() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Withdraw<'info> {
+                #[account(seeds = [b"authority"], bump)]
+                pub vault_authority: UncheckedAccount<'info>,
+                pub user: Signer<'info>,
+            }
+
+            pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+                let seeds: &[&[&[u8]]] = &[&[b"authority", &[ctx.bumps.vault_authority]]];
+                let accounts = Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.vault_authority.to_account_info(),
+                };
+                token::transfer(CpiContext::new_with_signer(program, accounts, seeds), amount)?;
+
+                let user_accounts = Transfer {
+                    from: ctx.accounts.user_tokens.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                };
+                token::transfer(CpiContext::new(program, user_accounts), amount)
+            }
+        };
+
+        assert!(flagged_authorities(&ast).is_empty(),
+                "Signed contexts and user authorities should not be flagged");
+    }
+}
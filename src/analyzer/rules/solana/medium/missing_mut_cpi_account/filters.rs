@@ -0,0 +1,146 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use std::collections::{HashMap, HashSet};
+use syn::visit::{self, Visit};
+use syn::{Expr, File, Member, Pat};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Accounts structs of token and system program CPIs, with the fields the
+/// program writes to
+const WRITABLE_CPI_FIELDS: [(&str, &[&str]); 19] = [
+    ("Transfer", &["from", "to"]),
+    ("TransferChecked", &["from", "to"]),
+    ("Burn", &["mint", "from"]),
+    ("BurnChecked", &["mint", "from"]),
+    ("MintTo", &["mint", "to"]),
+    ("MintToChecked", &["mint", "to"]),
+    ("Approve", &["to"]),
+    ("ApproveChecked", &["to"]),
+    ("Revoke", &["source"]),
+    ("CloseAccount", &["account", "destination"]),
+    ("FreezeAccount", &["account"]),
+    ("ThawAccount", &["account"]),
+    ("SetAuthority", &["account_or_mint"]),
+    ("SyncNative", &["account"]),
+    ("InitializeAccount", &["account"]),
+    ("InitializeMint", &["mint"]),
+    ("CreateAccount", &["from", "to"]),
+    ("Allocate", &["account_to_allocate"]),
+    ("Assign", &["account_to_assign"]),
+];
+
+/// Methods turning an account into another handle on the same account
+const ACCOUNT_CONVERSIONS: [&str; 3] = ["to_account_info", "clone", "as_ref"];
+
+/// Constraints that mark the account as writable
+const WRITABLE_CONSTRAINTS: [&str; 6] = ["mut", "init", "init_if_needed", "zero", "close", "realloc"];
+
+pub trait MissingMutCpiAccountFilters<'a> {
+    fn missing_mut_cpi_accounts(self, ast: &'a File) -> AstQuery<'a>;
+}
+
+impl<'a> MissingMutCpiAccountFilters<'a> for AstQuery<'a> {
+    fn missing_mut_cpi_accounts(self, ast: &'a File) -> AstQuery<'a> {
+        debug!("Filtering accounts written by CPIs but declared without mut");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let NodeData::Struct(struct_item) = node.data else {
+                continue;
+            };
+            let struct_name = struct_item.ident.to_string();
+
+            // CPIs built in the handlers or in helper methods of the accounts struct
+            let mut collector = CpiWriteCollector::default();
+            for handler in anchor::handlers_for(ast, &struct_name) {
+                collector.aliases.clear();
+                collector.visit_block(handler.block);
+            }
+            for method in anchor::impl_methods(ast, &struct_name) {
+                collector.aliases.clear();
+                collector.visit_block(&method.block);
+            }
+
+            for field in anchor::named_fields(struct_item) {
+                let Some(ident) = &field.ident else {
+                    continue;
+                };
+                let account = ident.to_string();
+                if !collector.written.contains(&account) {
+                    continue;
+                }
+
+                let writable = anchor::account_constraints(&field.attrs)
+                    .iter()
+                    .any(|c| WRITABLE_CONSTRAINTS.contains(&c.name.as_str()));
+                if !writable {
+                    trace!("Found account {account} written by a CPI but declared without mut");
+                    new_results.push(AstNode::from_field(field));
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Visitor that records the accounts handed to writable fields of CPI accounts structs
+#[derive(Default)]
+struct CpiWriteCollector {
+    /// Local variables bound to an accounts field
+    aliases: HashMap<String, String>,
+    /// Accounts passed in a writable position
+    written: HashSet<String>,
+}
+
+impl<'ast> Visit<'ast> for CpiWriteCollector {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        if let Pat::Ident(pat_ident) = &local.pat
+            && let Some(init) = &local.init
+        {
+            // `&ctx.accounts.x`, `ctx.accounts.x.to_account_info()` and similar
+            let mut expr = &*init.expr;
+            loop {
+                match expr {
+                    Expr::Reference(reference) => expr = &reference.expr,
+                    Expr::MethodCall(call)
+                        if call.args.is_empty() && ACCOUNT_CONVERSIONS.contains(&call.method.to_string().as_str()) =>
+                    {
+                        expr = &call.receiver
+                    }
+                    _ => break,
+                }
+            }
+            if let Some(account) = anchor::accounts_field(expr) {
+                self.aliases.insert(pat_ident.ident.to_string(), account);
+            }
+        }
+        visit::visit_local(self, local);
+    }
+
+    fn visit_expr_struct(&mut self, expr: &'ast syn::ExprStruct) {
+        let writable_fields = expr.path.segments.last().and_then(|segment| {
+            WRITABLE_CPI_FIELDS
+                .iter()
+                .find(|(name, _)| segment.ident == name)
+                .map(|(_, fields)| *fields)
+        });
+
+        if let Some(writable_fields) = writable_fields {
+            for field in &expr.fields {
+                let Member::Named(name) = &field.member else {
+                    continue;
+                };
+                // Events sharing a CPI struct name hold keys, not accounts
+                let tokens = field.expr.to_token_stream();
+                let is_key = anchor::flatten_tokens(tokens.clone()).iter().any(|token| token == "key");
+                if writable_fields.iter().any(|writable| name == writable) && !is_key {
+                    self.written.extend(anchor::accounts_in(tokens, &self.aliases));
+                }
+            }
+        }
+        visit::visit_expr_struct(self, expr);
+    }
+}
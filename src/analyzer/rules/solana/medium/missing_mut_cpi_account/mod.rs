@@ -0,0 +1,68 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::MissingMutCpiAccountFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("missing-mut-cpi-account")
+        .title("Account Written by CPI Without mut")
+        .description("Detects accounts passed in a writable position of a token or system program CPI (transfer and transfer_checked from/to, burn, mint_to, close_account, create_account), directly or through a local variable, that are declared without mut in the accounts struct. The runtime rejects the CPI because the account was not marked writable in the transaction, so the instruction always fails. Token transfers are covered here: the from and to token accounts of token::transfer and transfer_checked are both checked")
+        .severity(Severity::Medium)
+        .rule_type(RuleType::Anchor)
+        .category("accounts")
+        .category("cpi")
+        .tag("correctness")
+        .tag("account-validation")
+        .recommendations(vec![
+            "Add mut to the account constraint: #[account(mut)]",
+            "For accounts created in the instruction, init already marks the account as writable",
+        ])
+        .vulnerable_example(r#"
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    pub vault_tokens: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub user_tokens: Account<'info, TokenAccount>,
+    pub vault: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.vault_tokens.to_account_info(),
+        to: ctx.accounts.user_tokens.to_account_info(),
+        authority: ctx.accounts.vault.to_account_info(),
+    };
+    token::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), amount)
+}
+"#)
+        .fixed_example(r#"
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    pub vault_tokens: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub user_tokens: Account<'info, TokenAccount>,
+    pub vault: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing accounts written by CPIs for missing mut");
+
+            AstQuery::new(ast)
+                .structs()
+                .derives_accounts()
+                .missing_mut_cpi_accounts(ast)
+        })
+        .build()
+}
@@ -0,0 +1,147 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::medium::missing_mut_cpi_account::filters::MissingMutCpiAccountFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_fields(ast: &File) -> Vec<String> {
+        AstQuery::new(ast)
+            .structs()
+            .derives_accounts()
+            .missing_mut_cpi_accounts(ast)
+            .collect()
+            .into_iter()
+            .map(|node| node.name())
+            .collect()
+    }
+
+    #[test]
+    fn test_token_transfer_without_mut() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Withdraw<'info> {
+                pub vault_tokens: Account<'info, TokenAccount>,
+                #[account(mut)]
+                pub user_tokens: Account<'info, TokenAccount>,
+                pub vault: Signer<'info>,
+            }
+
+            pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+                let cpi_accounts = Transfer {
+                    from: ctx.accounts.vault_tokens.to_account_info(),
+                    to: ctx.accounts.user_tokens.to_account_info(),
+                    authority: ctx.accounts.vault.to_account_info(),
+                };
+                token::transfer(CpiContext::new(program, cpi_accounts), amount)
+            }
+        };
+
+        assert_eq!(flagged_fields(&ast), vec!["vault_tokens"],
+                "Only the source account lacks mut; the authority is not written");
+    }
+
+    #[test]
+    fn test_transfer_checked_without_mut() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Deposit<'info> {
+                pub user_tokens: InterfaceAccount<'info, TokenAccount>,
+                pub vault_tokens: Box<Account<'info, TokenAccount>>,
+                pub mint: InterfaceAccount<'info, Mint>,
+                pub user: Signer<'info>,
+            }
+
+            pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+                let from = ctx.accounts.user_tokens.to_account_info();
+                let cpi_accounts = TransferChecked {
+                    from,
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.vault_tokens.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                };
+                token_interface::transfer_checked(CpiContext::new(program, cpi_accounts), amount, 6)
+            }
+        };
+
+        assert_eq!(flagged_fields(&ast), vec!["user_tokens", "vault_tokens"],
+                "Both sides of the transfer are written; the mint is only read");
+    }
+
+    #[test]
+    fn test_system_transfer_payer_without_mut() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Tip<'info> {
+                pub payer: Signer<'info>,
+                #[account(mut)]
+                pub treasury: SystemAccount<'info>,
+            }
+
+            pub fn tip(ctx: Context<Tip>, lamports: u64) -> Result<()> {
+                let payer = &ctx.accounts.payer;
+                system_program::transfer(
+                    CpiContext::new(program, system_program::Transfer {
+                        from: payer.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                    }),
+                    lamports,
+                )
+            }
+        };
+
+        assert_eq!(flagged_fields(&ast), vec!["payer"], "The payer is debited and must be mutable");
+    }
+
+    #[test]
+    fn test_writable_accounts() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Burn<'info> {
+                #[account(mut)]
+                pub mint: Account<'info, Mint>,
+                #[account(init, payer = owner, token::mint = mint, token::authority = owner)]
+                pub holder_tokens: Account<'info, TokenAccount>,
+                pub owner: Signer<'info>,
+            }
+
+            impl<'info> Burn<'info> {
+                fn burn_ctx(&self) -> CpiContext<'_, '_, '_, 'info, token::Burn<'info>> {
+                    CpiContext::new(self.token_program.to_account_info(), token::Burn {
+                        mint: self.mint.to_account_info(),
+                        from: self.holder_tokens.to_account_info(),
+                        authority: self.owner.to_account_info(),
+                    })
+                }
+            }
+        };
+
+        assert!(flagged_fields(&ast).is_empty(), "Accounts marked mut or init should not be flagged");
+    }
+
+    #[test]
+    fn test_helper_method_close() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Close<'info> {
+                #[account(mut)]
+                pub escrow_tokens: Account<'info, TokenAccount>,
+                pub receiver: SystemAccount<'info>,
+                pub escrow: Signer<'info>,
+            }
+
+            impl<'info> Close<'info> {
+                fn close_ctx(&self) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
+                    CpiContext::new(self.token_program.to_account_info(), CloseAccount {
+                        account: self.escrow_tokens.to_account_info(),
+                        destination: self.receiver.to_account_info(),
+                        authority: self.escrow.to_account_info(),
+                    })
+                }
+            }
+        };
+
+        assert_eq!(flagged_fields(&ast), vec!["receiver"], "The rent receiver is credited and must be mutable");
+    }
+}
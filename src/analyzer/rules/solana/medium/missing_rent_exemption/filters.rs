@@ -0,0 +1,183 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use std::collections::{HashMap, HashSet};
+use syn::visit::{self, Visit};
+use syn::{Expr, FnArg, Lit, Pat};
+
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+pub trait MissingRentExemptionFilters<'a> {
+    fn underfunded_account_creations(self) -> AstQuery<'a>;
+}
+
+impl<'a> MissingRentExemptionFilters<'a> for AstQuery<'a> {
+    fn underfunded_account_creations(self) -> AstQuery<'a> {
+        debug!("Filtering account creations not funded with the rent-exempt minimum");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let (inputs, block) = match node.data {
+                NodeData::Function(func) => (&func.sig.inputs, func.block.as_ref()),
+                NodeData::ImplFunction(func) => (&func.sig.inputs, &func.block),
+                _ => continue,
+            };
+
+            let mut finder = CreateAccountFinder::default();
+            for input in inputs {
+                if let FnArg::Typed(pat_type) = input
+                    && let Pat::Ident(pat_ident) = &*pat_type.pat
+                {
+                    finder.params.insert(pat_ident.ident.to_string());
+                }
+            }
+            finder.visit_block(block);
+
+            for expr in finder.underfunded {
+                trace!("Found account creation without rent-exempt lamports");
+                new_results.push(AstNode::from_expression(expr, "create_account"));
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Visitor that checks the lamports funded by each `create_account` call
+#[derive(Default)]
+struct CreateAccountFinder<'ast> {
+    /// Function parameters, whose provenance is unknown
+    params: HashSet<String>,
+    /// Local bindings seen so far
+    locals: HashMap<String, &'ast Expr>,
+    /// Account creations not funded with `minimum_balance(space)`
+    underfunded: Vec<&'ast Expr>,
+}
+
+impl<'ast> CreateAccountFinder<'ast> {
+    /// Follow local bindings back to the expression that produced the value
+    fn resolve(&self, expr: &'ast Expr) -> &'ast Expr {
+        let mut current = expr;
+        for _ in 0..8 {
+            let next = match strip(current) {
+                Expr::Path(path) => path
+                    .path
+                    .get_ident()
+                    .and_then(|ident| self.locals.get(&ident.to_string()).copied()),
+                _ => None,
+            };
+            match next {
+                Some(next) => current = next,
+                None => break,
+            }
+        }
+        current
+    }
+
+    fn is_underfunded(&self, lamports: &'ast Expr, space: &'ast Expr) -> bool {
+        let lamports = self.resolve(lamports);
+
+        // The value comes from the caller, which may have computed it correctly
+        if let Expr::Path(path) = strip(lamports)
+            && path
+                .path
+                .get_ident()
+                .is_some_and(|ident| self.params.contains(&ident.to_string()))
+        {
+            return false;
+        }
+
+        let mut rent = MinimumBalanceFinder::default();
+        rent.visit_expr(lamports);
+        if !rent.found {
+            return true;
+        }
+
+        // `minimum_balance(100)` funding an account of a different literal size
+        let space = integer_literal(self.resolve(space));
+        rent.sizes.iter().any(|size| {
+            integer_literal(self.resolve(size)).is_some_and(|size| space.is_some_and(|space| size != space))
+        })
+    }
+}
+
+impl<'ast> Visit<'ast> for CreateAccountFinder<'ast> {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        visit::visit_local(self, local);
+        let pat = match &local.pat {
+            Pat::Type(pat_type) => &*pat_type.pat,
+            pat => pat,
+        };
+        if let Pat::Ident(pat_ident) = pat
+            && let Some(init) = &local.init
+        {
+            self.locals.insert(pat_ident.ident.to_string(), &init.expr);
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let Expr::Call(call) = expr
+            && let Expr::Path(path) = &*call.func
+            && let Some(function) = path.path.segments.last()
+        {
+            let args: Vec<&Expr> = call.args.iter().collect();
+            // (lamports, space) positions of the system program helpers
+            let positions = match (function.ident.to_string().as_str(), args.len()) {
+                ("create_account", 5) => Some((2, 3)),
+                ("create_account", 4) => Some((1, 2)),
+                ("create_account_with_seed", 7) => Some((4, 5)),
+                _ => None,
+            };
+            if let Some((lamports, space)) = positions
+                && self.is_underfunded(args[lamports], args[space])
+            {
+                self.underfunded.push(expr);
+            }
+        }
+        visit::visit_expr(self, expr);
+    }
+}
+
+/// Records calls to `minimum_balance` and the sizes they are computed for
+#[derive(Default)]
+struct MinimumBalanceFinder<'ast> {
+    found: bool,
+    sizes: Vec<&'ast Expr>,
+}
+
+impl<'ast> Visit<'ast> for MinimumBalanceFinder<'ast> {
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        if call.method == "minimum_balance" {
+            self.found = true;
+            self.sizes.extend(call.args.first());
+        }
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        if mac.tokens.to_token_stream().to_string().contains("minimum_balance") {
+            self.found = true;
+        }
+        visit::visit_macro(self, mac);
+    }
+}
+
+/// Look through references, parentheses and casts
+fn strip(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Reference(reference) => strip(&reference.expr),
+        Expr::Paren(paren) => strip(&paren.expr),
+        Expr::Cast(cast) => strip(&cast.expr),
+        Expr::Group(group) => strip(&group.expr),
+        _ => expr,
+    }
+}
+
+fn integer_literal(expr: &Expr) -> Option<u128> {
+    match strip(expr) {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Int(int) => int.base10_parse().ok(),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,50 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::MissingRentExemptionFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("missing-rent-exemption")
+        .title("Account Not Funded For Rent Exemption")
+        .description("Detects accounts created through the system program with hardcoded, zero or otherwise computed lamports instead of Rent::minimum_balance(space), leaving them subject to rent collection and eventual purge")
+        .severity(Severity::Medium)
+        .rule_type(RuleType::Solana)
+        .category("accounts")
+        .tag("security")
+        .tag("rent")
+        .recommendations(vec![
+            "Fund new accounts with the rent-exempt minimum for their size: Rent::get()?.minimum_balance(space)",
+            "Compute the lamports from the same space value passed to create_account",
+        ])
+        .vulnerable_example(r#"
+let ix = system_instruction::create_account(
+    payer.key, new_account.key, 1_000_000, 165, program_id,
+);
+invoke(&ix, &[payer.clone(), new_account.clone()])?;
+"#)
+        .fixed_example(r#"
+let space = 165;
+let lamports = Rent::get()?.minimum_balance(space);
+let ix = system_instruction::create_account(
+    payer.key, new_account.key, lamports, space as u64, program_id,
+);
+invoke(&ix, &[payer.clone(), new_account.clone()])?;
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing account creations for rent exemption");
+
+            AstQuery::new(ast)
+                .functions()
+                .underfunded_account_creations()
+        })
+        .build()
+}
@@ -0,0 +1,59 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::medium::missing_rent_exemption::filters::MissingRentExemptionFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast)
+            .functions()
+            .underfunded_account_creations()
+            .collect()
+            .len()
+    }
+
+    #[test]
+    fn test_hardcoded_lamports() {
+        let ast: File = parse_quote! {
+            fn create(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+                let ix = system_instruction::create_account(payer.key, account.key, 1_000_000, 165, program_id);
+                invoke(&ix, &[payer.clone(), account.clone()])?;
+                let zero = system_instruction::create_account(payer.key, other.key, 0, 165, program_id);
+                invoke(&zero, &[payer.clone(), other.clone()])
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 2, "Should flag hardcoded and zero lamports");
+    }
+
+    #[test]
+    fn test_rent_exempt_lamports() {
+        let ast: File = parse_quote! {
+            fn create(accounts: &[AccountInfo], program_id: &Pubkey, funding: u64) -> ProgramResult {
+                let space = 165;
+                let rent = Rent::get()?;
+                let lamports = rent.minimum_balance(space);
+                let ix = system_instruction::create_account(payer.key, account.key, lamports, space as u64, program_id);
+                invoke(&ix, &[payer.clone(), account.clone()])?;
+                let ix = system_instruction::create_account(payer.key, other.key, funding, 165, program_id);
+                invoke(&ix, &[payer.clone(), other.clone()])
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Rent-exempt or caller-provided lamports should not be flagged");
+    }
+
+    #[test]
+    fn test_minimum_balance_for_different_space() {
+        let ast: File = parse_quote! {
+            fn create(ctx: Context<Create>) -> Result<()> {
+                let lamports = Rent::get()?.minimum_balance(8);
+                system_program::create_account(cpi_ctx, lamports, 165, ctx.program_id)
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Should flag rent computed for a different size");
+    }
+}
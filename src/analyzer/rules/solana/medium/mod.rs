@@ -1,4 +1,22 @@
+pub mod ambiguous_pda_seeds;
+pub mod borrow_across_cpi;
 pub mod division_by_zero;
-pub mod duplicate_mutable_accounts;
+pub mod float_financial_math;
+pub mod missing_has_one;
+pub mod missing_invoke_signed;
+pub mod missing_mut_cpi_account;
+pub mod missing_rent_exemption;
 pub mod owner_check;
+pub mod permissionless_handler;
+pub mod stale_account_after_cpi;
+pub mod state_check_after_external_cpi;
+pub mod unbalanced_lamport_transfer;
+pub mod unchecked_arithmetic;
+pub mod unchecked_data_slice;
+pub mod unchecked_sysvar_account;
+pub mod unconstrained_token_account;
+pub mod unguarded_init_if_needed;
+pub mod unsafe_realloc;
+pub mod unsigned_init_payer;
+pub mod unverified_pda_authority;
 
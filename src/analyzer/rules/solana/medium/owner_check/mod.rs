@@ -11,8 +11,11 @@ pub fn create_rule() -> Arc<dyn Rule> {
     RuleBuilder::new()
         .id("owner-check")
         .severity(Severity::Medium)
+        .category("accounts")
         .title("Owner Check Validation")
         .description("Detects structs that properly implement owner checks for account validation")
+        .reference("CWE-345: Insufficient Verification of Data Authenticity (https://cwe.mitre.org/data/definitions/345.html)")
+        .reference("https://github.com/coral-xyz/sealevel-attacks/tree/master/programs/2-owner-checks")
         .recommendations(vec![
             "Add explicit owner validation in your account struct using #[account(constraint = account.owner == expected_owner)] or similar patterns",
             "Use Anchor's built-in Account<'info, T> wrapper which automatically validates the account owner",
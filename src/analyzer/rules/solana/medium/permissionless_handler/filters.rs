@@ -0,0 +1,126 @@
+use log::{debug, trace};
+use std::collections::{HashMap, HashSet};
+use syn::{Field, Item, ItemFn, ItemStruct};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Account constraints through which an instruction changes state
+const MUTATING_CONSTRAINTS: [&str; 5] = ["mut", "init", "init_if_needed", "close", "realloc"];
+
+/// Account constraints tying an account to an authority or to other state
+const GATING_CONSTRAINTS: [&str; 3] = ["signer", "has_one", "constraint"];
+
+pub trait PermissionlessHandlerFilters<'a> {
+    fn permissionless_handlers(self) -> AstQuery<'a>;
+}
+
+impl<'a> PermissionlessHandlerFilters<'a> for AstQuery<'a> {
+    fn permissionless_handlers(self) -> AstQuery<'a> {
+        debug!("Filtering #[program] handlers mutating state without access control");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let NodeData::File(file) = node.data else {
+                continue;
+            };
+
+            let accounts_structs: HashMap<String, &ItemStruct> = AstQuery::new(file)
+                .structs()
+                .derives_accounts()
+                .collect()
+                .into_iter()
+                .filter_map(|node| match node.data {
+                    NodeData::Struct(item_struct) => Some((item_struct.ident.to_string(), item_struct)),
+                    _ => None,
+                })
+                .collect();
+
+            for handler in program_handlers(&file.items) {
+                if handler.attrs.iter().any(|attr| attr.path().is_ident("access_control")) {
+                    continue;
+                }
+                // Handlers whose accounts struct is defined elsewhere can't be judged
+                let Some(accounts) = anchor::context_accounts_type(&handler.sig)
+                    .and_then(|name| accounts_structs.get(&name).copied())
+                else {
+                    continue;
+                };
+
+                let mut visited = HashSet::new();
+                let access = StructAccess::of(accounts, &accounts_structs, &mut visited);
+                if access.mutates && !access.gated {
+                    trace!("Found permissionless handler {}", handler.sig.ident);
+                    new_results.push(AstNode::from_function(handler));
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Public functions of the `#[program]` modules among the items, searched recursively
+fn program_handlers(items: &[Item]) -> Vec<&ItemFn> {
+    let mut handlers = Vec::new();
+    for item in items {
+        let Item::Mod(item_mod) = item else {
+            continue;
+        };
+        let Some((_, content)) = &item_mod.content else {
+            continue;
+        };
+        if item_mod.attrs.iter().any(|attr| attr.path().is_ident("program")) {
+            handlers.extend(content.iter().filter_map(|item| match item {
+                Item::Fn(func) if matches!(func.vis, syn::Visibility::Public(_)) => Some(func),
+                _ => None,
+            }));
+        } else {
+            handlers.extend(program_handlers(content));
+        }
+    }
+    handlers
+}
+
+/// How an accounts struct, including nested accounts structs, handles state and authority
+#[derive(Default)]
+struct StructAccess {
+    /// Some account is mutable, created, closed or reallocated
+    mutates: bool,
+    /// Some account is a signer or carries a has_one / constraint check
+    gated: bool,
+}
+
+impl StructAccess {
+    fn of(
+        item_struct: &ItemStruct,
+        accounts_structs: &HashMap<String, &ItemStruct>,
+        visited: &mut HashSet<String>,
+    ) -> Self {
+        let mut access = Self::default();
+        if !visited.insert(item_struct.ident.to_string()) {
+            return access;
+        }
+
+        for field in anchor::named_fields(item_struct) {
+            access.mutates |= MUTATING_CONSTRAINTS
+                .iter()
+                .any(|name| anchor::has_constraint(&field.attrs, name));
+            access.gated |= is_gated(field);
+
+            // Composite fields (`pub common: Common<'info>`) bring their own accounts
+            if let Some(nested) = anchor::type_name(&field.ty).and_then(|name| accounts_structs.get(&name)) {
+                let nested = Self::of(nested, accounts_structs, visited);
+                access.mutates |= nested.mutates;
+                access.gated |= nested.gated;
+            }
+        }
+        access
+    }
+}
+
+/// Whether a field is a signer or constrains the accounts against each other
+fn is_gated(field: &Field) -> bool {
+    anchor::type_name(&field.ty).as_deref() == Some("Signer")
+        || GATING_CONSTRAINTS.iter().any(|name| anchor::has_constraint(&field.attrs, name))
+}
@@ -0,0 +1,60 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::PermissionlessHandlerFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("permissionless-handler")
+        .title("Instruction Handler Without Access Control")
+        .description("Detects public handlers of a #[program] module whose accounts struct mutates state (mut, init, close or realloc accounts) without any signer account, has_one or constraint, and without an #[access_control] attribute. Anyone can call such an instruction. Some instructions are permissionless on purpose (cranks, public initializers); suppress the finding for those")
+        .severity(Severity::Medium)
+        .rule_type(RuleType::Anchor)
+        .category("access-control")
+        .category("accounts")
+        .tag("security")
+        .tag("authorization")
+        .reference("https://github.com/coral-xyz/sealevel-attacks/tree/master/programs/0-signer-authorization")
+        .recommendations(vec![
+            "Review whether the instruction should be restricted to an authority",
+            "Require the authority as a Signer and bind it to the state with has_one or a constraint",
+            "If the instruction is meant to be permissionless, document it and suppress the finding with an eloizer-ignore comment",
+        ])
+        .vulnerable_example(r#"
+#[program]
+pub mod vault {
+    pub fn set_fee(ctx: Context<SetFee>, fee_bps: u16) -> Result<()> {
+        ctx.accounts.config.fee_bps = fee_bps;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetFee<'info> {
+    #[account(mut)]
+    pub config: Account<'info, Config>,
+}
+"#)
+        .fixed_example(r#"
+#[derive(Accounts)]
+pub struct SetFee<'info> {
+    #[account(mut, has_one = admin)]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing #[program] handlers for access control");
+
+            AstQuery::new(ast).permissionless_handlers()
+        })
+        .build()
+}
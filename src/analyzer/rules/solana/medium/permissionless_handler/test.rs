@@ -0,0 +1,172 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::medium::permissionless_handler::filters::PermissionlessHandlerFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_handlers(ast: &File) -> Vec<String> {
+        AstQuery::new(ast)
+            .permissionless_handlers()
+            .collect()
+            .iter()
+            .filter_map(|node| node.name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_permissionless_mutation() {
+        let ast: File = parse_quote! {
+            #[program]
+            pub mod vault {
+                use super::*;
+
+                pub fn set_fee(ctx: Context<SetFee>, fee_bps: u16) -> Result<()> {
+                    ctx.accounts.config.fee_bps = fee_bps;
+                    Ok(())
+                }
+            }
+
+            #[derive(Accounts)]
+            pub struct SetFee<'info> {
+                #[account(mut)]
+                pub config: Account<'info, Config>,
+                pub system_program: Program<'info, System>,
+            }
+        };
+
+        assert_eq!(flagged_handlers(&ast), vec!["set_fee"], "State mutation without any gate should be flagged");
+    }
+
+    #[test]
+    fn test_gated_handlers() {
+        let ast: File = parse_quote! {
+            #[program]
+            pub mod vault {
+                use super::*;
+
+                pub fn set_fee(ctx: Context<SetFee>, fee_bps: u16) -> Result<()> {
+                    ctx.accounts.config.fee_bps = fee_bps;
+                    Ok(())
+                }
+
+                pub fn crank(ctx: Context<Crank>) -> Result<()> {
+                    ctx.accounts.market.last_crank = Clock::get()?.slot;
+                    Ok(())
+                }
+
+                #[access_control(only_admin(&ctx))]
+                pub fn pause(ctx: Context<Pause>) -> Result<()> {
+                    ctx.accounts.config.paused = true;
+                    Ok(())
+                }
+            }
+
+            #[derive(Accounts)]
+            pub struct SetFee<'info> {
+                #[account(mut, has_one = admin)]
+                pub config: Account<'info, Config>,
+                pub admin: Signer<'info>,
+            }
+
+            #[derive(Accounts)]
+            pub struct Crank<'info> {
+                #[account(mut, constraint = market.next_crank <= Clock::get()?.slot)]
+                pub market: Account<'info, Market>,
+            }
+
+            #[derive(Accounts)]
+            pub struct Pause<'info> {
+                #[account(mut)]
+                pub config: Account<'info, Config>,
+            }
+        };
+
+        assert!(flagged_handlers(&ast).is_empty(), "Handlers with a signer, constraint or access_control should not be flagged");
+    }
+
+    #[test]
+    fn test_nested_accounts_struct() {
+        let ast: File = parse_quote! {
+            #[program]
+            pub mod vault {
+                use super::*;
+
+                pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+                    ctx.accounts.common.vault.balance += amount;
+                    Ok(())
+                }
+
+                pub fn sync(ctx: Context<Sync>) -> Result<()> {
+                    ctx.accounts.inner.vault.synced = true;
+                    Ok(())
+                }
+            }
+
+            #[derive(Accounts)]
+            pub struct Common<'info> {
+                #[account(mut)]
+                pub vault: Account<'info, Vault>,
+                pub user: Signer<'info>,
+            }
+
+            #[derive(Accounts)]
+            pub struct Deposit<'info> {
+                pub common: Common<'info>,
+            }
+
+            #[derive(Accounts)]
+            pub struct Unsigned<'info> {
+                #[account(mut)]
+                pub vault: Account<'info, Vault>,
+            }
+
+            #[derive(Accounts)]
+            pub struct Sync<'info> {
+                pub inner: Unsigned<'info>,
+            }
+        };
+
+        assert_eq!(flagged_handlers(&ast), vec!["sync"], "Nested accounts structs should be taken into account");
+    }
+
+    #[test]
+    fn test_read_only_and_outside_program() {
+        let ast: File = parse_quote! {
+            #[program]
+            pub mod vault {
+                use super::*;
+
+                pub fn view(ctx: Context<View>) -> Result<u64> {
+                    Ok(ctx.accounts.vault.balance)
+                }
+
+                pub fn external(ctx: Context<External>) -> Result<()> {
+                    Ok(())
+                }
+            }
+
+            pub fn helper(ctx: Context<Helper>) -> Result<()> {
+                ctx.accounts.vault.balance = 0;
+                Ok(())
+            }
+
+            #[derive(Accounts)]
+            pub struct View<'info> {
+                pub vault: Account<'info, Vault>,
+            }
+
+            #[derive(Accounts)]
+            pub struct Helper<'info> {
+                #[account(mut)]
+                pub vault: Account<'info, Vault>,
+            }
+        };
+
+        assert!(
+            flagged_handlers(&ast).is_empty(),
+            "Read-only handlers, unknown accounts structs and functions outside #[program] should not be flagged"
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use std::collections::{HashMap, HashSet};
+use syn::visit::{self, Visit};
+use syn::{Expr, File, Item};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Functions performing a cross-program invocation
+const CPI_FUNCTIONS: [&str; 2] = ["invoke", "invoke_signed"];
+
+/// Calls deserializing account data into a local copy
+const DESERIALIZERS: [&str; 6] = [
+    "try_from_slice", "deserialize", "try_deserialize", "try_deserialize_unchecked", "unpack", "unpack_unchecked",
+];
+
+/// Accessors of the raw data of an `AccountInfo`
+const DATA_ACCESSORS: [&str; 4] = ["data", "try_borrow_data", "try_borrow_mut_data", "borrow_data"];
+
+/// `AccountInfo` fields the runtime refreshes after a CPI
+const LIVE_FIELDS: [&str; 8] = [
+    "key", "owner", "lamports", "data", "is_signer", "is_writable", "executable", "rent_epoch",
+];
+
+pub trait StaleAccountAfterCpiFilters<'a> {
+    fn stale_reads_after_cpi(self, ast: &'a File) -> AstQuery<'a>;
+}
+
+impl<'a> StaleAccountAfterCpiFilters<'a> for AstQuery<'a> {
+    fn stale_reads_after_cpi(self, ast: &'a File) -> AstQuery<'a> {
+        debug!("Filtering account data read after a CPI without a reload");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let (sig, block) = match node.data {
+                NodeData::Function(func) => (&func.sig, func.block.as_ref()),
+                NodeData::ImplFunction(func) => (&func.sig, &func.block),
+                _ => continue,
+            };
+
+            let readonly = anchor::context_accounts_type(sig)
+                .map(|accounts_type| readonly_accounts(ast, &accounts_type))
+                .unwrap_or_default();
+
+            let mut tracker = StaleDataTracker::new(readonly);
+            tracker.visit_block(block);
+
+            for (expr, account) in tracker.stale_reads {
+                trace!("Found '{account}' read after a CPI in function: {}", sig.ident);
+                new_results.push(AstNode::from_expression(expr, &account));
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Fields of an Anchor accounts struct that a CPI cannot modify (not `mut` nor `init`)
+fn readonly_accounts(ast: &File, accounts_type: &str) -> HashSet<String> {
+    ast.items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Struct(item_struct) if item_struct.ident == accounts_type => Some(item_struct),
+            _ => None,
+        })
+        .flat_map(anchor::named_fields)
+        .filter(|field| {
+            !["mut", "init", "init_if_needed"]
+                .iter()
+                .any(|constraint| anchor::has_constraint(&field.attrs, constraint))
+        })
+        .filter_map(|field| field.ident.as_ref().map(ToString::to_string))
+        .collect()
+}
+
+/// Visitor that follows, in source order, which accounts a CPI may have modified
+/// and reports reads of their data before a reload
+struct StaleDataTracker<'ast> {
+    /// Anchor accounts that cannot be passed as writable
+    readonly: HashSet<String>,
+    /// Locals bound to an Anchor account (`let vault = &mut ctx.accounts.vault;`)
+    aliases: HashMap<String, String>,
+    /// Locals holding data deserialized from an account, with that account
+    copies: HashMap<String, String>,
+    /// Accounts passed read-only in an `AccountMeta`
+    readonly_metas: HashSet<String>,
+    /// Locals holding a `CpiContext`, with the tokens it was built from
+    cpi_contexts: HashMap<String, proc_macro2::TokenStream>,
+    /// Accounts and locals whose data may be outdated
+    stale: HashSet<String>,
+    /// Accounts already reported, so each is reported once per function
+    reported: HashSet<String>,
+    stale_reads: Vec<(&'ast Expr, String)>,
+}
+
+impl<'ast> StaleDataTracker<'ast> {
+    fn new(readonly: HashSet<String>) -> Self {
+        Self {
+            readonly,
+            aliases: HashMap::new(),
+            copies: HashMap::new(),
+            readonly_metas: HashSet::new(),
+            cpi_contexts: HashMap::new(),
+            stale: HashSet::new(),
+            reported: HashSet::new(),
+            stale_reads: Vec::new(),
+        }
+    }
+
+    /// Anchor account an expression refers to, directly or through an alias
+    fn account(&self, expr: &Expr) -> Option<String> {
+        let expr = strip_references(expr);
+        anchor::accounts_field(expr).or_else(|| match expr {
+            Expr::Path(path) => path.path.get_ident().and_then(|ident| self.aliases.get(&ident.to_string()).cloned()),
+            _ => None,
+        })
+    }
+
+    /// Mark everything the accounts passed to a CPI may have changed as stale
+    fn record_cpi(&mut self, tokens: proc_macro2::TokenStream) {
+        let mut passed: HashSet<String> = anchor::accounts_fields_in_tokens(&tokens).into_iter().collect();
+        for token in anchor::flatten_tokens(tokens) {
+            if let Some(account) = self.aliases.get(&token) {
+                passed.insert(account.clone());
+            } else if let Some(context) = self.cpi_contexts.get(&token) {
+                passed.extend(anchor::accounts_fields_in_tokens(context));
+                passed.extend(anchor::flatten_tokens(context.clone()));
+            } else if !self.copies.contains_key(&token) {
+                passed.insert(token);
+            }
+        }
+        passed.retain(|account| !self.readonly.contains(account) && !self.readonly_metas.contains(account));
+
+        self.stale.extend(passed.iter().cloned());
+        for (local, account) in &self.copies {
+            if passed.contains(account) {
+                self.stale.insert(local.clone());
+            }
+        }
+    }
+
+    /// A `CpiContext` built in place or held by a local
+    fn is_cpi_context_arg(&self, expr: &Expr) -> bool {
+        is_cpi_context(expr)
+            || matches!(expr, Expr::Path(path) if path
+                .path
+                .get_ident()
+                .is_some_and(|ident| self.cpi_contexts.contains_key(&ident.to_string())))
+    }
+
+    fn record_read(&mut self, expr: &'ast Expr, name: String, account: String) {
+        if self.stale.contains(&name) && self.reported.insert(account.clone()) {
+            self.stale_reads.push((expr, account));
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for StaleDataTracker<'ast> {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        visit::visit_local(self, local);
+
+        let (syn::Pat::Ident(pat_ident), Some(init)) = (&local.pat, &local.init) else {
+            return;
+        };
+        let local_name = pat_ident.ident.to_string();
+        // A rebinding replaces whatever the name held before
+        if self.copies.remove(&local_name).is_some() {
+            self.stale.remove(&local_name);
+        }
+        self.cpi_contexts.remove(&local_name);
+
+        if is_cpi_context(&init.expr) {
+            self.cpi_contexts.insert(local_name, init.expr.to_token_stream());
+        } else if let Some(account) = self.account(&init.expr) {
+            self.aliases.insert(local_name, account);
+        } else if let Some(account) = deserialized_account(&init.expr) {
+            self.copies.insert(local_name, account);
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::Call(call) => {
+                if let Expr::Path(path) = &*call.func
+                    && let Some(last) = path.path.segments.last()
+                {
+                    let name = last.ident.to_string();
+                    if CPI_FUNCTIONS.contains(&name.as_str()) {
+                        self.record_cpi(call.args.to_token_stream());
+                        return;
+                    }
+                    if name == "new_readonly"
+                        && let Some(account) = call.args.first().and_then(|arg| {
+                            anchor::flatten_tokens(arg.to_token_stream())
+                                .into_iter()
+                                .find(|token| !matches!(token.as_str(), "*" | "&"))
+                        })
+                    {
+                        self.readonly_metas.insert(account);
+                    }
+                }
+                // Anchor CPI helpers take a `CpiContext` built from the accounts
+                if call.args.iter().any(|arg| self.is_cpi_context_arg(arg)) {
+                    self.record_cpi(call.args.to_token_stream());
+                    return;
+                }
+            }
+            Expr::MethodCall(call) if call.method == "reload" => {
+                if let Some(account) = self.account(&call.receiver) {
+                    self.stale.remove(&account);
+                }
+            }
+            Expr::Field(field) => {
+                let member = field.member.to_token_stream().to_string();
+                if let Some(account) = self.account(&field.base) {
+                    if !LIVE_FIELDS.contains(&member.as_str()) {
+                        self.record_read(expr, account.clone(), account);
+                    }
+                } else if let Expr::Path(path) = strip_references(&field.base)
+                    && let Some(ident) = path.path.get_ident()
+                    && let Some(account) = self.copies.get(&ident.to_string()).cloned()
+                {
+                    self.record_read(expr, ident.to_string(), account);
+                }
+            }
+            _ => {}
+        }
+        visit::visit_expr(self, expr);
+    }
+}
+
+fn strip_references(mut expr: &Expr) -> &Expr {
+    while let Expr::Reference(reference) = expr {
+        expr = &reference.expr;
+    }
+    expr
+}
+
+/// `CpiContext::new(...)` or `CpiContext::new_with_signer(...)`
+fn is_cpi_context(expr: &Expr) -> bool {
+    let tokens = anchor::flatten_tokens(expr.to_token_stream());
+    tokens.windows(2).any(|window| window[0] == "CpiContext" && window[1] == ":")
+}
+
+/// Account whose data an expression deserializes (`State::try_from_slice(&vault.data.borrow())`)
+fn deserialized_account(expr: &Expr) -> Option<String> {
+    let tokens = anchor::flatten_tokens(expr.to_token_stream());
+    if !tokens.iter().any(|token| DESERIALIZERS.contains(&token.as_str())) {
+        return None;
+    }
+    tokens.windows(3).find_map(|window| {
+        (window[1] == "." && DATA_ACCESSORS.contains(&window[2].as_str())).then(|| window[0].clone())
+    })
+}
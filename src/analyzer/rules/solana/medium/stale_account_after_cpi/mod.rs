@@ -0,0 +1,64 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::StaleAccountAfterCpiFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("stale-account-after-cpi")
+        .title("Account Data Read After CPI")
+        .description("Detects writable accounts passed to invoke/invoke_signed or a CpiContext whose deserialized data (an Anchor Account<T> or a local decoded from the account data) is read after the CPI without reload() or re-deserialization. The called program can modify the accounts, so the handler keeps acting on outdated balances or state")
+        .severity(Severity::Medium)
+        .rule_type(RuleType::Solana)
+        .category("cpi")
+        .category("accounts")
+        .tag("security")
+        .tag("cpi")
+        .recommendations(vec![
+            "Call account.reload()? on Anchor accounts after the CPI before reading their fields",
+            "Deserialize native account data again after the CPI instead of reusing the earlier copy",
+            "Read the values the handler needs before the CPI when the CPI is meant to change them",
+        ])
+        .vulnerable_example(r#"
+pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+    let accounts = Transfer {
+        from: ctx.accounts.vault.to_account_info(),
+        to: ctx.accounts.user_token.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    token::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), accounts), amount)?;
+    // Still the balance read before the transfer
+    ctx.accounts.state.last_balance = ctx.accounts.vault.amount;
+    Ok(())
+}
+"#)
+        .fixed_example(r#"
+pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+    let accounts = Transfer {
+        from: ctx.accounts.vault.to_account_info(),
+        to: ctx.accounts.user_token.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    token::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), accounts), amount)?;
+    ctx.accounts.vault.reload()?;
+    ctx.accounts.state.last_balance = ctx.accounts.vault.amount;
+    Ok(())
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing account data read after CPIs");
+
+            AstQuery::new(ast)
+                .functions()
+                .stale_reads_after_cpi(ast)
+        })
+        .build()
+}
@@ -0,0 +1,126 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::medium::stale_account_after_cpi::filters::StaleAccountAfterCpiFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast)
+            .functions()
+            .stale_reads_after_cpi(ast)
+            .collect()
+            .len()
+    }
+
+    #[test]
+    fn test_anchor_account_read_after_cpi() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Withdraw<'info> {
+                #[account(mut)]
+                pub vault: Account<'info, TokenAccount>,
+                #[account(mut)]
+                pub state: Account<'info, State>,
+                pub token_program: Program<'info, Token>,
+            }
+
+            pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+                let cpi_ctx = CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer { from: ctx.accounts.vault.to_account_info() },
+                );
+                token::transfer(cpi_ctx, amount)?;
+                ctx.accounts.state.last_balance = ctx.accounts.vault.amount;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Should flag the vault balance read after the transfer");
+    }
+
+    #[test]
+    fn test_anchor_account_reloaded() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Withdraw<'info> {
+                #[account(mut)]
+                pub vault: Account<'info, TokenAccount>,
+                #[account(mut)]
+                pub state: Account<'info, State>,
+                pub token_program: Program<'info, Token>,
+            }
+
+            pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+                token::transfer(
+                    CpiContext::new(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer { from: ctx.accounts.vault.to_account_info() },
+                    ),
+                    ctx.accounts.vault.amount,
+                )?;
+                ctx.accounts.vault.reload()?;
+                ctx.accounts.state.last_balance = ctx.accounts.vault.amount;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Reads before the CPI or after reload() are fresh");
+    }
+
+    #[test]
+    fn test_readonly_account_not_flagged() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Swap<'info> {
+                pub config: Account<'info, Config>,
+                #[account(mut)]
+                pub pool: AccountInfo<'info>,
+            }
+
+            pub fn swap(ctx: Context<Swap>, ix: Instruction) -> Result<()> {
+                invoke(&ix, &[ctx.accounts.config.to_account_info(), ctx.accounts.pool.clone()])?;
+                require!(ctx.accounts.config.enabled, SwapError::Disabled);
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Accounts without mut cannot change in the CPI");
+    }
+
+    #[test]
+    fn test_native_copy_used_after_invoke() {
+        let ast: File = parse_quote! {
+            pub fn process_withdraw(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+                let vault = &accounts[0];
+                let state = Vault::try_from_slice(&vault.data.borrow())?;
+                invoke(&transfer_ix, &[vault.clone(), accounts[1].clone()])?;
+                if state.balance < amount {
+                    return Err(ProgramError::InsufficientFunds);
+                }
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Should flag the copy deserialized before the CPI");
+    }
+
+    #[test]
+    fn test_native_copy_deserialized_again() {
+        let ast: File = parse_quote! {
+            pub fn process_withdraw(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+                let vault = &accounts[0];
+                let state = Vault::try_from_slice(&vault.data.borrow())?;
+                invoke(&transfer_ix, &[vault.clone(), accounts[1].clone()])?;
+                let state = Vault::try_from_slice(&vault.data.borrow())?;
+                if state.balance < amount {
+                    return Err(ProgramError::InsufficientFunds);
+                }
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "A copy deserialized after the CPI is fresh");
+    }
+}
@@ -0,0 +1,312 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use std::collections::{HashMap, HashSet};
+use syn::punctuated::Punctuated;
+use syn::visit::{self, Visit};
+use syn::{Expr, File, Item, Token};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Functions performing a cross-program invocation
+const CPI_FUNCTIONS: [&str; 2] = ["invoke", "invoke_signed"];
+
+/// Modules and account names of well-known programs (token, system, associated
+/// token, memo), whose effect on the accounts is known
+const KNOWN_PROGRAMS: [&str; 15] = [
+    "token",
+    "token_interface",
+    "token_2022",
+    "system_program",
+    "associated_token",
+    "anchor_spl",
+    "spl_token",
+    "spl_token_2022",
+    "system_instruction",
+    "spl_associated_token_account",
+    "spl_memo",
+    "token_program",
+    "associated_token_program",
+    "memo_program",
+    "token_instruction",
+];
+
+/// Calls deserializing account data into a local copy
+const DESERIALIZERS: [&str; 6] = [
+    "try_from_slice", "deserialize", "try_deserialize", "try_deserialize_unchecked", "unpack", "unpack_unchecked",
+];
+
+/// Accessors of the raw data of an `AccountInfo`
+const DATA_ACCESSORS: [&str; 4] = ["data", "try_borrow_data", "try_borrow_mut_data", "borrow_data"];
+
+/// `AccountInfo` fields the runtime refreshes after a CPI
+const LIVE_FIELDS: [&str; 8] = [
+    "key", "owner", "lamports", "data", "is_signer", "is_writable", "executable", "rent_epoch",
+];
+
+pub trait StateCheckAfterExternalCpiFilters<'a> {
+    fn state_checks_after_external_cpi(self, ast: &'a File) -> AstQuery<'a>;
+}
+
+impl<'a> StateCheckAfterExternalCpiFilters<'a> for AstQuery<'a> {
+    fn state_checks_after_external_cpi(self, ast: &'a File) -> AstQuery<'a> {
+        debug!("Filtering security checks on account state after CPIs to external programs");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let (sig, block) = match node.data {
+                NodeData::Function(func) => (&func.sig, func.block.as_ref()),
+                NodeData::ImplFunction(func) => (&func.sig, &func.block),
+                _ => continue,
+            };
+
+            let readonly = anchor::context_accounts_type(sig)
+                .map(|accounts_type| readonly_accounts(ast, &accounts_type))
+                .unwrap_or_default();
+
+            let mut tracker = ExternalCpiTracker::new(readonly);
+            tracker.visit_block(block);
+
+            for (check, account) in tracker.checks {
+                trace!("Found check on '{account}' after an external CPI in function: {}", sig.ident);
+                new_results.push(match check {
+                    Check::Condition(expr) => AstNode::from_expression(expr, &account),
+                    Check::Macro(mac) => AstNode::from_macro(mac, &account),
+                });
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Fields of an Anchor accounts struct that a CPI cannot modify (not `mut` nor `init`)
+fn readonly_accounts(ast: &File, accounts_type: &str) -> HashSet<String> {
+    ast.items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Struct(item_struct) if item_struct.ident == accounts_type => Some(item_struct),
+            _ => None,
+        })
+        .flat_map(anchor::named_fields)
+        .filter(|field| {
+            !["mut", "init", "init_if_needed"]
+                .iter()
+                .any(|constraint| anchor::has_constraint(&field.attrs, constraint))
+        })
+        .filter_map(|field| field.ident.as_ref().map(ToString::to_string))
+        .collect()
+}
+
+/// A branch condition or a `require!`/`assert!` macro deciding on account state
+enum Check<'ast> {
+    Condition(&'ast Expr),
+    Macro(&'ast syn::Macro),
+}
+
+/// Visitor that follows, in source order, which accounts a CPI to an external
+/// program may have modified and reports checks on their state before a reload
+struct ExternalCpiTracker<'ast> {
+    /// Anchor accounts that cannot be passed as writable
+    readonly: HashSet<String>,
+    /// Locals bound to an Anchor account (`let vault = &mut ctx.accounts.vault;`)
+    aliases: HashMap<String, String>,
+    /// Locals holding data deserialized from an account, with that account
+    copies: HashMap<String, String>,
+    /// Initializers of locals, to resolve instructions and `CpiContext`s built beforehand
+    locals: HashMap<String, proc_macro2::TokenStream>,
+    /// Accounts and locals whose state an external program may have changed
+    tainted: HashSet<String>,
+    /// Accounts already reported, so each is reported once per function
+    reported: HashSet<String>,
+    checks: Vec<(Check<'ast>, String)>,
+}
+
+impl<'ast> ExternalCpiTracker<'ast> {
+    fn new(readonly: HashSet<String>) -> Self {
+        Self {
+            readonly,
+            aliases: HashMap::new(),
+            copies: HashMap::new(),
+            locals: HashMap::new(),
+            tainted: HashSet::new(),
+            reported: HashSet::new(),
+            checks: Vec::new(),
+        }
+    }
+
+    /// Anchor account an expression refers to, directly or through an alias
+    fn account(&self, expr: &Expr) -> Option<String> {
+        let expr = strip_references(expr);
+        anchor::accounts_field(expr).or_else(|| match expr {
+            Expr::Path(path) => path.path.get_ident().and_then(|ident| self.aliases.get(&ident.to_string()).cloned()),
+            _ => None,
+        })
+    }
+
+    /// Tokens of a CPI's arguments, with the locals they use expanded once
+    fn expand(&self, tokens: proc_macro2::TokenStream) -> Vec<String> {
+        let mut flat = anchor::flatten_tokens(tokens);
+        let expanded: Vec<String> = flat
+            .iter()
+            .filter_map(|token| self.locals.get(token))
+            .flat_map(|init| anchor::flatten_tokens(init.clone()))
+            .collect();
+        flat.extend(expanded);
+        flat
+    }
+
+    /// Taint every writable account passed to a CPI to an external program
+    fn record_cpi(&mut self, tokens: &[String]) {
+        if tokens.iter().any(|token| KNOWN_PROGRAMS.contains(&token.as_str())) {
+            return;
+        }
+
+        let mut passed = HashSet::new();
+        for window in tokens.windows(3) {
+            if window[0] == "accounts" && window[1] == "." {
+                passed.insert(window[2].clone());
+            }
+        }
+        for token in tokens {
+            if let Some(account) = self.aliases.get(token) {
+                passed.insert(account.clone());
+            } else if !self.copies.contains_key(token) {
+                passed.insert(token.clone());
+            }
+        }
+        passed.retain(|account| !self.readonly.contains(account));
+
+        for (local, account) in &self.copies {
+            if passed.contains(account) {
+                self.tainted.insert(local.clone());
+            }
+        }
+        self.tainted.extend(passed);
+    }
+
+    /// First tainted account whose state an expression reads
+    fn tainted_read(&self, expr: &Expr) -> Option<String> {
+        let mut fields = FieldCollector::default();
+        fields.visit_expr(expr);
+        fields.fields.into_iter().find_map(|field| {
+            let member = field.member.to_token_stream().to_string();
+            if let Some(account) = self.account(&field.base) {
+                return (!LIVE_FIELDS.contains(&member.as_str()) && self.tainted.contains(&account)).then_some(account);
+            }
+            let Expr::Path(path) = strip_references(&field.base) else {
+                return None;
+            };
+            let local = path.path.get_ident()?.to_string();
+            self.tainted.contains(&local).then(|| self.copies.get(&local).cloned()).flatten()
+        })
+    }
+
+    fn record_check(&mut self, check: Check<'ast>, expr: &Expr) {
+        if let Some(account) = self.tainted_read(expr)
+            && self.reported.insert(account.clone())
+        {
+            self.checks.push((check, account));
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for ExternalCpiTracker<'ast> {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        visit::visit_local(self, local);
+
+        let (syn::Pat::Ident(pat_ident), Some(init)) = (&local.pat, &local.init) else {
+            return;
+        };
+        let local_name = pat_ident.ident.to_string();
+        // A rebinding replaces whatever the name held before
+        if self.copies.remove(&local_name).is_some() {
+            self.tainted.remove(&local_name);
+        }
+        self.locals.insert(local_name.clone(), init.expr.to_token_stream());
+
+        if let Some(account) = self.account(&init.expr) {
+            self.aliases.insert(local_name, account);
+        } else if let Some(account) = deserialized_account(&init.expr) {
+            self.copies.insert(local_name, account);
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::Call(call) => {
+                if let Expr::Path(path) = &*call.func {
+                    let is_invoke = path
+                        .path
+                        .segments
+                        .last()
+                        .is_some_and(|last| CPI_FUNCTIONS.contains(&last.ident.to_string().as_str()));
+                    let mut tokens = self.expand(call.args.to_token_stream());
+                    // Anchor CPI helpers (`other_program::cpi::deposit`) take a `CpiContext`
+                    let is_anchor_cpi = tokens.windows(2).any(|window| window[0] == "CpiContext" && window[1] == ":");
+                    if is_invoke || is_anchor_cpi {
+                        tokens.extend(path.path.segments.iter().map(|segment| segment.ident.to_string()));
+                        self.record_cpi(&tokens);
+                        return;
+                    }
+                }
+            }
+            Expr::MethodCall(call) if call.method == "reload" => {
+                if let Some(account) = self.account(&call.receiver) {
+                    self.tainted.remove(&account);
+                }
+            }
+            Expr::If(expr_if) => self.record_check(Check::Condition(&expr_if.cond), &expr_if.cond),
+            Expr::While(expr_while) => self.record_check(Check::Condition(&expr_while.cond), &expr_while.cond),
+            Expr::Match(expr_match) => self.record_check(Check::Condition(&expr_match.expr), &expr_match.expr),
+            _ => {}
+        }
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let is_check = mac.path.segments.last().is_some_and(|segment| {
+            let name = segment.ident.to_string();
+            name.starts_with("require") || name.starts_with("assert")
+        });
+        if is_check
+            && let Ok(args) = mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+        {
+            for arg in &args {
+                self.record_check(Check::Macro(mac), arg);
+            }
+        }
+        visit::visit_macro(self, mac);
+    }
+}
+
+/// Visitor collecting the field accesses of an expression
+#[derive(Default)]
+struct FieldCollector<'ast> {
+    fields: Vec<&'ast syn::ExprField>,
+}
+
+impl<'ast> Visit<'ast> for FieldCollector<'ast> {
+    fn visit_expr_field(&mut self, field: &'ast syn::ExprField) {
+        self.fields.push(field);
+        visit::visit_expr_field(self, field);
+    }
+}
+
+fn strip_references(mut expr: &Expr) -> &Expr {
+    while let Expr::Reference(reference) = expr {
+        expr = &reference.expr;
+    }
+    expr
+}
+
+/// Account whose data an expression deserializes (`State::try_from_slice(&vault.data.borrow())`)
+fn deserialized_account(expr: &Expr) -> Option<String> {
+    let tokens = anchor::flatten_tokens(expr.to_token_stream());
+    if !tokens.iter().any(|token| DESERIALIZERS.contains(&token.as_str())) {
+        return None;
+    }
+    tokens.windows(3).find_map(|window| {
+        (window[1] == "." && DATA_ACCESSORS.contains(&window[2].as_str())).then(|| window[0].clone())
+    })
+}
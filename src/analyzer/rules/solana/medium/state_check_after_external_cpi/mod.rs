@@ -0,0 +1,66 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::StateCheckAfterExternalCpiFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("state-check-after-external-cpi")
+        .title("Security Check on Account State After External CPI")
+        .description("Detects handlers that invoke a program other than the token, system, associated token or memo programs and then branch on, or require!/assert! against, the state of an account passed writable to that CPI without reloading it. The external program can change the account in ways the handler does not anticipate, so the check decides on a value the invariant no longer holds for")
+        .severity(Severity::Medium)
+        .rule_type(RuleType::Solana)
+        .category("cpi")
+        .category("accounts")
+        .tag("security")
+        .tag("reentrancy")
+        .recommendations(vec![
+            "Call account.reload()? after the CPI and re-check the invariant on the refreshed state",
+            "Deserialize native account data again after the CPI instead of checking the earlier copy",
+            "Check the invariants the handler relies on before the CPI, and only pass accounts the callee must write as writable",
+        ])
+        .vulnerable_example(r#"
+pub fn deposit_and_borrow(ctx: Context<Borrow>, amount: u64) -> Result<()> {
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.strategy_program.to_account_info(),
+        strategy::cpi::accounts::Deposit {
+            vault: ctx.accounts.vault.to_account_info(),
+        },
+    );
+    strategy::cpi::deposit(cpi_ctx, amount)?;
+    // The strategy program may have changed the vault
+    require!(ctx.accounts.vault.collateral >= amount, LendingError::Undercollateralized);
+    Ok(())
+}
+"#)
+        .fixed_example(r#"
+pub fn deposit_and_borrow(ctx: Context<Borrow>, amount: u64) -> Result<()> {
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.strategy_program.to_account_info(),
+        strategy::cpi::accounts::Deposit {
+            vault: ctx.accounts.vault.to_account_info(),
+        },
+    );
+    strategy::cpi::deposit(cpi_ctx, amount)?;
+    ctx.accounts.vault.reload()?;
+    require!(ctx.accounts.vault.collateral >= amount, LendingError::Undercollateralized);
+    Ok(())
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing security checks on account state after external CPIs");
+
+            AstQuery::new(ast)
+                .functions()
+                .state_checks_after_external_cpi(ast)
+        })
+        .build()
+}
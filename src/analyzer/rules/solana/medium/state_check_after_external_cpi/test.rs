@@ -0,0 +1,122 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::medium::state_check_after_external_cpi::filters::StateCheckAfterExternalCpiFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast)
+            .functions()
+            .state_checks_after_external_cpi(ast)
+            .collect()
+            .len()
+    }
+
+    #[test]
+    fn test_require_after_external_cpi() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Borrow<'info> {
+                #[account(mut)]
+                pub vault: Account<'info, Vault>,
+                pub strategy_program: UncheckedAccount<'info>,
+            }
+
+            pub fn borrow(ctx: Context<Borrow>, amount: u64) -> Result<()> {
+                let cpi_ctx = CpiContext::new(
+                    ctx.accounts.strategy_program.to_account_info(),
+                    strategy::cpi::accounts::Deposit { vault: ctx.accounts.vault.to_account_info() },
+                );
+                strategy::cpi::deposit(cpi_ctx, amount)?;
+                require!(ctx.accounts.vault.collateral >= amount, LendingError::Undercollateralized);
+                if ctx.accounts.vault.collateral > LIMIT {
+                    return err!(LendingError::Limit);
+                }
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Should flag the first check on the vault after the external CPI");
+    }
+
+    #[test]
+    fn test_reloaded_or_known_program() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Borrow<'info> {
+                #[account(mut)]
+                pub vault: Account<'info, Vault>,
+                #[account(mut)]
+                pub vault_tokens: Account<'info, TokenAccount>,
+                pub config: Account<'info, Config>,
+            }
+
+            pub fn borrow(ctx: Context<Borrow>, amount: u64) -> Result<()> {
+                strategy::cpi::deposit(
+                    CpiContext::new(ctx.accounts.strategy_program.to_account_info(), Deposit {
+                        vault: ctx.accounts.vault.to_account_info(),
+                        config: ctx.accounts.config.to_account_info(),
+                    }),
+                    amount,
+                )?;
+                ctx.accounts.vault.reload()?;
+                require!(ctx.accounts.vault.collateral >= amount, LendingError::Undercollateralized);
+                require!(ctx.accounts.config.enabled, LendingError::Disabled);
+
+                token::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), Transfer {
+                    from: ctx.accounts.vault_tokens.to_account_info(),
+                }), amount)?;
+                if ctx.accounts.vault_tokens.amount < MINIMUM {
+                    return err!(LendingError::Drained);
+                }
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Reloaded, read-only and token program accounts should not be flagged");
+    }
+
+    #[test]
+    fn test_native_copy_checked_after_invoke() {
+        let ast: File = parse_quote! {
+            fn process(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+                let vault_info = next_account_info(iter)?;
+                let target_program = next_account_info(iter)?;
+                let vault = Vault::try_from_slice(&vault_info.data.borrow())?;
+                let ix = Instruction { program_id: *target_program.key, accounts: metas, data };
+                invoke(&ix, &[vault_info.clone(), target_program.clone()])?;
+                if vault.balance < amount {
+                    return Err(ProgramError::InsufficientFunds);
+                }
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Should flag the check on the copy decoded before the invoke");
+    }
+
+    #[test]
+    fn test_native_system_transfer_and_check_before_cpi() {
+        let ast: File = parse_quote! {
+            fn process(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+                let vault = Vault::try_from_slice(&vault_info.data.borrow())?;
+                if vault.balance < amount {
+                    return Err(ProgramError::InsufficientFunds);
+                }
+                invoke(
+                    &system_instruction::transfer(vault_info.key, user.key, amount),
+                    &[vault_info.clone(), user.clone()],
+                )?;
+                let ix = Instruction { program_id: *target_program.key, accounts: metas, data };
+                invoke(&ix, &[vault_info.clone()])?;
+                let vault = Vault::try_from_slice(&vault_info.data.borrow())?;
+                assert!(vault.balance >= amount);
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Checks before the CPI or on re-decoded data should not be flagged");
+    }
+}
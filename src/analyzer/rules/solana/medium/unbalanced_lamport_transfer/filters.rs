@@ -0,0 +1,277 @@
+use log::{debug, trace};
+use std::collections::{HashMap, HashSet};
+use syn::visit::{self, Visit};
+use syn::{Expr, Lit, Pat};
+
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Names shared by every accounts access, which say nothing about the amount
+const CONTEXT_NAMES: [&str; 2] = ["ctx", "accounts"];
+
+pub trait UnbalancedLamportTransferFilters<'a> {
+    fn unbalanced_lamport_transfers(self) -> AstQuery<'a>;
+}
+
+impl<'a> UnbalancedLamportTransferFilters<'a> for AstQuery<'a> {
+    fn unbalanced_lamport_transfers(self) -> AstQuery<'a> {
+        debug!("Filtering lamport debits and credits that do not balance");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let block = match node.data {
+                NodeData::Function(func) => func.block.as_ref(),
+                NodeData::ImplFunction(func) => &func.block,
+                _ => continue,
+            };
+
+            let mut finder = LamportMutationFinder::default();
+            finder.visit_block(block);
+
+            let mutations: Vec<(&LamportMutation, HashSet<String>)> = finder
+                .mutations
+                .iter()
+                .map(|mutation| (mutation, finder.resolve(&mutation.sources)))
+                .collect();
+
+            // A mutation is matched when the other side moves an amount derived
+            // from at least one of the same values
+            for (mutation, sources) in &mutations {
+                let matched = mutations.iter().any(|(other, other_sources)| {
+                    other.debit != mutation.debit && !sources.is_disjoint(other_sources)
+                });
+                if !matched {
+                    trace!(
+                        "Found unmatched lamport {}",
+                        if mutation.debit { "debit" } else { "credit" }
+                    );
+                    new_results.push(AstNode::from_expression(mutation.expr, "lamports"));
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// A direct change to an account's lamports
+struct LamportMutation<'ast> {
+    expr: &'ast Expr,
+    debit: bool,
+    /// Values the moved amount is computed from
+    sources: HashSet<String>,
+}
+
+/// Visitor that records direct lamport debits and credits
+#[derive(Default)]
+struct LamportMutationFinder<'ast> {
+    /// Values each local binding was computed from
+    locals: HashMap<String, HashSet<String>>,
+    mutations: Vec<LamportMutation<'ast>>,
+}
+
+impl LamportMutationFinder<'_> {
+    /// Follow local bindings back to the values they were computed from
+    fn resolve(&self, sources: &HashSet<String>) -> HashSet<String> {
+        let mut resolved = sources.clone();
+        let mut pending: Vec<String> = sources.iter().cloned().collect();
+        while let Some(source) = pending.pop() {
+            if let Some(origins) = self.locals.get(&source) {
+                for origin in origins {
+                    if resolved.insert(origin.clone()) {
+                        pending.push(origin.clone());
+                    }
+                }
+            }
+        }
+        resolved
+    }
+}
+
+impl<'ast> Visit<'ast> for LamportMutationFinder<'ast> {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        visit::visit_local(self, local);
+        let pat = match &local.pat {
+            Pat::Type(pat_type) => &*pat_type.pat,
+            pat => pat,
+        };
+        if let Pat::Ident(pat_ident) = pat
+            && let Some(init) = &local.init
+        {
+            self.locals.insert(pat_ident.ident.to_string(), amount_sources(&init.expr));
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            // `**account.lamports.borrow_mut() -= amount`
+            Expr::Binary(binary) if lamports_account(&binary.left).is_some() => {
+                let debit = match binary.op {
+                    syn::BinOp::SubAssign(_) => Some(true),
+                    syn::BinOp::AddAssign(_) => Some(false),
+                    _ => None,
+                };
+                if let Some(debit) = debit {
+                    self.mutations.push(LamportMutation {
+                        expr,
+                        debit,
+                        sources: amount_sources(&binary.right),
+                    });
+                }
+            }
+            // `**account.lamports.borrow_mut() = 0` or `= balance.checked_sub(amount)...`
+            Expr::Assign(assign) => {
+                if let Some(account) = lamports_account(&assign.left) {
+                    if is_zero(&assign.right) {
+                        self.mutations.push(LamportMutation {
+                            expr,
+                            debit: true,
+                            sources: HashSet::from([account]),
+                        });
+                    } else if let Some(debit) = arithmetic_direction(&assign.right) {
+                        self.mutations.push(LamportMutation {
+                            expr,
+                            debit,
+                            sources: amount_sources(&assign.right),
+                        });
+                    }
+                }
+            }
+            // `account.sub_lamports(amount)?` and `account.add_lamports(amount)?`
+            Expr::MethodCall(call)
+                if matches!(call.method.to_string().as_str(), "sub_lamports" | "add_lamports")
+                    && call.args.len() == 1 =>
+            {
+                self.mutations.push(LamportMutation {
+                    expr,
+                    debit: call.method == "sub_lamports",
+                    sources: amount_sources(&call.args[0]),
+                });
+            }
+            _ => {}
+        }
+        visit::visit_expr(self, expr);
+    }
+}
+
+/// Collects the values an amount is computed from: local names, accessed fields,
+/// integer literals and the accounts whose balance is read
+#[derive(Default)]
+struct AmountSourceCollector {
+    sources: HashSet<String>,
+}
+
+impl<'ast> Visit<'ast> for AmountSourceCollector {
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        if call.method == "lamports" && call.args.is_empty() {
+            if let Some(account) = account_name(&call.receiver) {
+                self.sources.insert(account);
+            }
+            return;
+        }
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_path(&mut self, path: &'ast syn::ExprPath) {
+        if let Some(ident) = path.path.get_ident() {
+            self.sources.insert(ident.to_string());
+        }
+        visit::visit_expr_path(self, path);
+    }
+
+    fn visit_member(&mut self, member: &'ast syn::Member) {
+        if let syn::Member::Named(ident) = member {
+            self.sources.insert(ident.to_string());
+        }
+    }
+
+    fn visit_lit(&mut self, lit: &'ast Lit) {
+        if let Lit::Int(int) = lit {
+            self.sources.insert(int.base10_digits().to_string());
+        }
+    }
+}
+
+fn amount_sources(expr: &Expr) -> HashSet<String> {
+    let mut collector = AmountSourceCollector::default();
+    collector.visit_expr(expr);
+    collector
+        .sources
+        .into_iter()
+        .filter(|source| !CONTEXT_NAMES.contains(&source.as_str()))
+        .collect()
+}
+
+/// Whether an assigned balance is decreased (`Some(true)`) or increased (`Some(false)`)
+fn arithmetic_direction(expr: &Expr) -> Option<bool> {
+    #[derive(Default)]
+    struct DirectionFinder {
+        debit: bool,
+        credit: bool,
+    }
+
+    impl<'ast> Visit<'ast> for DirectionFinder {
+        fn visit_bin_op(&mut self, op: &'ast syn::BinOp) {
+            match op {
+                syn::BinOp::Sub(_) => self.debit = true,
+                syn::BinOp::Add(_) => self.credit = true,
+                _ => {}
+            }
+        }
+
+        fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+            match call.method.to_string().as_str() {
+                "checked_sub" | "saturating_sub" | "wrapping_sub" => self.debit = true,
+                "checked_add" | "saturating_add" | "wrapping_add" => self.credit = true,
+                _ => {}
+            }
+            visit::visit_expr_method_call(self, call);
+        }
+    }
+
+    let mut finder = DirectionFinder::default();
+    finder.visit_expr(expr);
+    match (finder.debit, finder.credit) {
+        (true, false) => Some(true),
+        (false, true) => Some(false),
+        _ => None,
+    }
+}
+
+fn is_zero(expr: &Expr) -> bool {
+    matches!(expr, Expr::Lit(lit) if matches!(&lit.lit, Lit::Int(int) if int.base10_digits() == "0"))
+}
+
+/// Returns the account of a `**account.lamports.borrow_mut()` or
+/// `**account.try_borrow_mut_lamports()?` place expression
+fn lamports_account(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Unary(unary) if matches!(unary.op, syn::UnOp::Deref(_)) => lamports_account(&unary.expr),
+        Expr::Paren(paren) => lamports_account(&paren.expr),
+        Expr::Try(try_expr) => lamports_account(&try_expr.expr),
+        Expr::MethodCall(call) if call.method == "borrow_mut" => match &*call.receiver {
+            Expr::Field(field) if matches!(&field.member, syn::Member::Named(m) if m == "lamports") => {
+                account_name(&field.base)
+            }
+            _ => None,
+        },
+        Expr::MethodCall(call) if call.method == "try_borrow_mut_lamports" => account_name(&call.receiver),
+        _ => None,
+    }
+}
+
+/// Name of the account an expression refers to, looking through `to_account_info()`
+fn account_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::MethodCall(call) if matches!(call.method.to_string().as_str(), "to_account_info" | "as_ref") => {
+            account_name(&call.receiver)
+        }
+        Expr::Field(field) => match &field.member {
+            syn::Member::Named(member) => Some(member.to_string()),
+            syn::Member::Unnamed(_) => None,
+        },
+        Expr::Path(path) => path.path.get_ident().map(ToString::to_string),
+        Expr::Reference(reference) => account_name(&reference.expr),
+        Expr::Paren(paren) => account_name(&paren.expr),
+        _ => None,
+    }
+}
@@ -0,0 +1,54 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::UnbalancedLamportTransferFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("unbalanced-lamport-transfer")
+        .title("Unbalanced Lamport Transfer")
+        .description("Heuristic: detects direct lamport debits with no matching credit in the same function (or credits with no matching debit), and debits and credits computed from unrelated amounts. Direct lamport moves must balance or the transaction fails at runtime; an unpaired or mismatched side usually points to an accounting bug")
+        .severity(Severity::Medium)
+        .rule_type(RuleType::Solana)
+        .category("arithmetic")
+        .tag("security")
+        .tag("lamports")
+        .tag("heuristic")
+        .recommendations(vec![
+            "Pair every lamport debit with a credit of the same amount in the same instruction",
+            "Compute the debited and credited amounts from a single value, splitting fees out of it explicitly",
+            "Prefer a system program transfer when the source account is owned by the system program",
+        ])
+        .vulnerable_example(r#"
+pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+    **ctx.accounts.vault.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.user.to_account_info().try_borrow_mut_lamports()? += ctx.accounts.position.deposited;
+    Ok(())
+}
+"#)
+        .fixed_example(r#"
+pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+    let payout = amount.checked_sub(FEE).ok_or(ErrorCode::Overflow)?;
+    **ctx.accounts.vault.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.user.to_account_info().try_borrow_mut_lamports()? += payout;
+    **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? += FEE;
+    Ok(())
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing direct lamport transfers");
+
+            AstQuery::new(ast)
+                .functions()
+                .unbalanced_lamport_transfers()
+        })
+        .build()
+}
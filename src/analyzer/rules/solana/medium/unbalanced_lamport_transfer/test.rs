@@ -0,0 +1,83 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::medium::unbalanced_lamport_transfer::filters::UnbalancedLamportTransferFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast)
+            .functions()
+            .unbalanced_lamport_transfers()
+            .collect()
+            .len()
+    }
+
+    #[test]
+    fn test_unpaired_debit() {
+        let ast: File = parse_quote! {
+            fn withdraw(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+                let vault = &accounts[0];
+                **vault.try_borrow_mut_lamports()? -= amount;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Should flag a debit without a credit");
+    }
+
+    #[test]
+    fn test_mismatched_amounts() {
+        let ast: File = parse_quote! {
+            pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+                **ctx.accounts.vault.to_account_info().try_borrow_mut_lamports()? -= amount;
+                **ctx.accounts.user.to_account_info().try_borrow_mut_lamports()? += ctx.accounts.position.deposited;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 2, "Should flag a debit and credit of unrelated amounts");
+    }
+
+    #[test]
+    fn test_balanced_transfers() {
+        let ast: File = parse_quote! {
+            pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+                let fee = amount * FEE_BPS / 10_000;
+                let payout = amount - fee;
+                **ctx.accounts.vault.to_account_info().try_borrow_mut_lamports()? -= amount;
+                **ctx.accounts.user.to_account_info().try_borrow_mut_lamports()? += payout;
+                **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? += fee;
+                Ok(())
+            }
+
+            fn close(vault: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+                **destination.lamports.borrow_mut() += vault.lamports();
+                **vault.lamports.borrow_mut() = 0;
+                Ok(())
+            }
+
+            fn pay(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+                from.sub_lamports(amount)?;
+                to.add_lamports(amount)?;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Balanced transfers should not be flagged");
+    }
+
+    #[test]
+    fn test_assigned_balances() {
+        let ast: File = parse_quote! {
+            fn transfer(from: &AccountInfo, to: &AccountInfo, amount: u64, bonus: u64) -> ProgramResult {
+                **from.lamports.borrow_mut() = from.lamports().checked_sub(amount).unwrap();
+                **to.lamports.borrow_mut() = to.lamports().checked_add(bonus).unwrap();
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 2, "Should flag assigned balances moving unrelated amounts");
+    }
+}
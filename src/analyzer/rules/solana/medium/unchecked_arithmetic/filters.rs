@@ -0,0 +1,185 @@
+use log::{debug, trace};
+use std::collections::HashMap;
+use syn::visit::{self, Visit};
+use syn::{BinOp, Expr, FnArg, Lit, Pat, Type};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+const INTEGER_TYPES: [&str; 12] = [
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+pub trait UncheckedArithmeticFilters<'a> {
+    fn unchecked_arithmetic(self) -> AstQuery<'a>;
+}
+
+impl<'a> UncheckedArithmeticFilters<'a> for AstQuery<'a> {
+    fn unchecked_arithmetic(self) -> AstQuery<'a> {
+        debug!("Filtering unchecked arithmetic in instruction handlers");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let (sig, block) = match node.data {
+                NodeData::Function(func) => (&func.sig, func.block.as_ref()),
+                NodeData::ImplFunction(func) => (&func.sig, &func.block),
+                _ => continue,
+            };
+
+            if !anchor::is_instruction_handler(sig) {
+                continue;
+            }
+
+            let mut finder = ArithmeticFinder::default();
+            for input in &sig.inputs {
+                if let FnArg::Typed(pat_type) = input
+                    && let Pat::Ident(pat_ident) = &*pat_type.pat
+                {
+                    finder.record_type(&pat_ident.ident.to_string(), &pat_type.ty);
+                }
+            }
+            finder.visit_block(block);
+
+            let handler_name = sig.ident.to_string();
+            for expr in finder.found {
+                trace!("Found unchecked arithmetic in handler: {handler_name}");
+                new_results.push(AstNode::from_expression(expr, &handler_name));
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Inferred numeric kind of an operand
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NumericKind {
+    Integer,
+    Float,
+    Unknown,
+}
+
+/// Visitor that collects bare `+`, `-` and `*` operations
+#[derive(Default)]
+struct ArithmeticFinder<'ast> {
+    /// Known numeric kind of locals and parameters
+    kinds: HashMap<String, NumericKind>,
+    /// Depth of enclosing arithmetic expressions already reported
+    depth: usize,
+    /// Reported expressions
+    found: Vec<&'ast Expr>,
+}
+
+impl ArithmeticFinder<'_> {
+    fn record_type(&mut self, name: &str, ty: &Type) {
+        if let Some(kind) = type_kind(ty) {
+            self.kinds.insert(name.to_string(), kind);
+        }
+    }
+
+    fn kind_of(&self, expr: &Expr) -> NumericKind {
+        match expr {
+            Expr::Lit(lit) => match &lit.lit {
+                Lit::Int(_) => NumericKind::Integer,
+                Lit::Float(_) => NumericKind::Float,
+                _ => NumericKind::Unknown,
+            },
+            Expr::Path(path) => path
+                .path
+                .get_ident()
+                .and_then(|ident| self.kinds.get(&ident.to_string()).copied())
+                .unwrap_or(NumericKind::Unknown),
+            Expr::Cast(cast) => type_kind(&cast.ty).unwrap_or(NumericKind::Unknown),
+            Expr::Paren(paren) => self.kind_of(&paren.expr),
+            Expr::Binary(binary) => match (self.kind_of(&binary.left), self.kind_of(&binary.right)) {
+                (NumericKind::Float, _) | (_, NumericKind::Float) => NumericKind::Float,
+                (NumericKind::Integer, _) | (_, NumericKind::Integer) => NumericKind::Integer,
+                _ => NumericKind::Unknown,
+            },
+            _ => NumericKind::Unknown,
+        }
+    }
+
+    /// Returns true if the operation should be reported
+    fn is_unchecked(&self, left: &Expr, right: &Expr) -> bool {
+        let kinds = (self.kind_of(left), self.kind_of(right));
+        if kinds.0 == NumericKind::Float || kinds.1 == NumericKind::Float {
+            return false;
+        }
+
+        // Literal-only expressions are evaluated (and overflow-checked) at compile time
+        !(is_literal(left) && is_literal(right))
+    }
+}
+
+impl<'ast> Visit<'ast> for ArithmeticFinder<'ast> {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        match &local.pat {
+            Pat::Type(pat_type) => {
+                if let Pat::Ident(pat_ident) = &*pat_type.pat {
+                    self.record_type(&pat_ident.ident.to_string(), &pat_type.ty);
+                }
+            }
+            Pat::Ident(pat_ident) => {
+                if let Some(init) = &local.init {
+                    let kind = self.kind_of(&init.expr);
+                    if kind != NumericKind::Unknown {
+                        self.kinds.insert(pat_ident.ident.to_string(), kind);
+                    }
+                }
+            }
+            _ => {}
+        }
+        visit::visit_local(self, local);
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        let reported = match expr {
+            Expr::Binary(binary) if is_arithmetic(&binary.op) => {
+                self.depth == 0 && self.is_unchecked(&binary.left, &binary.right)
+            }
+            _ => false,
+        };
+
+        if reported {
+            self.found.push(expr);
+            self.depth += 1;
+            visit::visit_expr(self, expr);
+            self.depth -= 1;
+        } else {
+            visit::visit_expr(self, expr);
+        }
+    }
+}
+
+fn is_arithmetic(op: &BinOp) -> bool {
+    matches!(
+        op,
+        BinOp::Add(_)
+            | BinOp::Sub(_)
+            | BinOp::Mul(_)
+            | BinOp::AddAssign(_)
+            | BinOp::SubAssign(_)
+            | BinOp::MulAssign(_)
+    )
+}
+
+fn is_literal(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(_) => true,
+        Expr::Paren(paren) => is_literal(&paren.expr),
+        Expr::Binary(binary) => is_literal(&binary.left) && is_literal(&binary.right),
+        _ => false,
+    }
+}
+
+fn type_kind(ty: &Type) -> Option<NumericKind> {
+    let name = anchor::type_name(ty)?;
+    if INTEGER_TYPES.contains(&name.as_str()) {
+        Some(NumericKind::Integer)
+    } else if name == "f32" || name == "f64" {
+        Some(NumericKind::Float)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,50 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::UncheckedArithmeticFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("unchecked-arithmetic")
+        .title("Unchecked Integer Arithmetic")
+        .description("Detects bare +, - and * operations in instruction handlers. Solana programs are built in release mode, where integer overflow wraps silently and can corrupt balances")
+        .severity(Severity::Medium)
+        .rule_type(RuleType::General)
+        .category("arithmetic")
+        .tag("arithmetic")
+        .reference("CWE-190: Integer Overflow or Wraparound (https://cwe.mitre.org/data/definitions/190.html)")
+        .recommendations(vec![
+            "Use checked arithmetic and map overflow to a program error: amount.checked_add(fee).ok_or(ErrorCode::Overflow)?",
+            "Use saturating_add/saturating_sub/saturating_mul where clamping is the intended behavior",
+            "Enable overflow-checks = true in the release profile of Cargo.toml as a defense in depth",
+        ])
+        .vulnerable_example(r#"
+pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+    ctx.accounts.vault.balance += amount;
+    Ok(())
+}
+"#)
+        .fixed_example(r#"
+pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.balance = vault.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+    Ok(())
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing unchecked arithmetic");
+
+            AstQuery::new(ast)
+                .functions()
+                .unchecked_arithmetic()
+        })
+        .build()
+}
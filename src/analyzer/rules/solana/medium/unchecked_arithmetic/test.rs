@@ -0,0 +1,68 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::medium::unchecked_arithmetic::filters::UncheckedArithmeticFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast)
+            .functions()
+            .unchecked_arithmetic()
+            .collect()
+            .len()
+    }
+
+    #[test]
+    fn test_bare_arithmetic_in_handler() {
+        let ast: File = parse_quote! {
+            pub fn deposit(ctx: Context<Deposit>, amount: u64, fee: u64) -> Result<()> {
+                ctx.accounts.vault.balance += amount - fee;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1,
+                "Should report the outermost unchecked operation once");
+    }
+
+    #[test]
+    fn test_checked_arithmetic_is_ignored() {
+        let ast: File = parse_quote! {
+            pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+                let vault = &mut ctx.accounts.vault;
+                vault.balance = vault.balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Checked arithmetic should not be flagged");
+    }
+
+    #[test]
+    fn test_float_and_constant_arithmetic_are_ignored() {
+        let ast: File = parse_quote! {
+            pub fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+                let ratio: f64 = 0.5;
+                let scaled = ratio * 2.0;
+                let space = 8 + 32;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0,
+                "Float and literal-only arithmetic should not be flagged");
+    }
+
+    #[test]
+    fn test_non_handler_is_ignored() {
+        let ast: File = parse_quote! {
+            fn helper(a: u64, b: u64) -> u64 {
+                a + b
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Only instruction handlers should be analyzed");
+    }
+}
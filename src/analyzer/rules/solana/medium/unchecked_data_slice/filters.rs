@@ -0,0 +1,176 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use std::collections::{HashMap, HashSet};
+use syn::visit::{self, Visit};
+use syn::{BinOp, Expr, Member, Pat};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Methods of `AccountInfo` borrowing its data
+const DATA_BORROW_METHODS: [&str; 2] = ["try_borrow_data", "try_borrow_mut_data"];
+
+/// Methods borrowing the `RefCell` behind `AccountInfo::data`
+const REFCELL_BORROW_METHODS: [&str; 4] = ["borrow", "borrow_mut", "try_borrow", "try_borrow_mut"];
+
+/// Tokens of a data length read
+const LENGTH_READS: [&str; 2] = ["len", "data_len"];
+
+/// Macro name prefixes of assertions (`require!`, `require_gte!`, `assert!`, `assert_eq!`)
+const ASSERTION_MACROS: [&str; 2] = ["require", "assert"];
+
+pub trait UncheckedDataSliceFilters<'a> {
+    fn unchecked_data_slices(self) -> AstQuery<'a>;
+}
+
+impl<'a> UncheckedDataSliceFilters<'a> for AstQuery<'a> {
+    fn unchecked_data_slices(self) -> AstQuery<'a> {
+        debug!("Filtering account data slices without a preceding length check");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let (sig, block) = match node.data {
+                NodeData::Function(func) => (&func.sig, func.block.as_ref()),
+                NodeData::ImplFunction(func) => (&func.sig, &func.block),
+                _ => continue,
+            };
+
+            let mut finder = SliceFinder::default();
+            finder.visit_block(block);
+
+            for expr in finder.slices {
+                trace!("Found unchecked account data slice in function: {}", sig.ident);
+                new_results.push(AstNode::from_expression(expr, &sig.ident.to_string()));
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Strip references, parentheses and `?` around an expression
+fn strip(mut expr: &Expr) -> &Expr {
+    loop {
+        match expr {
+            Expr::Reference(reference) => expr = &reference.expr,
+            Expr::Paren(paren) => expr = &paren.expr,
+            Expr::Try(try_expr) => expr = &try_expr.expr,
+            _ => return expr,
+        }
+    }
+}
+
+/// Whether an expression borrows account data (`account.try_borrow_data()?`,
+/// `account.data.borrow()`)
+fn is_account_data(expr: &Expr) -> bool {
+    let Expr::MethodCall(call) = strip(expr) else {
+        return false;
+    };
+    let method = call.method.to_string();
+    if DATA_BORROW_METHODS.contains(&method.as_str()) {
+        return true;
+    }
+    REFCELL_BORROW_METHODS.contains(&method.as_str())
+        && matches!(strip(&call.receiver), Expr::Field(field) if matches!(&field.member, Member::Named(name) if name == "data"))
+}
+
+/// Account an expression is rooted at: the `x` of `ctx.accounts.x` or the
+/// leftmost variable of a method or field chain
+fn root_account(mut expr: &Expr) -> Option<String> {
+    loop {
+        if let Some(account) = anchor::accounts_field(expr) {
+            return Some(account);
+        }
+        expr = match strip(expr) {
+            Expr::MethodCall(call) => &call.receiver,
+            Expr::Field(field) => &field.base,
+            Expr::Path(path) => return path.path.get_ident().map(|ident| ident.to_string()),
+            _ => return None,
+        };
+    }
+}
+
+/// Visitor that collects range indexing of account data not preceded by a
+/// length check on the same data or account
+#[derive(Default)]
+struct SliceFinder<'ast> {
+    /// Local variables holding borrowed account data, with their account
+    data_vars: HashMap<String, Option<String>>,
+    /// Variables and accounts whose length has been checked so far
+    guarded: HashSet<String>,
+    slices: Vec<&'ast Expr>,
+}
+
+impl SliceFinder<'_> {
+    /// Treat every identifier of a length check as guarded
+    fn record_guard(&mut self, tokens: proc_macro2::TokenStream) {
+        let flat = anchor::flatten_tokens(tokens);
+        if flat.iter().any(|token| LENGTH_READS.contains(&token.as_str())) {
+            self.guarded.extend(flat);
+        }
+    }
+
+    /// Whether the data indexed by `base` has been length checked, or `None`
+    /// if `base` is not account data
+    fn is_guarded(&self, base: &Expr) -> Option<bool> {
+        if let Expr::Path(path) = strip(base)
+            && let Some(ident) = path.path.get_ident()
+            && let Some(account) = self.data_vars.get(&ident.to_string())
+        {
+            let guarded = self.guarded.contains(&ident.to_string())
+                || account.as_ref().is_some_and(|account| self.guarded.contains(account));
+            return Some(guarded);
+        }
+        if is_account_data(base) {
+            return Some(root_account(base).is_some_and(|account| self.guarded.contains(&account)));
+        }
+        None
+    }
+}
+
+impl<'ast> Visit<'ast> for SliceFinder<'ast> {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        if let Pat::Ident(pat_ident) = &local.pat
+            && let Some(init) = &local.init
+            && is_account_data(&init.expr)
+        {
+            self.data_vars
+                .insert(pat_ident.ident.to_string(), root_account(&init.expr));
+        }
+        visit::visit_local(self, local);
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::Binary(binary)
+                if matches!(
+                    binary.op,
+                    BinOp::Lt(_) | BinOp::Le(_) | BinOp::Gt(_) | BinOp::Ge(_) | BinOp::Eq(_) | BinOp::Ne(_)
+                ) =>
+            {
+                self.record_guard(binary.to_token_stream());
+            }
+            Expr::Index(index) => {
+                // `data[..]` cannot panic
+                let partial_range = matches!(&*index.index, Expr::Range(range) if range.start.is_some() || range.end.is_some());
+                if partial_range && self.is_guarded(&index.expr) == Some(false) {
+                    self.slices.push(expr);
+                }
+            }
+            _ => {}
+        }
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let is_assertion = mac
+            .path
+            .segments
+            .last()
+            .is_some_and(|s| ASSERTION_MACROS.iter().any(|prefix| s.ident.to_string().starts_with(prefix)));
+        if is_assertion {
+            self.record_guard(mac.tokens.clone());
+        }
+        visit::visit_macro(self, mac);
+    }
+}
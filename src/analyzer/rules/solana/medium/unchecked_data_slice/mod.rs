@@ -0,0 +1,54 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::UncheckedDataSliceFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("unchecked-data-slice")
+        .title("Account Data Sliced Without Length Check")
+        .description("Detects range indexing of borrowed account data (`account.data.borrow()[a..b]`, `data[8..]` passed to try_from_slice) with no preceding check of the data length. An account shorter than expected makes the slice panic and abort the transaction, and an account of another type can be read at the wrong offsets")
+        .severity(Severity::Medium)
+        .rule_type(RuleType::Solana)
+        .category("accounts")
+        .category("error-handling")
+        .tag("security")
+        .tag("deserialization")
+        .tag("panic")
+        .reference("CWE-129: Improper Validation of Array Index (https://cwe.mitre.org/data/definitions/129.html)")
+        .recommendations(vec![
+            "Check the length first: if data.len() < END { return Err(ProgramError::AccountDataTooSmall) }",
+            "Or use data.get(start..end).ok_or(ProgramError::InvalidAccountData)? instead of indexing",
+        ])
+        .vulnerable_example(r#"
+pub fn read_amount(account: &AccountInfo) -> Result<u64, ProgramError> {
+    let data = account.try_borrow_data()?;
+    let amount = u64::try_from_slice(&data[8..16])?;
+    Ok(amount)
+}
+"#)
+        .fixed_example(r#"
+pub fn read_amount(account: &AccountInfo) -> Result<u64, ProgramError> {
+    let data = account.try_borrow_data()?;
+    let bytes = data.get(8..16).ok_or(ProgramError::AccountDataTooSmall)?;
+    let amount = u64::try_from_slice(bytes)?;
+    Ok(amount)
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing account data slicing for missing length checks");
+
+            AstQuery::new(ast)
+                .functions()
+                .unchecked_data_slices()
+        })
+        .build()
+}
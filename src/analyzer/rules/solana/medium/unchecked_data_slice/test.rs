@@ -0,0 +1,76 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::medium::unchecked_data_slice::filters::UncheckedDataSliceFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast).functions().unchecked_data_slices().collect().len()
+    }
+
+    #[test]
+    fn test_unchecked_slices() {
+        let ast: File = parse_quote! {
+            pub fn read_config(config: &AccountInfo, vault: &AccountInfo) -> ProgramResult {
+                let data = config.try_borrow_data()?;
+                let admin = Pubkey::try_from_slice(&data[8..40])?;
+                let fee = u64::from_le_bytes(vault.data.borrow()[40..48].try_into().unwrap());
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 2, "Both slices of account data lack a length check");
+    }
+
+    #[test]
+    fn test_length_checked() {
+        let ast: File = parse_quote! {
+            pub fn read_config(config: &AccountInfo, vault: &AccountInfo) -> ProgramResult {
+                let data = config.try_borrow_data()?;
+                if data.len() < 40 {
+                    return Err(ProgramError::AccountDataTooSmall);
+                }
+                let admin = Pubkey::try_from_slice(&data[8..40])?;
+
+                require!(vault.data_len() >= 48, ErrorCode::AccountDidNotDeserialize);
+                let fee = u64::from_le_bytes(vault.data.borrow()[40..48].try_into().unwrap());
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Slices after a length check should not be flagged");
+    }
+
+    #[test]
+    fn test_check_after_slice() {
+        let ast: File = parse_quote! {
+            impl Processor {
+                fn load(account: &AccountInfo) -> ProgramResult {
+                    let mut data = account.data.borrow_mut();
+                    data[..8].copy_from_slice(&DISCRIMINATOR);
+                    assert!(data.len() >= 8);
+                    Ok(())
+                }
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "A length check after the slice does not guard it");
+    }
+
+    #[test]
+    fn test_safe_access() {
+        let ast: File = parse_quote! {
+            pub fn read(account: &AccountInfo, instruction_data: &[u8]) -> ProgramResult {
+                let data = account.try_borrow_data()?;
+                let whole = &data[..];
+                let amount = data.get(8..16).ok_or(ProgramError::InvalidAccountData)?;
+                let tag = &instruction_data[..1];
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Full ranges, get() and non-account slices should not be flagged");
+    }
+}
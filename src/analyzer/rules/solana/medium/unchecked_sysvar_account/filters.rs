@@ -0,0 +1,205 @@
+use log::{debug, trace};
+use std::collections::HashSet;
+use syn::visit::{self, Visit};
+use syn::{Expr, GenericArgument, PathArguments, Type};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Sysvars exposing cluster state an attacker could spoof
+const SYSVAR_TYPES: [&str; 6] = [
+    "Clock",
+    "Rent",
+    "EpochSchedule",
+    "EpochRewards",
+    "SlotHashes",
+    "StakeHistory",
+];
+
+/// Methods that deserialize raw account data
+const DESERIALIZERS: [&str; 5] = [
+    "deserialize",
+    "try_from_slice",
+    "unpack",
+    "unpack_unchecked",
+    "deserialize_data",
+];
+
+pub trait UncheckedSysvarAccountFilters<'a> {
+    fn sysvars_from_unchecked_accounts(self) -> AstQuery<'a>;
+}
+
+impl<'a> UncheckedSysvarAccountFilters<'a> for AstQuery<'a> {
+    fn sysvars_from_unchecked_accounts(self) -> AstQuery<'a> {
+        debug!("Filtering sysvars deserialized from unchecked accounts");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let block = match node.data {
+                NodeData::Function(func) => func.block.as_ref(),
+                NodeData::ImplFunction(func) => &func.block,
+                _ => continue,
+            };
+
+            let mut finder = SysvarReadFinder::default();
+            finder.visit_block(block);
+
+            for (account, expr) in finder.reads {
+                if finder.key_checked.contains(&account) {
+                    continue;
+                }
+                trace!("Found sysvar read from unchecked account '{account}'");
+                new_results.push(AstNode::from_expression(expr, &account));
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Visitor that collects sysvar deserializations and account key checks
+#[derive(Default)]
+struct SysvarReadFinder<'ast> {
+    /// Deserializations of sysvar data, with the source account
+    reads: Vec<(String, &'ast Expr)>,
+    /// Accounts whose key is compared or checked
+    key_checked: HashSet<String>,
+    /// The enclosing `let` declares a sysvar type
+    typed_local: bool,
+}
+
+impl<'ast> Visit<'ast> for SysvarReadFinder<'ast> {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        let previous = self.typed_local;
+        if let syn::Pat::Type(pat_type) = &local.pat {
+            self.typed_local = is_sysvar_type(&pat_type.ty);
+        }
+        visit::visit_local(self, local);
+        self.typed_local = previous;
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        let sysvar_read = match expr {
+            // `Clock::deserialize(..)`, `bincode::deserialize::<Clock>(..)`, typed `let`
+            Expr::Call(call) => match &*call.func {
+                Expr::Path(path) => {
+                    let segments = &path.path.segments;
+                    let is_deserializer = segments
+                        .last()
+                        .is_some_and(|s| DESERIALIZERS.contains(&s.ident.to_string().as_str()));
+                    let sysvar_path = segments
+                        .iter()
+                        .rev()
+                        .nth(1)
+                        .is_some_and(|s| SYSVAR_TYPES.contains(&s.ident.to_string().as_str()));
+                    let sysvar_turbofish = segments
+                        .last()
+                        .is_some_and(|s| has_sysvar_generic(&s.arguments));
+                    is_deserializer && (sysvar_path || sysvar_turbofish || self.typed_local)
+                }
+                _ => false,
+            },
+            // `account.deserialize_data::<Clock>()`
+            Expr::MethodCall(call) => {
+                call.method == "deserialize_data"
+                    && call
+                        .turbofish
+                        .as_ref()
+                        .is_some_and(|turbofish| turbofish.args.iter().any(is_sysvar_argument))
+            }
+            _ => false,
+        };
+
+        if sysvar_read && let Some(account) = data_source_account(expr) {
+            self.reads.push((account, expr));
+        }
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_expr_binary(&mut self, expr: &'ast syn::ExprBinary) {
+        if matches!(expr.op, syn::BinOp::Eq(_) | syn::BinOp::Ne(_)) {
+            for side in [&expr.left, &expr.right] {
+                if let Some(account) = key_owner(side) {
+                    self.key_checked.insert(account);
+                }
+            }
+        }
+        visit::visit_expr_binary(self, expr);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        // `sysvar::clock::check_id(account.key)`
+        if let Expr::Path(path) = &*call.func
+            && path.path.segments.last().is_some_and(|s| s.ident == "check_id")
+        {
+            for arg in &call.args {
+                if let Some(account) = key_owner(arg) {
+                    self.key_checked.insert(account);
+                }
+            }
+        }
+        visit::visit_expr_call(self, call);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let macro_name = mac.path.segments.last().map(|s| s.ident.to_string()).unwrap_or_default();
+        if macro_name.starts_with("require") || macro_name.starts_with("assert") {
+            let tokens = anchor::flatten_tokens(mac.tokens.clone());
+            for window in tokens.windows(3) {
+                if window[1] == "." && window[2] == "key" {
+                    self.key_checked.insert(window[0].clone());
+                }
+            }
+        }
+        visit::visit_macro(self, mac);
+    }
+}
+
+fn is_sysvar_type(ty: &Type) -> bool {
+    anchor::type_name(ty).is_some_and(|name| SYSVAR_TYPES.contains(&name.as_str()))
+}
+
+fn is_sysvar_argument(arg: &GenericArgument) -> bool {
+    matches!(arg, GenericArgument::Type(ty) if is_sysvar_type(ty))
+}
+
+fn has_sysvar_generic(arguments: &PathArguments) -> bool {
+    match arguments {
+        PathArguments::AngleBracketed(args) => args.args.iter().any(is_sysvar_argument),
+        _ => false,
+    }
+}
+
+/// Account whose data feeds the deserialization (`x` in `x.data.borrow()`,
+/// `x.try_borrow_data()?` or `x.deserialize_data()`)
+fn data_source_account(expr: &Expr) -> Option<String> {
+    let tokens = anchor::flatten_tokens(quote::quote!(#expr));
+    tokens
+        .windows(3)
+        .find(|window| {
+            window[1] == "."
+                && matches!(window[2].as_str(), "data" | "try_borrow_data" | "deserialize_data")
+        })
+        .map(|window| window[0].clone())
+}
+
+/// Returns the account of a key expression such as `*x.key` or `x.key()`
+fn key_owner(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Unary(unary) => key_owner(&unary.expr),
+        Expr::Reference(reference) => key_owner(&reference.expr),
+        Expr::Paren(paren) => key_owner(&paren.expr),
+        Expr::Field(field) if matches!(&field.member, syn::Member::Named(m) if m == "key") => {
+            account_ident(&field.base)
+        }
+        Expr::MethodCall(call) if call.method == "key" => account_ident(&call.receiver),
+        _ => None,
+    }
+}
+
+fn account_ident(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Path(path) => path.path.get_ident().map(ToString::to_string),
+        _ => anchor::accounts_field(expr),
+    }
+}
@@ -0,0 +1,52 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::UncheckedSysvarAccountFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("unchecked-sysvar-account")
+        .title("Sysvar Read From Unchecked Account")
+        .description("Detects Clock, Rent and other sysvars deserialized from a passed-in account without verifying its key, letting an attacker supply a fake sysvar and lie about the current slot, time or rent")
+        .severity(Severity::Medium)
+        .rule_type(RuleType::Solana)
+        .category("accounts")
+        .tag("security")
+        .tag("sysvar")
+        .reference("https://github.com/coral-xyz/sealevel-attacks/tree/master/programs/10-sysvar-address-checking")
+        .reference("Wormhole bridge exploit (February 2022): a spoofed instructions sysvar account bypassed signature verification")
+        .recommendations(vec![
+            "Read sysvars through the syscall instead of an account: Clock::get()?, Rent::get()?",
+            "If the account must be passed, use Clock::from_account_info, which verifies the sysvar id, or check account.key == &sysvar::clock::ID",
+            "In Anchor, declare the account as Sysvar<'info, Clock>",
+        ])
+        .vulnerable_example(r#"
+pub fn process(accounts: &[AccountInfo]) -> ProgramResult {
+    let clock_info = next_account_info(&mut accounts.iter())?;
+    let clock: Clock = bincode::deserialize(&clock_info.data.borrow()).unwrap();
+    // ...
+}
+"#)
+        .fixed_example(r#"
+pub fn process(accounts: &[AccountInfo]) -> ProgramResult {
+    let clock = Clock::get()?;
+    // ...
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing sysvar reads from unchecked accounts");
+
+            AstQuery::new(ast)
+                .functions()
+                .sysvars_from_unchecked_accounts()
+        })
+        .build()
+}
@@ -0,0 +1,62 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::medium::unchecked_sysvar_account::filters::UncheckedSysvarAccountFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_accounts(ast: &File) -> Vec<String> {
+        AstQuery::new(ast)
+            .functions()
+            .sysvars_from_unchecked_accounts()
+            .collect()
+            .into_iter()
+            .map(|node| node.name())
+            .collect()
+    }
+
+    #[test]
+    fn test_clock_deserialized_without_key_check() {
+        let ast: File = parse_quote! {
+            fn process(accounts: &[AccountInfo]) -> ProgramResult {
+                let clock_info = next_account_info(&mut accounts.iter())?;
+                let clock: Clock = bincode::deserialize(&clock_info.data.borrow()).unwrap();
+                let rent = bincode::deserialize::<Rent>(&rent_info.try_borrow_data()?).unwrap();
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_accounts(&ast), vec!["clock_info", "rent_info"],
+                "Should detect sysvars deserialized from unchecked accounts");
+    }
+
+    #[test]
+    fn test_clock_with_key_check() {
+        let ast: File = parse_quote! {
+            fn process(accounts: &[AccountInfo]) -> ProgramResult {
+                let clock_info = next_account_info(&mut accounts.iter())?;
+                if !sysvar::clock::check_id(clock_info.key) {
+                    return Err(ProgramError::InvalidArgument);
+                }
+                let clock: Clock = bincode::deserialize(&clock_info.data.borrow()).unwrap();
+                Ok(())
+            }
+        };
+
+        assert!(flagged_accounts(&ast).is_empty(), "A verified sysvar account should not be flagged");
+    }
+
+    #[test]
+    fn test_syscall_is_ignored() {
+        let ast: File = parse_quote! {
+            fn process(accounts: &[AccountInfo]) -> ProgramResult {
+                let clock = Clock::get()?;
+                let vault = Vault::try_from_slice(&vault_info.data.borrow())?;
+                Ok(())
+            }
+        };
+
+        assert!(flagged_accounts(&ast).is_empty(), "Clock::get() and non-sysvar data should not be flagged");
+    }
+}
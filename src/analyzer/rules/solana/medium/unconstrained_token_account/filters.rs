@@ -0,0 +1,167 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use std::collections::{HashMap, HashSet};
+use syn::visit::{self, Visit};
+use syn::{Expr, File, Pat, Type};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Account structs of token program CPIs moving or controlling tokens
+const TOKEN_CPI_ACCOUNTS: [&str; 9] = [
+    "Transfer",
+    "TransferChecked",
+    "Burn",
+    "BurnChecked",
+    "MintTo",
+    "MintToChecked",
+    "Approve",
+    "ApproveChecked",
+    "CloseAccount",
+];
+
+/// Constraints binding a token account to its mint
+const MINT_CONSTRAINTS: [&str; 2] = ["token::mint", "associated_token::mint"];
+
+/// Constraints binding a token account to its owner
+const AUTHORITY_CONSTRAINTS: [&str; 2] = ["token::authority", "associated_token::authority"];
+
+pub trait UnconstrainedTokenAccountFilters<'a> {
+    fn unconstrained_token_accounts(self, ast: &'a File) -> AstQuery<'a>;
+}
+
+impl<'a> UnconstrainedTokenAccountFilters<'a> for AstQuery<'a> {
+    fn unconstrained_token_accounts(self, ast: &'a File) -> AstQuery<'a> {
+        debug!("Filtering token accounts used in CPIs without mint or authority validation");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let NodeData::Struct(struct_item) = node.data else {
+                continue;
+            };
+            let struct_name = struct_item.ident.to_string();
+
+            // Token CPIs built in the handlers or in helper methods of the accounts struct
+            let mut usage = TokenUsageCollector::default();
+            for handler in anchor::handlers_for(ast, &struct_name) {
+                usage.aliases.clear();
+                usage.visit_block(handler.block);
+            }
+            for method in anchor::impl_methods(ast, &struct_name) {
+                usage.aliases.clear();
+                usage.visit_block(&method.block);
+            }
+
+            for field in anchor::named_fields(struct_item) {
+                let Some(ident) = &field.ident else {
+                    continue;
+                };
+                let account = ident.to_string();
+                if !is_token_account(&field.ty) || !usage.cpi_accounts.contains(&account) {
+                    continue;
+                }
+
+                let constraints = anchor::account_constraints(&field.attrs);
+                let validated = constraints.iter().any(|c| {
+                    MINT_CONSTRAINTS.contains(&c.name.as_str())
+                        || AUTHORITY_CONSTRAINTS.contains(&c.name.as_str())
+                        || (c.name == "has_one"
+                            && matches!(c.value.as_deref(), Some("mint" | "owner" | "authority")))
+                        || (c.name == "constraint"
+                            && c.value.as_deref().is_some_and(|value| {
+                                value.contains(&format!("{account} . mint"))
+                                    || value.contains(&format!("{account} . owner"))
+                            }))
+                }) || usage.checked.contains(&account);
+
+                if !validated {
+                    trace!("Found token account {account} used in a CPI without mint or authority validation");
+                    new_results.push(AstNode::from_field(field));
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Whether a field holds an SPL token account (`Account<'info, TokenAccount>`,
+/// `InterfaceAccount<'info, TokenAccount>`, possibly boxed)
+fn is_token_account(ty: &Type) -> bool {
+    match anchor::type_name(ty).as_deref() {
+        Some("Box") => anchor::type_arguments(ty).first().is_some_and(|inner| is_token_account(inner)),
+        Some("Account" | "InterfaceAccount") => anchor::type_arguments(ty)
+            .last()
+            .and_then(|inner| anchor::type_name(inner))
+            .is_some_and(|name| name == "TokenAccount"),
+        _ => false,
+    }
+}
+
+/// Visitor that records the accounts handed to token CPIs and the accounts
+/// whose `mint` or `owner` is checked in code
+#[derive(Default)]
+struct TokenUsageCollector {
+    /// Local variables bound to an accounts field
+    aliases: HashMap<String, String>,
+    /// Accounts passed in the accounts struct of a token CPI
+    cpi_accounts: HashSet<String>,
+    /// Accounts whose `mint` or `owner` is compared in a macro (`require_keys_eq!`)
+    /// or an `if` condition
+    checked: HashSet<String>,
+}
+
+impl TokenUsageCollector {
+    fn record_checks(&mut self, tokens: proc_macro2::TokenStream) {
+        let flat = anchor::flatten_tokens(tokens);
+        for window in flat.windows(3) {
+            if window[1] == "." && matches!(window[2].as_str(), "mint" | "owner") {
+                let account = self.aliases.get(&window[0]).cloned().unwrap_or_else(|| window[0].clone());
+                self.checked.insert(account);
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for TokenUsageCollector {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        if let Pat::Ident(pat_ident) = &local.pat
+            && let Some(init) = &local.init
+        {
+            let mut expr = &*init.expr;
+            while let Expr::Reference(reference) = expr {
+                expr = &reference.expr;
+            }
+            if let Some(account) = anchor::accounts_field(expr) {
+                self.aliases.insert(pat_ident.ident.to_string(), account);
+            }
+        }
+        visit::visit_local(self, local);
+    }
+
+    fn visit_expr_struct(&mut self, expr: &'ast syn::ExprStruct) {
+        if expr
+            .path
+            .segments
+            .last()
+            .is_some_and(|s| TOKEN_CPI_ACCOUNTS.contains(&s.ident.to_string().as_str()))
+        {
+            for field in &expr.fields {
+                let accounts = anchor::accounts_in(field.expr.to_token_stream(), &self.aliases);
+                self.cpi_accounts.extend(accounts);
+            }
+        }
+        visit::visit_expr_struct(self, expr);
+    }
+
+    fn visit_expr_if(&mut self, expr: &'ast syn::ExprIf) {
+        self.record_checks(expr.cond.to_token_stream());
+        visit::visit_expr_if(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        // Checks inside require_keys_eq!, require!, assert_eq! and similar
+        self.record_checks(mac.tokens.clone());
+        visit::visit_macro(self, mac);
+    }
+}
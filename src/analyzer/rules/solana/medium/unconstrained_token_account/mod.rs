@@ -0,0 +1,72 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::UnconstrainedTokenAccountFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("unconstrained-token-account")
+        .title("Token Account Without Mint or Authority Validation")
+        .description("Detects TokenAccount fields passed to token CPIs (transfer, burn, mint_to, approve, close_account) whose mint and owner are both left unvalidated: no token::mint / token::authority (or associated_token::) constraint, no has_one or constraint on them, and no check in the handler. An attacker can then supply a token account of the wrong mint or owned by someone else")
+        .severity(Severity::Medium)
+        .rule_type(RuleType::Anchor)
+        .category("accounts")
+        .category("cpi")
+        .tag("security")
+        .tag("token")
+        .reference("https://github.com/coral-xyz/sealevel-attacks/tree/master/programs/1-account-data-matching")
+        .recommendations(vec![
+            "Constrain the account with #[account(token::mint = expected_mint, token::authority = expected_authority)]",
+            "For associated token accounts, use associated_token::mint and associated_token::authority",
+            "Alternatively, check the mint and owner in the handler with require_keys_eq! before the CPI",
+        ])
+        .vulnerable_example(r#"
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut)]
+    pub user_tokens: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"vault"], bump)]
+    pub vault_tokens: Account<'info, TokenAccount>,
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.user_tokens.to_account_info(),
+        to: ctx.accounts.vault_tokens.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    token::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), amount)
+}
+"#)
+        .fixed_example(r#"
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut, token::mint = mint, token::authority = user)]
+    pub user_tokens: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"vault"], bump, token::mint = mint)]
+    pub vault_tokens: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing token accounts for mint and authority validation");
+
+            AstQuery::new(ast)
+                .structs()
+                .derives_accounts()
+                .unconstrained_token_accounts(ast)
+        })
+        .build()
+}
@@ -0,0 +1,134 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::medium::unconstrained_token_account::filters::UnconstrainedTokenAccountFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast)
+            .structs()
+            .derives_accounts()
+            .unconstrained_token_accounts(ast)
+            .collect()
+            .len()
+    }
+
+    #[test]
+    fn test_unconstrained_transfer_accounts() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Deposit<'info> {
+                #[account(mut)]
+                pub user_tokens: Account<'info, TokenAccount>,
+                #[account(mut)]
+                pub vault_tokens: Box<Account<'info, TokenAccount>>,
+                pub user: Signer<'info>,
+            }
+
+            pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+                let cpi_accounts = Transfer {
+                    from: ctx.accounts.user_tokens.to_account_info(),
+                    to: ctx.accounts.vault_tokens.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                };
+                token::transfer(CpiContext::new(program, cpi_accounts), amount)
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 2, "Both token accounts lack mint and authority validation");
+    }
+
+    #[test]
+    fn test_constrained_accounts() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Deposit<'info> {
+                #[account(mut, token::mint = mint, token::authority = user)]
+                pub user_tokens: Account<'info, TokenAccount>,
+                #[account(mut, associated_token::mint = mint, associated_token::authority = vault)]
+                pub vault_tokens: Account<'info, TokenAccount>,
+                #[account(mut, constraint = fee_tokens.mint == mint.key())]
+                pub fee_tokens: Account<'info, TokenAccount>,
+                pub user: Signer<'info>,
+            }
+
+            pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+                let cpi_accounts = Transfer {
+                    from: ctx.accounts.user_tokens.to_account_info(),
+                    to: ctx.accounts.vault_tokens.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                };
+                token::transfer(CpiContext::new(program, cpi_accounts), amount)?;
+                token::transfer(CpiContext::new(program, Transfer {
+                    from: ctx.accounts.user_tokens.to_account_info(),
+                    to: ctx.accounts.fee_tokens.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                }), fee)
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Constrained token accounts should not be flagged");
+    }
+
+    #[test]
+    fn test_checked_in_handler() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Withdraw<'info> {
+                #[account(mut)]
+                pub destination: Account<'info, TokenAccount>,
+                #[account(mut)]
+                pub vault_tokens: Account<'info, TokenAccount>,
+            }
+
+            pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+                let destination = &ctx.accounts.destination;
+                require_keys_eq!(destination.owner, ctx.accounts.user.key(), VaultError::WrongOwner);
+                if ctx.accounts.vault_tokens.mint != ctx.accounts.config.mint {
+                    return err!(VaultError::WrongMint);
+                }
+                token::transfer(ctx.accounts.transfer_ctx(), amount)
+            }
+
+            impl<'info> Withdraw<'info> {
+                fn transfer_ctx(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+                    CpiContext::new(self.token_program.to_account_info(), Transfer {
+                        from: self.vault_tokens.to_account_info(),
+                        to: self.destination.to_account_info(),
+                        authority: self.vault.to_account_info(),
+                    })
+                }
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Accounts checked in the handler should not be flagged");
+    }
+
+    #[test]
+    fn test_helper_method_cpi() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Burn<'info> {
+                #[account(mut)]
+                pub holder_tokens: Account<'info, TokenAccount>,
+                #[account(mut)]
+                pub unused_tokens: Account<'info, TokenAccount>,
+                pub holder: Signer<'info>,
+            }
+
+            impl<'info> Burn<'info> {
+                fn burn_ctx(&self) -> CpiContext<'_, '_, '_, 'info, token::Burn<'info>> {
+                    CpiContext::new(self.token_program.to_account_info(), token::Burn {
+                        mint: self.mint.to_account_info(),
+                        from: self.holder_tokens.to_account_info(),
+                        authority: self.holder.to_account_info(),
+                    })
+                }
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Only the token account used in the CPI should be flagged");
+    }
+}
@@ -0,0 +1,116 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use std::collections::HashSet;
+use syn::visit::{self, Visit};
+use syn::File;
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+pub trait UnguardedInitIfNeededFilters<'a> {
+    fn unguarded_init_if_needed(self, ast: &'a File) -> AstQuery<'a>;
+}
+
+impl<'a> UnguardedInitIfNeededFilters<'a> for AstQuery<'a> {
+    fn unguarded_init_if_needed(self, ast: &'a File) -> AstQuery<'a> {
+        debug!("Filtering init_if_needed accounts without an initialization check");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let NodeData::Struct(struct_item) = node.data else {
+                continue;
+            };
+
+            // Without a handler in the file there is no body to look for a guard in
+            let handlers = anchor::handlers_for(ast, &struct_item.ident.to_string());
+            if handlers.is_empty() {
+                continue;
+            }
+
+            for field in anchor::named_fields(struct_item) {
+                if !anchor::has_constraint(&field.attrs, "init_if_needed") {
+                    continue;
+                }
+                let Some(name) = field.ident.as_ref().map(ToString::to_string) else {
+                    continue;
+                };
+
+                let guarded = handlers.iter().any(|handler| {
+                    let mut finder = GuardFinder::new(&name);
+                    finder.visit_block(handler.block);
+                    finder.guarded
+                });
+
+                if !guarded {
+                    trace!("Found init_if_needed account '{name}' without an initialization check");
+                    new_results.push(AstNode::from_field(field));
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Visitor that looks for a condition on whether an account was already initialized
+struct GuardFinder {
+    /// The account field and the locals bound to it
+    names: HashSet<String>,
+    guarded: bool,
+}
+
+impl GuardFinder {
+    fn new(field: &str) -> Self {
+        Self {
+            names: HashSet::from([field.to_string()]),
+            guarded: false,
+        }
+    }
+
+    /// A condition mentioning the account and an initialization marker
+    /// (`is_initialized`, `initialized`, `Pubkey::default()`, ...)
+    fn check(&mut self, tokens: proc_macro2::TokenStream) {
+        let tokens = anchor::flatten_tokens(tokens);
+        let mentions_account = tokens.iter().any(|token| self.names.contains(token));
+        let has_marker = tokens.iter().any(|token| {
+            let token = token.to_lowercase();
+            token.contains("init") || token.contains("default")
+        });
+        if mentions_account && has_marker {
+            self.guarded = true;
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for GuardFinder {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        // `let vault = &mut ctx.accounts.vault;` makes `vault` an alias
+        if let syn::Pat::Ident(pat_ident) = &local.pat
+            && let Some(init) = &local.init
+            && anchor::AccountRefCollector::collect_expr(&init.expr)
+                .iter()
+                .any(|field| self.names.contains(field))
+        {
+            self.names.insert(pat_ident.ident.to_string());
+        }
+        visit::visit_local(self, local);
+    }
+
+    fn visit_expr_if(&mut self, expr: &'ast syn::ExprIf) {
+        self.check(expr.cond.to_token_stream());
+        visit::visit_expr_if(self, expr);
+    }
+
+    fn visit_expr_match(&mut self, expr: &'ast syn::ExprMatch) {
+        self.check(expr.expr.to_token_stream());
+        visit::visit_expr_match(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let name = mac.path.segments.last().map(|s| s.ident.to_string()).unwrap_or_default();
+        if name.starts_with("require") || name.starts_with("assert") {
+            self.check(mac.tokens.clone());
+        }
+        visit::visit_macro(self, mac);
+    }
+}
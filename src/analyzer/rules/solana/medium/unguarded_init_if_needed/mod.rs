@@ -0,0 +1,68 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::UnguardedInitIfNeededFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("unguarded-init-if-needed")
+        .title("init_if_needed Without Initialization Check")
+        .description("Detects #[account(init_if_needed, ...)] accounts whose instruction handlers never check whether the account already existed (e.g. an is_initialized flag or a default authority). The handler runs the same way for a fresh and an existing account, so calling it again re-initializes the state and can overwrite its authority or balances")
+        .severity(Severity::Medium)
+        .rule_type(RuleType::Anchor)
+        .category("initialization")
+        .tag("security")
+        .tag("initialization")
+        .reference("https://github.com/coral-xyz/sealevel-attacks/tree/master/programs/4-initialization")
+        .recommendations(vec![
+            "Store an is_initialized flag and reject or skip initialization when it is already set: require!(!vault.is_initialized, VaultError::AlreadyInitialized)",
+            "Compare a field set on initialization with its default value, e.g. vault.authority == Pubkey::default()",
+            "Prefer init when the account is only meant to be created once",
+        ])
+        .vulnerable_example(r#"
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(init_if_needed, payer = user, space = 8 + Vault::INIT_SPACE, seeds = [b"vault", user.key().as_ref()], bump)]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.owner = ctx.accounts.user.key();
+    vault.balance = amount;
+    Ok(())
+}
+"#)
+        .fixed_example(r#"
+pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    if !vault.is_initialized {
+        vault.owner = ctx.accounts.user.key();
+        vault.is_initialized = true;
+    }
+    require_keys_eq!(vault.owner, ctx.accounts.user.key());
+    vault.balance = vault.balance.checked_add(amount).ok_or(VaultError::Overflow)?;
+    Ok(())
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing init_if_needed accounts");
+
+            AstQuery::new(ast)
+                .structs()
+                .derives_accounts()
+                .unguarded_init_if_needed(ast)
+        })
+        .build()
+}
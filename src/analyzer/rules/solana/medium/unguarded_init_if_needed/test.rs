@@ -0,0 +1,122 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::medium::unguarded_init_if_needed::filters::UnguardedInitIfNeededFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast)
+            .structs()
+            .derives_accounts()
+            .unguarded_init_if_needed(ast)
+            .collect()
+            .len()
+    }
+
+    #[test]
+    fn test_unguarded_reinitialization() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Deposit<'info> {
+                #[account(init_if_needed, payer = user, space = 8 + 40)]
+                pub vault: Account<'info, Vault>,
+                #[account(mut)]
+                pub user: Signer<'info>,
+            }
+
+            pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+                let vault = &mut ctx.accounts.vault;
+                vault.owner = ctx.accounts.user.key();
+                vault.balance = amount;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Should flag init_if_needed without a guard");
+    }
+
+    #[test]
+    fn test_is_initialized_guard() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Deposit<'info> {
+                #[account(init_if_needed, payer = user, space = 8 + 40)]
+                pub vault: Account<'info, Vault>,
+                #[account(mut)]
+                pub user: Signer<'info>,
+            }
+
+            pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+                let vault = &mut ctx.accounts.vault;
+                if !vault.is_initialized {
+                    vault.owner = ctx.accounts.user.key();
+                    vault.is_initialized = true;
+                }
+                vault.balance += amount;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "An is_initialized check guards the account");
+    }
+
+    #[test]
+    fn test_default_authority_guard() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Register<'info> {
+                #[account(init_if_needed, payer = user, space = 8 + 32)]
+                pub profile: Account<'info, Profile>,
+                #[account(mut)]
+                pub user: Signer<'info>,
+            }
+
+            pub fn register(ctx: Context<Register>) -> Result<()> {
+                require!(ctx.accounts.profile.authority == Pubkey::default(), ProfileError::Exists);
+                ctx.accounts.profile.authority = ctx.accounts.user.key();
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "A default authority check guards the account");
+    }
+
+    #[test]
+    fn test_guard_on_other_account() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Deposit<'info> {
+                #[account(init_if_needed, payer = user, space = 8 + 40)]
+                pub vault: Account<'info, Vault>,
+                pub config: Account<'info, Config>,
+                #[account(mut)]
+                pub user: Signer<'info>,
+            }
+
+            pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+                require!(ctx.accounts.config.is_initialized, VaultError::NotReady);
+                ctx.accounts.vault.balance = amount;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "A check on another account does not guard the vault");
+    }
+
+    #[test]
+    fn test_without_handler() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Deposit<'info> {
+                #[account(init_if_needed, payer = user, space = 8 + 40)]
+                pub vault: Account<'info, Vault>,
+                #[account(mut)]
+                pub user: Signer<'info>,
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Handlers in other files cannot be checked");
+    }
+}
@@ -0,0 +1,265 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use std::collections::{HashMap, HashSet};
+use syn::visit::{self, Visit};
+use syn::{Expr, FnArg, Lit, Pat};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Methods whose receiver and arguments are bounded by the result
+const BOUNDING_METHODS: [&str; 3] = ["min", "max", "clamp"];
+
+/// Parameter types that never carry a caller-chosen size
+const ACCOUNT_TYPES: [&str; 3] = ["AccountInfo", "Context", "Pubkey"];
+
+pub trait UnsafeReallocFilters<'a> {
+    fn unsafe_realloc_constraints(self) -> AstQuery<'a>;
+    fn unsafe_realloc_calls(self) -> AstQuery<'a>;
+}
+
+impl<'a> UnsafeReallocFilters<'a> for AstQuery<'a> {
+    fn unsafe_realloc_constraints(self) -> AstQuery<'a> {
+        debug!("Filtering realloc constraints without zeroing or size bounds");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let NodeData::Struct(struct_item) = node.data else {
+                continue;
+            };
+
+            let fields = anchor::named_fields(struct_item);
+            let instruction_args = anchor::instruction_args(struct_item);
+
+            // Constraint expressions that compare or clamp a value
+            let bounds: Vec<Vec<String>> = fields
+                .iter()
+                .flat_map(|field| anchor::account_constraints(&field.attrs))
+                .filter(|c| c.name == "constraint")
+                .filter_map(|c| c.value)
+                .filter(|value| is_comparison(value))
+                .map(|value| identifiers(&value))
+                .collect();
+
+            for field in fields {
+                let Some(size) = anchor::constraint_value(&field.attrs, "realloc") else {
+                    continue;
+                };
+
+                let zeroed = anchor::constraint_value(&field.attrs, "realloc::zero")
+                    .is_some_and(|zero| zero == "true");
+
+                // Sizes taken from instruction data must be validated by a constraint
+                let size_idents = identifiers(&size);
+                let clamped = size_idents
+                    .iter()
+                    .any(|ident| BOUNDING_METHODS.contains(&ident.as_str()));
+                let unbounded = !clamped
+                    && size_idents.iter().any(|ident| {
+                        instruction_args.contains(ident)
+                            && !bounds.iter().any(|bound| bound.contains(ident))
+                    });
+
+                if !zeroed || unbounded {
+                    trace!(
+                        "Found unsafe realloc constraint on '{}' (zeroed: {zeroed}, unbounded: {unbounded})",
+                        field.ident.as_ref().map(ToString::to_string).unwrap_or_default()
+                    );
+                    new_results.push(AstNode::from_field(field));
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+
+    fn unsafe_realloc_calls(self) -> AstQuery<'a> {
+        debug!("Filtering realloc calls without zeroing or size bounds");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let (inputs, block) = match node.data {
+                NodeData::Function(func) => (&func.sig.inputs, func.block.as_ref()),
+                NodeData::ImplFunction(func) => (&func.sig.inputs, &func.block),
+                _ => continue,
+            };
+
+            let mut finder = ReallocFinder::default();
+            for input in inputs {
+                if let FnArg::Typed(pat_type) = input
+                    && let Pat::Ident(pat_ident) = &*pat_type.pat
+                {
+                    let ty = pat_type.ty.to_token_stream().to_string();
+                    if !ACCOUNT_TYPES.iter().any(|account| ty.contains(account)) {
+                        finder.params.insert(pat_ident.ident.to_string());
+                    }
+                }
+            }
+            finder.visit_block(block);
+
+            for (call, new_len, zero_init) in &finder.reallocs {
+                let skips_zeroing = matches!(
+                    zero_init,
+                    Expr::Lit(lit) if matches!(&lit.lit, Lit::Bool(value) if !value.value)
+                ) && !finder.zeroes;
+                let unbounded = finder.is_unbounded(new_len);
+
+                if skips_zeroing || unbounded {
+                    trace!("Found unsafe realloc call (zeroing skipped: {skips_zeroing}, unbounded: {unbounded})");
+                    new_results.push(AstNode::from_expression(call, "realloc"));
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Visitor that records `realloc` calls and the checks surrounding them
+#[derive(Default)]
+struct ReallocFinder<'ast> {
+    /// Function parameters that may carry a caller-chosen size
+    params: HashSet<String>,
+    /// Identifiers each local binding was computed from
+    locals: HashMap<String, HashSet<String>>,
+    /// Identifiers compared, clamped or asserted on
+    bounded: HashSet<String>,
+    /// Whether the function zeroes account data by hand
+    zeroes: bool,
+    /// `(call, new_len, zero_init)` of every `realloc` call
+    reallocs: Vec<(&'ast Expr, &'ast Expr, &'ast Expr)>,
+}
+
+impl ReallocFinder<'_> {
+    /// A size derived from a parameter that is never compared or clamped
+    fn is_unbounded(&self, new_len: &Expr) -> bool {
+        let mut sources = expression_identifiers(new_len);
+        let mut pending: Vec<String> = sources.iter().cloned().collect();
+        while let Some(ident) = pending.pop() {
+            if let Some(origins) = self.locals.get(&ident) {
+                for origin in origins {
+                    if sources.insert(origin.clone()) {
+                        pending.push(origin.clone());
+                    }
+                }
+            }
+        }
+
+        sources.iter().any(|ident| self.params.contains(ident))
+            && !sources.iter().any(|ident| self.bounded.contains(ident))
+    }
+}
+
+impl<'ast> Visit<'ast> for ReallocFinder<'ast> {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        visit::visit_local(self, local);
+        let pat = match &local.pat {
+            Pat::Type(pat_type) => &*pat_type.pat,
+            pat => pat,
+        };
+        if let Pat::Ident(pat_ident) = pat
+            && let Some(init) = &local.init
+        {
+            self.locals
+                .insert(pat_ident.ident.to_string(), expression_identifiers(&init.expr));
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let Expr::MethodCall(call) = expr
+            && call.method == "realloc"
+            && call.args.len() == 2
+        {
+            self.reallocs.push((expr, &call.args[0], &call.args[1]));
+        }
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_expr_binary(&mut self, binary: &'ast syn::ExprBinary) {
+        if matches!(
+            binary.op,
+            syn::BinOp::Lt(_) | syn::BinOp::Le(_) | syn::BinOp::Gt(_) | syn::BinOp::Ge(_)
+        ) {
+            self.bounded.extend(expression_identifiers(&binary.left));
+            self.bounded.extend(expression_identifiers(&binary.right));
+        }
+        visit::visit_expr_binary(self, binary);
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        let method = call.method.to_string();
+        if BOUNDING_METHODS.contains(&method.as_str()) {
+            self.bounded.extend(expression_identifiers(&call.receiver));
+            for arg in &call.args {
+                self.bounded.extend(expression_identifiers(arg));
+            }
+        }
+        if method == "fill"
+            && call.args.first().is_some_and(|arg| arg.to_token_stream().to_string() == "0")
+        {
+            self.zeroes = true;
+        }
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        if let Expr::Path(path) = &*call.func
+            && path.path.segments.last().is_some_and(|segment| segment.ident == "sol_memset")
+        {
+            self.zeroes = true;
+        }
+        visit::visit_expr_call(self, call);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let name = mac
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+            .unwrap_or_default();
+        if name.starts_with("require") || name.starts_with("assert") {
+            self.bounded.extend(identifiers(&mac.tokens.to_string()));
+        }
+        visit::visit_macro(self, mac);
+    }
+}
+
+/// Collects the single-segment paths referenced by an expression
+#[derive(Default)]
+struct IdentCollector {
+    idents: HashSet<String>,
+}
+
+impl<'ast> Visit<'ast> for IdentCollector {
+    fn visit_expr_path(&mut self, path: &'ast syn::ExprPath) {
+        if let Some(ident) = path.path.get_ident() {
+            self.idents.insert(ident.to_string());
+        }
+        visit::visit_expr_path(self, path);
+    }
+}
+
+fn expression_identifiers(expr: &Expr) -> HashSet<String> {
+    let mut collector = IdentCollector::default();
+    collector.visit_expr(expr);
+    collector.idents
+}
+
+/// Split the string form of tokens into identifier-like words
+fn identifiers(tokens: &str) -> Vec<String> {
+    tokens
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|word| !word.is_empty())
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Whether a constraint expression compares or clamps a value
+fn is_comparison(value: &str) -> bool {
+    value.contains('<')
+        || value.contains('>')
+        || identifiers(value)
+            .iter()
+            .any(|word| BOUNDING_METHODS.contains(&word.as_str()))
+}
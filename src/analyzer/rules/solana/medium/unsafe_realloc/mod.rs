@@ -0,0 +1,71 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::UnsafeReallocFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("unsafe-realloc")
+        .title("Unsafe Account Reallocation")
+        .description("Detects account reallocations that do not zero the new bytes, through #[account(realloc = ...)] without realloc::zero = true or AccountInfo::realloc(len, false), and reallocations sized from instruction data without bounds validation. Memory freed by an earlier shrink in the same transaction is handed back with its stale contents")
+        .severity(Severity::Medium)
+        .rule_type(RuleType::Solana)
+        .category("accounts")
+        .tag("security")
+        .tag("realloc")
+        .recommendations(vec![
+            "Zero the new bytes when growing an account: realloc::zero = true, or realloc(new_len, true)",
+            "Validate the requested size against the data the account must keep and a maximum before reallocating",
+        ])
+        .vulnerable_example(r#"
+#[derive(Accounts)]
+#[instruction(len: u16)]
+pub struct Resize<'info> {
+    #[account(
+        mut,
+        realloc = 8 + len as usize,
+        realloc::payer = payer,
+        realloc::zero = false,
+    )]
+    pub data: Account<'info, Data>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+"#)
+        .fixed_example(r#"
+#[derive(Accounts)]
+#[instruction(len: u16)]
+pub struct Resize<'info> {
+    #[account(
+        mut,
+        realloc = 8 + len as usize,
+        realloc::payer = payer,
+        realloc::zero = true,
+        constraint = len as usize >= data.items.len() && len <= MAX_LEN,
+    )]
+    pub data: Account<'info, Data>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing account reallocations");
+
+            AstQuery::new(ast)
+                .structs()
+                .derives_accounts()
+                .unsafe_realloc_constraints()
+                .or(AstQuery::new(ast).functions().unsafe_realloc_calls())
+        })
+        .build()
+}
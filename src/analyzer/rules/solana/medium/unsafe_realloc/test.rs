@@ -0,0 +1,98 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::medium::unsafe_realloc::filters::UnsafeReallocFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast)
+            .structs()
+            .derives_accounts()
+            .unsafe_realloc_constraints()
+            .or(AstQuery::new(ast).functions().unsafe_realloc_calls())
+            .collect()
+            .len()
+    }
+
+    #[test]
+    fn test_realloc_constraint_without_zeroing() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Resize<'info> {
+                #[account(mut, realloc = 8 + 64, realloc::payer = payer, realloc::zero = false)]
+                pub data: Account<'info, Data>,
+                #[account(mut)]
+                pub payer: Signer<'info>,
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Should flag realloc::zero = false");
+    }
+
+    #[test]
+    fn test_realloc_constraint_sized_from_instruction_args() {
+        let unbounded: File = parse_quote! {
+            #[derive(Accounts)]
+            #[instruction(len: u16)]
+            pub struct Resize<'info> {
+                #[account(mut, realloc = 8 + len as usize, realloc::payer = payer, realloc::zero = true)]
+                pub data: Account<'info, Data>,
+                #[account(mut)]
+                pub payer: Signer<'info>,
+            }
+        };
+        let bounded: File = parse_quote! {
+            #[derive(Accounts)]
+            #[instruction(len: u16)]
+            pub struct Resize<'info> {
+                #[account(
+                    mut,
+                    realloc = 8 + len as usize,
+                    realloc::payer = payer,
+                    realloc::zero = true,
+                    constraint = len <= MAX_LEN
+                )]
+                pub data: Account<'info, Data>,
+                #[account(mut)]
+                pub payer: Signer<'info>,
+            }
+        };
+
+        assert_eq!(flagged_count(&unbounded), 1, "Should flag a size taken from instruction data");
+        assert_eq!(flagged_count(&bounded), 0, "A constraint bounding the size should not be flagged");
+    }
+
+    #[test]
+    fn test_manual_realloc_calls() {
+        let ast: File = parse_quote! {
+            fn grow(accounts: &[AccountInfo], new_len: usize) -> ProgramResult {
+                let data = &accounts[0];
+                data.realloc(data.data_len() + 32, false)?;
+                data.realloc(new_len, true)?;
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 2, "Should flag missing zeroing and an unbounded size");
+    }
+
+    #[test]
+    fn test_safe_manual_realloc() {
+        let ast: File = parse_quote! {
+            fn grow(accounts: &[AccountInfo], new_len: usize) -> ProgramResult {
+                let data = &accounts[0];
+                require!(new_len <= MAX_LEN, ErrorCode::TooLarge);
+                let len = new_len;
+                data.realloc(len, true)?;
+                let old_len = data.data_len();
+                data.realloc(old_len + 32, false)?;
+                data.try_borrow_mut_data()?[old_len..].fill(0);
+                Ok(())
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Bounded sizes and manual zeroing should not be flagged");
+    }
+}
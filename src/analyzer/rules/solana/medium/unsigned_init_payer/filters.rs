@@ -0,0 +1,52 @@
+use log::{debug, trace};
+use std::collections::HashSet;
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+pub trait UnsignedInitPayerFilters<'a> {
+    fn unsigned_init_payers(self) -> AstQuery<'a>;
+}
+
+impl<'a> UnsignedInitPayerFilters<'a> for AstQuery<'a> {
+    fn unsigned_init_payers(self) -> AstQuery<'a> {
+        debug!("Filtering init payers that are not signers");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let NodeData::Struct(struct_item) = node.data else {
+                continue;
+            };
+
+            let fields = anchor::named_fields(struct_item);
+
+            // Payers named by init constraints, each reported once
+            let payers: HashSet<String> = fields
+                .iter()
+                .filter(|field| {
+                    anchor::has_constraint(&field.attrs, "init")
+                        || anchor::has_constraint(&field.attrs, "init_if_needed")
+                })
+                .filter_map(|field| anchor::constraint_value(&field.attrs, "payer"))
+                .collect();
+
+            for field in fields {
+                let Some(name) = field.ident.as_ref().map(ToString::to_string) else {
+                    continue;
+                };
+                if !payers.contains(&name) {
+                    continue;
+                }
+
+                let is_signer = anchor::type_name(&field.ty).as_deref() == Some("Signer")
+                    || anchor::has_constraint(&field.attrs, "signer");
+                if !is_signer {
+                    trace!("Found init payer '{name}' that is not a signer");
+                    new_results.push(AstNode::from_field(field));
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
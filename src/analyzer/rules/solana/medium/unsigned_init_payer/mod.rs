@@ -0,0 +1,58 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::UnsignedInitPayerFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("unsigned-init-payer")
+        .title("Init Payer Not A Signer")
+        .description("Detects accounts named as payer = ... of an init or init_if_needed constraint that are neither Signer<'info> nor declared with the signer constraint. The payer is debited for the new account's rent and must sign; otherwise the instruction fails at runtime, and manual account creation built on the same assumption lets anyone name another account as payer")
+        .severity(Severity::Medium)
+        .rule_type(RuleType::Anchor)
+        .category("access-control")
+        .category("initialization")
+        .tag("security")
+        .tag("signer")
+        .recommendations(vec![
+            "Declare the payer as Signer<'info>: #[account(mut)] pub payer: Signer<'info>",
+            "If the payer must stay an AccountInfo, add the signer constraint: #[account(mut, signer)]",
+        ])
+        .vulnerable_example(r#"
+#[derive(Accounts)]
+pub struct CreateVault<'info> {
+    #[account(init, payer = payer, space = 8 + Vault::INIT_SPACE)]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+"#)
+        .fixed_example(r#"
+#[derive(Accounts)]
+pub struct CreateVault<'info> {
+    #[account(init, payer = payer, space = 8 + Vault::INIT_SPACE)]
+    pub vault: Account<'info, Vault>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing init payers");
+
+            AstQuery::new(ast)
+                .structs()
+                .derives_accounts()
+                .unsigned_init_payers()
+        })
+        .build()
+}
@@ -0,0 +1,71 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::medium::unsigned_init_payer::filters::UnsignedInitPayerFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_count(ast: &File) -> usize {
+        AstQuery::new(ast)
+            .structs()
+            .derives_accounts()
+            .unsigned_init_payers()
+            .collect()
+            .len()
+    }
+
+    #[test]
+    fn test_unchecked_payer() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Create<'info> {
+                #[account(init, payer = funder, space = 8 + 32)]
+                pub vault: Account<'info, Vault>,
+                #[account(init_if_needed, payer = funder, space = 8 + 32)]
+                pub config: Account<'info, Config>,
+                #[account(mut)]
+                pub funder: UncheckedAccount<'info>,
+                pub system_program: Program<'info, System>,
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Should flag the payer once");
+    }
+
+    #[test]
+    fn test_signer_payers() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Create<'info> {
+                #[account(init, payer = payer, space = 8 + 32)]
+                pub vault: Account<'info, Vault>,
+                #[account(mut)]
+                pub payer: Signer<'info>,
+                #[account(init, payer = funder, space = 8 + 32)]
+                pub config: Account<'info, Config>,
+                #[account(mut, signer)]
+                pub funder: AccountInfo<'info>,
+                pub system_program: Program<'info, System>,
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 0, "Signer payers should not be flagged");
+    }
+
+    #[test]
+    fn test_program_owned_payer() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Create<'info> {
+                #[account(init, payer = treasury, space = 8 + 32)]
+                pub vault: Account<'info, Vault>,
+                #[account(mut)]
+                pub treasury: Account<'info, Treasury>,
+                pub user: Signer<'info>,
+            }
+        };
+
+        assert_eq!(flagged_count(&ast), 1, "Should flag a program-owned account as payer");
+    }
+}
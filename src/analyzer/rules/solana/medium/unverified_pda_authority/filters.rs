@@ -0,0 +1,203 @@
+use log::{debug, trace};
+use quote::ToTokens;
+use std::collections::{HashMap, HashSet};
+use syn::visit::{self, Visit};
+use syn::{Expr, Field, File, Pat};
+
+use crate::analyzer::dsl::anchor;
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+
+/// Fields of Anchor CPI account structs that must sign
+const SIGNER_FIELDS: [&str; 2] = ["authority", "current_authority"];
+
+/// Account types holding no data, typical of PDAs that only sign
+const RAW_ACCOUNT_TYPES: [&str; 2] = ["AccountInfo", "UncheckedAccount"];
+
+/// Constraints verifying which account was passed
+const VERIFYING_CONSTRAINTS: [&str; 2] = ["seeds", "address"];
+
+pub trait UnverifiedPdaAuthorityFilters<'a> {
+    fn unverified_pda_authorities(self, ast: &'a File) -> AstQuery<'a>;
+}
+
+impl<'a> UnverifiedPdaAuthorityFilters<'a> for AstQuery<'a> {
+    fn unverified_pda_authorities(self, ast: &'a File) -> AstQuery<'a> {
+        debug!("Filtering PDA authorities signing CPIs without seeds verification");
+        let mut new_results = Vec::new();
+
+        for node in self.results() {
+            let NodeData::Struct(struct_item) = node.data else {
+                continue;
+            };
+            let struct_name = struct_item.ident.to_string();
+
+            // Signed CPIs in the handlers or in helper methods of the accounts struct
+            let mut signed = SignedCpiCollector::default();
+            for handler in anchor::handlers_for(ast, &struct_name) {
+                signed.aliases.clear();
+                signed.visit_block(handler.block);
+            }
+            for method in anchor::impl_methods(ast, &struct_name) {
+                signed.aliases.clear();
+                signed.visit_block(&method.block);
+            }
+
+            for field in anchor::named_fields(struct_item) {
+                let Some(ident) = &field.ident else {
+                    continue;
+                };
+                let account = ident.to_string();
+
+                let authority = signed.context_signers.contains(&account)
+                    || (signed.invoke_accounts.contains(&account) && is_raw_authority(field, &account));
+                if authority && !is_verified(field) && !signed.key_checked.contains(&account) {
+                    trace!("Found PDA authority {account} signing without seeds verification");
+                    new_results.push(AstNode::from_field(field));
+                }
+            }
+        }
+
+        AstQuery::from_nodes(new_results)
+    }
+}
+
+/// Whether an account handed to `invoke_signed` looks like the signing PDA
+/// rather than an account the instruction only reads or writes
+fn is_raw_authority(field: &Field, account: &str) -> bool {
+    anchor::type_name(&field.ty).is_some_and(|name| RAW_ACCOUNT_TYPES.contains(&name.as_str()))
+        || account.contains("authority")
+}
+
+/// Whether the accounts struct pins the account: a real signer, seeds or a fixed address
+fn is_verified(field: &Field) -> bool {
+    anchor::type_name(&field.ty).as_deref() == Some("Signer")
+        || VERIFYING_CONSTRAINTS.iter().any(|name| anchor::has_constraint(&field.attrs, name))
+}
+
+/// Visitor that records the accounts signing CPIs with program seeds and the
+/// accounts whose key is checked in code
+#[derive(Default)]
+struct SignedCpiCollector<'ast> {
+    /// Local variables bound to an accounts field
+    aliases: HashMap<String, String>,
+    /// Local variables bound to a CPI accounts struct literal
+    literals: HashMap<String, &'ast syn::ExprStruct>,
+    /// Authorities of `CpiContext::new_with_signer` / `.with_signer` contexts
+    context_signers: HashSet<String>,
+    /// Accounts handed to `invoke_signed`
+    invoke_accounts: HashSet<String>,
+    /// Accounts whose `key` is compared in a macro or an `if` condition
+    key_checked: HashSet<String>,
+}
+
+impl<'ast> SignedCpiCollector<'ast> {
+    /// Record the signing fields of a CPI accounts struct, given inline or through a local
+    fn record_context_signers(&mut self, accounts: &'ast Expr) {
+        let literal = match accounts {
+            Expr::Struct(literal) => Some(literal),
+            Expr::Path(path) => path
+                .path
+                .get_ident()
+                .and_then(|ident| self.literals.get(&ident.to_string()).copied()),
+            _ => None,
+        };
+        let Some(literal) = literal else {
+            return;
+        };
+        let is_system = literal.path.segments.iter().any(|s| s.ident == "system_program");
+
+        for field in &literal.fields {
+            let signs = matches!(&field.member, syn::Member::Named(member)
+                if SIGNER_FIELDS.iter().any(|name| member == name) || (is_system && member == "from"));
+            if signs {
+                let accounts = anchor::accounts_in(field.expr.to_token_stream(), &self.aliases);
+                self.context_signers.extend(accounts);
+            }
+        }
+    }
+
+    fn record_key_checks(&mut self, tokens: proc_macro2::TokenStream) {
+        let flat = anchor::flatten_tokens(tokens);
+        for window in flat.windows(3) {
+            if window[1] == "." && window[2] == "key" {
+                let account = self.aliases.get(&window[0]).cloned().unwrap_or_else(|| window[0].clone());
+                self.key_checked.insert(account);
+            }
+        }
+    }
+}
+
+/// Whether a call expression is `CpiContext::new(..)`, returning its accounts argument
+fn cpi_context_accounts<'ast>(expr: &'ast Expr, constructor: &str) -> Option<&'ast Expr> {
+    let Expr::Call(call) = expr else {
+        return None;
+    };
+    let Expr::Path(path) = &*call.func else {
+        return None;
+    };
+    let segments: Vec<String> = path.path.segments.iter().map(|s| s.ident.to_string()).collect();
+    if segments.ends_with(&["CpiContext".to_string(), constructor.to_string()]) {
+        call.args.iter().nth(1)
+    } else {
+        None
+    }
+}
+
+impl<'ast> Visit<'ast> for SignedCpiCollector<'ast> {
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        if let Pat::Ident(pat_ident) = &local.pat
+            && let Some(init) = &local.init
+        {
+            let name = pat_ident.ident.to_string();
+            let mut expr = &*init.expr;
+            while let Expr::Reference(reference) = expr {
+                expr = &reference.expr;
+            }
+            if let Expr::MethodCall(call) = expr
+                && call.method == "to_account_info"
+            {
+                expr = &call.receiver;
+            }
+            if let Some(account) = anchor::accounts_field(expr) {
+                self.aliases.insert(name, account);
+            } else if let Expr::Struct(literal) = expr {
+                self.literals.insert(name, literal);
+            }
+        }
+        visit::visit_local(self, local);
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::Call(call) => {
+                if let Some(accounts) = cpi_context_accounts(expr, "new_with_signer") {
+                    self.record_context_signers(accounts);
+                } else if let Expr::Path(path) = &*call.func
+                    && path.path.segments.last().is_some_and(|s| s.ident == "invoke_signed")
+                    && let Some(account_infos) = call.args.iter().nth(1)
+                {
+                    let accounts = anchor::accounts_in(account_infos.to_token_stream(), &self.aliases);
+                    self.invoke_accounts.extend(accounts);
+                }
+            }
+            Expr::MethodCall(call) if call.method == "with_signer" => {
+                if let Some(accounts) = cpi_context_accounts(&call.receiver, "new") {
+                    self.record_context_signers(accounts);
+                }
+            }
+            _ => {}
+        }
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_expr_if(&mut self, expr: &'ast syn::ExprIf) {
+        self.record_key_checks(expr.cond.to_token_stream());
+        visit::visit_expr_if(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        // Checks inside require_keys_eq!, require!, assert_eq! and similar
+        self.record_key_checks(mac.tokens.clone());
+        visit::visit_macro(self, mac);
+    }
+}
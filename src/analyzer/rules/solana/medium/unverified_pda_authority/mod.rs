@@ -0,0 +1,72 @@
+use log::debug;
+use std::sync::Arc;
+
+use crate::analyzer::dsl::{AstQuery, RuleBuilder};
+use crate::analyzer::engine::RuleType;
+use crate::analyzer::{Rule, Severity};
+
+mod filters;
+use filters::UnverifiedPdaAuthorityFilters;
+
+#[cfg(test)]
+mod test;
+
+pub fn create_rule() -> Arc<dyn Rule> {
+    RuleBuilder::new()
+        .id("unverified-pda-authority")
+        .title("PDA Authority Without Seeds Verification")
+        .description("Detects accounts that sign a CPI through invoke_signed or CpiContext::new_with_signer / with_signer (the authority of an Anchor CPI, or a raw AccountInfo/UncheckedAccount handed to invoke_signed) while the accounts struct validates neither their seeds (seeds and bump constraints) nor their address. The program then signs for whatever account the caller passes, and a different PDA of the program, such as another user's vault authority, can be substituted")
+        .severity(Severity::Medium)
+        .rule_type(RuleType::Anchor)
+        .category("pda")
+        .category("cpi")
+        .tag("security")
+        .tag("pda")
+        .reference("https://github.com/coral-xyz/sealevel-attacks/tree/master/programs/7-bump-seed-canonicalization")
+        .recommendations(vec![
+            "Constrain the PDA authority with #[account(seeds = [...], bump)] using the same seeds as the signer seeds",
+            "Alternatively, compare its key with the address derived from the signer seeds before the CPI",
+        ])
+        .vulnerable_example(r#"
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    pub vault_tokens: Account<'info, TokenAccount>,
+    /// CHECK: PDA signing for the vault
+    pub vault_authority: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn withdraw(ctx: Context<Withdraw>, amount: u64, bump: u8) -> Result<()> {
+    let seeds: &[&[u8]] = &[b"vault", &[bump]];
+    token::transfer(
+        CpiContext::new_with_signer(program, Transfer {
+            from: ctx.accounts.vault_tokens.to_account_info(),
+            to: ctx.accounts.user_tokens.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        }, &[seeds]),
+        amount,
+    )
+}
+"#)
+        .fixed_example(r#"
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    pub vault_tokens: Account<'info, TokenAccount>,
+    /// CHECK: PDA signing for the vault
+    #[account(seeds = [b"vault"], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+}
+"#)
+        .dsl_query(|ast, _file_path, _span_extractor| {
+            debug!("Analyzing PDA authorities of signed CPIs for seeds verification");
+
+            AstQuery::new(ast)
+                .structs()
+                .derives_accounts()
+                .unverified_pda_authorities(ast)
+        })
+        .build()
+}
@@ -0,0 +1,132 @@
+use crate::analyzer::dsl::AstQuery;
+use crate::analyzer::rules::solana::medium::unverified_pda_authority::filters::UnverifiedPdaAuthorityFilters;
+use syn::{File, parse_quote};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged_fields(ast: &File) -> Vec<String> {
+        AstQuery::new(ast)
+            .structs()
+            .derives_accounts()
+            .unverified_pda_authorities(ast)
+            .collect()
+            .iter()
+            .filter_map(|node| node.name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_unverified_context_authority() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Withdraw<'info> {
+                #[account(mut)]
+                pub vault_tokens: Account<'info, TokenAccount>,
+                /// CHECK: PDA signing for the vault
+                pub vault_authority: UncheckedAccount<'info>,
+                pub token_program: Program<'info, Token>,
+            }
+
+            pub fn withdraw(ctx: Context<Withdraw>, amount: u64, bump: u8) -> Result<()> {
+                let seeds: &[&[u8]] = &[b"vault", &[bump]];
+                let cpi_accounts = Transfer {
+                    from: ctx.accounts.vault_tokens.to_account_info(),
+                    to: ctx.accounts.user_tokens.to_account_info(),
+                    authority: ctx.accounts.vault_authority.to_account_info(),
+                };
+                token::transfer(CpiContext::new_with_signer(program, cpi_accounts, &[seeds]), amount)
+            }
+        };
+
+        assert_eq!(flagged_fields(&ast), vec!["vault_authority"], "Signing authority without seeds should be flagged");
+    }
+
+    #[test]
+    fn test_unverified_invoke_signed_authority() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Release<'info> {
+                #[account(mut)]
+                pub escrow: Account<'info, Escrow>,
+                /// CHECK: PDA
+                pub escrow_signer: AccountInfo<'info>,
+                #[account(mut)]
+                pub destination: Account<'info, TokenAccount>,
+            }
+
+            impl<'info> Release<'info> {
+                fn release(&self, ix: &Instruction, seeds: &[&[u8]]) -> Result<()> {
+                    invoke_signed(ix, &[self.escrow_signer.to_account_info(), self.destination.to_account_info()], &[seeds])?;
+                    Ok(())
+                }
+            }
+        };
+
+        assert_eq!(
+            flagged_fields(&ast),
+            vec!["escrow_signer"],
+            "Raw PDA accounts handed to invoke_signed should be flagged, data accounts should not"
+        );
+    }
+
+    #[test]
+    fn test_verified_authorities() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Withdraw<'info> {
+                /// CHECK: PDA signing for the vault
+                #[account(seeds = [b"vault", user.key().as_ref()], bump)]
+                pub vault_authority: UncheckedAccount<'info>,
+                /// CHECK: fixed fee authority
+                #[account(address = FEE_AUTHORITY)]
+                pub fee_authority: UncheckedAccount<'info>,
+                /// CHECK: checked in the handler
+                pub treasury_authority: UncheckedAccount<'info>,
+            }
+
+            pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+                let (expected, _) = Pubkey::find_program_address(&[b"treasury"], ctx.program_id);
+                require_keys_eq!(ctx.accounts.treasury_authority.key(), expected);
+                token::transfer(CpiContext::new(program, Transfer {
+                    from: ctx.accounts.vault_tokens.to_account_info(),
+                    to: ctx.accounts.user_tokens.to_account_info(),
+                    authority: ctx.accounts.vault_authority.to_account_info(),
+                }).with_signer(&[seeds]), amount)?;
+                token::transfer(CpiContext::new_with_signer(program, Transfer {
+                    from: ctx.accounts.fee_tokens.to_account_info(),
+                    to: ctx.accounts.user_tokens.to_account_info(),
+                    authority: ctx.accounts.fee_authority.to_account_info(),
+                }, &[fee_seeds]), amount)?;
+                invoke_signed(&ix, &[ctx.accounts.treasury_authority.to_account_info()], &[treasury_seeds])
+            }
+        };
+
+        assert!(flagged_fields(&ast).is_empty(), "Authorities with seeds, address or key checks should not be flagged");
+    }
+
+    #[test]
+    fn test_unsigned_cpi() {
+        let ast: File = parse_quote! {
+            #[derive(Accounts)]
+            pub struct Deposit<'info> {
+                #[account(mut)]
+                pub user_tokens: Account<'info, TokenAccount>,
+                pub user: Signer<'info>,
+                /// CHECK: only read
+                pub delegate_authority: UncheckedAccount<'info>,
+            }
+
+            pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+                token::transfer(CpiContext::new(program, Transfer {
+                    from: ctx.accounts.user_tokens.to_account_info(),
+                    to: ctx.accounts.vault_tokens.to_account_info(),
+                    authority: ctx.accounts.delegate_authority.to_account_info(),
+                }), amount)
+            }
+        };
+
+        assert!(flagged_fields(&ast).is_empty(), "Authorities of CPIs without signer seeds should not be flagged");
+    }
+}
@@ -1,4 +1,5 @@
 pub mod high;
+pub mod informational;
 pub mod low;
 pub mod medium;
 
@@ -31,9 +31,10 @@ impl SpanExtractor {
             Location {
                 file: self.file_path.clone(),
                 line: start.line,
-                column: Some(start.column),
+                // proc-macro2 columns are 0-indexed
+                column: Some(start.column + 1),
                 end_line: Some(end.line),
-                end_column: Some(end.column),
+                end_column: Some(end.column + 1),
             }
         } else {
             Location {
@@ -201,6 +202,14 @@ impl Location {
         }
     }
 
+    /// Returns `line:column`, or just `line` when the column is unknown
+    pub fn position(&self) -> String {
+        match self.column {
+            Some(column) => format!("{}:{}", self.line, column),
+            None => self.line.to_string(),
+        }
+    }
+
     pub fn format_location(&self) -> String {
         match (&self.column, &self.end_line, &self.end_column) {
             (Some(col), Some(end_line), Some(end_col)) if end_line != &self.line => {
@@ -226,9 +235,11 @@ pub fn extract_span_from_node_data(node_data: &NodeData) -> Span {
         NodeData::Function(func) => func.span(),
         NodeData::ImplFunction(impl_func) => impl_func.span(),
         NodeData::Struct(struct_item) => struct_item.span(),
+        NodeData::Field(field) => field.span(),
         NodeData::Enum(enum_item) => enum_item.span(),
         NodeData::Block(block) => block.span(),
         NodeData::Expression(expr) => expr.span(),
+        NodeData::Macro(mac) => mac.span(),
         NodeData::Other => Span::call_site(),
     }
 }
\ No newline at end of file
@@ -0,0 +1,489 @@
+use anyhow::Context;
+use log::{debug, trace};
+use regex::Regex;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use syn::visit::{self, Visit};
+use syn::{Expr, File};
+
+use crate::analyzer::dsl::query::{AstNode, AstQuery, NodeData};
+use crate::analyzer::engine::{RULE_CATEGORIES, Rule, RuleType};
+use crate::analyzer::span_utils::SpanExtractor;
+use crate::analyzer::{Finding, Location, Severity};
+
+/// Rule template as written in a YAML or TOML file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TemplateDefinition {
+    id: String,
+    title: String,
+    severity: String,
+    #[serde(default)]
+    rule_type: Option<String>,
+    description: String,
+    #[serde(default)]
+    recommendations: Vec<String>,
+    #[serde(default)]
+    categories: Vec<String>,
+    #[serde(default)]
+    references: Vec<String>,
+    pattern: PatternDefinition,
+}
+
+/// Pattern section of a rule template
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PatternDefinition {
+    /// Regular expression matched against each source line
+    #[serde(default)]
+    regex: Option<String>,
+    /// Function, method or macro whose calls are flagged
+    #[serde(default)]
+    calls: Option<String>,
+    /// Call that, when made earlier in the same function, accepts `calls`
+    #[serde(default)]
+    without_preceding: Option<String>,
+}
+
+/// What a template rule matches
+#[derive(Debug, Clone)]
+enum TemplatePattern {
+    /// Source lines matching a regular expression
+    Regex(Regex),
+    /// Calls to a function, method or macro not preceded by a guard call
+    Call {
+        target: CallPath,
+        without_preceding: Option<CallPath>,
+    },
+}
+
+/// Path of a called function, method or macro (e.g. `token::transfer`, `require!`)
+#[derive(Debug, Clone)]
+struct CallPath(Vec<String>);
+
+impl CallPath {
+    fn parse(path: &str) -> Option<Self> {
+        let segments: Vec<String> = path
+            .trim()
+            .trim_end_matches('!')
+            .split("::")
+            .map(|segment| segment.trim().to_string())
+            .collect();
+
+        let valid = segments.iter().all(|segment| {
+            !segment.is_empty()
+                && !segment.starts_with(|c: char| c.is_ascii_digit())
+                && segment.chars().all(|c| c.is_alphanumeric() || c == '_')
+        });
+        valid.then_some(Self(segments))
+    }
+
+    /// Whether the trailing segments of a call or macro path match
+    fn matches_path(&self, path: &syn::Path) -> bool {
+        let segments: Vec<String> = path.segments.iter().map(|s| s.ident.to_string()).collect();
+        segments.ends_with(&self.0)
+    }
+
+    /// Whether a method name matches; only single-segment paths match methods
+    fn matches_method(&self, method: &syn::Ident) -> bool {
+        self.0.len() == 1 && *method == self.0[0]
+    }
+}
+
+/// Custom rule loaded from a YAML or TOML template
+pub struct TemplateRule {
+    id: String,
+    title: String,
+    description: String,
+    severity: Severity,
+    rule_type: RuleType,
+    recommendations: Vec<String>,
+    categories: Vec<String>,
+    references: Vec<String>,
+    pattern: TemplatePattern,
+}
+
+impl TemplateRule {
+    fn regex_findings(&self, regex: &Regex, file_path: &str, source_code: &str) -> Vec<Finding> {
+        source_code
+            .lines()
+            .enumerate()
+            .filter_map(|(index, line)| {
+                let found = regex.find(line)?;
+                let column = line[..found.start()].chars().count() + 1;
+                trace!("Template {} matched line {} of {file_path}", self.id, index + 1);
+
+                Some(Finding {
+                    rule_id: self.id.clone(),
+                    description: format!("{}: {}", self.title, self.description),
+                    severity: self.severity.clone(),
+                    location: Location {
+                        file: file_path.to_string(),
+                        line: index + 1,
+                        column: Some(column),
+                        end_line: Some(index + 1),
+                        end_column: Some(column + found.as_str().chars().count()),
+                    },
+                    code_snippet: Some(line.trim().to_string()),
+                    context: None,
+                    recommendations: self.recommendations.clone(),
+                    references: self.references.clone(),
+                    fingerprint: String::new(),
+                })
+            })
+            .collect()
+    }
+
+    fn call_findings(
+        &self,
+        ast: &File,
+        target: &CallPath,
+        without_preceding: Option<&CallPath>,
+        file_path: &str,
+        span_extractor: &SpanExtractor,
+    ) -> Vec<Finding> {
+        let mut nodes = Vec::new();
+
+        for node in AstQuery::new(ast).functions().collect() {
+            let (name, block) = match node.data {
+                NodeData::Function(func) => (func.sig.ident.to_string(), func.block.as_ref()),
+                NodeData::ImplFunction(func) => (func.sig.ident.to_string(), &func.block),
+                _ => continue,
+            };
+
+            let mut finder = CallFinder {
+                target,
+                guard: without_preceding,
+                guarded: false,
+                calls: Vec::new(),
+            };
+            finder.visit_block(block);
+
+            for call in finder.calls {
+                nodes.push(match call {
+                    CallSite::Expr(expr) => AstNode::from_expression(expr, &name),
+                    CallSite::Macro(mac) => AstNode::from_macro(mac, &name),
+                });
+            }
+        }
+
+        AstQuery::from_nodes(nodes).to_findings_with_span_extractor(
+            self.severity.clone(),
+            &self.title,
+            &self.description,
+            &self.recommendations,
+            file_path,
+            span_extractor,
+        )
+    }
+}
+
+impl Rule for TemplateRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity.clone()
+    }
+
+    fn rule_type(&self) -> RuleType {
+        self.rule_type.clone()
+    }
+
+    fn recommendations(&self) -> Vec<String> {
+        self.recommendations.clone()
+    }
+
+    fn categories(&self) -> &[String] {
+        &self.categories
+    }
+
+    fn references(&self) -> &[String] {
+        &self.references
+    }
+
+    fn execute(&self, ast: &File, file_path: &str) -> anyhow::Result<Vec<Finding>> {
+        self.execute_with_source(ast, file_path, "")
+    }
+
+    fn execute_with_source(&self, ast: &File, file_path: &str, source_code: &str) -> anyhow::Result<Vec<Finding>> {
+        debug!("Executing template rule {} in {file_path}", self.id);
+
+        let findings = match &self.pattern {
+            TemplatePattern::Regex(regex) => self.regex_findings(regex, file_path, source_code),
+            TemplatePattern::Call {
+                target,
+                without_preceding,
+            } => {
+                let span_extractor = SpanExtractor::new(source_code.to_string(), file_path.to_string());
+                self.call_findings(ast, target, without_preceding.as_ref(), file_path, &span_extractor)
+            }
+        };
+
+        Ok(findings)
+    }
+}
+
+/// A flagged call, as an expression or a macro invocation
+enum CallSite<'ast> {
+    Expr(&'ast Expr),
+    Macro(&'ast syn::Macro),
+}
+
+/// Visitor that records target calls made before any guard call, in source order
+struct CallFinder<'ast, 'p> {
+    target: &'p CallPath,
+    guard: Option<&'p CallPath>,
+    /// Whether a guard call was seen so far
+    guarded: bool,
+    calls: Vec<CallSite<'ast>>,
+}
+
+impl<'ast> CallFinder<'ast, '_> {
+    fn record(&mut self, is_guard: bool, is_target: bool, site: CallSite<'ast>) {
+        if is_guard {
+            self.guarded = true;
+        } else if is_target && !self.guarded {
+            self.calls.push(site);
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for CallFinder<'ast, '_> {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::Call(call) => {
+                if let Expr::Path(path) = &*call.func {
+                    let is_guard = self.guard.is_some_and(|guard| guard.matches_path(&path.path));
+                    let is_target = self.target.matches_path(&path.path);
+                    self.record(is_guard, is_target, CallSite::Expr(expr));
+                }
+            }
+            Expr::MethodCall(call) => {
+                let is_guard = self.guard.is_some_and(|guard| guard.matches_method(&call.method));
+                let is_target = self.target.matches_method(&call.method);
+                self.record(is_guard, is_target, CallSite::Expr(expr));
+            }
+            _ => {}
+        }
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let is_guard = self.guard.is_some_and(|guard| guard.matches_path(&mac.path));
+        let is_target = self.target.matches_path(&mac.path);
+        self.record(is_guard, is_target, CallSite::Macro(mac));
+        visit::visit_macro(self, mac);
+    }
+}
+
+/// Returns true if the file is a YAML or TOML rule template
+pub fn is_template_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| matches!(extension, "yaml" | "yml" | "toml"))
+}
+
+/// Parse a YAML or TOML rule template. Errors are prefixed with the file and,
+/// when known, the line of the problem (e.g. `rules/foo.yaml:3: ...`).
+pub fn parse_template(path: &Path, content: &str) -> Result<TemplateRule, String> {
+    let key_error =
+        |key: &str, message: String| format!("{}: {message}", location(path, key_line(content, key)));
+
+    let is_toml = path.extension().is_some_and(|extension| extension == "toml");
+    let definition: TemplateDefinition = if is_toml {
+        toml::from_str(content).map_err(|e| {
+            let line = e.span().map(|span| line_of_offset(content, span.start));
+            format!("{}: {}", location(path, line), e.message().trim())
+        })?
+    } else {
+        serde_yaml::from_str(content).map_err(|e| {
+            let line = e.location().map(|location| location.line());
+            format!("{}: {}", location(path, line), strip_yaml_location(&e.to_string()))
+        })?
+    };
+
+    let valid_id = !definition.id.is_empty()
+        && definition
+            .id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid_id {
+        return Err(key_error(
+            "id",
+            format!("invalid id '{}' (use letters, digits, '-' and '_')", definition.id),
+        ));
+    }
+    for (key, value) in [("title", &definition.title), ("description", &definition.description)] {
+        if value.trim().is_empty() {
+            return Err(key_error(key, format!("`{key}` must not be empty")));
+        }
+    }
+
+    let severity = definition.severity.parse::<Severity>().map_err(|e| {
+        key_error("severity", format!("{e} (expected high, medium, low or informational)"))
+    })?;
+    let rule_type = match &definition.rule_type {
+        Some(rule_type) => rule_type
+            .parse::<RuleType>()
+            .map_err(|e| key_error("rule_type", format!("{e} (expected solana, anchor or general)")))?,
+        None => RuleType::General,
+    };
+    if let Some(category) = definition
+        .categories
+        .iter()
+        .find(|category| !RULE_CATEGORIES.contains(&category.as_str()))
+    {
+        return Err(key_error(
+            "categories",
+            format!("unknown category '{category}' (expected one of {})", RULE_CATEGORIES.join(", ")),
+        ));
+    }
+
+    let PatternDefinition {
+        regex,
+        calls,
+        without_preceding,
+    } = definition.pattern;
+    let parse_call = |key: &str, path: &str| {
+        CallPath::parse(path).ok_or_else(|| {
+            key_error(
+                key,
+                format!("`{key}` must be a function, method or macro path such as `invoke` or `token::transfer`, got '{path}'"),
+            )
+        })
+    };
+    let pattern = match (regex, calls) {
+        (Some(regex), None) => {
+            if without_preceding.is_some() {
+                return Err(key_error("without_preceding", "`without_preceding` requires `calls`".to_string()));
+            }
+            let regex = Regex::new(&regex).map_err(|e| {
+                // Syntax errors render over several lines with a caret; keep the last one
+                let message = e.to_string();
+                let reason = message.lines().last().unwrap_or_default().trim_start_matches("error: ").to_string();
+                key_error("regex", format!("invalid regex: {reason}"))
+            })?;
+            TemplatePattern::Regex(regex)
+        }
+        (None, Some(calls)) => TemplatePattern::Call {
+            target: parse_call("calls", &calls)?,
+            without_preceding: without_preceding
+                .map(|guard| parse_call("without_preceding", &guard))
+                .transpose()?,
+        },
+        _ => {
+            return Err(key_error(
+                "pattern",
+                "`pattern` must set exactly one of `regex` or `calls`".to_string(),
+            ));
+        }
+    };
+
+    Ok(TemplateRule {
+        id: definition.id,
+        title: definition.title,
+        description: definition.description,
+        severity,
+        rule_type,
+        recommendations: definition.recommendations,
+        categories: definition.categories,
+        references: definition.references,
+        pattern,
+    })
+}
+
+/// Load every rule template (`*.yaml`, `*.yml`, `*.toml`) of a directory.
+/// Fails listing every malformed template, or on duplicate ids.
+pub fn load_templates(dir: &Path) -> anyhow::Result<Vec<Arc<dyn Rule>>> {
+    Ok(load_template_files(dir)?.into_iter().map(|(_, rule)| rule).collect())
+}
+
+/// Like `load_templates`, keeping the file each rule was defined in
+pub fn load_template_files(dir: &Path) -> anyhow::Result<Vec<(String, Arc<dyn Rule>)>> {
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read templates directory {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && is_template_file(path))
+        .collect();
+    paths.sort();
+
+    let mut rules: Vec<(String, Arc<dyn Rule>)> = Vec::new();
+    let mut errors = Vec::new();
+
+    for path in paths {
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                errors.push(format!("{}: {e}", path.display()));
+                continue;
+            }
+        };
+
+        match parse_template(&path, &content) {
+            Ok(rule) => {
+                if let Some((other, _)) = rules.iter().find(|(_, existing)| existing.id() == rule.id) {
+                    errors.push(format!(
+                        "{}: duplicate rule id '{}' (also defined in {other})",
+                        location(&path, key_line(&content, "id")),
+                        rule.id
+                    ));
+                    continue;
+                }
+                debug!("Loaded template rule {} from {}", rule.id, path.display());
+                rules.push((path.display().to_string(), Arc::new(rule)));
+            }
+            Err(e) => errors.push(e),
+        }
+    }
+
+    if !errors.is_empty() {
+        anyhow::bail!("Invalid rule templates:\n  {}", errors.join("\n  "));
+    }
+
+    Ok(rules)
+}
+
+/// `file:line`, or just the file when the line is unknown
+fn location(path: &Path, line: Option<usize>) -> String {
+    match line {
+        Some(line) => format!("{}:{line}", path.display()),
+        None => path.display().to_string(),
+    }
+}
+
+/// Line (1-indexed) where a YAML or TOML key is first assigned
+fn key_line(content: &str, key: &str) -> Option<usize> {
+    content.lines().position(|line| {
+        let line = line.trim_start();
+        line.strip_prefix(key)
+            .map(str::trim_start)
+            .is_some_and(|rest| rest.starts_with(':') || rest.starts_with('='))
+            || line.trim_end() == format!("[{key}]")
+    })
+    .map(|index| index + 1)
+}
+
+/// Line (1-indexed) of a byte offset
+fn line_of_offset(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())].matches('\n').count() + 1
+}
+
+/// Remove the ` at line X column Y` suffix serde_yaml appends to its messages
+fn strip_yaml_location(message: &str) -> &str {
+    match message.rfind(" at line ") {
+        Some(index) => &message[..index],
+        None => message,
+    }
+}
@@ -0,0 +1,77 @@
+use anyhow::{Context, Result};
+use log::debug;
+use std::fs::File;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Supported archive formats, detected by file extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveKind {
+    /// `.tar.gz` / `.tgz`
+    TarGz,
+    /// `.zip`
+    Zip,
+}
+
+impl ArchiveKind {
+    fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else {
+            None
+        }
+    }
+}
+
+/// Sources of an archive extracted to a temporary directory, removed when dropped
+#[derive(Debug)]
+pub struct ExtractedArchive {
+    dir: TempDir,
+}
+
+impl ExtractedArchive {
+    /// Directory holding the extracted sources
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+}
+
+/// Whether a path names a supported source archive (`.tar.gz`, `.tgz` or `.zip`)
+pub fn is_archive(path: &Path) -> bool {
+    ArchiveKind::from_path(path).is_some()
+}
+
+/// Extract a `.tar.gz`, `.tgz` or `.zip` archive to a temporary directory.
+/// Entries escaping the directory (`../`, absolute paths) are not written.
+pub fn extract(path: &Path) -> Result<ExtractedArchive> {
+    let kind = ArchiveKind::from_path(path)
+        .with_context(|| format!("{} is not a .tar.gz or .zip archive", path.display()))?;
+    let dir = tempfile::Builder::new()
+        .prefix("eloizer-")
+        .tempdir()
+        .context("Failed to create a directory for the archive")?;
+    unpack(path, kind, dir.path())?;
+    Ok(ExtractedArchive { dir })
+}
+
+/// Extract a `.tar.gz`, `.tgz` or `.zip` archive into an existing directory
+pub fn extract_to(path: &Path, dest: &Path) -> Result<()> {
+    let kind = ArchiveKind::from_path(path)
+        .with_context(|| format!("{} is not a .tar.gz or .zip archive", path.display()))?;
+    unpack(path, kind, dest)
+}
+
+fn unpack(path: &Path, kind: ArchiveKind, dest: &Path) -> Result<()> {
+    let file = File::open(path).with_context(|| format!("Failed to open archive {}", path.display()))?;
+    debug!("Extracting {} to {}", path.display(), dest.display());
+    match kind {
+        ArchiveKind::TarGz => tar::Archive::new(flate2::read::GzDecoder::new(file)).unpack(dest),
+        ArchiveKind::Zip => zip::ZipArchive::new(file)
+            .and_then(|mut archive| archive.extract(dest))
+            .map_err(std::io::Error::other),
+    }
+    .with_context(|| format!("Failed to extract archive {}", path.display()))
+}
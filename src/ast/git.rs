@@ -0,0 +1,48 @@
+use anyhow::{Context, Result};
+use log::debug;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Run a git command in the given directory and return its stdout
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .context("Failed to run git")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Top-level directory of the git repository containing `dir`
+pub fn toplevel(dir: &Path) -> Result<PathBuf> {
+    Ok(PathBuf::from(git(dir, &["rev-parse", "--show-toplevel"])?.trim()))
+}
+
+/// Files changed since the given git ref (committed, staged, unstaged and
+/// untracked), as canonical paths
+pub fn changed_files(dir: &Path, git_ref: &str) -> Result<HashSet<PathBuf>> {
+    let root = toplevel(dir)?;
+    let diff = git(dir, &["diff", "--name-only", git_ref, "--"])?;
+    let untracked = git(dir, &["ls-files", "--others", "--exclude-standard", "--full-name"])?;
+
+    let changed: HashSet<PathBuf> = diff
+        .lines()
+        .chain(untracked.lines())
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| root.join(line).canonicalize().ok())
+        .collect();
+
+    debug!("{} file(s) changed since {}", changed.len(), git_ref);
+    Ok(changed)
+}
@@ -1,5 +1,8 @@
+pub mod archive;
+pub mod git;
 pub mod json;
 pub mod parser;
+pub mod suppressions;
 
 
 
@@ -1,8 +1,15 @@
 use anyhow::{Context, Result};
-use log::{error, info};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+use log::{debug, error, info, trace, warn};
+use sha2::{Digest, Sha256};
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
+
+/// Number of parsed files kept by the AST cache of each thread
+pub const AST_CACHE_CAPACITY: usize = 128;
 
 /// Parse a Rust file and return the AST
 pub fn parse_rust_file(path: &Path) -> Result<syn::File> {
@@ -12,34 +19,222 @@ pub fn parse_rust_file(path: &Path) -> Result<syn::File> {
     parse_rust_code(&content).with_context(|| format!("Failed to parse file {}", path.display()))
 }
 
-/// Parse a string of Rust code and return the AST
+/// Parse a string of Rust code and return the AST. Content parsed before on
+/// the same thread is served from the AST cache.
 pub fn parse_rust_code(content: &str) -> Result<syn::File> {
-    syn::parse_str::<syn::File>(content)
-        .map_err(|e| anyhow::anyhow!("Failed to parse Rust code: {}", e))
+    let key: [u8; 32] = Sha256::digest(content.as_bytes()).into();
+    if let Some(ast) = AST_CACHE.with_borrow_mut(|cache| cache.get(&key)) {
+        trace!("AST cache hit for {} bytes of source", content.len());
+        return Ok(ast);
+    }
+
+    let ast = syn::parse_str::<syn::File>(content)
+        .map_err(|e| anyhow::anyhow!("Failed to parse Rust code: {}", e))?;
+    AST_CACHE.with_borrow_mut(|cache| cache.insert(key, ast.clone()));
+    Ok(ast)
+}
+
+/// Drop the ASTs cached by the current thread
+pub fn clear_cache() {
+    AST_CACHE.with_borrow_mut(AstCache::clear);
+}
+
+thread_local! {
+    /// Parsed files keyed by the SHA-256 of their content. Watch mode and the
+    /// language server parse the same content over and over; the cache skips
+    /// the re-parse. It is per thread because syn trees are not `Send` and their
+    /// spans point into the thread's source map. Findings of unchanged files
+    /// are already cached across runs on disk by `analyzer::cache`, so ASTs are
+    /// only cached in memory.
+    static AST_CACHE: RefCell<AstCache> = RefCell::new(AstCache::default());
+}
+
+/// Least recently used cache of parsed files
+#[derive(Default)]
+struct AstCache {
+    /// AST and last use tick of each content hash
+    entries: HashMap<[u8; 32], (syn::File, u64)>,
+    tick: u64,
+}
+
+impl AstCache {
+    fn get(&mut self, key: &[u8; 32]) -> Option<syn::File> {
+        self.tick += 1;
+        let (ast, used) = self.entries.get_mut(key)?;
+        *used = self.tick;
+        Some(ast.clone())
+    }
+
+    fn insert(&mut self, key: [u8; 32], ast: syn::File) {
+        if self.entries.len() >= AST_CACHE_CAPACITY
+            && !self.entries.contains_key(&key)
+            && let Some(oldest) = self.entries.iter().min_by_key(|(_, (_, used))| *used).map(|(key, _)| *key)
+        {
+            self.entries.remove(&oldest);
+        }
+        self.tick += 1;
+        self.entries.insert(key, (ast, self.tick));
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
 }
 
+/// Options controlling which files are collected from a directory
+#[derive(Debug, Clone)]
+pub struct WalkOptions {
+    /// Only collect files matching one of these globs (relative to the root)
+    pub include_globs: Vec<String>,
+    /// Skip files and directories matching one of these globs (relative to the root)
+    pub exclude_globs: Vec<String>,
+    /// Skip files ignored by `.gitignore`/`.ignore` files
+    pub respect_gitignore: bool,
+    /// Only collect files changed since this git ref
+    pub since: Option<String>,
+    /// Collect files under `tests/`, `benches/` and `examples/` directories
+    pub include_tests: bool,
+}
+
+impl Default for WalkOptions {
+    fn default() -> Self {
+        Self {
+            include_globs: Vec::new(),
+            exclude_globs: Vec::new(),
+            respect_gitignore: true,
+            since: None,
+            include_tests: false,
+        }
+    }
+}
+
+/// Cargo directories of integration tests, benchmarks and examples, skipped
+/// unless `WalkOptions::include_tests` is set. Their panics and unwraps are not
+/// production risks.
+pub const TEST_DIRECTORIES: [&str; 3] = ["tests", "benches", "examples"];
+
 /// Process a directory and return a vector of (path, AST) pairs
 pub fn process_directory(dir_path: &Path) -> Vec<(PathBuf, syn::File)> {
+    process_directory_with_options(dir_path, &WalkOptions::default()).unwrap_or_else(|e| {
+        error!("Failed to process directory {}: {}", dir_path.display(), e);
+        Vec::new()
+    })
+}
+
+/// Process a directory honoring the given walk options and return a vector of (path, AST) pairs
+pub fn process_directory_with_options(
+    dir_path: &Path,
+    options: &WalkOptions,
+) -> Result<Vec<(PathBuf, syn::File)>> {
+    process_directory_with_progress(dir_path, options, |_, _| {})
+}
+
+/// Process a directory like [`process_directory_with_options`], reporting
+/// `(processed, total)` to `progress` once the files are collected and after each file is parsed
+pub fn process_directory_with_progress<F>(
+    dir_path: &Path,
+    options: &WalkOptions,
+    mut progress: F,
+) -> Result<Vec<(PathBuf, syn::File)>>
+where
+    F: FnMut(usize, usize),
+{
+    let paths = collect_rust_files(dir_path, options)?;
+    let total = paths.len();
     let mut results = Vec::new();
+    progress(0, total);
 
-    for entry in WalkDir::new(dir_path)
+    for (processed, path) in paths.into_iter().enumerate() {
+        match parse_rust_file(&path) {
+            Ok(ast) => {
+                info!("Successfully parsed file {}", path.display());
+                results.push((path, ast));
+            }
+            Err(e) => error!("Failed to parse file {}: {}", path.display(), e),
+        }
+        progress(processed + 1, total);
+    }
+    info!("Processed {} Rust files", results.len());
+    Ok(results)
+}
+
+/// Walk a directory and return the Rust files selected by the walk options,
+/// leaving the parsing to the analysis (see `Analyzer::analyze_paths`)
+pub fn collect_rust_files(dir_path: &Path, options: &WalkOptions) -> Result<Vec<PathBuf>> {
+    let include = build_glob_set(&options.include_globs)?;
+    let exclude = build_glob_set(&options.exclude_globs)?;
+    let root = dir_path.to_path_buf();
+    let mut paths = Vec::new();
+
+    // Outside a git repository (or with an unknown ref) fall back to a full scan
+    let changed = options.since.as_deref().and_then(|git_ref| {
+        match super::git::changed_files(dir_path, git_ref) {
+            Ok(changed) => Some(changed),
+            Err(e) => {
+                warn!("Cannot list files changed since {git_ref}, analyzing all files: {e:#}");
+                None
+            }
+        }
+    });
+
+    let exclude_root = root.clone();
+    let include_tests = options.include_tests;
+    let walker = WalkBuilder::new(dir_path)
         .follow_links(true)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-    {
+        .hidden(false)
+        .git_ignore(options.respect_gitignore)
+        .git_exclude(options.respect_gitignore)
+        .git_global(options.respect_gitignore)
+        .ignore(options.respect_gitignore)
+        .require_git(false)
+        .filter_entry(move |entry| {
+            let relative = entry.path().strip_prefix(&exclude_root).unwrap_or(entry.path());
+            if relative.as_os_str().is_empty() {
+                return true;
+            }
+            let test_directory = entry.file_type().is_some_and(|kind| kind.is_dir())
+                && TEST_DIRECTORIES.iter().any(|name| entry.file_name() == *name);
+            if test_directory && !include_tests {
+                debug!("Skipping {} (test directory)", entry.path().display());
+                return false;
+            }
+            !exclude.is_match(relative)
+        })
+        .build();
+
+    for entry in walker.filter_map(std::result::Result::ok) {
         let path = entry.path();
 
         // Only process Rust files
-        if path.is_file() && path.extension().is_some_and(|ext| ext == "rs") {
-            match parse_rust_file(path) {
-                Ok(ast) => {
-                    info!("Successfully parsed file {}", path.display());
-                    results.push((path.to_path_buf(), ast));
-                }
-                Err(e) => error!("Failed to parse file {}: {}", path.display(), e),
-            }
+        let is_rust_file = path.is_file() && path.extension().is_some_and(|ext| ext == "rs");
+        if !is_rust_file {
+            continue;
+        }
+
+        let relative = path.strip_prefix(&root).unwrap_or(path);
+        if !options.include_globs.is_empty() && !include.is_match(relative) {
+            debug!("Skipping {} (not matched by include globs)", path.display());
+            continue;
+        }
+
+        if let Some(changed) = &changed
+            && !path.canonicalize().is_ok_and(|path| changed.contains(&path))
+        {
+            debug!("Skipping {} (unchanged)", path.display());
+            continue;
         }
+
+        paths.push(path.to_path_buf());
     }
-    info!("Processed {} Rust files", results.len());
-    results
+    debug!("Collected {} Rust files", paths.len());
+    Ok(paths)
+}
+
+/// Compile a list of glob patterns into a single matcher
+fn build_glob_set(globs: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for glob in globs {
+        builder.add(Glob::new(glob).with_context(|| format!("Invalid glob pattern '{glob}'"))?);
+    }
+    Ok(builder.build()?)
 }
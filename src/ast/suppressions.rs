@@ -0,0 +1,91 @@
+use log::trace;
+
+const IGNORE_DIRECTIVE: &str = "eloizer-ignore";
+const IGNORE_NEXT_LINE_DIRECTIVE: &str = "eloizer-ignore-next-line";
+
+/// An inline `// eloizer-ignore` directive found in a source file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suppression {
+    /// Line of the comment (1-based)
+    pub line: usize,
+    /// Line whose findings are suppressed (1-based)
+    pub target_line: usize,
+    /// Suppressed rule IDs; empty means every rule
+    pub rule_ids: Vec<String>,
+}
+
+impl Suppression {
+    /// Returns true if the directive suppresses the given rule on the given line
+    pub fn matches(&self, rule_id: &str, line: usize) -> bool {
+        self.target_line == line && (self.rule_ids.is_empty() || self.rule_ids.iter().any(|id| id == rule_id))
+    }
+}
+
+/// Collect the suppression directives of a source file.
+///
+/// Supported forms:
+/// - `// eloizer-ignore` on its own line suppresses every rule on the next line,
+///   and as a trailing comment suppresses every rule on its own line
+/// - `// eloizer-ignore: RULE_A, RULE_B` limits the suppression to the listed rules
+/// - `// eloizer-ignore-next-line[: RULE]` always targets the next line
+pub fn parse_suppressions(source: &str) -> Vec<Suppression> {
+    let mut suppressions = Vec::new();
+
+    for (index, text) in source.lines().enumerate() {
+        let Some(comment_start) = line_comment_start(text) else {
+            continue;
+        };
+        let comment = text[comment_start + 2..].trim_start_matches('/').trim();
+
+        let (rest, next_line) = if let Some(rest) = comment.strip_prefix(IGNORE_NEXT_LINE_DIRECTIVE) {
+            (rest, true)
+        } else if let Some(rest) = comment.strip_prefix(IGNORE_DIRECTIVE) {
+            // A comment-only line applies to the code below it
+            (rest, text[..comment_start].trim().is_empty())
+        } else {
+            continue;
+        };
+
+        let rest = rest.trim();
+        let rule_ids = match rest.strip_prefix(':') {
+            Some(ids) => ids
+                .split(',')
+                .map(|id| id.trim().to_string())
+                .filter(|id| !id.is_empty())
+                .collect(),
+            None if rest.is_empty() => Vec::new(),
+            // Longer words such as `eloizer-ignored` are not directives
+            None => continue,
+        };
+
+        let line = index + 1;
+        let suppression = Suppression {
+            line,
+            target_line: if next_line { line + 1 } else { line },
+            rule_ids,
+        };
+        trace!("Found suppression directive: {suppression:?}");
+        suppressions.push(suppression);
+    }
+
+    suppressions
+}
+
+/// Byte offset of the `//` starting a line comment, skipping `//` inside string literals
+fn line_comment_start(line: &str) -> Option<usize> {
+    let bytes = line.as_bytes();
+    let mut in_string = false;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' if in_string => index += 1,
+            b'"' => in_string = !in_string,
+            b'/' if !in_string && bytes.get(index + 1) == Some(&b'/') => return Some(index),
+            _ => {}
+        }
+        index += 1;
+    }
+
+    None
+}
@@ -15,7 +15,7 @@ struct Cli {
     #[arg(short, long)]
     path: PathBuf,
 
-    /// Custom templates path
+    /// Directory of YAML/TOML custom rule templates
     #[arg(short, long)]
     templates: Option<PathBuf>,
 
@@ -145,10 +145,19 @@ fn main() -> Result<()> {
                     let report_generator = analyzer::reporting::ReportGenerator::new(
                         analysis_result.findings.clone(),
                         args.path.to_string_lossy().to_string(),
-                    );
+                    )
+                    .with_stats(analysis_result.stats.clone());
 
                     let output_str = output_path.to_string_lossy();
-                    if output_str.ends_with(".md") || output_str.ends_with(".markdown") {
+                    if output_str.ends_with(".json") {
+                        // Generate JSON report
+                        match report_generator.save_json_report(&output_str) {
+                            Ok(()) => {
+                                info!("📄 JSON report saved to: {}", output_path.display());
+                            }
+                            Err(e) => error!("Failed to save report: {e}"),
+                        }
+                    } else if output_str.ends_with(".md") || output_str.ends_with(".markdown") {
                         // Generate Markdown report
                         match report_generator.save_markdown_report(&output_str) {
                             Ok(()) => {
@@ -198,7 +207,7 @@ fn main() -> Result<()> {
                                         index,
                                         finding.description,
                                         finding.location.file,
-                                        finding.location.line
+                                        finding.location.position()
                                     );
 
                                     // Show code snippet if available